@@ -0,0 +1,229 @@
+//! In-process VRF provider.
+//!
+//! This is a native implementation of the `vrf-server` sidecar. It exposes the same operations
+//! as the sidecar's HTTP endpoints (`/info`, `/proof` and `/outside_execution`) but generates the
+//! Stark-VRF proofs directly within the calling process, so no external binary is required.
+
+use ark_ec::short_weierstrass::Affine;
+use katana_primitives::execution::Call;
+use katana_primitives::{ContractAddress, Felt};
+use katana_rpc_types::outside_execution::{
+    MessageHashRev1, OutsideExecution, OutsideExecutionV2, SignedOutsideExecution,
+};
+use stark_vrf::{generate_public_key, BaseField, ScalarField, StarkCurve, StarkVRF};
+use starknet::core::types::{BlockId, BlockTag};
+use starknet::core::utils::get_storage_var_address;
+use starknet::macros::{selector, short_string};
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider, ProviderError};
+use starknet::signers::SigningKey;
+use starknet_crypto::poseidon_hash_many;
+
+use super::{felt_from_field, field_from_felt, InfoResponse, RequestContext, StarkVrfProof};
+
+/// Storage variable of the VRF account holding the per-address nonces of `Source::Nonce`.
+const NONCES_STORAGE_VAR: &str = "VrfAccount_nonces";
+
+/// `request_random` source variant that derives the seed from the VRF provider's nonce.
+const SOURCE_NONCE: Felt = Felt::ZERO;
+/// `request_random` source variant that derives the seed from a user provided salt.
+const SOURCE_SALT: Felt = Felt::ONE;
+
+/// Error type for in-process VRF operations.
+#[derive(thiserror::Error, Debug)]
+pub enum InProcessVrfError {
+    #[error("no request_random call targeting the VRF account {0}")]
+    MissingRequestRandom(ContractAddress),
+
+    #[error("malformed request_random call: {0}")]
+    MalformedRequestRandom(&'static str),
+
+    #[error("unknown request_random source variant {0:#x}")]
+    UnknownSource(Felt),
+
+    #[error("invalid chain id {0}")]
+    InvalidChainId(String),
+
+    #[error("rpc url is required to read the VRF nonce")]
+    MissingRpcUrl,
+
+    #[error("failed to read VRF nonce: {0}")]
+    Provider(#[from] ProviderError),
+
+    #[error("failed to generate VRF proof: {0}")]
+    Proof(String),
+
+    #[error("failed to sign outside execution: {0}")]
+    Signing(String),
+}
+
+/// A VRF provider that runs within the node process.
+#[derive(Debug, Clone)]
+pub struct InProcessVrf {
+    /// The address of the VRF account contract.
+    account_address: ContractAddress,
+    /// The key used to sign outside executions on behalf of the VRF account.
+    account_signer: SigningKey,
+    /// The VRF secret key.
+    secret_key: ScalarField,
+    /// The VRF public key derived from [`Self::secret_key`].
+    public_key: Affine<StarkCurve>,
+}
+
+impl InProcessVrf {
+    /// Creates a new [`InProcessVrf`].
+    ///
+    /// The `secret_key` is the VRF secret key (same as the sidecar's `--secret-key`), whereas the
+    /// `account_private_key` is the signing key of the VRF account.
+    pub fn new(
+        account_address: ContractAddress,
+        account_private_key: Felt,
+        secret_key: u64,
+    ) -> Self {
+        let secret_key = field_from_felt::<ScalarField>(secret_key.into());
+        let public_key = generate_public_key(secret_key);
+        let account_signer = SigningKey::from_secret_scalar(account_private_key);
+        Self { account_address, account_signer, secret_key, public_key }
+    }
+
+    /// Returns the address of the VRF account contract.
+    pub fn account_address(&self) -> ContractAddress {
+        self.account_address
+    }
+
+    /// Get VRF public key info. Equivalent to the sidecar's `GET /info`.
+    pub fn info(&self) -> InfoResponse {
+        InfoResponse {
+            public_key_x: felt_from_field(self.public_key.x).to_hex_string(),
+            public_key_y: felt_from_field(self.public_key.y).to_hex_string(),
+        }
+    }
+
+    /// Generate a Stark-VRF proof for the given seed. Equivalent to the sidecar's `POST /proof`.
+    pub fn proof(&self, seed: &[Felt]) -> Result<StarkVrfProof, InProcessVrfError> {
+        let seed = seed.iter().copied().map(field_from_felt::<BaseField>).collect::<Vec<_>>();
+
+        let vrf = StarkVRF::new(self.public_key).map_err(proof_error)?;
+        let proof = vrf.prove(&self.secret_key, &seed).map_err(proof_error)?;
+        let sqrt_ratio = vrf.hash_to_sqrt_ratio_hint(&seed);
+        let rnd = vrf.proof_to_hash(&proof).map_err(proof_error)?;
+
+        Ok(StarkVrfProof {
+            gamma_x: felt_from_field(proof.0.x),
+            gamma_y: felt_from_field(proof.0.y),
+            c: felt_from_field(proof.1),
+            s: felt_from_field(proof.2),
+            sqrt_ratio: felt_from_field(sqrt_ratio),
+            rnd: felt_from_field(rnd),
+        })
+    }
+
+    /// Process outside execution with VRF. Equivalent to the sidecar's `POST /outside_execution`.
+    ///
+    /// Computes the seed of the `request_random` call, generates its proof and wraps the user's
+    /// outside execution in a new outside execution issued by the VRF account:
+    ///
+    /// 1. `submit_random(seed, proof)` on the VRF account
+    /// 2. `execute_from_outside_v*` on the user's account with the original request
+    ///
+    /// The returned execution is signed by the VRF account. Consumption of the random value is
+    /// asserted by the VRF account itself at the end of its `__execute__`.
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub async fn outside_execution(
+        &self,
+        request: &SignedOutsideExecution,
+        context: &RequestContext,
+    ) -> Result<SignedOutsideExecution, InProcessVrfError> {
+        let chain_id = Felt::from_hex(&context.chain_id)
+            .map_err(|_| InProcessVrfError::InvalidChainId(context.chain_id.clone()))?;
+
+        let request_random = request
+            .outside_execution
+            .calls()
+            .iter()
+            .find(|call| {
+                call.entry_point_selector == selector!("request_random")
+                    && call.contract_address == self.account_address
+            })
+            .ok_or(InProcessVrfError::MissingRequestRandom(self.account_address))?;
+
+        let seed = self.compute_seed(request_random, chain_id, context).await?;
+        let proof = self.proof(&[seed])?;
+
+        let submit_random = Call {
+            contract_address: self.account_address,
+            entry_point_selector: selector!("submit_random"),
+            calldata: vec![seed, proof.gamma_x, proof.gamma_y, proof.c, proof.s, proof.sqrt_ratio],
+        };
+
+        let (execute_after, execute_before) = match &request.outside_execution {
+            OutsideExecution::V2(v2) => (v2.execute_after, v2.execute_before),
+            OutsideExecution::V3(v3) => (v3.execute_after, v3.execute_before),
+        };
+
+        // The user's execution message hash is unique per request, which makes it a suitable
+        // nonce for the VRF account's own outside execution.
+        let nonce = request.outside_execution.get_message_hash_rev_1(chain_id, request.address);
+
+        let outside_execution = OutsideExecution::V2(OutsideExecutionV2 {
+            caller: short_string!("ANY_CALLER").into(),
+            nonce,
+            execute_after,
+            execute_before,
+            calls: vec![submit_random, Call::from(request.clone())],
+        });
+
+        let message_hash = outside_execution.get_message_hash_rev_1(chain_id, self.account_address);
+        let signature = self
+            .account_signer
+            .sign(&message_hash)
+            .map_err(|err| InProcessVrfError::Signing(err.to_string()))?;
+
+        Ok(SignedOutsideExecution {
+            address: self.account_address,
+            outside_execution,
+            signature: vec![signature.r, signature.s],
+        })
+    }
+
+    /// Computes the seed of a `request_random` call, mirroring the onchain computation.
+    ///
+    /// The call's calldata is `[caller, source_variant, source_value]`.
+    async fn compute_seed(
+        &self,
+        call: &Call,
+        chain_id: Felt,
+        context: &RequestContext,
+    ) -> Result<Felt, InProcessVrfError> {
+        let [caller, variant, value] = call.calldata.as_slice() else {
+            return Err(InProcessVrfError::MalformedRequestRandom(
+                "expected calldata [caller, source_variant, source_value]",
+            ));
+        };
+
+        if *variant == SOURCE_NONCE {
+            let rpc_url = context.rpc_url.clone().ok_or(InProcessVrfError::MissingRpcUrl)?;
+            let provider = JsonRpcClient::new(HttpTransport::new(rpc_url));
+
+            let key = get_storage_var_address(NONCES_STORAGE_VAR, &[*value])
+                .map_err(|_| InProcessVrfError::MalformedRequestRandom("invalid nonce key"))?;
+            let nonce = provider
+                .get_storage_at(
+                    Felt::from(self.account_address),
+                    key,
+                    BlockId::Tag(BlockTag::PreConfirmed),
+                )
+                .await?;
+
+            Ok(poseidon_hash_many(&[nonce, *value, *caller, chain_id]))
+        } else if *variant == SOURCE_SALT {
+            Ok(poseidon_hash_many(&[*value, *caller, chain_id]))
+        } else {
+            Err(InProcessVrfError::UnknownSource(*variant))
+        }
+    }
+}
+
+fn proof_error<E: std::fmt::Debug>(err: E) -> InProcessVrfError {
+    InProcessVrfError::Proof(format!("{err:?}"))
+}
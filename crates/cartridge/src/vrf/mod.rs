@@ -1,6 +1,23 @@
 //! Cartridge VRF (Verifiable Random Function) service.
 
 mod client;
+mod in_process;
 pub mod server;
 
+use std::fmt::Display;
+
+use ark_ff::PrimeField;
 pub use client::*;
+pub use in_process::*;
+use katana_primitives::Felt;
+
+/// Converts a [`Felt`] into a field element of the Stark curve, reduced modulo the field order.
+pub(crate) fn field_from_felt<F: PrimeField>(value: Felt) -> F {
+    F::from_be_bytes_mod_order(&value.to_bytes_be())
+}
+
+/// Converts a field element of the Stark curve into a [`Felt`].
+pub(crate) fn felt_from_field<T: Display>(value: T) -> Felt {
+    // Both the base and scalar fields of the Stark curve are at most as large as the felt field.
+    Felt::from_dec_str(&value.to_string()).expect("field element must fit in a felt")
+}
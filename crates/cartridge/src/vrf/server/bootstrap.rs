@@ -11,7 +11,6 @@ use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context, Result};
 use ark_ec::short_weierstrass::Affine;
-use ark_ff::PrimeField;
 use katana_contracts::vrf::{CartridgeVrfAccount, CartridgeVrfConsumer};
 use katana_genesis::constant::DEFAULT_STRK_FEE_TOKEN_ADDRESS;
 use katana_primitives::chain::ChainId;
@@ -30,6 +29,8 @@ use starknet::signers::{LocalWallet, SigningKey};
 use tokio::time::sleep;
 use url::Url;
 
+use crate::vrf::{felt_from_field, field_from_felt};
+
 /// Salt used for deploying VRF accounts via UDC.
 pub const VRF_ACCOUNT_SALT: u64 = 0x54321;
 /// Salt used for deploying VRF consumer contracts via UDC.
@@ -93,7 +94,7 @@ pub struct VrfAccountCredentials {
 pub fn get_default_vrf_account() -> Result<VrfAccountCredentials> {
     let secret_key = VRF_HARDCODED_SECRET_KEY;
     let vrf_account_private_key = Felt::from(secret_key);
-    let public_key = generate_public_key(scalar_from_felt(secret_key.into()));
+    let public_key = generate_public_key(field_from_felt::<ScalarField>(secret_key.into()));
 
     let account_public_key =
         SigningKey::from_secret_scalar(vrf_account_private_key).verifying_key().scalar();
@@ -223,8 +224,8 @@ async fn bootstrap_vrf_account(
         to: vrf_account_address.into(),
         selector: selector!("set_vrf_public_key"),
         calldata: vec![
-            felt_from_field(vrf_acc_cred.public_key.x)?,
-            felt_from_field(vrf_acc_cred.public_key.y)?,
+            felt_from_field(vrf_acc_cred.public_key.x),
+            felt_from_field(vrf_acc_cred.public_key.y),
         ],
     };
 
@@ -368,16 +369,6 @@ async fn wait_for_tx(
     }
 }
 
-fn scalar_from_felt(value: Felt) -> ScalarField {
-    let bytes = value.to_bytes_be();
-    ScalarField::from_be_bytes_mod_order(&bytes)
-}
-
-fn felt_from_field<T: std::fmt::Display>(value: T) -> Result<Felt> {
-    let decimal = value.to_string();
-    Felt::from_dec_str(&decimal).map_err(|err| anyhow!("invalid field value: {err}"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::{get_default_vrf_account, VRF_HARDCODED_SECRET_KEY};
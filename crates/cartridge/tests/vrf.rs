@@ -1,12 +1,24 @@
-use cartridge::vrf::RequestContext;
+use std::sync::Arc;
+
+use ark_ec::short_weierstrass::Affine;
+use ark_ff::PrimeField;
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use cartridge::vrf::{InProcessVrf, RequestContext};
 use cartridge::{get_default_vrf_account, VrfServer, VrfServerConfig};
 use katana_primitives::execution::Call;
 use katana_primitives::{address, felt, ContractAddress, Felt};
 use katana_rpc_types::{
     MessageHashRev1, OutsideExecution, OutsideExecutionV2, SignedOutsideExecution,
 };
+use serde_json::{json, Value};
+use stark_vrf::{BaseField, Proof, ScalarField, StarkCurve, StarkVRF};
+use starknet::core::utils::get_storage_var_address;
 use starknet::macros::selector;
 use starknet::signers::SigningKey;
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
 use url::Url;
 
 const TEST_CHAIN_ID: Felt = felt!("0x57505f4b4154414e41"); // WP_KATANA
@@ -27,6 +39,26 @@ fn test_calls() -> Vec<Call> {
     ]
 }
 
+/// Verifies the proof of a `submit_random` call, whose calldata is
+/// `[seed, gamma_x, gamma_y, c, s, sqrt_ratio]`, against the VRF public key.
+fn assert_valid_proof(public_key: Affine<StarkCurve>, calldata: &[Felt]) {
+    fn field<F: PrimeField>(value: &Felt) -> F {
+        F::from_be_bytes_mod_order(&value.to_bytes_be())
+    }
+
+    let [seed, gamma_x, gamma_y, c, s, _sqrt_ratio] = calldata else {
+        panic!("malformed submit_random calldata: {calldata:?}");
+    };
+
+    let gamma = Affine::<StarkCurve>::new(field::<BaseField>(gamma_x), field::<BaseField>(gamma_y));
+    let proof = Proof(gamma, field::<ScalarField>(c), field::<ScalarField>(s));
+
+    StarkVRF::new(public_key)
+        .unwrap()
+        .verify(&proof, &[field::<BaseField>(seed)])
+        .expect("invalid vrf proof");
+}
+
 fn outside_execution_v2() -> OutsideExecution {
     OutsideExecution::V2(OutsideExecutionV2 {
         caller: TEST_CALLER,
@@ -85,3 +117,172 @@ async fn vrf_signed_outside_execution() {
         "invalid vrf signature"
     );
 }
+
+#[tokio::test]
+async fn in_process_vrf_signed_outside_execution() {
+    let vrf_creds = get_default_vrf_account().unwrap();
+    let vrf =
+        InProcessVrf::new(vrf_creds.account_address, vrf_creds.private_key, vrf_creds.secret_key);
+
+    // Use the salt source so that the seed can be computed without reading the VRF nonce.
+    let salt = felt!("0x2a");
+    let consumer = felt!("0x111");
+    let calls = vec![
+        Call {
+            contract_address: vrf_creds.account_address,
+            entry_point_selector: selector!("request_random"),
+            calldata: vec![consumer, Felt::ONE, salt],
+        },
+        Call {
+            contract_address: consumer.into(),
+            entry_point_selector: selector!("dice"),
+            calldata: vec![],
+        },
+    ];
+
+    let outside_execution = OutsideExecution::V2(OutsideExecutionV2 {
+        caller: TEST_CALLER,
+        nonce: felt!("0x1"),
+        execute_after: 0,
+        execute_before: 3000000000,
+        calls,
+    });
+
+    let signed_outside_execution = SignedOutsideExecution {
+        signature: vec![felt!("0xaa"), felt!("0xbb")],
+        address: felt!("0xcafe").into(),
+        outside_execution,
+    };
+
+    let context = RequestContext { chain_id: TEST_CHAIN_ID.to_hex_string(), rpc_url: None };
+    let result = vrf.outside_execution(&signed_outside_execution, &context).await.unwrap();
+
+    assert_eq!(result.address, vrf_creds.account_address);
+
+    let calls = result.outside_execution.calls();
+    let seed = starknet_crypto::poseidon_hash_many(&[salt, consumer, TEST_CHAIN_ID]);
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].entry_point_selector, selector!("submit_random"));
+    assert_eq!(calls[0].calldata[0], seed);
+    assert_valid_proof(vrf_creds.public_key, &calls[0].calldata);
+    assert_eq!(calls[1], Call::from(signed_outside_execution));
+
+    let signing_key = SigningKey::from_secret_scalar(vrf_creds.private_key);
+    let public_key = signing_key.verifying_key().scalar();
+
+    let message_hash =
+        result.outside_execution.get_message_hash_rev_1(TEST_CHAIN_ID, vrf_creds.account_address);
+
+    assert!(
+        starknet_crypto::verify(
+            &public_key,
+            &message_hash,
+            &result.signature[0],
+            &result.signature[1]
+        )
+        .unwrap(),
+        "invalid vrf signature"
+    );
+}
+
+#[tokio::test]
+async fn in_process_vrf_nonce_source() {
+    let vrf_creds = get_default_vrf_account().unwrap();
+    let vrf =
+        InProcessVrf::new(vrf_creds.account_address, vrf_creds.private_key, vrf_creds.secret_key);
+
+    let nonce = felt!("0x7");
+    let (rpc_url, requests) = start_mock_rpc(nonce).await;
+
+    // With the nonce source, the source value is the address whose nonce is used.
+    let nonce_owner = felt!("0xcafe");
+    let consumer = felt!("0x111");
+    let calls = vec![
+        Call {
+            contract_address: vrf_creds.account_address,
+            entry_point_selector: selector!("request_random"),
+            calldata: vec![consumer, Felt::ZERO, nonce_owner],
+        },
+        Call {
+            contract_address: consumer.into(),
+            entry_point_selector: selector!("dice"),
+            calldata: vec![],
+        },
+    ];
+
+    let signed_outside_execution = SignedOutsideExecution {
+        signature: vec![felt!("0xaa"), felt!("0xbb")],
+        address: nonce_owner.into(),
+        outside_execution: OutsideExecution::V2(OutsideExecutionV2 {
+            caller: TEST_CALLER,
+            nonce: felt!("0x1"),
+            execute_after: 0,
+            execute_before: 3000000000,
+            calls,
+        }),
+    };
+
+    // The nonce can't be read without an RPC url.
+    let context = RequestContext { chain_id: TEST_CHAIN_ID.to_hex_string(), rpc_url: None };
+    assert!(vrf.outside_execution(&signed_outside_execution, &context).await.is_err());
+
+    let context =
+        RequestContext { chain_id: TEST_CHAIN_ID.to_hex_string(), rpc_url: Some(rpc_url) };
+    let result = vrf.outside_execution(&signed_outside_execution, &context).await.unwrap();
+
+    // The nonce is read from the `VrfAccount_nonces` storage of the VRF account.
+    let requests = requests.lock().await;
+    assert_eq!(requests.len(), 1);
+
+    let key = get_storage_var_address("VrfAccount_nonces", &[nonce_owner]).unwrap();
+    let account = Felt::from(vrf_creds.account_address);
+    assert_eq!(requests[0]["contract_address"], json!(account.to_hex_string()));
+    assert_eq!(requests[0]["key"], json!(key.to_hex_string()));
+    assert_eq!(requests[0]["block_id"], json!("pre_confirmed"));
+
+    let calls = result.outside_execution.calls();
+    let seed = starknet_crypto::poseidon_hash_many(&[nonce, nonce_owner, consumer, TEST_CHAIN_ID]);
+
+    assert_eq!(calls[0].entry_point_selector, selector!("submit_random"));
+    assert_eq!(calls[0].calldata[0], seed);
+    assert_valid_proof(vrf_creds.public_key, &calls[0].calldata);
+}
+
+type RecordedParams = Arc<Mutex<Vec<Value>>>;
+
+/// Starts a JSON-RPC server that answers `starknet_getStorageAt` with `value`, and records the
+/// params of the requests it receives.
+async fn start_mock_rpc(value: Felt) -> (Url, RecordedParams) {
+    async fn handler(
+        State((value, requests)): State<(Felt, RecordedParams)>,
+        Json(request): Json<Value>,
+    ) -> Json<Value> {
+        assert_eq!(request["method"], "starknet_getStorageAt");
+        requests.lock().await.push(request["params"].clone());
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": value.to_hex_string() }))
+    }
+
+    let requests = RecordedParams::default();
+    let app = Router::new().route("/", post(handler)).with_state((value, requests.clone()));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (Url::parse(&format!("http://{addr}")).unwrap(), requests)
+}
+
+#[test]
+fn in_process_vrf_info_matches_derived_public_key() {
+    let vrf_creds = get_default_vrf_account().unwrap();
+    let vrf =
+        InProcessVrf::new(vrf_creds.account_address, vrf_creds.private_key, vrf_creds.secret_key);
+
+    let info = vrf.info();
+    let x = Felt::from_dec_str(&vrf_creds.public_key.x.to_string()).unwrap();
+    let y = Felt::from_dec_str(&vrf_creds.public_key.y.to_string()).unwrap();
+
+    assert_eq!(Felt::from_hex(&info.public_key_x).unwrap(), x);
+    assert_eq!(Felt::from_hex(&info.public_key_y).unwrap(), y);
+}
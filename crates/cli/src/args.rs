@@ -2,6 +2,7 @@
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use alloy_primitives::U256;
 #[cfg(feature = "server")]
@@ -11,20 +12,22 @@ pub use clap::Parser;
 use katana_chain_spec::rollup::ChainConfigDir;
 use katana_chain_spec::ChainSpec;
 use katana_core::constants::DEFAULT_SEQUENCER_ADDRESS;
+use katana_gas_price_oracle::{DynamicGasPriceConfig, SampledPriceOracleConfig};
 use katana_genesis::allocation::DevAllocationsGenerator;
 use katana_genesis::constant::{
     DEFAULT_FROZEN_DEV_ACCOUNT_ADDRESS_CLASS_HASH, DEFAULT_PREFUNDED_ACCOUNT_BALANCE,
 };
 use katana_messaging::MessagingConfig;
+use katana_primitives::Felt;
 use katana_sequencer_node::config::db::DbConfig;
 use katana_sequencer_node::config::dev::{DevConfig, FixedL1GasPriceConfig};
 use katana_sequencer_node::config::execution::ExecutionConfig;
-use katana_sequencer_node::config::fork::ForkingConfig;
+use katana_sequencer_node::config::fork::{ForkBackendConfig, ForkingConfig};
 use katana_sequencer_node::config::gateway::GatewayConfig;
 #[cfg(all(feature = "server", feature = "grpc"))]
 use katana_sequencer_node::config::grpc::GrpcConfig;
 use katana_sequencer_node::config::metrics::MetricsConfig;
-use katana_sequencer_node::config::paymaster::{PaymasterConfig, VrfConfig};
+use katana_sequencer_node::config::paymaster::{PaymasterConfig, VrfConfig, VrfProviderConfig};
 use katana_sequencer_node::config::rpc::RpcConfig;
 #[cfg(feature = "server")]
 use katana_sequencer_node::config::rpc::{RpcModuleKind, RpcModulesList};
@@ -68,6 +71,31 @@ pub struct SequencerNodeArgs {
     #[arg(value_name = "TOTAL")]
     pub block_cairo_steps_limit: Option<u64>,
 
+    /// The maximum amount of L2 gas that can be consumed in a block.
+    #[arg(long = "sequencing.block-max-l2-gas")]
+    #[arg(value_name = "TOTAL")]
+    pub block_l2_gas_limit: Option<u64>,
+
+    /// The maximum number of transactions in a block.
+    #[arg(long = "sequencing.block-max-txs")]
+    #[arg(value_name = "TOTAL")]
+    pub block_txs_limit: Option<u64>,
+
+    /// The maximum size of the state diff of a block, in number of felts.
+    #[arg(long = "sequencing.block-max-state-diff-size")]
+    #[arg(value_name = "TOTAL")]
+    pub block_state_diff_size_limit: Option<u64>,
+
+    /// The maximum amount of L1 data gas that can be consumed in a block.
+    #[arg(long = "sequencing.block-max-l1-data-gas")]
+    #[arg(value_name = "TOTAL")]
+    pub block_l1_data_gas_limit: Option<u64>,
+
+    /// The maximum number of events that can be emitted in a block.
+    #[arg(long = "sequencing.block-max-events")]
+    #[arg(value_name = "TOTAL")]
+    pub block_events_limit: Option<u64>,
+
     /// Disable state trie computation during block production.
     ///
     /// When set, block headers carry `state_root = 0` and no trie data is
@@ -76,6 +104,22 @@ pub struct SequencerNodeArgs {
     #[arg(long)]
     pub no_state_trie: bool,
 
+    /// Private key used to sign every produced block.
+    ///
+    /// Each signature covers the block hash and its state diff commitment, and is served by the
+    /// feeder gateway `get_signature` endpoint alongside the public key at `get_public_key`.
+    #[arg(long = "sequencing.signing-key", value_name = "PRIVATE_KEY")]
+    #[arg(env = "KATANA_SEQUENCING_SIGNING_KEY", hide_env_values = true)]
+    #[arg(conflicts_with = "block_signing_key_file")]
+    #[serde(skip_serializing)]
+    pub block_signing_key: Option<Felt>,
+
+    /// Path to a file containing the private key used to sign every produced block.
+    ///
+    /// Alternative to `--sequencing.signing-key` that keeps the key out of the command line.
+    #[arg(long = "sequencing.signing-key-file", value_name = "PATH")]
+    pub block_signing_key_file: Option<PathBuf>,
+
     /// Configuration file
     #[arg(long)]
     pub config: Option<PathBuf>,
@@ -185,7 +229,7 @@ impl SequencerNodeArgs {
             None
         };
 
-        let vrf_bin = if self.cartridge.vrf.enabled && !self.cartridge.vrf.is_external() {
+        let vrf_bin = if self.cartridge.vrf.enabled && self.cartridge.vrf.is_sidecar() {
             use anyhow::anyhow;
 
             use crate::sidecar::{resolve_sidecar_binary, SidecarKind};
@@ -237,7 +281,7 @@ impl SequencerNodeArgs {
 
                 let paymaster_cfg = handle.node().config().paymaster.as_ref().unwrap();
                 let cartridge_api_cfg = paymaster_cfg.cartridge_api.as_ref().unwrap();
-                let vrf_url = cartridge_api_cfg.vrf.as_ref().unwrap().url.clone();
+                let vrf_url = cartridge_api_cfg.vrf.as_ref().and_then(|v| v.url()).unwrap().clone();
 
                 let vrf = bootstrap_vrf(
                     bin_path,
@@ -254,6 +298,13 @@ impl SequencerNodeArgs {
                 None
             };
 
+            if self.cartridge.vrf.enabled && self.cartridge.vrf.in_process {
+                use crate::sidecar::bootstrap_vrf_contracts;
+
+                bootstrap_vrf_contracts(*handle.rpc().addr(), &handle.node().config().chain)
+                    .await?;
+            }
+
             // Wait until an OS signal (ie SIGINT, SIGTERM) is received or the node is shutdown.
             tokio::select! {
                 _ = katana_utils::wait_shutdown_signals() => {
@@ -314,7 +365,7 @@ impl SequencerNodeArgs {
 
                 let paymaster_cfg = handle.node().config().paymaster.as_ref().unwrap();
                 let cartridge_api_cfg = paymaster_cfg.cartridge_api.as_ref().unwrap();
-                let vrf_url = cartridge_api_cfg.vrf.as_ref().unwrap().url.clone();
+                let vrf_url = cartridge_api_cfg.vrf.as_ref().and_then(|v| v.url()).unwrap().clone();
 
                 let vrf = bootstrap_vrf(
                     bin_path,
@@ -331,6 +382,13 @@ impl SequencerNodeArgs {
                 None
             };
 
+            if self.cartridge.vrf.enabled && self.cartridge.vrf.in_process {
+                use crate::sidecar::bootstrap_vrf_contracts;
+
+                bootstrap_vrf_contracts(*handle.rpc().addr(), &handle.node().config().chain)
+                    .await?;
+            }
+
             // Wait until an OS signal (ie SIGINT, SIGTERM) is received or the node is shutdown.
             tokio::select! {
                 _ = katana_utils::wait_shutdown_signals() => {
@@ -361,12 +419,12 @@ impl SequencerNodeArgs {
         let dev = self.dev_config();
         let (chain, cs_messaging) = self.chain_spec()?;
         let metrics = self.metrics_config();
-        let gateway = self.gateway_config();
+        let gateway = self.gateway_config()?;
         #[cfg(all(feature = "server", feature = "grpc"))]
-        let grpc = self.grpc_config();
+        let grpc = self.grpc_config()?;
         let forking = self.forking_config()?;
         let execution = self.execution_config();
-        let sequencing = self.sequencer_config();
+        let sequencing = self.sequencer_config()?;
 
         let paymaster = self.paymaster_config(&chain)?;
 
@@ -394,13 +452,125 @@ impl SequencerNodeArgs {
         })
     }
 
-    fn sequencer_config(&self) -> SequencingConfig {
-        SequencingConfig {
+    fn sequencer_config(&self) -> Result<SequencingConfig> {
+        let limits = [
+            ("--sequencing.block-max-cairo-steps", self.block_cairo_steps_limit),
+            ("--sequencing.block-max-l2-gas", self.block_l2_gas_limit),
+            ("--sequencing.block-max-txs", self.block_txs_limit),
+            ("--sequencing.block-max-state-diff-size", self.block_state_diff_size_limit),
+            ("--sequencing.block-max-l1-data-gas", self.block_l1_data_gas_limit),
+            ("--sequencing.block-max-events", self.block_events_limit),
+        ];
+
+        // A zero limit can't fit any transaction, and would only produce empty blocks.
+        for (name, limit) in limits {
+            if limit == Some(0) {
+                bail!("`{name}` must be greater than zero");
+            }
+        }
+
+        Ok(SequencingConfig {
             block_time: self.block_time,
             no_mining: self.no_mining,
             block_cairo_steps_limit: self.block_cairo_steps_limit,
+            block_l2_gas_limit: self.block_l2_gas_limit,
+            block_txs_limit: self.block_txs_limit,
+            block_state_diff_size_limit: self.block_state_diff_size_limit,
+            block_l1_data_gas_limit: self.block_l1_data_gas_limit,
+            block_events_limit: self.block_events_limit,
             no_state_trie: self.no_state_trie,
+            block_signing_key: self.block_signing_key()?,
+            dynamic_l2_gas_price: self.dynamic_l2_gas_price_config(),
+            gas_price_sampling: self.gas_price_sampling_config(),
+            gas_price_fallback_urls: self.gpo.fallback_urls.clone(),
+        })
+    }
+
+    /// Returns the block signing key, reading it from the key file if one is provided.
+    fn block_signing_key(&self) -> Result<Option<Felt>> {
+        let Some(path) = &self.block_signing_key_file else { return Ok(self.block_signing_key) };
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read signing key file {}", path.display()))?;
+        let key = content
+            .trim()
+            .parse::<Felt>()
+            .with_context(|| format!("invalid signing key in file {}", path.display()))?;
+
+        Ok(Some(key))
+    }
+
+    fn gas_price_sampling_config(&self) -> SampledPriceOracleConfig {
+        let mut config = SampledPriceOracleConfig::default();
+
+        if let Some(interval) = self.gpo.sample_interval {
+            config.interval = Duration::from_secs(interval);
+        }
+
+        if let Some(window_size) = self.gpo.window_size {
+            config.window_size = window_size;
+        }
+
+        if let Some(aggregation) = self.gpo.aggregation {
+            config.aggregation = aggregation;
+        }
+
+        if let Some(max_staleness) = self.gpo.max_staleness {
+            config.max_staleness = Duration::from_secs(max_staleness);
+        }
+
+        if let Some(eth) = self.gpo.fallback_l2_eth_gas_price {
+            config.fallback_prices.l2_gas_prices.eth = eth;
+        }
+
+        if let Some(strk) = self.gpo.fallback_l2_strk_gas_price {
+            config.fallback_prices.l2_gas_prices.strk = strk;
+        }
+
+        if let Some(eth) = self.gpo.fallback_l1_eth_gas_price {
+            config.fallback_prices.l1_gas_prices.eth = eth;
+        }
+
+        if let Some(strk) = self.gpo.fallback_l1_strk_gas_price {
+            config.fallback_prices.l1_gas_prices.strk = strk;
+        }
+
+        if let Some(eth) = self.gpo.fallback_l1_eth_data_gas_price {
+            config.fallback_prices.l1_data_gas_prices.eth = eth;
+        }
+
+        if let Some(strk) = self.gpo.fallback_l1_strk_data_gas_price {
+            config.fallback_prices.l1_data_gas_prices.strk = strk;
+        }
+
+        config
+    }
+
+    fn dynamic_l2_gas_price_config(&self) -> Option<DynamicGasPriceConfig> {
+        let target_gas = self.gpo.l2_target_gas?;
+        let mut config = DynamicGasPriceConfig { target_gas, ..Default::default() };
+
+        if let Some(eth) = self.gpo.l2_min_eth_gas_price {
+            config.min_prices.eth = eth;
+        }
+
+        if let Some(strk) = self.gpo.l2_min_strk_gas_price {
+            config.min_prices.strk = strk;
+        }
+
+        if let Some(eth) = self.gpo.l2_max_eth_gas_price {
+            config.max_prices.eth = eth;
+        }
+
+        if let Some(strk) = self.gpo.l2_max_strk_gas_price {
+            config.max_prices.strk = strk;
+        }
+
+        if let Some(denominator) = self.gpo.l2_max_change_denominator {
+            config.max_change_denominator = denominator;
         }
+
+        Some(config)
     }
 
     pub fn rpc_config(&self) -> Result<RpcConfig> {
@@ -456,6 +626,9 @@ impl SequencerNodeArgs {
                 max_event_page_size: Some(self.server.max_event_page_size),
                 max_proof_keys: Some(self.server.max_proof_keys),
                 max_call_gas: Some(self.server.max_call_gas),
+                auth: self.server.auth_config(),
+                rate_limit: self.server.rate_limit_config(),
+                tls: self.server.tls_config()?,
             })
         }
 
@@ -551,6 +724,7 @@ impl SequencerNodeArgs {
             validation_max_steps: self.starknet.environment.validate_max_steps,
             #[cfg(feature = "native")]
             compile_native: self.starknet.environment.compile_native,
+            parallel_execution: self.starknet.environment.parallel_execution,
             ..Default::default()
         }
     }
@@ -561,6 +735,9 @@ impl SequencerNodeArgs {
                 url: url.clone(),
                 block: self.forking.fork_block,
                 init_dev_genesis: !self.forking.no_dev_genesis,
+                pre_confirmed: self.forking.fork_pre_confirmed,
+                cache_dir: self.forking.fork_cache_dir.clone(),
+                backend: self.fork_backend_config(),
             };
             return Ok(Some(cfg));
         }
@@ -568,6 +745,20 @@ impl SequencerNodeArgs {
         Ok(None)
     }
 
+    fn fork_backend_config(&self) -> ForkBackendConfig {
+        let mut config = ForkBackendConfig {
+            max_requests_per_second: self.forking.fork_requests_per_second,
+            fallback_urls: self.forking.fork_fallback_urls.clone(),
+            ..Default::default()
+        };
+
+        if let Some(max_retries) = self.forking.fork_max_retries {
+            config.max_retries = max_retries;
+        }
+
+        config
+    }
+
     fn db_config(&self) -> Result<DbConfig> {
         let mut migrate = self.db.migrate;
 
@@ -594,36 +785,38 @@ impl SequencerNodeArgs {
         None
     }
 
-    fn gateway_config(&self) -> Option<GatewayConfig> {
+    fn gateway_config(&self) -> Result<Option<GatewayConfig>> {
         #[cfg(feature = "server")]
         if self.gateway.enable {
             use std::time::Duration;
 
-            Some(GatewayConfig {
+            Ok(Some(GatewayConfig {
                 addr: self.gateway.gateway_addr,
                 port: self.gateway.gateway_port,
                 timeout: Some(Duration::from_secs(self.gateway.gateway_timeout)),
-            })
+                tls: self.gateway.tls_config()?,
+            }))
         } else {
-            None
+            Ok(None)
         }
 
         #[cfg(not(feature = "server"))]
-        None
+        Ok(None)
     }
 
     #[cfg(all(feature = "server", feature = "grpc"))]
-    fn grpc_config(&self) -> Option<GrpcConfig> {
+    fn grpc_config(&self) -> Result<Option<GrpcConfig>> {
         if self.grpc.grpc_enable {
             use std::time::Duration;
 
-            Some(GrpcConfig {
+            Ok(Some(GrpcConfig {
                 addr: self.grpc.grpc_addr,
                 port: self.grpc.grpc_port,
                 timeout: self.grpc.grpc_timeout.map(Duration::from_secs),
-            })
+                tls: self.grpc.tls_config()?,
+            }))
         } else {
-            None
+            Ok(None)
         }
     }
 
@@ -706,7 +899,19 @@ impl SequencerNodeArgs {
             let url = options.url.clone().expect("must be set if external");
             let vrf_account = options.vrf_account_contract.expect("must be set if external");
 
-            Ok(Some(VrfConfig { url, vrf_account }))
+            Ok(Some(VrfConfig { provider: VrfProviderConfig::Remote(url), vrf_account }))
+        } else if options.in_process {
+            use cartridge::get_default_vrf_account;
+
+            let vrf_account_info = get_default_vrf_account()?;
+
+            Ok(Some(VrfConfig {
+                provider: VrfProviderConfig::InProcess {
+                    vrf_account_private_key: vrf_account_info.private_key,
+                    secret_key: vrf_account_info.secret_key,
+                },
+                vrf_account: vrf_account_info.account_address,
+            }))
         } else {
             use cartridge::get_default_vrf_account;
 
@@ -717,7 +922,10 @@ impl SequencerNodeArgs {
             let vrf_account_info = get_default_vrf_account()?;
             let vrf_account_address = vrf_account_info.account_address;
 
-            Ok(Some(VrfConfig { url, vrf_account: vrf_account_address }))
+            Ok(Some(VrfConfig {
+                provider: VrfProviderConfig::Remote(url),
+                vrf_account: vrf_account_address,
+            }))
         }
     }
 
@@ -750,6 +958,22 @@ impl SequencerNodeArgs {
             self.block_time = config.block_time;
         }
 
+        self.block_cairo_steps_limit =
+            self.block_cairo_steps_limit.or(config.block_cairo_steps_limit);
+        self.block_l2_gas_limit = self.block_l2_gas_limit.or(config.block_l2_gas_limit);
+        self.block_txs_limit = self.block_txs_limit.or(config.block_txs_limit);
+        self.block_state_diff_size_limit =
+            self.block_state_diff_size_limit.or(config.block_state_diff_size_limit);
+        self.block_l1_data_gas_limit =
+            self.block_l1_data_gas_limit.or(config.block_l1_data_gas_limit);
+        self.block_events_limit = self.block_events_limit.or(config.block_events_limit);
+        // A key given on the command line or in the environment takes precedence over any key, or
+        // key file, from the config file.
+        if self.block_signing_key.is_none() && self.block_signing_key_file.is_none() {
+            self.block_signing_key = config.block_signing_key;
+            self.block_signing_key_file = config.block_signing_key_file;
+        }
+
         self.db.merge(config.db.as_ref());
 
         if self.logging == LoggingOptions::default() {
@@ -820,13 +1044,16 @@ impl SequencerNodeArgs {
 
 #[cfg(test)]
 mod test {
+    use std::num::NonZeroU32;
     use std::str::FromStr;
 
     use assert_matches::assert_matches;
     use katana_gas_price_oracle::{
-        DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_ETH_L2_GAS_PRICE,
-        DEFAULT_STRK_L1_DATA_GAS_PRICE, DEFAULT_STRK_L1_GAS_PRICE,
+        Aggregation, SampledPriceOracleConfig, DEFAULT_ETH_L1_DATA_GAS_PRICE,
+        DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
+        DEFAULT_STRK_L1_GAS_PRICE,
     };
+    use katana_primitives::block::GasPrice;
     use katana_primitives::chain::ChainId;
     use katana_primitives::{address, felt, Felt};
     use katana_sequencer_node::config::execution::{
@@ -1031,6 +1258,247 @@ mod test {
         })
     }
 
+    #[test]
+    fn dynamic_l2_gas_price() {
+        let config =
+            SequencerNodeArgs::parse_from(["katana"]).config(crate::BuildInfo::default()).unwrap();
+        assert_eq!(config.sequencing.dynamic_l2_gas_price, None);
+
+        let config = SequencerNodeArgs::parse_from([
+            "katana",
+            "--gpo.l2-target-gas",
+            "1000000",
+            "--gpo.l2-min-strk-gas-price",
+            "10",
+            "--gpo.l2-max-strk-gas-price",
+            "100",
+            "--gpo.l2-max-change-denominator",
+            "4",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        assert_matches!(config.sequencing.dynamic_l2_gas_price, Some(dynamic) => {
+            assert_eq!(dynamic.target_gas, 1_000_000);
+            assert_eq!(dynamic.min_prices.strk.get(), 10);
+            assert_eq!(dynamic.max_prices.strk.get(), 100);
+            assert_eq!(dynamic.min_prices.eth, GasPrice::MIN);
+            assert_eq!(dynamic.max_prices.eth, GasPrice::MAX);
+            assert_eq!(dynamic.max_change_denominator, 4);
+        });
+
+        // The bounds can't be set without a target.
+        let result =
+            SequencerNodeArgs::try_parse_from(["katana", "--gpo.l2-min-eth-gas-price", "1"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn gas_price_sampling() {
+        let config =
+            SequencerNodeArgs::parse_from(["katana"]).config(crate::BuildInfo::default()).unwrap();
+        assert_eq!(config.sequencing.gas_price_sampling, SampledPriceOracleConfig::default());
+        assert!(config.sequencing.gas_price_fallback_urls.is_empty());
+
+        let config = SequencerNodeArgs::parse_from([
+            "katana",
+            "--gpo.sample-interval",
+            "12",
+            "--gpo.window-size",
+            "10",
+            "--gpo.aggregation",
+            "p90",
+            "--gpo.max-staleness",
+            "120",
+            "--gpo.fallback-url",
+            "http://localhost:1234,http://localhost:5678",
+            "--gpo.fallback-l1-eth-gas-price",
+            "30",
+            "--gpo.fallback-l1-strk-data-gas-price",
+            "40",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let sampling = &config.sequencing.gas_price_sampling;
+        assert_eq!(sampling.interval, Duration::from_secs(12));
+        assert_eq!(sampling.window_size, 10);
+        assert_eq!(sampling.aggregation, Aggregation::Percentile(90));
+        assert_eq!(sampling.max_staleness, Duration::from_secs(120));
+        assert_eq!(sampling.fallback_prices.l1_gas_prices.eth.get(), 30);
+        assert_eq!(sampling.fallback_prices.l1_data_gas_prices.strk.get(), 40);
+        assert_eq!(sampling.fallback_prices.l2_gas_prices.eth, DEFAULT_ETH_L2_GAS_PRICE);
+        assert_eq!(
+            config.sequencing.gas_price_fallback_urls,
+            vec![
+                Url::parse("http://localhost:1234").unwrap(),
+                Url::parse("http://localhost:5678").unwrap()
+            ]
+        );
+
+        let result = SequencerNodeArgs::try_parse_from(["katana", "--gpo.aggregation", "mean"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn gas_price_sampling_from_config_file() {
+        let content = r#"
+[gpo]
+aggregation = "median"
+fallback_l2_strk_gas_price = "0x10"
+fallback_l1_eth_data_gas_price = "20"
+        "#;
+        let path = std::env::temp_dir().join("katana-gpo-config.toml");
+        std::fs::write(&path, content).unwrap();
+
+        let path_str = path.to_string_lossy().to_string();
+        let config = SequencerNodeArgs::parse_from(["katana", "--config", path_str.as_str()])
+            .with_config_file()
+            .unwrap()
+            .config(crate::BuildInfo::default())
+            .unwrap();
+
+        let sampling = &config.sequencing.gas_price_sampling;
+        assert_eq!(sampling.aggregation, Aggregation::Median);
+        assert_eq!(sampling.fallback_prices.l2_gas_prices.strk.get(), 16);
+        assert_eq!(sampling.fallback_prices.l1_data_gas_prices.eth.get(), 20);
+
+        // An explicit `null` is the same as not setting the aggregation at all.
+        let gpo: GasPriceOracleOptions = serde_json::from_str(r#"{"aggregation":null}"#).unwrap();
+        assert_eq!(gpo.aggregation, None);
+    }
+
+    #[test]
+    fn block_signing_key_from_file() {
+        let path = std::env::temp_dir().join("katana-signing-key");
+        std::fs::write(&path, "0x1337\n").unwrap();
+
+        let path_str = path.to_string_lossy().to_string();
+        let args = SequencerNodeArgs::parse_from([
+            "katana",
+            "--sequencing.signing-key-file",
+            path_str.as_str(),
+        ]);
+        let config = args.config(crate::BuildInfo::default()).unwrap();
+        assert_eq!(config.sequencing.block_signing_key, Some(felt!("0x1337")));
+
+        // The key must not show up in the debug output of the config.
+        let debug = format!("{:?}", config.sequencing);
+        assert!(!debug.contains(&format!("{:?}", felt!("0x1337"))));
+        assert!(!debug.contains("1337"));
+
+        let result = SequencerNodeArgs::try_parse_from([
+            "katana",
+            "--sequencing.signing-key",
+            "0x1337",
+            "--sequencing.signing-key-file",
+            path_str.as_str(),
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn block_signing_key_is_not_serialized() {
+        let args = SequencerNodeArgs::parse_from(["katana", "--sequencing.signing-key", "0x1337"]);
+        let config = NodeArgsConfig::try_from(args).unwrap();
+        assert_eq!(config.block_signing_key, Some(felt!("0x1337")));
+
+        let serialized = toml::to_string(&config).unwrap();
+        assert!(!serialized.contains("block_signing_key"));
+    }
+
+    #[test]
+    fn fork_cache_dir() {
+        let config = SequencerNodeArgs::parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050",
+            "--fork.cache-dir",
+            "/path/to/cache",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let forking = config.forking.expect("must be forked");
+        assert_eq!(forking.cache_dir, Some(PathBuf::from("/path/to/cache")));
+
+        let result =
+            SequencerNodeArgs::try_parse_from(["katana", "--fork.cache-dir", "/path/to/cache"]);
+        assert!(result.is_err(), "requires a fork provider");
+    }
+
+    #[test]
+    fn fork_pre_confirmed() {
+        let config = SequencerNodeArgs::parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050",
+            "--fork.pre-confirmed",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let forking = config.forking.expect("must be forked");
+        assert!(forking.pre_confirmed);
+        assert_eq!(forking.block, None);
+
+        let result = SequencerNodeArgs::try_parse_from(["katana", "--fork.pre-confirmed"]);
+        assert!(result.is_err(), "requires a fork provider");
+
+        let result = SequencerNodeArgs::try_parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050",
+            "--fork.block",
+            "1",
+            "--fork.pre-confirmed",
+        ]);
+        assert!(result.is_err(), "conflicts with fork block");
+    }
+
+    #[test]
+    fn fork_backend() {
+        let config =
+            SequencerNodeArgs::parse_from(["katana", "--fork.provider", "http://localhost:5050"])
+                .config(crate::BuildInfo::default())
+                .unwrap();
+        assert_eq!(config.forking.unwrap().backend, ForkBackendConfig::default());
+
+        let config = SequencerNodeArgs::parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050",
+            "--fork.fallback-url",
+            "http://localhost:1234,http://localhost:5678",
+            "--fork.max-retries",
+            "3",
+            "--fork.requests-per-second",
+            "20",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let backend = config.forking.unwrap().backend;
+        assert_eq!(backend.max_retries, 3);
+        assert_eq!(backend.max_requests_per_second, NonZeroU32::new(20));
+        assert_eq!(
+            backend.fallback_urls,
+            vec![
+                Url::parse("http://localhost:1234").unwrap(),
+                Url::parse("http://localhost:5678").unwrap()
+            ]
+        );
+
+        let result = SequencerNodeArgs::try_parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050",
+            "--fork.requests-per-second",
+            "0",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn config_from_file_and_cli() {
         // CLI args must take precedence over the config file.
@@ -1100,6 +1568,52 @@ explorer = true
         assert!(config.rpc.explorer);
     }
 
+    #[test]
+    fn block_limits() {
+        let result = SequencerNodeArgs::parse_from([
+            "katana",
+            "--sequencing.block-max-cairo-steps",
+            "1000",
+            "--sequencing.block-max-l2-gas",
+            "2000",
+            "--sequencing.block-max-txs",
+            "10",
+            "--sequencing.block-max-state-diff-size",
+            "300",
+            "--sequencing.block-max-l1-data-gas",
+            "4000",
+            "--sequencing.block-max-events",
+            "50",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let limits = result.sequencing.block_limits();
+        assert_eq!(limits.cairo_steps, 1000);
+        assert_eq!(limits.l2_gas, Some(2000));
+        assert_eq!(limits.transactions, Some(10));
+        assert_eq!(limits.state_diff_size, Some(300));
+        assert_eq!(limits.l1_data_gas, Some(4000));
+        assert_eq!(limits.events, Some(50));
+
+        // Only the Cairo steps are limited by default.
+        let limits = SequencerNodeArgs::parse_from(["katana"])
+            .config(crate::BuildInfo::default())
+            .unwrap()
+            .sequencing
+            .block_limits();
+        assert_eq!(limits.l2_gas, None);
+        assert_eq!(limits.transactions, None);
+        assert_eq!(limits.state_diff_size, None);
+        assert_eq!(limits.l1_data_gas, None);
+        assert_eq!(limits.events, None);
+
+        let err = SequencerNodeArgs::parse_from(["katana", "--sequencing.block-max-txs", "0"])
+            .config(crate::BuildInfo::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("`--sequencing.block-max-txs` must be greater than zero"));
+    }
+
     #[test]
     #[cfg(feature = "server")]
     fn parse_cors_origins() {
@@ -1177,6 +1691,132 @@ explorer = true
         assert!(modules.contains(&RpcModuleKind::Node));
     }
 
+    #[cfg(feature = "server")]
+    #[test]
+    fn rpc_auth() {
+        // Authentication is disabled if no credentials are configured.
+        let result =
+            SequencerNodeArgs::parse_from(["katana"]).config(crate::BuildInfo::default()).unwrap();
+        assert!(result.rpc.auth.is_none());
+
+        let result = SequencerNodeArgs::parse_from([
+            "katana",
+            "--rpc.api-key",
+            "admin",
+            "--rpc.api-key",
+            "ops:dev_*,katana_*",
+            "--rpc.jwt-secret",
+            "secret",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let auth = result.rpc.auth.expect("auth must be enabled");
+        assert_eq!(auth.public_methods, vec!["starknet_*", "node_*"]);
+        assert_eq!(auth.jwt_secret.as_deref(), Some("secret"));
+        assert_eq!(auth.jwt_methods, vec!["*"]);
+        assert_eq!(auth.api_keys.len(), 2);
+        assert_eq!(auth.api_keys[0].key, "admin");
+        assert_eq!(auth.api_keys[0].methods, vec!["*"]);
+        assert_eq!(auth.api_keys[1].key, "ops");
+        assert_eq!(auth.api_keys[1].methods, vec!["dev_*", "katana_*"]);
+
+        let result = SequencerNodeArgs::parse_from([
+            "katana",
+            "--rpc.jwt-secret",
+            "secret",
+            "--rpc.jwt-methods",
+            "katana_*",
+            "--rpc.public-methods",
+            "starknet_chainId,starknet_blockNumber",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let auth = result.rpc.auth.expect("auth must be enabled");
+        assert!(auth.api_keys.is_empty());
+        assert_eq!(auth.jwt_methods, vec!["katana_*"]);
+        assert_eq!(auth.public_methods, vec!["starknet_chainId", "starknet_blockNumber"]);
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn rpc_rate_limit() {
+        let result =
+            SequencerNodeArgs::parse_from(["katana"]).config(crate::BuildInfo::default()).unwrap();
+        assert!(result.rpc.rate_limit.is_none());
+
+        let result = SequencerNodeArgs::parse_from([
+            "katana",
+            "--rpc.rate-limit",
+            "100",
+            "--rpc.rate-limit-burst",
+            "200",
+            "--rpc.rate-limit-weight",
+            "starknet_getEvents=50",
+            "--rpc.rate-limit-weight",
+            "starknet_call=2",
+            "--rpc.rate-limit-trust-proxy",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let rate_limit = result.rpc.rate_limit.expect("rate limiting must be enabled");
+        assert_eq!(rate_limit.requests_per_second.get(), 100);
+        assert_eq!(rate_limit.burst.map(|b| b.get()), Some(200));
+        assert!(rate_limit.trust_proxy_headers);
+        assert_eq!(rate_limit.method_weights.len(), 2);
+        assert_eq!(rate_limit.method_weights[0].method, "starknet_getEvents");
+        assert_eq!(rate_limit.method_weights[0].weight, 50);
+
+        // The rate limit options require the rate limit to be enabled.
+        let result = SequencerNodeArgs::try_parse_from(["katana", "--rpc.rate-limit-burst", "200"]);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn server_tls() {
+        use std::path::PathBuf;
+
+        let result =
+            SequencerNodeArgs::parse_from(["katana"]).config(crate::BuildInfo::default()).unwrap();
+        assert!(result.rpc.tls.is_none());
+
+        let result = SequencerNodeArgs::parse_from([
+            "katana",
+            "--http.tls-cert",
+            "server.pem",
+            "--http.tls-key",
+            "server.key",
+            "--http.tls-client-ca",
+            "ca.pem",
+            "--gateway",
+            "--gateway.tls-cert",
+            "gateway.pem",
+            "--gateway.tls-key",
+            "gateway.key",
+        ])
+        .config(crate::BuildInfo::default())
+        .unwrap();
+
+        let tls = result.rpc.tls.expect("rpc tls must be enabled");
+        assert_eq!(tls.cert, PathBuf::from("server.pem"));
+        assert_eq!(tls.key, PathBuf::from("server.key"));
+        assert_eq!(tls.client_ca, Some(PathBuf::from("ca.pem")));
+
+        let tls = result.gateway.unwrap().tls.expect("gateway tls must be enabled");
+        assert_eq!(tls.cert, PathBuf::from("gateway.pem"));
+        assert_eq!(tls.key, PathBuf::from("gateway.key"));
+        assert_eq!(tls.client_ca, None);
+
+        // The certificate and the key must be set together.
+        let result = SequencerNodeArgs::try_parse_from(["katana", "--http.tls-cert", "server.pem"]);
+        assert!(result.is_err());
+        let result = SequencerNodeArgs::try_parse_from(["katana", "--http.tls-key", "server.key"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn cartridge_paymaster() {
         // Test with --paymaster flag (sidecar mode)
@@ -1,7 +1,8 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use katana_messaging::MessagingConfig;
+use katana_primitives::Felt;
 use serde::{Deserialize, Serialize};
 
 use crate::options::*;
@@ -13,7 +14,17 @@ pub struct NodeArgsConfig {
     pub no_mining: Option<bool>,
     pub block_time: Option<u64>,
     pub block_cairo_steps_limit: Option<u64>,
+    pub block_l2_gas_limit: Option<u64>,
+    pub block_txs_limit: Option<u64>,
+    pub block_state_diff_size_limit: Option<u64>,
+    pub block_l1_data_gas_limit: Option<u64>,
+    pub block_events_limit: Option<u64>,
     pub no_state_trie: Option<bool>,
+    /// Never written out, so that the key doesn't leak into generated config files. Use
+    /// `block_signing_key_file` instead.
+    #[serde(skip_serializing)]
+    pub block_signing_key: Option<Felt>,
+    pub block_signing_key_file: Option<PathBuf>,
     #[serde(flatten)]
     pub db: Option<DbOptions>,
     pub messaging: Option<MessagingConfig>,
@@ -53,7 +64,14 @@ impl TryFrom<SequencerNodeArgs> for NodeArgsConfig {
             no_mining: if args.no_mining { Some(true) } else { None },
             block_time: args.block_time,
             block_cairo_steps_limit: args.block_cairo_steps_limit,
+            block_l2_gas_limit: args.block_l2_gas_limit,
+            block_txs_limit: args.block_txs_limit,
+            block_state_diff_size_limit: args.block_state_diff_size_limit,
+            block_l1_data_gas_limit: args.block_l1_data_gas_limit,
+            block_events_limit: args.block_events_limit,
             no_state_trie: if args.no_state_trie { Some(true) } else { None },
+            block_signing_key: args.block_signing_key,
+            block_signing_key_file: args.block_signing_key_file,
             db: (!args.db.is_default()).then_some(args.db),
             messaging: args.messaging,
             ..Default::default()
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
 pub use clap::Parser;
+use katana_chain_spec::rollup::ChainConfigDir;
+use katana_core::constants::DEFAULT_SEQUENCER_ADDRESS;
 use katana_full_node::config::db::DbConfig;
 use katana_full_node::config::gateway::GatewayConfig;
 use katana_full_node::config::metrics::MetricsConfig;
@@ -9,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use crate::options::*;
-use crate::utils::prompt_db_migration;
+use crate::utils::{parse_chain_config_dir, prompt_db_migration};
 
 pub(crate) const LOG_TARGET: &str = "katana::cli::full";
 
@@ -21,7 +23,16 @@ pub struct FullNodeArgs {
     pub silent: bool,
 
     #[arg(long)]
-    pub network: Network,
+    #[arg(required_unless_present = "chain")]
+    pub network: Option<Network>,
+
+    /// Path to the configuration of a custom chain (eg a Katana appchain) to follow instead of
+    /// a public network. Requires either `--sync.gateway` or `--sync.rpc` pointing to the
+    /// chain's sequencer.
+    #[arg(long, value_name = "PATH")]
+    #[arg(value_parser = parse_chain_config_dir)]
+    #[arg(conflicts_with = "network")]
+    pub chain: Option<ChainConfigDir>,
 
     /// Gateway API key for accessing the sequencer gateway.
     #[arg(long)]
@@ -111,7 +122,8 @@ impl FullNodeArgs {
         let rpc = self.rpc_config()?;
         let metrics = self.metrics_config();
         let pruning = self.pruning_config();
-        let gateway = self.gateway_config();
+        let gateway = self.gateway_config()?;
+        let chain = self.chain_spec()?;
 
         Ok(katana_full_node::Config {
             db,
@@ -119,13 +131,16 @@ impl FullNodeArgs {
             metrics,
             pruning,
             gateway,
-            network: self.network,
+            network: self.network.unwrap_or_default(),
+            chain,
             gateway_api_key: self.gateway_api_key.clone(),
             sync: SyncConfig {
                 max_tip: self.sync.tip,
                 source: self.sync_source(),
                 chunk_size: Some(self.sync.chunk_size),
                 stages: self.sync.stages.clone().unwrap_or_default(),
+                sequencer_public_key: self.sync.public_key,
+                tls: self.sync.tls_config()?,
                 stage: katana_full_node::StageConfig {
                     blocks_batch_size: self.stage.blocks_batch_size,
                     classes_batch_size: self.stage.classes_batch_size,
@@ -135,6 +150,17 @@ impl FullNodeArgs {
         })
     }
 
+    fn chain_spec(&self) -> Result<Option<katana_chain_spec::rollup::ChainSpec>> {
+        let Some(path) = &self.chain else { return Ok(None) };
+
+        let mut chain_spec = katana_chain_spec::rollup::read(path)?;
+        // Match the genesis sequencer address used by the sequencer when it's started with
+        // the same chain spec. Otherwise, the synced genesis block won't match the spec.
+        chain_spec.genesis.sequencer_address = *DEFAULT_SEQUENCER_ADDRESS;
+
+        Ok(Some(chain_spec))
+    }
+
     fn sync_source(&self) -> Option<SyncSource> {
         if let Some(ref url) = self.sync.rpc {
             Some(SyncSource::JsonRpc(url.clone()))
@@ -155,17 +181,18 @@ impl FullNodeArgs {
         katana_full_node::PruningConfig { distance }
     }
 
-    fn gateway_config(&self) -> Option<GatewayConfig> {
+    fn gateway_config(&self) -> Result<Option<GatewayConfig>> {
         #[cfg(feature = "server")]
         if self.gateway.enable {
-            return Some(GatewayConfig {
+            return Ok(Some(GatewayConfig {
                 addr: self.gateway.gateway_addr,
                 port: self.gateway.gateway_port,
                 timeout: Some(std::time::Duration::from_secs(self.gateway.gateway_timeout)),
-            });
+                tls: self.gateway.tls_config()?,
+            }));
         }
 
-        None
+        Ok(None)
     }
 
     fn db_config(&self) -> Result<DbConfig> {
@@ -204,6 +231,9 @@ impl FullNodeArgs {
                 max_event_page_size: Some(self.server.max_event_page_size),
                 max_proof_keys: Some(self.server.max_proof_keys),
                 max_call_gas: Some(self.server.max_call_gas),
+                auth: self.server.auth_config(),
+                rate_limit: self.server.rate_limit_config(),
+                tls: self.server.tls_config()?,
             })
         }
 
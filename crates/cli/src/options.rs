@@ -9,19 +9,23 @@
 
 #[cfg(feature = "server")]
 use std::net::IpAddr;
-use std::num::NonZeroU128;
+use std::num::{NonZeroU128, NonZeroU32};
 use std::path::PathBuf;
 
 use clap::Args;
 use katana_full_node::SyncStagesList;
+use katana_gas_price_oracle::Aggregation;
 use katana_genesis::Genesis;
 #[cfg(feature = "server")]
 use katana_node_config::gateway::{
     DEFAULT_GATEWAY_ADDR, DEFAULT_GATEWAY_PORT, DEFAULT_GATEWAY_TIMEOUT_SECS,
 };
+#[cfg(feature = "server")]
+use katana_node_config::tls::TlsConfig;
+use katana_node_config::tls::{ClientIdentity, ClientTlsConfig};
 use katana_primitives::block::{BlockHashOrNumber, GasPrice};
 use katana_primitives::chain::ChainId;
-use katana_primitives::ContractAddress;
+use katana_primitives::{ContractAddress, Felt};
 #[cfg(feature = "server")]
 use katana_rpc_server::middleware::cors::HeaderValue;
 use katana_sequencer_node::config::execution::{
@@ -30,7 +34,10 @@ use katana_sequencer_node::config::execution::{
 #[cfg(feature = "server")]
 use katana_sequencer_node::config::metrics::{DEFAULT_METRICS_ADDR, DEFAULT_METRICS_PORT};
 #[cfg(feature = "server")]
-use katana_sequencer_node::config::rpc::{RpcModulesList, DEFAULT_RPC_MAX_PROOF_KEYS};
+use katana_sequencer_node::config::rpc::{
+    RpcApiKey, RpcAuthConfig, RpcMethodWeight, RpcModulesList, RpcRateLimitConfig,
+    DEFAULT_RPC_JWT_METHODS, DEFAULT_RPC_MAX_PROOF_KEYS, DEFAULT_RPC_PUBLIC_METHODS,
+};
 #[cfg(feature = "server")]
 use katana_sequencer_node::config::rpc::{
     DEFAULT_RPC_ADDR, DEFAULT_RPC_MAX_CALL_GAS, DEFAULT_RPC_MAX_EVENT_PAGE_SIZE, DEFAULT_RPC_PORT,
@@ -153,6 +160,28 @@ pub struct GatewayOptions {
     #[arg(default_value_t = DEFAULT_GATEWAY_TIMEOUT_SECS)]
     #[serde(default = "default_feeder_gateway_timeout")]
     pub gateway_timeout: u64,
+
+    /// Path to the PEM-encoded certificate chain used to serve the gateway over TLS.
+    ///
+    /// The certificate and its key are reloaded whenever their files change.
+    #[arg(requires_all = ["gateway_enable", "gateway_tls_key"])]
+    #[arg(long = "gateway.tls-cert", value_name = "PATH")]
+    #[serde(default)]
+    pub gateway_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key used to serve the gateway over TLS.
+    #[arg(requires = "gateway_tls_cert")]
+    #[arg(long = "gateway.tls-key", value_name = "PATH")]
+    #[serde(default)]
+    pub gateway_tls_key: Option<PathBuf>,
+
+    /// Path to the PEM-encoded CA certificates used to verify the gateway's client certificates.
+    ///
+    /// When set, clients must present a certificate signed by one of these CAs (ie, mutual TLS).
+    #[arg(requires = "gateway_tls_cert")]
+    #[arg(long = "gateway.tls-client-ca", value_name = "PATH")]
+    #[serde(default)]
+    pub gateway_tls_client_ca: Option<PathBuf>,
 }
 
 #[cfg(feature = "server")]
@@ -163,10 +192,21 @@ impl Default for GatewayOptions {
             gateway_addr: DEFAULT_GATEWAY_ADDR,
             gateway_port: DEFAULT_GATEWAY_PORT,
             gateway_timeout: DEFAULT_GATEWAY_TIMEOUT_SECS,
+            gateway_tls_cert: None,
+            gateway_tls_key: None,
+            gateway_tls_client_ca: None,
         }
     }
 }
 
+#[cfg(feature = "server")]
+impl GatewayOptions {
+    /// Returns the TLS config of the gateway server, or `None` if TLS is disabled.
+    pub fn tls_config(&self) -> anyhow::Result<Option<TlsConfig>> {
+        tls_config(&self.gateway_tls_cert, &self.gateway_tls_key, &self.gateway_tls_client_ca)
+    }
+}
+
 #[cfg(feature = "server")]
 #[derive(Debug, Args, Clone, Serialize, Deserialize, PartialEq)]
 #[command(next_help_heading = "Server options")]
@@ -232,6 +272,89 @@ pub struct ServerOptions {
     #[arg(default_value_t = DEFAULT_RPC_MAX_CALL_GAS)]
     #[serde(default = "default_max_call_gas")]
     pub max_call_gas: u64,
+
+    /// API key allowed to call the RPC server, in the form of `<KEY>[:<METHODS>]`.
+    ///
+    /// `<METHODS>` is a comma-separated list of method patterns (eg, `dev_*,katana_*`) the key is
+    /// allowed to call. A key without methods is allowed to call every method. Can be specified
+    /// multiple times. Keys are passed with the `x-api-key` header or as a bearer token.
+    #[arg(long = "rpc.api-key", value_name = "KEY[:METHODS]")]
+    #[arg(value_parser = RpcApiKey::parse)]
+    #[serde(default)]
+    pub api_keys: Vec<RpcApiKey>,
+
+    /// Secret used to verify HS256 JSON Web Tokens passed as bearer tokens.
+    #[arg(long = "rpc.jwt-secret", value_name = "SECRET")]
+    #[serde(default)]
+    pub jwt_secret: Option<String>,
+
+    /// Comma separated list of method patterns that JWTs without a `methods` claim are allowed
+    /// to call.
+    #[arg(long = "rpc.jwt-methods", value_name = "METHODS")]
+    #[arg(value_delimiter = ',', default_values = DEFAULT_RPC_JWT_METHODS)]
+    #[serde(default = "default_jwt_methods")]
+    pub jwt_methods: Vec<String>,
+
+    /// Comma separated list of method patterns that can be called without credentials.
+    ///
+    /// Only applies if authentication is enabled (ie, `--rpc.api-key` or `--rpc.jwt-secret`).
+    #[arg(long = "rpc.public-methods", value_name = "METHODS")]
+    #[arg(value_delimiter = ',', default_values = DEFAULT_RPC_PUBLIC_METHODS)]
+    #[serde(default = "default_public_methods")]
+    pub public_methods: Vec<String>,
+
+    /// Enables per-client rate limiting, allowing each client to spend this many call weight
+    /// units per second.
+    ///
+    /// Clients are identified by their credentials if authenticated. Most methods have a weight
+    /// of 1, while the more expensive ones (eg, `starknet_getEvents`) have higher weights.
+    #[arg(long = "rpc.rate-limit", value_name = "UNITS")]
+    #[serde(default)]
+    pub rate_limit: Option<NonZeroU32>,
+
+    /// Maximum number of call weight units a client can spend at once. Defaults to the rate
+    /// limit.
+    #[arg(long = "rpc.rate-limit-burst", value_name = "UNITS")]
+    #[arg(requires = "rate_limit")]
+    #[serde(default)]
+    pub rate_limit_burst: Option<NonZeroU32>,
+
+    /// Weight of a method, in the form of `<METHOD>=<WEIGHT>`. Can be specified multiple times.
+    #[arg(long = "rpc.rate-limit-weight", value_name = "METHOD=WEIGHT")]
+    #[arg(value_parser = RpcMethodWeight::parse, requires = "rate_limit")]
+    #[serde(default)]
+    pub rate_limit_weights: Vec<RpcMethodWeight>,
+
+    /// Identify unauthenticated clients by the IP address in the `X-Forwarded-For` and
+    /// `X-Real-IP` headers.
+    ///
+    /// Only enable this if the node is behind a reverse proxy that sets these headers, as they
+    /// can otherwise be forged by the clients.
+    #[arg(long = "rpc.rate-limit-trust-proxy", requires = "rate_limit")]
+    #[serde(default)]
+    pub rate_limit_trust_proxy: bool,
+
+    /// Path to the PEM-encoded certificate chain used to serve the RPC over TLS.
+    ///
+    /// The certificate and its key are reloaded whenever their files change.
+    #[arg(long = "http.tls-cert", value_name = "PATH")]
+    #[arg(requires = "http_tls_key")]
+    #[serde(default)]
+    pub http_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key used to serve the RPC over TLS.
+    #[arg(long = "http.tls-key", value_name = "PATH")]
+    #[arg(requires = "http_tls_cert")]
+    #[serde(default)]
+    pub http_tls_key: Option<PathBuf>,
+
+    /// Path to the PEM-encoded CA certificates used to verify the RPC's client certificates.
+    ///
+    /// When set, clients must present a certificate signed by one of these CAs (ie, mutual TLS).
+    #[arg(long = "http.tls-client-ca", value_name = "PATH")]
+    #[arg(requires = "http_tls_cert")]
+    #[serde(default)]
+    pub http_tls_client_ca: Option<PathBuf>,
 }
 
 #[cfg(feature = "server")]
@@ -249,12 +372,52 @@ impl Default for ServerOptions {
             max_response_body_size: None,
             timeout: None,
             max_call_gas: DEFAULT_RPC_MAX_CALL_GAS,
+            api_keys: Vec::new(),
+            jwt_secret: None,
+            jwt_methods: default_jwt_methods(),
+            public_methods: default_public_methods(),
+            rate_limit: None,
+            rate_limit_burst: None,
+            rate_limit_weights: Vec::new(),
+            rate_limit_trust_proxy: false,
+            http_tls_cert: None,
+            http_tls_key: None,
+            http_tls_client_ca: None,
         }
     }
 }
 
 #[cfg(feature = "server")]
 impl ServerOptions {
+    /// Returns the RPC authentication config, or `None` if no credentials are configured.
+    pub fn auth_config(&self) -> Option<RpcAuthConfig> {
+        if self.api_keys.is_empty() && self.jwt_secret.is_none() {
+            return None;
+        }
+
+        Some(RpcAuthConfig {
+            public_methods: self.public_methods.clone(),
+            api_keys: self.api_keys.clone(),
+            jwt_secret: self.jwt_secret.clone(),
+            jwt_methods: self.jwt_methods.clone(),
+        })
+    }
+
+    /// Returns the RPC rate limiting config, or `None` if rate limiting is disabled.
+    pub fn rate_limit_config(&self) -> Option<RpcRateLimitConfig> {
+        Some(RpcRateLimitConfig {
+            requests_per_second: self.rate_limit?,
+            burst: self.rate_limit_burst,
+            method_weights: self.rate_limit_weights.clone(),
+            trust_proxy_headers: self.rate_limit_trust_proxy,
+        })
+    }
+
+    /// Returns the TLS config of the RPC server, or `None` if TLS is disabled.
+    pub fn tls_config(&self) -> anyhow::Result<Option<TlsConfig>> {
+        tls_config(&self.http_tls_cert, &self.http_tls_key, &self.http_tls_client_ca)
+    }
+
     pub fn merge(&mut self, other: Option<&Self>) {
         if let Some(other) = other {
             if self.http_addr == DEFAULT_RPC_ADDR {
@@ -290,6 +453,38 @@ impl ServerOptions {
             if self.max_call_gas == DEFAULT_RPC_MAX_CALL_GAS {
                 self.max_call_gas = other.max_call_gas;
             }
+            if self.api_keys.is_empty() {
+                self.api_keys = other.api_keys.clone();
+            }
+            if self.jwt_secret.is_none() {
+                self.jwt_secret = other.jwt_secret.clone();
+            }
+            if self.jwt_methods == DEFAULT_RPC_JWT_METHODS {
+                self.jwt_methods = other.jwt_methods.clone();
+            }
+            if self.public_methods == DEFAULT_RPC_PUBLIC_METHODS {
+                self.public_methods = other.public_methods.clone();
+            }
+            if self.rate_limit.is_none() {
+                self.rate_limit = other.rate_limit;
+            }
+            if self.rate_limit_burst.is_none() {
+                self.rate_limit_burst = other.rate_limit_burst;
+            }
+            if self.rate_limit_weights.is_empty() {
+                self.rate_limit_weights = other.rate_limit_weights.clone();
+            }
+            if !self.rate_limit_trust_proxy {
+                self.rate_limit_trust_proxy = other.rate_limit_trust_proxy;
+            }
+            // The TLS files are merged together, as a certificate is only valid with its key.
+            if self.http_tls_cert.is_none() {
+                self.http_tls_cert = other.http_tls_cert.clone();
+                self.http_tls_key = other.http_tls_key.clone();
+            }
+            if self.http_tls_client_ca.is_none() {
+                self.http_tls_client_ca = other.http_tls_client_ca.clone();
+            }
         }
     }
 }
@@ -349,6 +544,15 @@ pub struct EnvironmentOptions {
     #[arg(long = "enable-native-compilation")]
     #[serde(default)]
     pub compile_native: bool,
+
+    /// Execute the transactions of a block in parallel.
+    ///
+    /// Transactions are executed optimistically and re-executed sequentially if they conflict
+    /// with the transactions before them, so the results are the same as with sequential
+    /// execution.
+    #[arg(long)]
+    #[serde(default)]
+    pub parallel_execution: bool,
 }
 
 impl Default for EnvironmentOptions {
@@ -359,6 +563,7 @@ impl Default for EnvironmentOptions {
             chain_id: None,
             #[cfg(feature = "native")]
             compile_native: false,
+            parallel_execution: false,
         }
     }
 }
@@ -378,6 +583,10 @@ impl EnvironmentOptions {
             if !self.compile_native {
                 self.compile_native = other.compile_native;
             }
+
+            if !self.parallel_execution {
+                self.parallel_execution = other.parallel_execution;
+            }
         }
     }
 }
@@ -480,6 +689,41 @@ pub struct ForkingOptions {
     /// state roots must stay aligned with the forked network.
     #[arg(long = "fork.no-dev-genesis", requires = "fork_provider")]
     pub no_dev_genesis: bool,
+
+    /// Fork the network from its pre-confirmed block.
+    ///
+    /// The node forks the network at the parent of the pre-confirmed block and then applies the
+    /// pre-confirmed state on top of it, so that transactions that are pre-confirmed but not yet
+    /// included in a block are visible on the forked node.
+    #[arg(long = "fork.pre-confirmed", requires = "fork_provider", conflicts_with = "fork_block")]
+    pub fork_pre_confirmed: bool,
+
+    /// Directory where the state fetched from the forked network is cached.
+    ///
+    /// The cache is keyed by the forked network's chain id and the fork block, so subsequent runs
+    /// forking the same network at the same block read the state from disk instead of fetching it
+    /// again.
+    #[arg(long = "fork.cache-dir", value_name = "PATH", requires = "fork_provider")]
+    pub fork_cache_dir: Option<PathBuf>,
+
+    /// RPC URLs of the forked network to fall back to, in order of priority, when requests to
+    /// `fork.provider` keep failing.
+    #[arg(long = "fork.fallback-url", value_name = "URL", requires = "fork_provider")]
+    #[arg(value_delimiter = ',')]
+    #[serde(default)]
+    pub fork_fallback_urls: Vec<Url>,
+
+    /// Maximum number of times a request to the forked network is retried on transient errors
+    /// (eg network issues or being rate limited) before falling back to the next URL.
+    /// [default: 5]
+    #[arg(long = "fork.max-retries", value_name = "COUNT", requires = "fork_provider")]
+    #[serde(default)]
+    pub fork_max_retries: Option<usize>,
+
+    /// Maximum number of requests sent to the forked network per second. Unlimited by default.
+    #[arg(long = "fork.requests-per-second", value_name = "COUNT", requires = "fork_provider")]
+    #[serde(default)]
+    pub fork_requests_per_second: Option<NonZeroU32>,
 }
 
 #[derive(Debug, Args, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -582,6 +826,129 @@ pub struct GasPriceOracleOptions {
     #[serde(deserialize_with = "deserialize_gas_price")]
     #[serde(default)]
     pub l1_strk_data_gas_price: Option<GasPrice>,
+
+    /// Enable dynamic L2 gas pricing, targeting this amount of L2 gas per block.
+    ///
+    /// The L2 gas prices are adjusted after every block based on how much L2 gas the block used
+    /// relative to the target, similar to EIP-1559. The initial prices are the L2 gas prices
+    /// that would be used otherwise.
+    #[arg(long = "gpo.l2-target-gas", value_name = "GAS")]
+    #[serde(default)]
+    pub l2_target_gas: Option<u64>,
+
+    /// The minimum L2 ETH gas price when using dynamic pricing. (denominated in wei)
+    #[arg(long = "gpo.l2-min-eth-gas-price", value_name = "WEI")]
+    #[arg(requires = "l2_target_gas")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub l2_min_eth_gas_price: Option<GasPrice>,
+
+    /// The maximum L2 ETH gas price when using dynamic pricing. (denominated in wei)
+    #[arg(long = "gpo.l2-max-eth-gas-price", value_name = "WEI")]
+    #[arg(requires = "l2_target_gas")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub l2_max_eth_gas_price: Option<GasPrice>,
+
+    /// The minimum L2 STRK gas price when using dynamic pricing. (denominated in fri)
+    #[arg(long = "gpo.l2-min-strk-gas-price", value_name = "FRI")]
+    #[arg(requires = "l2_target_gas")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub l2_min_strk_gas_price: Option<GasPrice>,
+
+    /// The maximum L2 STRK gas price when using dynamic pricing. (denominated in fri)
+    #[arg(long = "gpo.l2-max-strk-gas-price", value_name = "FRI")]
+    #[arg(requires = "l2_target_gas")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub l2_max_strk_gas_price: Option<GasPrice>,
+
+    /// Bounds the change of the L2 gas prices between two blocks to `1 / DENOMINATOR` of the
+    /// prices, when using dynamic pricing. [default: 8]
+    #[arg(long = "gpo.l2-max-change-denominator", value_name = "DENOMINATOR")]
+    #[arg(requires = "l2_target_gas")]
+    #[serde(default)]
+    pub l2_max_change_denominator: Option<u64>,
+
+    /// The interval, in seconds, at which the gas prices are sampled from the settlement layer.
+    /// [default: 60]
+    #[arg(long = "gpo.sample-interval", value_name = "SECONDS")]
+    #[serde(default)]
+    pub sample_interval: Option<u64>,
+
+    /// The number of most recent samples the gas prices are aggregated over. [default: 60]
+    #[arg(long = "gpo.window-size", value_name = "COUNT")]
+    #[arg(value_parser = parse_nonzero_usize)]
+    #[serde(default)]
+    pub window_size: Option<usize>,
+
+    /// How the samples are aggregated into the gas prices: `average`, `median` or a percentile
+    /// (eg. `p75`). [default: average]
+    #[arg(long = "gpo.aggregation", value_name = "AGGREGATION")]
+    #[serde(serialize_with = "serialize_opt_aggregation")]
+    #[serde(deserialize_with = "deserialize_opt_aggregation")]
+    #[serde(default)]
+    pub aggregation: Option<Aggregation>,
+
+    /// If the gas prices couldn't be sampled for this many seconds, the fallback gas prices are
+    /// used until sampling succeeds again. [default: 600]
+    #[arg(long = "gpo.max-staleness", value_name = "SECONDS")]
+    #[serde(default)]
+    pub max_staleness: Option<u64>,
+
+    /// The L2 ETH gas price used when the sampled gas prices are stale. (denominated in wei)
+    #[arg(long = "gpo.fallback-l2-eth-gas-price", value_name = "WEI")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub fallback_l2_eth_gas_price: Option<GasPrice>,
+
+    /// The L2 STRK gas price used when the sampled gas prices are stale. (denominated in fri)
+    #[arg(long = "gpo.fallback-l2-strk-gas-price", value_name = "FRI")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub fallback_l2_strk_gas_price: Option<GasPrice>,
+
+    /// The L1 ETH gas price used when the sampled gas prices are stale. (denominated in wei)
+    #[arg(long = "gpo.fallback-l1-eth-gas-price", value_name = "WEI")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub fallback_l1_eth_gas_price: Option<GasPrice>,
+
+    /// The L1 STRK gas price used when the sampled gas prices are stale. (denominated in fri)
+    #[arg(long = "gpo.fallback-l1-strk-gas-price", value_name = "FRI")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub fallback_l1_strk_gas_price: Option<GasPrice>,
+
+    /// The L1 ETH data gas price used when the sampled gas prices are stale. (denominated in wei)
+    #[arg(long = "gpo.fallback-l1-eth-data-gas-price", value_name = "WEI")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub fallback_l1_eth_data_gas_price: Option<GasPrice>,
+
+    /// The L1 STRK data gas price used when the sampled gas prices are stale. (denominated in fri)
+    #[arg(long = "gpo.fallback-l1-strk-data-gas-price", value_name = "FRI")]
+    #[serde(serialize_with = "serialize_opt_as_hex")]
+    #[serde(deserialize_with = "deserialize_gas_price")]
+    #[serde(default)]
+    pub fallback_l1_strk_data_gas_price: Option<GasPrice>,
+
+    /// RPC URLs of the settlement layer to sample the gas prices from when its primary RPC URL
+    /// fails, in order of priority.
+    #[arg(long = "gpo.fallback-url", value_name = "URL")]
+    #[arg(value_delimiter = ',')]
+    #[serde(default)]
+    pub fallback_urls: Vec<Url>,
 }
 
 #[derive(Debug, Default, Args, Clone, Serialize, Deserialize, PartialEq)]
@@ -723,7 +1090,8 @@ pub struct VrfOptions {
     /// Enable the Cartridge VRF service.
     ///
     /// By default, the VRF runs as a sidecar process. If `--vrf.url` is provided,
-    /// it will connect to an external VRF service instead.
+    /// it will connect to an external VRF service instead. If `--vrf.in-process` is provided,
+    /// the VRF proofs are generated within the node process.
     ///
     /// Requires the Cartridge paymaster to be enabled i.e., `--paymaster.cartridge`.
     #[arg(long = "vrf", id = "vrf_enabled")]
@@ -752,6 +1120,14 @@ pub struct VrfOptions {
     #[arg(long = "vrf.bin", value_name = "PATH", id = "vrf_bin")]
     #[serde(default)]
     pub bin: Option<PathBuf>,
+
+    /// Generate the VRF proofs natively within the node process.
+    ///
+    /// No sidecar binary is needed in this mode. Not applicable if `--vrf.url` is provided.
+    #[arg(requires = "vrf_enabled", conflicts_with_all = ["vrf_url", "vrf_bin"])]
+    #[arg(long = "vrf.in-process", id = "vrf_in_process")]
+    #[serde(default)]
+    pub in_process: bool,
 }
 
 impl VrfOptions {
@@ -760,6 +1136,11 @@ impl VrfOptions {
         self.url.is_some()
     }
 
+    /// Returns true if the VRF should run in a sidecar process.
+    pub fn is_sidecar(&self) -> bool {
+        !self.is_external() && !self.in_process
+    }
+
     pub fn merge(&mut self, other: Option<&Self>) {
         if let Some(other) = other {
             if !self.enabled {
@@ -777,6 +1158,10 @@ impl VrfOptions {
             if self.bin.is_none() {
                 self.bin = other.bin.clone();
             }
+
+            if !self.in_process {
+                self.in_process = other.in_process;
+            }
         }
     }
 }
@@ -849,6 +1234,28 @@ fn default_feeder_gateway_timeout() -> u64 {
     DEFAULT_GATEWAY_TIMEOUT_SECS
 }
 
+/// Returns the TLS config made of the given files, or `None` if neither the certificate nor the
+/// key is set.
+///
+/// The CLI already requires the certificate and the key to be set together, but the config file
+/// doesn't.
+#[cfg(feature = "server")]
+fn tls_config(
+    cert: &Option<PathBuf>,
+    key: &Option<PathBuf>,
+    client_ca: &Option<PathBuf>,
+) -> anyhow::Result<Option<TlsConfig>> {
+    match (cert, key) {
+        (Some(cert), Some(key)) => Ok(Some(TlsConfig {
+            cert: cert.clone(),
+            key: key.clone(),
+            client_ca: client_ca.clone(),
+        })),
+        (None, None) => Ok(None),
+        _ => anyhow::bail!("the TLS certificate and private key must be set together"),
+    }
+}
+
 #[cfg(feature = "server")]
 fn default_metrics_addr() -> IpAddr {
     DEFAULT_METRICS_ADDR
@@ -864,6 +1271,16 @@ fn default_max_call_gas() -> u64 {
     DEFAULT_RPC_MAX_CALL_GAS
 }
 
+#[cfg(feature = "server")]
+fn default_jwt_methods() -> Vec<String> {
+    DEFAULT_RPC_JWT_METHODS.iter().map(|m| m.to_string()).collect()
+}
+
+#[cfg(feature = "server")]
+fn default_public_methods() -> Vec<String> {
+    DEFAULT_RPC_PUBLIC_METHODS.iter().map(|m| m.to_string()).collect()
+}
+
 /// Deserialize a string (hex or decimal) into a [`GasPrice`]
 fn deserialize_gas_price<'de, D>(deserializer: D) -> Result<Option<GasPrice>, D::Error>
 where
@@ -888,6 +1305,31 @@ where
         .ok_or_else(|| D::Error::custom("value cannot be zero"))
 }
 
+fn serialize_opt_aggregation<S>(
+    value: &Option<Aggregation>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        Some(aggregation) => serializer.serialize_some(&aggregation.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_opt_aggregation<'de, D>(deserializer: D) -> Result<Option<Aggregation>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    match Option::<String>::deserialize(deserializer)? {
+        Some(s) => s.parse().map(Some).map_err(D::Error::custom),
+        None => Ok(None),
+    }
+}
+
 fn default_api_url() -> Url {
     Url::parse("https://api.cartridge.gg").expect("qed; invalid url")
 }
@@ -972,12 +1414,11 @@ pub struct SyncOptions {
     #[arg(conflicts_with = "rpc")]
     pub gateway: Option<Url>,
 
-    /// JSON-RPC endpoint URL to use as the block download source instead of
-    /// the feeder gateway. When set, blocks and classes are fetched via
-    /// JSON-RPC (`starknet_getBlockWithReceipts`, `starknet_getStateUpdate`,
-    /// `starknet_getClass`).
-    ///
-    /// This is mainly intended for development and testing purposes.
+    /// JSON-RPC endpoint URL to use as the sync source instead of the feeder
+    /// gateway. When set, blocks and classes are fetched via JSON-RPC
+    /// (`starknet_getBlockWithReceipts`, `starknet_getStateUpdate`,
+    /// `starknet_getClass`). The chain tip and the pre-confirmed block are
+    /// tracked, and transactions are forwarded, through the same endpoint.
     #[arg(long = "sync.rpc")]
     #[arg(value_name = "URL")]
     #[arg(conflicts_with = "gateway")]
@@ -999,6 +1440,36 @@ pub struct SyncOptions {
     #[arg(value_parser = SyncStagesList::parse)]
     #[serde(default)]
     pub stages: Option<SyncStagesList>,
+
+    /// Public key of the upstream sequencer.
+    ///
+    /// When set, the signature of every synced block is fetched from the feeder
+    /// gateway and verified against this key. Blocks with a missing or invalid
+    /// signature are rejected. Only supported when syncing from a feeder gateway.
+    #[arg(long = "sync.public-key")]
+    #[arg(value_name = "PUBLIC_KEY")]
+    #[arg(conflicts_with = "rpc")]
+    #[serde(default)]
+    pub public_key: Option<Felt>,
+
+    /// Path to the PEM-encoded CA certificates used to verify the certificate of the sync
+    /// source, in addition to the default trusted CAs.
+    #[arg(long = "sync.tls-ca", value_name = "PATH")]
+    #[serde(default)]
+    pub tls_ca: Option<PathBuf>,
+
+    /// Path to the PEM-encoded client certificate presented to a sync source requiring mutual
+    /// TLS.
+    #[arg(long = "sync.tls-cert", value_name = "PATH")]
+    #[arg(requires = "tls_key")]
+    #[serde(default)]
+    pub tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key of the client certificate.
+    #[arg(long = "sync.tls-key", value_name = "PATH")]
+    #[arg(requires = "tls_cert")]
+    #[serde(default)]
+    pub tls_key: Option<PathBuf>,
 }
 
 impl Default for SyncOptions {
@@ -1008,8 +1479,32 @@ impl Default for SyncOptions {
             gateway: None,
             rpc: None,
             stages: None,
+            public_key: None,
             chunk_size: katana_full_node::DEFAULT_SYNC_CHUNK_SIZE,
+            tls_ca: None,
+            tls_cert: None,
+            tls_key: None,
+        }
+    }
+}
+
+impl SyncOptions {
+    /// Returns the TLS config used to connect to the sync source, or `None` if none of the TLS
+    /// options is set.
+    pub fn tls_config(&self) -> anyhow::Result<Option<ClientTlsConfig>> {
+        let identity = match (&self.tls_cert, &self.tls_key) {
+            (Some(cert), Some(key)) => {
+                Some(ClientIdentity { cert: cert.clone(), key: key.clone() })
+            }
+            (None, None) => None,
+            _ => anyhow::bail!("the TLS certificate and private key must be set together"),
+        };
+
+        if self.tls_ca.is_none() && identity.is_none() {
+            return Ok(None);
         }
+
+        Ok(Some(ClientTlsConfig { ca: self.tls_ca.clone(), identity }))
     }
 }
 
@@ -1122,7 +1617,8 @@ pub struct GrpcOptions {
     /// Enable the gRPC server.
     ///
     /// When enabled, the gRPC server will start alongside the JSON-RPC server,
-    /// providing high-performance endpoints for Starknet operations.
+    /// providing high-performance endpoints for Starknet operations. The `dev` and `txpool`
+    /// services are only served if their JSON-RPC modules are enabled with `--http.api`.
     #[arg(long = "grpc")]
     #[serde(default)]
     pub grpc_enable: bool,
@@ -1145,6 +1641,28 @@ pub struct GrpcOptions {
     #[arg(requires = "grpc_enable")]
     #[arg(long = "grpc.timeout", value_name = "TIMEOUT")]
     pub grpc_timeout: Option<u64>,
+
+    /// Path to the PEM-encoded certificate chain used to serve gRPC over TLS.
+    ///
+    /// The certificate and its key are reloaded whenever their files change.
+    #[arg(requires_all = ["grpc_enable", "grpc_tls_key"])]
+    #[arg(long = "grpc.tls-cert", value_name = "PATH")]
+    #[serde(default)]
+    pub grpc_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key used to serve gRPC over TLS.
+    #[arg(requires = "grpc_tls_cert")]
+    #[arg(long = "grpc.tls-key", value_name = "PATH")]
+    #[serde(default)]
+    pub grpc_tls_key: Option<PathBuf>,
+
+    /// Path to the PEM-encoded CA certificates used to verify gRPC client certificates.
+    ///
+    /// When set, clients must present a certificate signed by one of these CAs (ie, mutual TLS).
+    #[arg(requires = "grpc_tls_cert")]
+    #[arg(long = "grpc.tls-client-ca", value_name = "PATH")]
+    #[serde(default)]
+    pub grpc_tls_client_ca: Option<PathBuf>,
 }
 
 #[cfg(all(feature = "server", feature = "grpc"))]
@@ -1155,12 +1673,20 @@ impl Default for GrpcOptions {
             grpc_addr: default_grpc_addr(),
             grpc_port: default_grpc_port(),
             grpc_timeout: None,
+            grpc_tls_cert: None,
+            grpc_tls_key: None,
+            grpc_tls_client_ca: None,
         }
     }
 }
 
 #[cfg(all(feature = "server", feature = "grpc"))]
 impl GrpcOptions {
+    /// Returns the TLS config of the gRPC server, or `None` if TLS is disabled.
+    pub fn tls_config(&self) -> anyhow::Result<Option<TlsConfig>> {
+        tls_config(&self.grpc_tls_cert, &self.grpc_tls_key, &self.grpc_tls_client_ca)
+    }
+
     pub fn merge(&mut self, other: Option<&Self>) {
         if let Some(other) = other {
             if !self.grpc_enable {
@@ -1175,6 +1701,14 @@ impl GrpcOptions {
             if self.grpc_timeout.is_none() {
                 self.grpc_timeout = other.grpc_timeout;
             }
+            // The TLS files are merged together, as a certificate is only valid with its key.
+            if self.grpc_tls_cert.is_none() {
+                self.grpc_tls_cert = other.grpc_tls_cert.clone();
+                self.grpc_tls_key = other.grpc_tls_key.clone();
+            }
+            if self.grpc_tls_client_ca.is_none() {
+                self.grpc_tls_client_ca = other.grpc_tls_client_ca.clone();
+            }
         }
     }
 }
@@ -14,7 +14,7 @@
 //! # Binary resolution
 //!
 //! When Katana starts with `--paymaster` or `--vrf` in **sidecar mode** (the
-//! default — no `--paymaster.url` / `--vrf.url` provided), it needs to locate
+//! default — no `--paymaster.url` / `--vrf.url` / `--vrf.in-process` provided), it needs to locate
 //! the sidecar binary on disk. The resolution order is:
 //!
 //! 1. **Explicit path** — `--paymaster.bin <PATH>` or `--vrf.bin <PATH>`. If provided, the file
@@ -117,10 +117,7 @@ pub async fn bootstrap_vrf(
     rpc_addr: SocketAddr,
     chain: &ChainSpec,
 ) -> Result<VrfServer> {
-    let rpc_url = local_rpc_url(&rpc_addr);
-    let (account_address, pk) = prefunded_account(chain, 0)?;
-
-    let result = cartridge::vrf::server::bootstrap_vrf(rpc_url, account_address, pk).await?;
+    let result = bootstrap_vrf_contracts(rpc_addr, chain).await?;
 
     let vrf_service = VrfServer::new(VrfServerConfig {
         port: vrf_url.port().unwrap(),
@@ -133,6 +130,18 @@ pub async fn bootstrap_vrf(
     Ok(vrf_service)
 }
 
+/// Declares and deploys the VRF account and consumer contracts.
+///
+/// This is the only bootstrap step needed when the VRF proofs are generated in-process.
+pub async fn bootstrap_vrf_contracts(
+    rpc_addr: SocketAddr,
+    chain: &ChainSpec,
+) -> Result<VrfBootstrapResult> {
+    let rpc_url = local_rpc_url(&rpc_addr);
+    let (account_address, pk) = prefunded_account(chain, 0)?;
+    Ok(cartridge::vrf::server::bootstrap_vrf(rpc_url, account_address, pk).await?)
+}
+
 fn prefunded_account(chain_spec: &ChainSpec, index: u16) -> Result<(ContractAddress, Felt)> {
     let (address, allocation) = chain_spec
         .genesis()
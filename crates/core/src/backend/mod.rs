@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::{anyhow, Context};
 use katana_chain_spec::ChainSpec;
 use katana_executor::{ExecutionOutput, ExecutionResult, ExecutorFactory};
 use katana_gas_price_oracle::GasPriceOracle;
 use katana_primitives::block::{
-    BlockHash, BlockNumber, FinalityStatus, Header, PartialHeader, SealedBlock,
+    BlockHash, BlockNumber, BlockSignature, FinalityStatus, Header, PartialHeader, SealedBlock,
     SealedBlockWithStatus,
 };
 use katana_primitives::cairo::ShortString;
@@ -19,7 +20,7 @@ use katana_primitives::state::{compute_state_diff_hash, StateUpdates, StateUpdat
 use katana_primitives::transaction::{TxHash, TxWithHash};
 use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_primitives::{address, ContractAddress, Felt};
-use katana_provider::api::block::{BlockHashProvider, BlockWriter};
+use katana_provider::api::block::{BlockHashProvider, BlockSignatureWriter, BlockWriter};
 use katana_provider::api::trie::TrieWriter;
 use katana_provider::providers::EmptyStateProvider;
 use katana_provider::{MutableProvider, ProviderFactory, ProviderRO, ProviderRW};
@@ -30,11 +31,15 @@ use katana_trie::{
 };
 use parking_lot::RwLock;
 use rayon::prelude::*;
+use starknet::core::crypto::EcdsaSignError;
+use starknet::signers::SigningKey;
 use starknet_types_core::hash::{self, StarkHash};
+use tokio::sync::watch;
 use tracing::{info, warn};
 
 use crate::env::BlockContextGenerator;
 use crate::service::block_producer::{BlockProductionError, MinedBlockOutcome};
+use crate::service::metrics::{record_block_commit, CommitMethod};
 use crate::utils::get_current_timestamp;
 
 pub(crate) const LOG_TARGET: &str = "katana::core::backend";
@@ -47,6 +52,10 @@ pub struct Backend<PF> {
     pub gas_oracle: GasPriceOracle,
     /// When true, skip state trie computation during block production.
     pub no_state_trie: bool,
+    /// The key used to sign every mined block. Blocks are left unsigned if `None`.
+    pub block_signer: Option<SigningKey>,
+    /// Broadcasts the number of every block mined by this backend.
+    pub block_notifier: watch::Sender<Option<BlockNumber>>,
 }
 
 impl<PF> std::fmt::Debug for Backend<PF> {
@@ -75,9 +84,45 @@ impl<PF> Backend<PF> {
             gas_oracle,
             executor_factory,
             no_state_trie,
+            block_signer: None,
+            block_notifier: watch::channel(None).0,
             block_context_generator: RwLock::new(BlockContextGenerator::default()),
         }
     }
+
+    /// Subscribes to the blocks mined by this backend.
+    pub fn subscribe_blocks(&self) -> MinedBlockSubscription {
+        MinedBlockSubscription { rx: self.block_notifier.subscribe() }
+    }
+}
+
+/// A subscription to the blocks mined by a [`Backend`].
+#[derive(Clone)]
+pub struct MinedBlockSubscription {
+    rx: watch::Receiver<Option<BlockNumber>>,
+}
+
+impl MinedBlockSubscription {
+    /// Returns the number of the last mined block, if any.
+    ///
+    /// Returns `None` if no blocks have been mined since the subscription was created.
+    pub fn block(&self) -> Option<BlockNumber> {
+        *self.rx.borrow()
+    }
+
+    /// Waits for the next block to be mined and returns its number.
+    ///
+    /// Returns an error if the backend has been dropped.
+    pub async fn changed(&mut self) -> Result<Option<BlockNumber>, watch::error::RecvError> {
+        self.rx.changed().await?;
+        Ok(*self.rx.borrow_and_update())
+    }
+}
+
+impl std::fmt::Debug for MinedBlockSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MinedBlockSubscription").field("last_block", &self.block()).finish()
+    }
 }
 
 impl<PF> Backend<PF>
@@ -152,6 +197,7 @@ where
         }
 
         let tx_count = transactions.len();
+        let reverted_txs = receipts.iter().filter(|r| r.receipt.is_reverted()).count();
         let tx_hashes = transactions.iter().map(|tx| tx.hash).collect::<Vec<_>>();
 
         let parent_hash = if block_env.number == 0 {
@@ -191,23 +237,43 @@ where
         let block_hash = block.block.hash;
         let block_number = block.block.header.number;
 
+        let signature = self.sign_block(&block.block)?;
+
         // TODO: maybe should change the arguments for insert_block_with_states_and_receipts to
         // accept ReceiptWithTxHash instead to avoid this conversion.
         let receipts = receipts.into_iter().map(|r| r.receipt).collect::<Vec<_>>();
         store_block(&provider_mut, block, execution_output.states, receipts, traces)?;
 
+        if let Some(signature) = signature {
+            provider_mut.insert_block_signature(block_number, signature)?;
+        }
+
         info!(target: LOG_TARGET, %block_number, %tx_count, "Block mined.");
 
         provider_mut.commit()?;
 
+        // Let the gas oracle price the next block based on the utilization of this one.
+        let l2_gas_used = u64::try_from(execution_output.stats.l2_gas_used).unwrap_or(u64::MAX);
+        self.gas_oracle.record_l2_gas_used(l2_gas_used);
+
+        self.block_notifier.send_replace(Some(block_number));
+
         Ok(MinedBlockOutcome {
             block_hash,
             block_number,
             txs: tx_hashes,
+            reverted_txs,
             stats: execution_output.stats,
         })
     }
 
+    /// Signs `block` with the [block signer](Self::block_signer), if any.
+    fn sign_block(&self, block: &SealedBlock) -> Result<Option<BlockSignature>, EcdsaSignError> {
+        let Some(key) = &self.block_signer else { return Ok(None) };
+        let signature = BlockSignature::sign(key, block.hash, block.header.state_diff_commitment)?;
+        Ok(Some(signature))
+    }
+
     pub fn mine_empty_block(
         &self,
         block_env: &BlockEnv,
@@ -215,7 +281,35 @@ where
         self.do_mine_block(block_env, Default::default())
     }
 
-    fn init_dev_genesis(
+    /// Mines a block with `transactions` that applies `states`, without executing the
+    /// transactions.
+    ///
+    /// This is for transactions that were executed outside of the node, such as the pre-confirmed
+    /// transactions of a forked network. `states` must be the state changes made by the
+    /// transactions. As they aren't executed, the transactions have no execution traces.
+    pub fn mine_block_with_states(
+        &self,
+        block_env: &BlockEnv,
+        transactions: Vec<(TxWithHash, Receipt)>,
+        states: StateUpdatesWithClasses,
+    ) -> Result<MinedBlockOutcome, BlockProductionError> {
+        let transactions = transactions
+            .into_iter()
+            .map(|(tx, receipt)| (tx, ExecutionResult::new_success(receipt, Default::default())))
+            .collect();
+
+        self.do_mine_block(
+            block_env,
+            ExecutionOutput { states, transactions, ..Default::default() },
+        )
+    }
+
+    /// Initializes the genesis block of a dev chain, unless `skip_dev_genesis` is set.
+    ///
+    /// Unlike [`init_genesis`](Self::init_genesis), the chain spec doesn't have to be the
+    /// backend's, which allows re-initializing the genesis of a chain whose spec changed, such as
+    /// when re-forking.
+    pub fn init_dev_genesis(
         &self,
         chain_spec: &katana_chain_spec::dev::ChainSpec,
         skip_dev_genesis: bool,
@@ -348,6 +442,11 @@ where
                 &mut output.states.state_updates,
             )?;
 
+            // The genesis block is signed like any other block, for nodes syncing from this one to
+            // be able to verify it.
+            let signature = self.sign_block(&block).context("failed to sign genesis block")?;
+            let block_number = block.header.number;
+
             let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 };
 
             // TODO: maybe should change the arguments for insert_block_with_states_and_receipts to
@@ -355,6 +454,10 @@ where
             let receipts = receipts.into_iter().map(|r| r.receipt).collect::<Vec<_>>();
             store_block(&provider_mut, block, output.states, receipts, traces)?;
 
+            if let Some(signature) = signature {
+                provider_mut.insert_block_signature(block_number, signature)?;
+            }
+
             provider_mut.commit()?;
             info!("Genesis initialized");
         }
@@ -393,6 +496,8 @@ impl<'a, P: TrieWriter> UncommittedBlock<'a, P> {
     }
 
     pub fn commit(self) -> SealedBlock {
+        let start = Instant::now();
+
         // get the hash of the latest committed block
         let parent_hash = self.header.parent_hash;
         let events_count = self.receipts.iter().map(|r| r.events().len() as u32).sum::<u32>();
@@ -427,11 +532,14 @@ impl<'a, P: TrieWriter> UncommittedBlock<'a, P> {
         };
 
         let hash = header.compute_hash();
+        record_block_commit(CommitMethod::Sequential, start.elapsed());
 
         SealedBlock { hash, header, body: self.transactions }
     }
 
     pub fn commit_parallel(self) -> SealedBlock {
+        let start = Instant::now();
+
         // get the hash of the latest committed block
         let parent_hash = self.header.parent_hash;
         let events_count = self.receipts.iter().map(|r| r.events().len() as u32).sum::<u32>();
@@ -473,6 +581,7 @@ impl<'a, P: TrieWriter> UncommittedBlock<'a, P> {
         };
 
         let hash = header.compute_hash();
+        record_block_commit(CommitMethod::Parallel, start.elapsed());
 
         SealedBlock { hash, header, body: self.transactions }
     }
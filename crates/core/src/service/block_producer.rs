@@ -35,6 +35,9 @@ use parking_lot::{Mutex, RwLock};
 use tokio::time::{interval_at, Instant, Interval};
 use tracing::{error, info, trace};
 
+use super::metrics::{
+    record_block_closed, BlockCloseReason, BlockProducerMetrics, PoolStateMetrics,
+};
 use crate::backend::Backend;
 
 #[cfg(test)]
@@ -56,6 +59,9 @@ pub enum BlockProductionError {
 
     #[error("inconsistent state updates: {0}")]
     InconsistentState(String),
+
+    #[error("failed to sign block: {0}")]
+    BlockSigning(#[from] starknet::core::crypto::EcdsaSignError),
 }
 
 impl BlockProductionError {
@@ -73,6 +79,8 @@ pub struct MinedBlockOutcome {
     pub block_hash: BlockHash,
     pub block_number: u64,
     pub txs: Vec<TxHash>,
+    /// The number of transactions in `txs` that were reverted.
+    pub reverted_txs: usize,
     pub stats: ExecutionStats,
 }
 
@@ -136,8 +144,8 @@ where
     pub(super) fn queue(&self, transactions: Vec<ExecutableTxWithHash>) {
         let mut mode = self.producer.write();
         match &mut *mode {
-            BlockProducerMode::Instant(producer) => producer.queued.push_back(transactions),
-            BlockProducerMode::Interval(producer) => producer.queued.push_back(transactions),
+            BlockProducerMode::Instant(producer) => producer.queue(transactions),
+            BlockProducerMode::Interval(producer) => producer.queue(transactions),
         }
     }
 
@@ -222,6 +230,35 @@ where
     Instant(InstantBlockProducer<PF>),
 }
 
+impl<PF> BlockProducerMode<PF>
+where
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    /// Returns `true` if a block is being mined or transactions are being executed.
+    pub fn is_busy(&self) -> bool {
+        match self {
+            Self::Instant(producer) => producer.block_mining.is_some(),
+            Self::Interval(producer) => {
+                producer.ongoing_mining.is_some() || producer.ongoing_execution.is_some()
+            }
+        }
+    }
+
+    /// Discards the pending block and the queued transactions, and starts over from the latest
+    /// state of the storage.
+    ///
+    /// This is meant to be used after the storage has been replaced (eg. when re-forking), and
+    /// must not be called while the producer is [busy](Self::is_busy).
+    pub fn reset(&mut self) -> Result<(), BlockProductionError> {
+        match self {
+            Self::Instant(producer) => producer.reset(),
+            Self::Interval(producer) => producer.reset(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, derive_more::Deref)]
 pub struct PendingExecutor(#[deref] Arc<RwLock<Box<dyn Executor>>>);
 
@@ -264,6 +301,9 @@ where
     timer: Option<Interval>,
 
     is_block_full: bool,
+
+    metrics: BlockProducerMetrics,
+    pool_metrics: PoolStateMetrics,
 }
 
 impl<PF> IntervalBlockProducer<PF>
@@ -314,6 +354,8 @@ where
             queued: VecDeque::default(),
             executor: PendingExecutor::new(executor),
             blocking_task_spawner,
+            metrics: BlockProducerMetrics::default(),
+            pool_metrics: PoolStateMetrics::default(),
         }
     }
 
@@ -337,11 +379,17 @@ where
 {
     /// Force mine a new block. It will only able to mine if there is no ongoing mining process.
     pub fn force_mine(&mut self) -> Option<MinedBlockOutcome> {
-        match Self::do_mine(self.permit.clone(), self.executor.clone(), self.backend.clone()) {
+        let permit = self.permit.clone();
+        let executor = self.executor.clone();
+        let backend = self.backend.clone();
+
+        match Self::do_mine(permit, executor, backend, BlockCloseReason::Forced) {
             Ok(outcome) => {
                 info!(target: LOG_TARGET, block_number = %outcome.block_number, "Force mined block.");
+                self.metrics.record_mined_block(&outcome);
                 self.executor =
                     self.create_new_executor_for_next_block().expect("fail to create executor");
+                self.pool_metrics.executed_transactions.set(0.0);
 
                 // update pool validator state here ---------
 
@@ -368,6 +416,7 @@ where
         permit: Arc<Mutex<()>>,
         executor: PendingExecutor,
         backend: Arc<Backend<PF>>,
+        reason: BlockCloseReason,
     ) -> Result<MinedBlockOutcome, BlockProductionError> {
         unsafe { permit.raw() }.lock();
         let executor = &mut executor.write();
@@ -377,6 +426,7 @@ where
         let block_env = executor.block_env();
         let execution_output = executor.take_execution_output()?;
         let outcome = backend.do_mine_block(&block_env, execution_output)?;
+        record_block_closed(reason);
 
         trace!(target: LOG_TARGET, block_number = %outcome.block_number, "Created new block.");
 
@@ -386,13 +436,18 @@ where
     fn execute_transactions(
         executor: PendingExecutor,
         mut transactions: Vec<ExecutableTxWithHash>,
+        metrics: BlockProducerMetrics,
+        pool_metrics: PoolStateMetrics,
     ) -> TxExecutionResult {
         let executor = &mut executor.write();
 
+        let start = std::time::Instant::now();
         let (total_executed, is_full) = executor.execute_transactions(transactions.clone())?;
+        metrics.execution_time_seconds.record(start.elapsed().as_secs_f64());
 
         let txs = executor.transactions();
         let total_txs = txs.len();
+        pool_metrics.executed_transactions.set(total_txs as f64);
 
         // Take only the results of the newly executed transactions
         let results = txs
@@ -414,6 +469,36 @@ where
         Ok((results, non_executed_txs))
     }
 
+    fn reset(&mut self) -> Result<(), BlockProductionError> {
+        let executor = self.create_new_executor_for_next_block()?;
+
+        let provider = self.backend.storage.provider();
+        let state = executor.0.read().state();
+        let num = provider.latest_number()?;
+        let block_env = provider.block_env_at(num.into())?.expect("latest block env");
+        self.validator.reset(state, block_env);
+
+        self.executor = executor;
+        self.queued.clear();
+        self.timer = None;
+        self.is_block_full = false;
+
+        self.pool_metrics.queued_transactions.set(0.0);
+        self.pool_metrics.executed_transactions.set(0.0);
+
+        Ok(())
+    }
+
+    fn queue(&mut self, transactions: Vec<ExecutableTxWithHash>) {
+        self.queued.push_back(transactions);
+        self.update_queued_transactions();
+    }
+
+    fn update_queued_transactions(&self) {
+        let queued = self.queued.iter().map(Vec::len).sum::<usize>();
+        self.pool_metrics.queued_transactions.set(queued as f64);
+    }
+
     fn create_new_executor_for_next_block(&self) -> Result<PendingExecutor, BlockProductionError> {
         let backend = &self.backend;
         let provider = backend.storage.provider();
@@ -449,17 +534,21 @@ where
             // transaction is executed.
             if (timer.poll_tick(cx).is_ready() || pin.is_block_full) && pin.ongoing_mining.is_none()
             {
-                if pin.is_block_full {
+                let reason = if pin.is_block_full {
                     info!("Block has reached capacity! Closing block...");
                     pin.is_block_full = false;
-                }
+                    BlockCloseReason::Full
+                } else {
+                    BlockCloseReason::Interval
+                };
 
                 pin.ongoing_mining = Some(Box::pin({
                     let executor = pin.executor.clone();
                     let backend = pin.backend.clone();
                     let permit = pin.permit.clone();
 
-                    pin.blocking_task_spawner.spawn(|| Self::do_mine(permit, executor, backend))
+                    pin.blocking_task_spawner
+                        .spawn(move || Self::do_mine(permit, executor, backend, reason))
                 }));
             } else {
                 pin.timer = Some(timer);
@@ -472,7 +561,8 @@ where
                 let backend = pin.backend.clone();
                 let permit = pin.permit.clone();
 
-                pin.blocking_task_spawner.spawn(|| Self::do_mine(permit, executor, backend))
+                pin.blocking_task_spawner
+                    .spawn(|| Self::do_mine(permit, executor, backend, BlockCloseReason::Full))
             }));
 
             pin.is_block_full = false;
@@ -485,13 +575,16 @@ where
                 && pin.ongoing_mining.is_none()
             {
                 let executor = pin.executor.clone();
+                let metrics = pin.metrics.clone();
+                let pool_metrics = pin.pool_metrics.clone();
 
                 let transactions: Vec<ExecutableTxWithHash> =
                     std::mem::take(&mut pin.queued).into_iter().flatten().collect();
+                pin.update_queued_transactions();
 
-                let fut = pin
-                    .blocking_task_spawner
-                    .spawn(|| Self::execute_transactions(executor, transactions));
+                let fut = pin.blocking_task_spawner.spawn(|| {
+                    Self::execute_transactions(executor, transactions, metrics, pool_metrics)
+                });
 
                 pin.ongoing_execution = Some(Box::pin(fut));
 
@@ -516,6 +609,7 @@ where
 
                                 // Push leftover transactions back to front of queue
                                 pin.queued.push_front(leftovers);
+                                pin.update_queued_transactions();
 
                                 // Schedule future poll if block is full
                                 cx.waker().wake_by_ref();
@@ -566,6 +660,7 @@ where
                                 // -------------------------------------------
 
                                 pin.executor = executor;
+                                pin.pool_metrics.executed_transactions.set(0.0);
                                 unsafe { pin.permit.raw().unlock() };
                             }
 
@@ -612,6 +707,9 @@ pub struct InstantBlockProducer<PF> {
     // the validator needs to always be built against the state of the block producer, so
     // im putting here for now until we find a better way to handle this.
     validator: TxValidator,
+
+    metrics: BlockProducerMetrics,
+    pool_metrics: PoolStateMetrics,
 }
 
 impl<PF> InstantBlockProducer<PF>
@@ -657,19 +755,28 @@ where
             block_mining: None,
             queued: VecDeque::default(),
             blocking_task_spawner,
+            metrics: BlockProducerMetrics::default(),
+            pool_metrics: PoolStateMetrics::default(),
         }
     }
 
     pub fn force_mine(&mut self) -> Option<MinedBlockOutcome> {
         if self.block_mining.is_none() {
             let txs = std::mem::take(&mut self.queued);
+            self.pool_metrics.queued_transactions.set(0.0);
+
             match Self::do_mine(
                 self.validator.clone(),
                 self.permit.clone(),
                 self.backend.clone(),
                 txs,
+                self.metrics.clone(),
+                BlockCloseReason::Forced,
             ) {
-                Ok(outcome) => Some(outcome),
+                Ok(outcome) => {
+                    self.metrics.record_mined_block(&outcome);
+                    Some(outcome)
+                }
                 Err(e) => {
                     error!(target: LOG_TARGET, error = %e, "On force mine.");
                     None
@@ -681,11 +788,33 @@ where
         }
     }
 
+    fn reset(&mut self) -> Result<(), BlockProductionError> {
+        let provider = self.backend.storage.provider();
+        let state = provider.latest()?;
+        let latest_num = provider.latest_number()?;
+        let block_env = provider.block_env_at(latest_num.into())?.expect("latest block env");
+        self.validator.reset(state, block_env);
+
+        self.queued.clear();
+        self.pool_metrics.queued_transactions.set(0.0);
+
+        Ok(())
+    }
+
+    fn queue(&mut self, transactions: Vec<ExecutableTxWithHash>) {
+        self.queued.push_back(transactions);
+
+        let queued = self.queued.iter().map(Vec::len).sum::<usize>();
+        self.pool_metrics.queued_transactions.set(queued as f64);
+    }
+
     fn do_mine(
         validator: TxValidator,
         permit: Arc<Mutex<()>>,
         backend: Arc<Backend<PF>>,
         transactions: VecDeque<Vec<ExecutableTxWithHash>>,
+        metrics: BlockProducerMetrics,
+        reason: BlockCloseReason,
     ) -> Result<MinedBlockOutcome, BlockProductionError> {
         let _permit = permit.lock();
 
@@ -704,12 +833,15 @@ where
         let latest_state = provider.latest()?;
         let mut executor = backend.executor_factory.executor(latest_state, block_env);
 
+        let start = std::time::Instant::now();
         let _ = executor.execute_transactions(transactions)?;
+        metrics.execution_time_seconds.record(start.elapsed().as_secs_f64());
 
         let block_env = executor.block_env();
         let execution_output = executor.take_execution_output()?;
 
         let outcome = backend.do_mine_block(&block_env, execution_output)?;
+        record_block_closed(reason);
 
         // update pool validator state here ---------
 
@@ -746,9 +878,14 @@ where
                 let validator = pin.validator.clone();
                 let backend = pin.backend.clone();
                 let permit = pin.permit.clone();
+                let metrics = pin.metrics.clone();
 
-                pin.blocking_task_spawner
-                    .spawn(|| Self::do_mine(validator, permit, backend, transactions))
+                pin.pool_metrics.queued_transactions.set(0.0);
+
+                pin.blocking_task_spawner.spawn(|| {
+                    let reason = BlockCloseReason::Instant;
+                    Self::do_mine(validator, permit, backend, transactions, metrics, reason)
+                })
             }));
         }
 
@@ -1,11 +1,140 @@
+use std::sync::LazyLock;
+
 use katana_metrics::Metrics;
-use metrics::Counter;
+use metrics::{Counter, Gauge, Histogram};
 
-#[derive(Metrics)]
+use super::block_producer::MinedBlockOutcome;
+
+#[derive(Metrics, Clone)]
 #[metrics(scope = "block_producer")]
 pub(crate) struct BlockProducerMetrics {
     /// The amount of L1 gas processed in a block.
     pub(crate) l1_gas_processed_total: Counter,
     /// The amount of Cairo steps processed in a block.
     pub(crate) cairo_steps_processed_total: Counter,
+    /// The number of transactions included in blocks.
+    pub(crate) transactions_total: Counter,
+    /// The number of reverted transactions included in blocks.
+    pub(crate) reverted_transactions_total: Counter,
+    /// The number of transactions included in a block.
+    pub(crate) transactions_per_block: Histogram,
+    /// The time it takes to execute a batch of transactions. With instant mining, every block is
+    /// executed in a single batch.
+    pub(crate) execution_time_seconds: Histogram,
+}
+
+impl BlockProducerMetrics {
+    pub(crate) fn record_mined_block(&self, outcome: &MinedBlockOutcome) {
+        self.l1_gas_processed_total.increment(outcome.stats.l1_gas_used as u64);
+        self.cairo_steps_processed_total.increment(outcome.stats.cairo_steps_used as u64);
+        self.transactions_total.increment(outcome.txs.len() as u64);
+        self.reverted_transactions_total.increment(outcome.reverted_txs as u64);
+        self.transactions_per_block.record(outcome.txs.len() as f64);
+    }
+}
+
+impl std::fmt::Debug for BlockProducerMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockProducerMetrics").finish_non_exhaustive()
+    }
+}
+
+/// The size of the pool for the transactions that have been handed over to the block producer.
+///
+/// These transactions remain in the pool, and are thus also counted by its pending transactions
+/// gauge, until the block that includes them is closed.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "pool")]
+pub(crate) struct PoolStateMetrics {
+    /// The number of pool transactions waiting to be executed by the block producer.
+    pub(crate) queued_transactions: Gauge,
+    /// The number of pool transactions executed in the open block, waiting for the block to be
+    /// closed.
+    pub(crate) executed_transactions: Gauge,
+}
+
+impl std::fmt::Debug for PoolStateMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolStateMetrics").finish_non_exhaustive()
+    }
+}
+
+/// The reason a block was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BlockCloseReason {
+    /// The block time has elapsed.
+    Interval,
+    /// The block has reached one of its limits.
+    Full,
+    /// Instant mining closes a block for every batch of transactions.
+    Instant,
+    /// The block was mined on demand, eg through `dev_generateBlock`.
+    Forced,
+}
+
+impl BlockCloseReason {
+    const ALL: [Self; 4] = [Self::Interval, Self::Full, Self::Instant, Self::Forced];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Interval => "interval",
+            Self::Full => "full",
+            Self::Instant => "instant",
+            Self::Forced => "forced",
+        }
+    }
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "block_producer")]
+struct BlockCloseMetrics {
+    /// The number of blocks closed.
+    blocks_closed_total: Counter,
+}
+
+/// The metrics of every [`BlockCloseReason`], indexed by the reason.
+static BLOCK_CLOSE_METRICS: LazyLock<[BlockCloseMetrics; 4]> = LazyLock::new(|| {
+    BlockCloseReason::ALL
+        .map(|reason| BlockCloseMetrics::new_with_labels(&[("reason", reason.as_str())]))
+});
+
+pub(crate) fn record_block_closed(reason: BlockCloseReason) {
+    BLOCK_CLOSE_METRICS[reason as usize].blocks_closed_total.increment(1);
+}
+
+/// How the commitments of a block were computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommitMethod {
+    /// [`UncommittedBlock::commit`](crate::backend::UncommittedBlock::commit).
+    Sequential,
+    /// [`UncommittedBlock::commit_parallel`](crate::backend::UncommittedBlock::commit_parallel).
+    Parallel,
+}
+
+impl CommitMethod {
+    const ALL: [Self; 2] = [Self::Sequential, Self::Parallel];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sequential => "sequential",
+            Self::Parallel => "parallel",
+        }
+    }
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "block_producer")]
+struct BlockCommitMetrics {
+    /// The time it takes to compute the state root and commitments of a block.
+    commit_time_seconds: Histogram,
+}
+
+/// The metrics of every [`CommitMethod`], indexed by the method.
+static BLOCK_COMMIT_METRICS: LazyLock<[BlockCommitMetrics; 2]> = LazyLock::new(|| {
+    CommitMethod::ALL
+        .map(|method| BlockCommitMetrics::new_with_labels(&[("method", method.as_str())]))
+});
+
+pub(crate) fn record_block_commit(method: CommitMethod, elapsed: std::time::Duration) {
+    BLOCK_COMMIT_METRICS[method as usize].commit_time_seconds.record(elapsed.as_secs_f64());
 }
@@ -14,7 +14,7 @@ use self::block_producer::BlockProducer;
 use self::metrics::BlockProducerMetrics;
 
 pub mod block_producer;
-mod metrics;
+pub(crate) mod metrics;
 
 pub(crate) const LOG_TARGET: &str = "node";
 
@@ -74,10 +74,7 @@ where
                     Ok(outcome) => {
                         info!(target: LOG_TARGET, block_number = %outcome.block_number, "Mined block.");
 
-                        let gas_used = outcome.stats.l1_gas_used;
-                        let steps_used = outcome.stats.cairo_steps_used;
-                        this.metrics.l1_gas_processed_total.increment(gas_used as u64);
-                        this.metrics.cairo_steps_processed_total.increment(steps_used as u64);
+                        this.metrics.record_mined_block(&outcome);
 
                         // remove mined transactions from the pool
                         this.pool.remove_transactions(&outcome.txs);
@@ -0,0 +1,123 @@
+use blockifier::bouncer::{n_steps_to_gas, Bouncer, BouncerConfig, BouncerWeights};
+use blockifier::context::BlockContext;
+use blockifier::state::cached_state::StateMaps;
+use blockifier::state::state_api::StateReader;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use starknet_api::execution_resources::GasAmount;
+
+use crate::error::ExecutorError;
+use crate::{BlockLimits, ExecutorResult};
+
+/// Tracks the resources used by the transactions of a block against the [`BlockLimits`].
+///
+/// Most of the limits are enforced by blockifier's [`Bouncer`]. The L1 data gas isn't among the
+/// resources accounted by the bouncer, so it's tracked separately.
+#[derive(Debug)]
+pub struct BlockBouncer {
+    bouncer: Bouncer,
+    l1_data_gas_limit: Option<u64>,
+    l1_data_gas_used: u64,
+    /// The number of transactions accounted so far.
+    transactions: usize,
+}
+
+impl BlockBouncer {
+    pub fn new(limits: &BlockLimits, block_context: &BlockContext) -> Self {
+        let mut block_max_capacity = BouncerWeights::max();
+
+        // Initially, the primary reason why we introduced the cairo steps limit is to limit the
+        // number of steps that needs to be proven during the prove generation process. As
+        // of Starknet v0.13.4 update, a new type of resources is introduced, that is the L2 gas.
+        // Which is supposed to pay for every L2-related resources (eg., computation, and
+        // other blockchain-related resources such as tx payload, events emission, etc.)
+        //
+        // Now blockifier uses L2 gas as the primary resource for pricing the transactions. Hence,
+        // we need to convert the cairo steps limit to L2 gas. Where 1 Cairo step = 100 L2
+        // gas.
+        //
+        // To learn more about the L2 gas, refer to <https://community.starknet.io/t/starknet-v0-13-4-pre-release-notes/115257>.
+        block_max_capacity.sierra_gas =
+            n_steps_to_gas(limits.cairo_steps as usize, block_context.versioned_constants());
+
+        // If an explicit L2 gas limit is also set, the lowest of the two applies.
+        if let Some(l2_gas) = limits.l2_gas {
+            block_max_capacity.sierra_gas = block_max_capacity.sierra_gas.min(GasAmount(l2_gas));
+        }
+
+        if let Some(transactions) = limits.transactions {
+            block_max_capacity.n_txs = transactions as usize;
+        }
+
+        if let Some(state_diff_size) = limits.state_diff_size {
+            block_max_capacity.state_diff_size = state_diff_size as usize;
+        }
+
+        if let Some(events) = limits.events {
+            block_max_capacity.n_events = events as usize;
+        }
+
+        let bouncer = Bouncer::new(BouncerConfig { block_max_capacity, ..Default::default() });
+        Self {
+            bouncer,
+            l1_data_gas_limit: limits.l1_data_gas,
+            l1_data_gas_used: 0,
+            transactions: 0,
+        }
+    }
+
+    /// Accounts the resources used by an executed transaction.
+    ///
+    /// `state` is the state the transaction was executed on, and `state_changes` the changes it
+    /// made. Returns [`ExecutorError::LimitsExhausted`], without accounting anything, if the
+    /// transaction doesn't fit in the block anymore, or [`ExecutorError::TransactionTooLarge`] if
+    /// it doesn't even fit in an empty block.
+    pub fn try_update<S: StateReader>(
+        &mut self,
+        state: &S,
+        state_changes: &StateMaps,
+        info: &TransactionExecutionInfo,
+        block_context: &BlockContext,
+    ) -> ExecutorResult<()> {
+        let result = self.update(state, state_changes, info, block_context);
+
+        // Closing the block wouldn't help a transaction that is too large to ever fit in one, so
+        // it must not be retried in the next block.
+        match result {
+            Err(ExecutorError::LimitsExhausted) if self.transactions == 0 => {
+                Err(ExecutorError::TransactionTooLarge)
+            }
+            Ok(()) => {
+                self.transactions += 1;
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    fn update<S: StateReader>(
+        &mut self,
+        state: &S,
+        state_changes: &StateMaps,
+        info: &TransactionExecutionInfo,
+        block_context: &BlockContext,
+    ) -> ExecutorResult<()> {
+        let l1_data_gas_used = self.l1_data_gas_used + info.receipt.gas.l1_data_gas.0;
+        if self.l1_data_gas_limit.is_some_and(|limit| l1_data_gas_used > limit) {
+            return Err(ExecutorError::LimitsExhausted);
+        }
+
+        let versioned_constants = block_context.versioned_constants();
+
+        self.bouncer.try_update(
+            state,
+            &state_changes.keys(),
+            &info.summarize(versioned_constants),
+            &info.summarize_builtins(),
+            &info.receipt.resources,
+            versioned_constants,
+        )?;
+
+        self.l1_data_gas_used = l1_data_gas_used;
+        Ok(())
+    }
+}
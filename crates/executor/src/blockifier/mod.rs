@@ -3,15 +3,17 @@ use std::sync::Arc;
 // Re-export the blockifier crate.
 pub use blockifier;
 use blockifier::blockifier_versioned_constants::VersionedConstants;
-use blockifier::bouncer::{n_steps_to_gas, Bouncer, BouncerConfig, BouncerWeights};
 
+pub mod bouncer;
 pub mod cache;
 pub mod call;
 mod error;
+mod parallel;
 pub mod state;
 pub mod utils;
 
 use blockifier::context::BlockContext;
+use bouncer::BlockBouncer;
 use cache::ClassCache;
 use katana_chain_spec::ChainSpec;
 use katana_primitives::block::{ExecutableBlock, GasPrices as KatanaGasPrices, PartialHeader};
@@ -19,6 +21,7 @@ use katana_primitives::env::{BlockEnv, VersionedConstantsOverrides};
 use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, TxWithHash};
 use katana_primitives::version::StarknetVersion;
 use katana_provider::api::state::StateProvider;
+use katana_tasks::CpuBlockingTaskPool;
 use starknet_api::block::{
     BlockInfo, BlockNumber, BlockTimestamp, GasPriceVector, GasPrices, NonzeroGasPrice,
 };
@@ -41,6 +44,7 @@ pub struct BlockifierFactory {
     class_cache: ClassCache,
     chain_spec: Arc<ChainSpec>,
     overrides: Option<VersionedConstantsOverrides>,
+    parallel_execution: Option<CpuBlockingTaskPool>,
 }
 
 impl BlockifierFactory {
@@ -52,7 +56,15 @@ impl BlockifierFactory {
         class_cache: ClassCache,
         chain_spec: Arc<ChainSpec>,
     ) -> Self {
-        Self { overrides: cfg, flags, limits, class_cache, chain_spec }
+        Self { overrides: cfg, flags, limits, class_cache, chain_spec, parallel_execution: None }
+    }
+
+    /// Enables the parallel execution of transactions on `pool` in the created executors.
+    ///
+    /// See [`StarknetVMProcessor::with_parallel_execution`].
+    pub fn with_parallel_execution(mut self, pool: CpuBlockingTaskPool) -> Self {
+        self.parallel_execution = Some(pool);
+        self
     }
 
     pub fn chain(&self) -> &Arc<ChainSpec> {
@@ -65,7 +77,7 @@ impl ExecutorFactory for BlockifierFactory {
         let cfg_env = self.overrides.clone();
         let flags = self.flags.clone();
         let limits = self.limits.clone();
-        Box::new(StarknetVMProcessor::new(
+        let processor = StarknetVMProcessor::new(
             state,
             block_env,
             cfg_env,
@@ -73,7 +85,11 @@ impl ExecutorFactory for BlockifierFactory {
             limits,
             self.class_cache.clone(),
             self.chain_spec.clone(),
-        ))
+        );
+        match &self.parallel_execution {
+            Some(pool) => Box::new(processor.with_parallel_execution(pool.clone())),
+            None => Box::new(processor),
+        }
     }
 
     fn overrides(&self) -> Option<&VersionedConstantsOverrides> {
@@ -97,9 +113,10 @@ pub struct StarknetVMProcessor {
     transactions: Vec<(TxWithHash, ExecutionResult)>,
     simulation_flags: ExecutionFlags,
     stats: ExecutionStats,
-    bouncer: Bouncer,
+    bouncer: BlockBouncer,
     starknet_version: StarknetVersion,
     cfg_env: Option<VersionedConstantsOverrides>,
+    parallel_execution: Option<CpuBlockingTaskPool>,
 }
 
 impl StarknetVMProcessor {
@@ -121,23 +138,7 @@ impl StarknetVMProcessor {
 
         let state = state::CachedState::new(state, class_cache);
 
-        let mut block_max_capacity = BouncerWeights::max();
-
-        // Initially, the primary reason why we introduced the cairo steps limit is to limit the
-        // number of steps that needs to be proven during the prove generation process. As
-        // of Starknet v0.13.4 update, a new type of resources is introduced, that is the L2 gas.
-        // Which is supposed to pay for every L2-related resources (eg., computation, and
-        // other blockchain-related resources such as tx payload, events emission, etc.)
-        //
-        // Now blockifier uses L2 gas as the primary resource for pricing the transactions. Hence,
-        // we need to convert the cairo steps limit to L2 gas. Where 1 Cairo step = 100 L2
-        // gas.
-        //
-        // To learn more about the L2 gas, refer to <https://community.starknet.io/t/starknet-v0-13-4-pre-release-notes/115257>.
-        block_max_capacity.sierra_gas =
-            n_steps_to_gas(limits.cairo_steps as usize, block_context.versioned_constants());
-
-        let bouncer = Bouncer::new(BouncerConfig { block_max_capacity, ..Default::default() });
+        let bouncer = BlockBouncer::new(&limits, &block_context);
 
         Self {
             cfg_env,
@@ -148,9 +149,22 @@ impl StarknetVMProcessor {
             stats: Default::default(),
             bouncer,
             starknet_version: block_env.starknet_version,
+            parallel_execution: None,
         }
     }
 
+    /// Enables the parallel execution of transactions on `pool`.
+    ///
+    /// When enabled, the transactions of every batch passed to
+    /// [`execute_transactions`](Executor::execute_transactions) are executed optimistically in
+    /// parallel, and re-executed one by one only if they conflict with the transactions before
+    /// them. The execution results and state updates are identical to those of sequential
+    /// execution. Disabled by default.
+    pub fn with_parallel_execution(mut self, pool: CpuBlockingTaskPool) -> Self {
+        self.parallel_execution = Some(pool);
+        self
+    }
+
     fn fill_block_env_from_header(&mut self, header: &PartialHeader) {
         let number = BlockNumber(header.number);
         let timestamp = BlockTimestamp(header.timestamp);
@@ -225,6 +239,15 @@ impl Executor for StarknetVMProcessor {
         let flags = &self.simulation_flags;
         let mut state = self.state.inner.lock();
 
+        let mut speculations = match &self.parallel_execution {
+            Some(pool) if transactions.len() > 1 => {
+                let state = &mut *state;
+                pool.install(|| parallel::speculate(state, block_context, flags, &transactions))
+            }
+            _ => Vec::new(),
+        }
+        .into_iter();
+
         let mut total_executed = 0;
         for exec_tx in transactions {
             // Collect class artifacts if its a declare tx
@@ -237,13 +260,38 @@ impl Executor for StarknetVMProcessor {
 
             let tx = TxWithHash::from(exec_tx.clone());
             let hash = tx.hash;
-            let result = utils::transact(
-                &mut state.cached_state,
-                block_context,
-                flags,
-                exec_tx,
-                Some(&mut self.bouncer),
-            );
+
+            let speculation = speculations.next().flatten();
+            let speculated = speculation.is_some();
+            let committed = speculation.and_then(|speculation| {
+                speculation.commit(
+                    &mut state.cached_state,
+                    &exec_tx,
+                    block_context,
+                    flags,
+                    &mut self.bouncer,
+                )
+            });
+
+            let result = match committed {
+                Some(result) => {
+                    self.stats.speculations_committed += 1;
+                    result
+                }
+                None => {
+                    if speculated {
+                        self.stats.speculations_reexecuted += 1;
+                    }
+
+                    utils::transact(
+                        &mut state.cached_state,
+                        block_context,
+                        flags,
+                        exec_tx,
+                        Some(&mut self.bouncer),
+                    )
+                }
+            };
 
             match result {
                 Ok(exec_result) => {
@@ -251,6 +299,8 @@ impl Executor for StarknetVMProcessor {
                         ExecutionResult::Success { receipt, .. } => {
                             self.stats.l1_gas_used +=
                                 receipt.resources_used().total_gas_consumed.l1_gas as u128;
+                            self.stats.l2_gas_used +=
+                                receipt.resources_used().total_gas_consumed.l2_gas as u128;
                             self.stats.cairo_steps_used +=
                                 receipt.resources_used().vm_resources.n_steps as u128;
 
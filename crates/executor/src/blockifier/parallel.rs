@@ -0,0 +1,327 @@
+//! Optimistic parallel execution of transactions.
+//!
+//! The transactions of a batch are first executed speculatively and in parallel, each on top of
+//! the state at the start of the batch, while recording every value they read. They are then
+//! committed one by one, in order. The outcome of a transaction only depends on the values it
+//! reads, so if all of them are unchanged in the state left by the transactions before it, the
+//! speculative outcome is exactly the one a sequential execution would produce and its writes are
+//! applied as is. Otherwise, the transaction conflicts with an earlier one and is re-executed on
+//! top of the current state.
+//!
+//! Transactions that charge fees all read and write the sequencer's fee token balance. To keep
+//! them from always conflicting with each other, they are executed in blockifier's concurrency
+//! mode, in which the fee transfer leaves the sequencer's balance untouched, and the balance is
+//! only updated when the transaction is committed.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use blockifier::concurrency::fee_utils::complete_fee_transfer_flow;
+use blockifier::context::BlockContext;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::cached_state::{self, ContractClassMapping, StateMaps, TransactionalState};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader, StateResult, UpdatableState};
+use katana_primitives::class::{self, ContractClass};
+use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash};
+use rayon::prelude::*;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::hash::StarkHash;
+use starknet_api::state::StorageKey;
+
+use super::bouncer::BlockBouncer;
+use super::state::{CachedStateInner, StateProviderDb};
+use super::utils;
+use crate::error::{ExecutionError, ExecutorError};
+use crate::{ExecutionFlags, ExecutionResult, ExecutorResult};
+
+/// The outcome of the speculative execution of a transaction.
+#[derive(Debug)]
+pub(super) struct Speculation {
+    result: ExecutionResult,
+    reads: ReadSet,
+    writes: StateMaps,
+}
+
+/// Speculatively executes `transactions` in parallel, each on top of `state`.
+///
+/// Returns the speculation of every transaction, in order. A speculation is `None` if the
+/// transaction couldn't be executed speculatively, in which case it must be executed normally.
+pub(super) fn speculate(
+    state: &mut CachedStateInner,
+    block_context: &BlockContext,
+    flags: &ExecutionFlags,
+    transactions: &[ExecutableTxWithHash],
+) -> Vec<Option<Speculation>> {
+    let Ok(changes) = state.cached_state.to_state_diff() else {
+        return transactions.iter().map(|_| None).collect();
+    };
+
+    let base = BaseState {
+        db: &state.cached_state.state,
+        writes: changes.state_maps,
+        declared_classes: &state.declared_classes,
+    };
+
+    transactions
+        .par_iter()
+        .map(|tx| {
+            let mut state = cached_state::CachedState::new(RecordingState::new(&base));
+
+            // `transact_concurrently` can't fail as it isn't given a bouncer.
+            let result =
+                utils::transact_concurrently(&mut state, block_context, flags, tx.clone()).ok()?;
+            let writes = state.to_state_diff().ok()?.state_maps;
+            let reads = state.state.reads.into_inner();
+
+            (!reads.incomplete).then_some(Speculation { result, reads, writes })
+        })
+        .collect()
+}
+
+impl Speculation {
+    /// Commits the speculative execution of `tx` to `state`.
+    ///
+    /// Returns `None`, without modifying `state`, if any of the values read by the speculative
+    /// execution has since been changed in `state`, in which case the transaction must be
+    /// re-executed.
+    pub(super) fn commit(
+        mut self,
+        state: &mut cached_state::CachedState<StateProviderDb>,
+        tx: &ExecutableTxWithHash,
+        block_context: &BlockContext,
+        flags: &ExecutionFlags,
+        bouncer: &mut BlockBouncer,
+    ) -> Option<ExecutorResult<ExecutionResult>> {
+        if !self.reads.is_valid(state) {
+            return None;
+        }
+
+        // The class of a declare transaction must be known by the state for the transactions
+        // after it to be able to use it.
+        let mut classes = ContractClassMapping::default();
+        if let ExecutableTx::Declare(declare) = tx.as_ref() {
+            let hash = ClassHash(declare.class_hash());
+            if self.writes.declared_contracts.contains_key(&hash)
+                || self.writes.compiled_class_hashes.contains_key(&hash)
+            {
+                let class =
+                    state.state.class_cache().insert(hash.0, declare.class.as_ref().clone());
+                classes.insert(hash, class);
+            }
+        }
+
+        // Same as in `utils::transact`, the bouncer is updated against the state with the
+        // transaction's changes applied, and the changes are only committed if it fits in the
+        // block.
+        let mut tx_state = TransactionalState::create_transactional(state);
+        tx_state.apply_writes(&self.writes, &classes);
+
+        if let ExecutionResult::Success { trace, .. } = &mut self.result {
+            // The speculative execution left the sequencer's fee token balance untouched, so the
+            // fee is added to it now, on top of the balance left by the transactions before it.
+            let transaction = utils::to_executor_tx(tx.clone(), flags.clone());
+            let tx_context = block_context.to_tx_context(&transaction);
+            let tx_type = transaction.tx_type();
+            complete_fee_transfer_flow(
+                &tx_context,
+                trace,
+                &mut self.writes,
+                &mut tx_state,
+                tx_type,
+            );
+
+            match bouncer.try_update(&tx_state, &self.writes, trace, block_context) {
+                Ok(()) => {}
+                Err(ExecutorError::TransactionTooLarge) => {
+                    let error = ExecutionError::TransactionTooLarge;
+                    return Some(Ok(ExecutionResult::new_failed(error)));
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+
+        tx_state.commit();
+        Some(Ok(self.result))
+    }
+}
+
+/// The values read by a transaction during its speculative execution.
+#[derive(Debug, Default)]
+struct ReadSet {
+    storage: HashMap<(ContractAddress, StorageKey), StarkHash>,
+    nonces: HashMap<ContractAddress, Nonce>,
+    class_hashes: HashMap<ContractAddress, ClassHash>,
+    compiled_class_hashes: HashMap<ClassHash, Option<CompiledClassHash>>,
+    /// Whether each class that was loaded was found.
+    classes: HashMap<ClassHash, bool>,
+    /// Set if a read failed with an unexpected error, in which case the read set can't be
+    /// validated.
+    incomplete: bool,
+}
+
+impl ReadSet {
+    /// Returns whether all the values in the set are the same in `state`.
+    fn is_valid<S: StateReader>(&self, state: &S) -> bool {
+        let storage = self.storage.iter().all(|((address, key), value)| {
+            state.get_storage_at(*address, *key).is_ok_and(|current| current == *value)
+        });
+
+        let nonces = self.nonces.iter().all(|(address, nonce)| {
+            state.get_nonce_at(*address).is_ok_and(|current| current == *nonce)
+        });
+
+        let class_hashes = self.class_hashes.iter().all(|(address, class_hash)| {
+            state.get_class_hash_at(*address).is_ok_and(|current| current == *class_hash)
+        });
+
+        let compiled_class_hashes = self.compiled_class_hashes.iter().all(|(class_hash, hash)| {
+            match state.get_compiled_class_hash(*class_hash) {
+                Ok(current) => Some(current) == *hash,
+                Err(StateError::UndeclaredClassHash(..)) => hash.is_none(),
+                Err(..) => false,
+            }
+        });
+
+        // Classes can't be removed once declared, so only the classes that weren't found may
+        // have been declared since.
+        let classes = self
+            .classes
+            .iter()
+            .filter(|(_, found)| !**found)
+            .all(|(class_hash, _)| state.get_compiled_class(*class_hash).is_err());
+
+        storage && nonces && class_hashes && compiled_class_hashes && classes
+    }
+}
+
+/// The state at the start of a batch: the underlying database, with the changes made by the
+/// transactions executed in the block so far on top of it.
+struct BaseState<'a> {
+    db: &'a StateProviderDb,
+    writes: StateMaps,
+    declared_classes: &'a HashMap<class::ClassHash, ContractClass>,
+}
+
+impl StateReader for BaseState<'_> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<StarkHash> {
+        match self.writes.storage.get(&(contract_address, key)) {
+            Some(value) => Ok(*value),
+            None => self.db.get_storage_at(contract_address, key),
+        }
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        match self.writes.nonces.get(&contract_address) {
+            Some(nonce) => Ok(*nonce),
+            None => self.db.get_nonce_at(contract_address),
+        }
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        match self.writes.class_hashes.get(&contract_address) {
+            Some(class_hash) => Ok(*class_hash),
+            None => self.db.get_class_hash_at(contract_address),
+        }
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        match self.declared_classes.get(&class_hash.0) {
+            Some(class) => {
+                let cache = self.db.class_cache();
+                Ok(cache
+                    .get(&class_hash.0)
+                    .unwrap_or_else(|| cache.insert(class_hash.0, class.clone())))
+            }
+            None => self.db.get_compiled_class(class_hash),
+        }
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        match self.writes.compiled_class_hashes.get(&class_hash) {
+            Some(hash) => Ok(*hash),
+            None => self.db.get_compiled_class_hash(class_hash),
+        }
+    }
+}
+
+/// A state reader that records the values read from the underlying state.
+struct RecordingState<'a, S> {
+    state: &'a S,
+    reads: RefCell<ReadSet>,
+}
+
+impl<'a, S: StateReader> RecordingState<'a, S> {
+    fn new(state: &'a S) -> Self {
+        Self { state, reads: RefCell::default() }
+    }
+
+    /// Records the value of a read if it succeeded, or marks the read set as incomplete otherwise.
+    fn record<T: Clone>(
+        &self,
+        result: StateResult<T>,
+        record: impl FnOnce(&mut ReadSet, T),
+    ) -> StateResult<T> {
+        let mut reads = self.reads.borrow_mut();
+        match &result {
+            Ok(value) => record(&mut reads, value.clone()),
+            Err(..) => reads.incomplete = true,
+        }
+        result
+    }
+}
+
+impl<S: StateReader> StateReader for RecordingState<'_, S> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<StarkHash> {
+        let result = self.state.get_storage_at(contract_address, key);
+        self.record(result, |reads, value| {
+            reads.storage.insert((contract_address, key), value);
+        })
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let result = self.state.get_nonce_at(contract_address);
+        self.record(result, |reads, nonce| {
+            reads.nonces.insert(contract_address, nonce);
+        })
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let result = self.state.get_class_hash_at(contract_address);
+        self.record(result, |reads, class_hash| {
+            reads.class_hashes.insert(contract_address, class_hash);
+        })
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        let result = self.state.get_compiled_class(class_hash);
+        let mut reads = self.reads.borrow_mut();
+        match &result {
+            Ok(..) => _ = reads.classes.insert(class_hash, true),
+            Err(StateError::UndeclaredClassHash(..)) => _ = reads.classes.insert(class_hash, false),
+            Err(..) => reads.incomplete = true,
+        }
+        result
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        let result = self.state.get_compiled_class_hash(class_hash);
+        let mut reads = self.reads.borrow_mut();
+        match &result {
+            Ok(hash) => _ = reads.compiled_class_hashes.insert(class_hash, Some(*hash)),
+            Err(StateError::UndeclaredClassHash(..)) => {
+                _ = reads.compiled_class_hashes.insert(class_hash, None)
+            }
+            Err(..) => reads.incomplete = true,
+        }
+        result
+    }
+}
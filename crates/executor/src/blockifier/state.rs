@@ -1,13 +1,15 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use blockifier::execution::contract_class::RunnableCompiledClass;
-use blockifier::state::cached_state::{self, MutRefState};
+use blockifier::state::cached_state::{self, ContractClassMapping, MutRefState, StateMaps};
 use blockifier::state::errors::StateError;
-use blockifier::state::state_api::{StateReader, StateResult};
+use blockifier::state::state_api::{StateReader, StateResult, UpdatableState};
 use katana_primitives::class::{self, ContractClass};
+use katana_primitives::state::{StateAccesses, StateUpdates};
 use katana_primitives::Felt;
 use katana_provider::api::contract::ContractClassProvider;
 use katana_provider::api::state::{StateProofProvider, StateProvider, StateRootProvider};
@@ -183,6 +185,11 @@ impl StateProviderDb {
     pub fn new(provider: Box<dyn StateProvider>, class_cache: ClassCache) -> Self {
         Self { provider, compiled_class_cache: class_cache }
     }
+
+    /// Returns the class cache used to compile the classes read from the provider.
+    pub(super) fn class_cache(&self) -> &ClassCache {
+        &self.compiled_class_cache
+    }
 }
 
 impl StateReader for StateProviderDb {
@@ -249,3 +256,128 @@ impl StateReader for StateProviderDb {
             .map_err(|e| StateError::StateReadError(e.to_string()))
     }
 }
+
+/// A state wrapper that records the parts of the underlying state accessed through it.
+///
+/// Reads are only recorded when they reach this layer, ie when they're not served by a cache above
+/// it. To capture everything a single transaction accesses, the recorder should be passed directly
+/// to [`transact`](super::utils::transact), so that the only cache above it is the transaction's
+/// own transactional state.
+pub struct StateAccessRecorder<'a, S> {
+    state: &'a mut S,
+    accesses: RefCell<StateAccesses>,
+    updates: StateUpdates,
+}
+
+impl<S: Debug> Debug for StateAccessRecorder<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateAccessRecorder")
+            .field("state", &self.state)
+            .field("accesses", &self.accesses)
+            .field("updates", &self.updates)
+            .finish()
+    }
+}
+
+impl<'a, S: UpdatableState> StateAccessRecorder<'a, S> {
+    /// Creates a new recorder on top of `state`.
+    pub fn new(state: &'a mut S) -> Self {
+        Self { state, accesses: RefCell::default(), updates: StateUpdates::default() }
+    }
+
+    /// Returns the recorded state accesses, and the state updates that were written through the
+    /// recorder.
+    pub fn finish(self) -> (StateAccesses, StateUpdates) {
+        (self.accesses.into_inner(), self.updates)
+    }
+
+    /// Records the writes applied through the recorder. Must be called before the writes are
+    /// applied to the underlying state.
+    ///
+    /// Every write is recorded as an access, but only the writes that change the value in the
+    /// underlying state are included in the state updates, the same way
+    /// [`to_state_diff`](cached_state::CachedState::to_state_diff) filters them.
+    fn record_writes(&mut self, writes: &StateMaps, classes: &ContractClassMapping) {
+        let accesses = self.accesses.get_mut();
+
+        for ((contract_address, storage_key), value) in &writes.storage {
+            let address = utils::to_address(*contract_address);
+            let key = *storage_key.0.key();
+            accesses.storage_writes.entry(address).or_default().insert(key);
+
+            if self.state.get_storage_at(*contract_address, *storage_key).ok() != Some(*value) {
+                self.updates.storage_updates.entry(address).or_default().insert(key, *value);
+            }
+        }
+
+        for (contract_address, nonce) in &writes.nonces {
+            let address = utils::to_address(*contract_address);
+            accesses.nonces.insert(address);
+
+            if self.state.get_nonce_at(*contract_address).ok() != Some(*nonce) {
+                self.updates.nonce_updates.insert(address, nonce.0);
+            }
+        }
+
+        for (contract_address, class_hash) in &writes.class_hashes {
+            let address = utils::to_address(*contract_address);
+            accesses.class_hashes.insert(address);
+
+            if self.state.get_class_hash_at(*contract_address).ok() != Some(*class_hash) {
+                self.updates.deployed_contracts.insert(address, class_hash.0);
+            }
+        }
+
+        for (class_hash, compiled_hash) in &writes.compiled_class_hashes {
+            if let Some(RunnableCompiledClass::V0(..)) = classes.get(class_hash) {
+                self.updates.deprecated_declared_classes.insert(class_hash.0);
+            } else {
+                self.updates.declared_classes.insert(class_hash.0, compiled_hash.0);
+            }
+        }
+    }
+}
+
+impl<S: StateReader> StateReader for StateAccessRecorder<'_, S> {
+    fn get_storage_at(
+        &self,
+        contract_address: starknet_api::core::ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<starknet_api::hash::StarkHash> {
+        let address = utils::to_address(contract_address);
+        self.accesses.borrow_mut().storage_reads.entry(address).or_default().insert(*key.0.key());
+        self.state.get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(
+        &self,
+        contract_address: starknet_api::core::ContractAddress,
+    ) -> StateResult<Nonce> {
+        self.accesses.borrow_mut().nonces.insert(utils::to_address(contract_address));
+        self.state.get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(
+        &self,
+        contract_address: starknet_api::core::ContractAddress,
+    ) -> StateResult<ClassHash> {
+        self.accesses.borrow_mut().class_hashes.insert(utils::to_address(contract_address));
+        self.state.get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.accesses.borrow_mut().classes.insert(class_hash.0);
+        self.state.get_compiled_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.state.get_compiled_class_hash(class_hash)
+    }
+}
+
+impl<S: UpdatableState> UpdatableState for StateAccessRecorder<'_, S> {
+    fn apply_writes(&mut self, writes: &StateMaps, class_hash_to_class: &ContractClassMapping) {
+        self.record_writes(writes, class_hash_to_class);
+        self.state.apply_writes(writes, class_hash_to_class);
+    }
+}
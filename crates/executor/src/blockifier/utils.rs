@@ -3,14 +3,14 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use blockifier::blockifier_versioned_constants::VersionedConstants;
-use blockifier::bouncer::{Bouncer, BouncerConfig};
+use blockifier::bouncer::BouncerConfig;
 use blockifier::context::{BlockContext, ChainInfo, FeeTokenAddresses};
 use blockifier::execution::contract_class::{
     CompiledClassV0, CompiledClassV1, RunnableCompiledClass,
 };
 use blockifier::fee::fee_utils::get_fee_by_gas_vector;
-use blockifier::state::cached_state::{self, TransactionalState};
-use blockifier::state::state_api::StateReader;
+use blockifier::state::cached_state::TransactionalState;
+use blockifier::state::state_api::UpdatableState;
 use blockifier::state::stateful_compression::allocate_aliases_in_storage;
 use blockifier::transaction::account_transaction::{
     AccountTransaction, ExecutionFlags as BlockifierExecutionFlags,
@@ -58,41 +58,72 @@ use starknet_api::transaction::{
 
 const ALIAS_CONTRACT_ADDRESS: &str = "0x2";
 
+use super::bouncer::BlockBouncer;
 use super::state::CachedState;
-use crate::error::ExecutionError;
+use crate::error::{ExecutionError, ExecutorError};
 use crate::utils::build_receipt;
 use crate::{ExecutionFlags, ExecutionResult, ExecutorResult};
 
 #[tracing::instrument(level = "trace", target = "executor", skip_all, fields(type = tx.transaction.r#type().as_ref(), validate = simulation_flags.account_validation()))]
-pub fn transact<S: StateReader>(
-    state: &mut cached_state::CachedState<S>,
+pub fn transact<U: UpdatableState>(
+    state: &mut U,
     block_context: &BlockContext,
     simulation_flags: &ExecutionFlags,
     tx: ExecutableTxWithHash,
-    bouncer: Option<&mut Bouncer>,
+    bouncer: Option<&mut BlockBouncer>,
+) -> ExecutorResult<ExecutionResult> {
+    execute(state, block_context, simulation_flags, tx, bouncer, false)
+}
+
+/// Same as [`transact`], but executes the transaction in blockifier's concurrency mode.
+///
+/// In this mode, the fee transfer doesn't read nor update the sequencer's fee token balance, so
+/// that concurrently executed transactions don't all conflict on it. The sequencer's balance must
+/// then be updated using [`complete_fee_transfer_flow`] once the transaction is committed.
+///
+/// [`complete_fee_transfer_flow`]: blockifier::concurrency::fee_utils::complete_fee_transfer_flow
+pub(super) fn transact_concurrently<U: UpdatableState>(
+    state: &mut U,
+    block_context: &BlockContext,
+    simulation_flags: &ExecutionFlags,
+    tx: ExecutableTxWithHash,
+) -> ExecutorResult<ExecutionResult> {
+    execute(state, block_context, simulation_flags, tx, None, true)
+}
+
+fn execute<U: UpdatableState>(
+    state: &mut U,
+    block_context: &BlockContext,
+    simulation_flags: &ExecutionFlags,
+    tx: ExecutableTxWithHash,
+    bouncer: Option<&mut BlockBouncer>,
+    concurrency_mode: bool,
 ) -> ExecutorResult<ExecutionResult> {
     let transaction = to_executor_tx(tx.clone(), simulation_flags.clone());
     let mut tx_state = TransactionalState::create_transactional(state);
 
     let result = match &transaction {
-        Transaction::Account(tx) => tx.execute(&mut tx_state, block_context),
-        Transaction::L1Handler(tx) => tx.execute(&mut tx_state, block_context),
+        Transaction::Account(tx) => tx.execute_raw(&mut tx_state, block_context, concurrency_mode),
+        Transaction::L1Handler(tx) => {
+            tx.execute_raw(&mut tx_state, block_context, concurrency_mode)
+        }
     };
 
     match result {
         Ok(info) => {
             if let Some(bouncer) = bouncer {
-                let tx_state_changes_keys = tx_state.to_state_diff().unwrap().state_maps.keys();
-                let versioned_constants = block_context.versioned_constants();
-
-                bouncer.try_update(
-                    &tx_state,
-                    &tx_state_changes_keys,
-                    &info.summarize(versioned_constants),
-                    &info.summarize_builtins(),
-                    &info.receipt.resources,
-                    versioned_constants,
-                )?;
+                let tx_state_changes = tx_state.to_state_diff().unwrap().state_maps;
+                match bouncer.try_update(&tx_state, &tx_state_changes, &info, block_context) {
+                    Ok(()) => {}
+                    // The transaction can never be included in a block, so it's dropped.
+                    Err(ExecutorError::TransactionTooLarge) => {
+                        tx_state.abort();
+                        return Ok(ExecutionResult::new_failed(
+                            ExecutionError::TransactionTooLarge,
+                        ));
+                    }
+                    Err(error) => return Err(error),
+                }
             }
 
             tx_state.commit();
@@ -103,7 +134,7 @@ pub fn transact<S: StateReader>(
         }
 
         Err(e) => {
-            tx_state.commit();
+            tx_state.abort();
             Ok(ExecutionResult::new_failed(ExecutionError::from(e)))
         }
     }
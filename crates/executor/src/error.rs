@@ -1,5 +1,6 @@
 use katana_primitives::class::ClassHash;
 use katana_primitives::contract::{ContractAddress, Nonce};
+use katana_primitives::revert::RevertStack;
 use katana_primitives::Felt;
 
 /// Errors that can be returned by the executor.
@@ -8,6 +9,10 @@ pub enum ExecutorError {
     #[error("Limits exhausted")]
     LimitsExhausted,
 
+    /// The transaction doesn't fit in a block even on its own.
+    #[error("Transaction exceeds the block limits")]
+    TransactionTooLarge,
+
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync + 'static>),
 }
@@ -75,6 +80,22 @@ pub enum ExecutionError {
     #[error("Transaction reverted: {revert_error}")]
     TransactionReverted { revert_error: String },
 
+    #[error("Transaction exceeds the block limits")]
+    TransactionTooLarge,
+
     #[error("{0}")]
     Other(String),
 }
+
+impl ExecutionError {
+    /// Returns the structured error stack of errors caused by a failing contract execution.
+    pub fn revert_stack(&self) -> Option<RevertStack> {
+        match self {
+            Self::ExecutionFailed { reason }
+            | Self::ConstructorExecutionFailed { reason }
+            | Self::TransactionValidationFailed { reason }
+            | Self::TransactionReverted { revert_error: reason } => RevertStack::parse(reason),
+            _ => None,
+        }
+    }
+}
@@ -6,13 +6,14 @@ pub mod error;
 
 pub mod blockifier;
 pub mod noop;
+pub mod revert;
 
 use katana_primitives::block::ExecutableBlock;
 use katana_primitives::contract::{ContractAddress, StorageKey, StorageValue};
 use katana_primitives::env::{BlockEnv, VersionedConstantsOverrides};
 use katana_primitives::execution::TransactionExecutionInfo;
 use katana_primitives::receipt::Receipt;
-use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
+use katana_primitives::state::{StateAccesses, StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::transaction::{ExecutableTxWithHash, TxWithHash};
 use katana_provider::api::state::StateProvider;
 
@@ -20,16 +21,41 @@ use crate::blockifier::cache::ClassCache;
 
 pub type ExecutorResult<T> = Result<T, error::ExecutorError>;
 
+/// The limits on the resources used by the transactions of a block.
+///
+/// Once a transaction doesn't fit within any of the limits, the block is closed and the
+/// transaction is left for the next block. Limits that are `None` are unbounded.
+///
 /// See <https://docs.starknet.io/chain-info/#current_limits>.
 #[derive(Debug, Clone)]
 pub struct BlockLimits {
     /// The maximum number of Cairo steps that can be completed within each block.
     pub cairo_steps: u64,
+    /// The maximum amount of L2 (Sierra) gas that can be consumed within each block.
+    ///
+    /// Cairo steps are also accounted as L2 gas, so the lowest of this and [`Self::cairo_steps`]
+    /// applies.
+    pub l2_gas: Option<u64>,
+    /// The maximum number of transactions in each block.
+    pub transactions: Option<u64>,
+    /// The maximum size of the state diff of each block, in number of felts.
+    pub state_diff_size: Option<u64>,
+    /// The maximum amount of L1 data gas that can be consumed within each block.
+    pub l1_data_gas: Option<u64>,
+    /// The maximum number of events that can be emitted within each block.
+    pub events: Option<u64>,
 }
 
 impl Default for BlockLimits {
     fn default() -> Self {
-        Self { cairo_steps: 50_000_000 }
+        Self {
+            cairo_steps: 50_000_000,
+            l2_gas: None,
+            transactions: None,
+            state_diff_size: None,
+            l1_data_gas: None,
+            events: None,
+        }
     }
 }
 
@@ -97,8 +123,15 @@ impl ExecutionFlags {
 pub struct ExecutionStats {
     /// The total gas used.
     pub l1_gas_used: u128,
+    /// The total L2 gas used.
+    pub l2_gas_used: u128,
     /// The total cairo steps used.
     pub cairo_steps_used: u128,
+    /// The number of transactions whose parallel speculative execution was committed as is.
+    pub speculations_committed: u64,
+    /// The number of transactions that were re-executed because their parallel speculative
+    /// execution conflicted with the transactions before them.
+    pub speculations_reexecuted: u64,
 }
 
 /// The output of a executor after a series of executions.
@@ -161,6 +194,8 @@ impl ExecutionResult {
 pub struct ResultAndStates {
     pub result: ExecutionResult,
     pub states: StateUpdates,
+    /// The state accessed during the execution, if it was recorded.
+    pub accesses: Option<StateAccesses>,
 }
 
 /// A type that can create an [Executor] instance.
@@ -0,0 +1,89 @@
+//! Resolution of the Cairo functions in which reverted calls failed.
+
+use std::sync::{Arc, LazyLock};
+
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use katana_primitives::class::{ClassHash, ContractClass, SierraContractClass};
+use katana_primitives::revert::RevertStack;
+use quick_cache::sync::Cache;
+
+/// The maximum number of [`FunctionMap`]s kept in [`FUNCTION_MAPS`].
+const FUNCTION_MAPS_CACHE_SIZE: usize = 100;
+
+/// The function maps of the classes of previously resolved frames, keyed by class hash.
+///
+/// Building a map requires compiling the class, which is too expensive to do on every reverted
+/// simulation. A class hash always refers to the same class, so the maps can be shared globally.
+/// Classes without debug info are cached as `None`.
+static FUNCTION_MAPS: LazyLock<Cache<ClassHash, Option<Arc<FunctionMap>>>> =
+    LazyLock::new(|| Cache::new(FUNCTION_MAPS_CACHE_SIZE));
+
+/// Sets the [`function`](katana_primitives::revert::RevertFrame::function) of the revert frames
+/// whose class has Sierra debug info, by mapping the frame's program counter back to the Sierra
+/// function it belongs to.
+///
+/// `class` is used to look up the class of each frame. Frames of classes without debug info (which
+/// is the case for most declared classes) are left untouched.
+pub fn resolve_revert_functions<F>(stack: &mut RevertStack, mut class: F)
+where
+    F: FnMut(ClassHash) -> Option<ContractClass>,
+{
+    for frame in &mut stack.frames {
+        let (Some(class_hash), Some(pc)) = (frame.class_hash, frame.pc) else { continue };
+
+        let map = match FUNCTION_MAPS.get(&class_hash) {
+            Some(map) => map,
+            None => {
+                let Some(class) = class(class_hash) else { continue };
+
+                let map = match class {
+                    ContractClass::Class(class) => FunctionMap::new(class).map(Arc::new),
+                    ContractClass::Legacy(_) => None,
+                };
+
+                FUNCTION_MAPS.insert(class_hash, map.clone());
+                map
+            }
+        };
+
+        frame.function =
+            map.as_deref().and_then(|map| map.function_at(pc)).map(ToString::to_string);
+    }
+}
+
+/// Maps the bytecode offsets of a compiled Sierra class to the functions they belong to.
+struct FunctionMap {
+    /// Function names sorted by the offset of their first instruction.
+    functions: Vec<(usize, String)>,
+}
+
+impl FunctionMap {
+    fn new(class: SierraContractClass) -> Option<Self> {
+        class.sierra_program_debug_info.as_ref()?;
+
+        let class = cairo_lang_starknet_classes::contract_class::ContractClass::from(class);
+        let program = class.extract_sierra_program().ok()?;
+        let (_, debug_info) =
+            CasmContractClass::from_contract_class_with_debug_info(class, false, usize::MAX)
+                .ok()?;
+
+        let mut functions = program
+            .funcs
+            .iter()
+            .filter_map(|func| {
+                let name = func.id.debug_name.as_ref()?;
+                let offset = debug_info.sierra_statement_info.get(func.entry_point.0)?.start_offset;
+                Some((offset, name.to_string()))
+            })
+            .collect::<Vec<_>>();
+
+        functions.sort_by_key(|(offset, _)| *offset);
+        Some(Self { functions })
+    }
+
+    fn function_at(&self, pc: u64) -> Option<&str> {
+        let pc = usize::try_from(pc).ok()?;
+        let index = self.functions.partition_point(|(offset, _)| *offset <= pc);
+        index.checked_sub(1).map(|i| self.functions[i].1.as_str())
+    }
+}
@@ -3,21 +3,25 @@ mod fixtures;
 use std::collections::BTreeMap;
 
 use fixtures::{state_provider, valid_blocks};
+use katana_chain_spec::ChainSpec;
 use katana_contracts::contracts;
 use katana_executor::{ExecutionOutput, ExecutionResult, ExecutorFactory};
 use katana_genesis::constant::{
     DEFAULT_ETH_FEE_TOKEN_ADDRESS, DEFAULT_PREFUNDED_ACCOUNT_BALANCE, DEFAULT_UDC_ADDRESS,
 };
 use katana_primitives::block::ExecutableBlock;
+use katana_primitives::chain::ChainId;
 use katana_primitives::env::BlockEnv;
-use katana_primitives::transaction::TxWithHash;
+use katana_primitives::transaction::{
+    ExecutableTx, ExecutableTxWithHash, InvokeTx, InvokeTxV1, TxWithHash,
+};
 use katana_primitives::{address, Felt};
 use katana_provider::api::contract::ContractClassProviderExt;
 use katana_provider::api::state::StateProvider;
 use starknet::core::utils::{
     get_storage_var_address, get_udc_deployed_address, UdcUniqueSettings, UdcUniqueness,
 };
-use starknet::macros::felt;
+use starknet::macros::{felt, selector};
 
 fn test_executor_with_valid_blocks_impl(
     factory: BlockifierFactory,
@@ -343,3 +347,183 @@ fn test_executor_with_valid_blocks(
 ) {
     test_executor_with_valid_blocks_impl(factory, state, blocks)
 }
+
+#[rstest::rstest]
+fn parallel_execution_matches_sequential_execution(
+    #[from(factory)] sequential: BlockifierFactory,
+    #[from(factory)] parallel: BlockifierFactory,
+    #[from(state_provider)] sequential_state: Box<dyn StateProvider>,
+    #[from(state_provider)] parallel_state: Box<dyn StateProvider>,
+    #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+) {
+    let pool = katana_tasks::CpuBlockingTaskPool::builder().build().unwrap();
+    let parallel = parallel.with_parallel_execution(pool);
+
+    let expected = execute_blocks(&sequential, sequential_state, &blocks);
+    let actual = execute_blocks(&parallel, parallel_state, &blocks);
+    assert_same_execution_output(&actual, &expected);
+
+    // every transaction was executed in parallel, none had to be re-executed
+    assert_eq!(actual.stats.speculations_committed, blocks[0].body.len() as u64);
+    assert_eq!(actual.stats.speculations_reexecuted, 0);
+    assert_eq!(expected.stats.speculations_committed, 0);
+}
+
+/// Transactions from different senders only share the sequencer's fee token balance, which must
+/// not make them conflict with each other.
+#[rstest::rstest]
+fn parallel_execution_with_multiple_senders(
+    #[from(fixtures::chain)] chain: &ChainSpec,
+    #[from(factory)] sequential: BlockifierFactory,
+    #[from(factory)] parallel: BlockifierFactory,
+    #[from(state_provider)] sequential_state: Box<dyn StateProvider>,
+    #[from(state_provider)] parallel_state: Box<dyn StateProvider>,
+    #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+) {
+    let pool = katana_tasks::CpuBlockingTaskPool::builder().build().unwrap();
+    let parallel = parallel.with_parallel_execution(pool);
+
+    let [block, ..] = blocks;
+    let chain_id = ChainId::parse("KATANA").unwrap();
+
+    // every account transfers some ETH to its own recipient, so that the transactions don't
+    // conflict with each other
+    let body = chain
+        .genesis()
+        .accounts()
+        .enumerate()
+        .map(|(i, (sender_address, _))| {
+            let recipient = felt!("0x1337") + Felt::from(i);
+            ExecutableTxWithHash::new(ExecutableTx::Invoke(InvokeTx::V1(InvokeTxV1 {
+                chain_id,
+                sender_address: *sender_address,
+                calldata: vec![
+                    felt!("0x1"),
+                    DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+                    selector!("transfer"),
+                    felt!("0x3"),
+                    recipient,
+                    felt!("0x1"),
+                    felt!("0x0"),
+                ],
+                max_fee: 4367000000000000,
+                signature: vec![],
+                nonce: Felt::ZERO,
+            })))
+        })
+        .collect::<Vec<_>>();
+
+    assert!(body.len() > 1, "the chain should have multiple accounts");
+    let blocks = [ExecutableBlock { header: block.header, body }];
+
+    let expected = execute_blocks(&sequential, sequential_state, &blocks);
+    let actual = execute_blocks(&parallel, parallel_state, &blocks);
+    assert_same_execution_output(&actual, &expected);
+
+    for (_, result) in &actual.transactions {
+        let receipt = result.receipt().expect("transaction should succeed");
+        assert!(receipt.revert_reason().is_none());
+        assert!(receipt.fee().overall_fee > 0, "fee should be charged");
+    }
+}
+
+fn execute_blocks(
+    factory: &BlockifierFactory,
+    state: Box<dyn StateProvider>,
+    blocks: &[ExecutableBlock],
+) -> ExecutionOutput {
+    let mut executor = factory.executor(state, BlockEnv::default());
+    for block in blocks {
+        executor.execute_block(block.clone()).unwrap();
+    }
+    executor.take_execution_output().unwrap()
+}
+
+fn assert_same_execution_output(actual: &ExecutionOutput, expected: &ExecutionOutput) {
+    let receipts = |output: &ExecutionOutput| {
+        output.transactions.iter().map(|(_, result)| result.receipt().cloned()).collect::<Vec<_>>()
+    };
+
+    assert_eq!(actual.transactions.len(), expected.transactions.len());
+    similar_asserts::assert_eq!(receipts(actual), receipts(expected));
+    similar_asserts::assert_eq!(actual.states.state_updates, expected.states.state_updates);
+    assert_eq!(actual.states.classes, expected.states.classes);
+}
+
+#[rstest::rstest]
+fn block_is_closed_when_limits_are_exhausted(
+    #[from(fixtures::overrides)] overrides: katana_primitives::env::VersionedConstantsOverrides,
+    #[from(fixtures::flags)]
+    #[with(true)]
+    flags: katana_executor::ExecutionFlags,
+    #[from(state_provider)] state: Box<dyn StateProvider>,
+    #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+) {
+    use katana_executor::blockifier::cache::ClassCache;
+    use katana_executor::error::ExecutorError;
+    use katana_executor::BlockLimits;
+
+    let limits = BlockLimits { transactions: Some(1), ..Default::default() };
+    let factory = BlockifierFactory::new(
+        Some(overrides),
+        flags,
+        limits,
+        ClassCache::new().unwrap(),
+        katana_chain_spec::ChainSpec::dev().into(),
+    );
+
+    let [block, ..] = blocks;
+    assert_eq!(block.body.len(), 2);
+
+    let mut executor = factory.executor(state, BlockEnv::default());
+    executor.execute_block(block.clone()).unwrap();
+    assert_eq!(executor.transactions().len(), 1, "only the first transaction fits in the block");
+
+    // the remaining transaction doesn't fit either
+    let (executed, error) = executor.execute_transactions(block.body[1..].to_vec()).unwrap();
+    assert_eq!(executed, 0);
+    assert!(matches!(error, Some(ExecutorError::LimitsExhausted)));
+}
+
+#[rstest::rstest]
+fn transaction_exceeding_the_block_limits_is_dropped(
+    #[from(fixtures::overrides)] overrides: katana_primitives::env::VersionedConstantsOverrides,
+    #[from(fixtures::flags)]
+    #[with(true)]
+    flags: katana_executor::ExecutionFlags,
+    #[from(state_provider)] state: Box<dyn StateProvider>,
+    #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+) {
+    use katana_executor::blockifier::cache::ClassCache;
+    use katana_executor::error::ExecutionError;
+    use katana_executor::BlockLimits;
+
+    // no transaction can fit in a block with this limit, even an empty one
+    let limits = BlockLimits { l2_gas: Some(1), ..Default::default() };
+    let factory = BlockifierFactory::new(
+        Some(overrides),
+        flags,
+        limits,
+        ClassCache::new().unwrap(),
+        katana_chain_spec::ChainSpec::dev().into(),
+    );
+
+    let [block, ..] = blocks;
+    let mut executor = factory.executor(state, BlockEnv::default());
+
+    // the transactions are dropped instead of closing the (empty) block, which would otherwise
+    // leave them to be retried in the next block, forever
+    let (executed, error) = executor.execute_transactions(block.body.clone()).unwrap();
+    assert_eq!(executed, block.body.len());
+    assert!(error.is_none());
+
+    for (_, result) in executor.transactions() {
+        let ExecutionResult::Failed { error } = result else { panic!("expected failed result") };
+        assert!(matches!(error, ExecutionError::TransactionTooLarge));
+    }
+
+    // none of the transactions' changes are applied
+    let sender = address!("0x2af9427c5a277474c079a1283c880ee8a6f0f8fbf73ce969c08d88befec1bba");
+    let nonce = executor.state().nonce(sender).unwrap();
+    assert_eq!(nonce.unwrap_or_default(), Felt::ZERO);
+}
@@ -4,11 +4,13 @@ use std::str::FromStr;
 use katana_gateway_types::{
     AddDeclareTransactionResponse, AddDeployAccountTransactionResponse,
     AddInvokeTransactionResponse, Block, BlockId, BlockSignature, BroadcastedDeclareTx,
-    BroadcastedDeployAccountTx, BroadcastedInvokeTx, ContractClass, GatewayError,
-    PreConfirmedBlock, SequencerPublicKey, StateUpdate, StateUpdateWithBlock,
+    BroadcastedDeployAccountTx, BroadcastedInvokeTx, ContractAddresses, ContractClass,
+    GatewayError, PreConfirmedBlock, SequencerPublicKey, StateUpdate, StateUpdateWithBlock,
+    TransactionInfo, TransactionReceipt, TransactionStatusResponse,
 };
 use katana_primitives::block::BlockNumber;
 use katana_primitives::class::CasmContractClass;
+use katana_primitives::transaction::TxHash;
 use katana_primitives::Felt;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Method, Request, StatusCode};
@@ -29,6 +31,8 @@ pub struct Client {
     feeder_gateway: Url,
     /// The API key used to bypass the rate limiting of the feeder gateway.
     api_key: Option<String>,
+    /// The HTTP client used to send the requests.
+    http: reqwest::Client,
 }
 
 impl Client {
@@ -64,7 +68,7 @@ impl Client {
 
     /// Creates a new client with the given gateway and feeder gateway URLs.
     pub fn new(gateway: Url, feeder_gateway: Url) -> Self {
-        Self { gateway, feeder_gateway, api_key: None }
+        Self { gateway, feeder_gateway, api_key: None, http: reqwest::Client::new() }
     }
 
     /// Sets the API key.
@@ -73,6 +77,13 @@ impl Client {
         self
     }
 
+    /// Connects to the gateway using the given TLS configuration, eg to trust a private CA or to
+    /// present a client certificate to gateways requiring mutual TLS.
+    pub fn with_tls(mut self, config: rustls::ClientConfig) -> Result<Self, Error> {
+        self.http = reqwest::Client::builder().use_preconfigured_tls(config).build()?;
+        Ok(self)
+    }
+
     pub async fn get_preconfirmed_block(
         &self,
         block_number: BlockNumber,
@@ -122,6 +133,34 @@ impl Client {
             .await
     }
 
+    pub async fn get_transaction(&self, hash: TxHash) -> Result<TransactionInfo, Error> {
+        self.feeder_gateway("get_transaction")
+            .query_param("transactionHash", &format!("{hash:#x}"))
+            .send()
+            .await
+    }
+
+    pub async fn get_transaction_receipt(&self, hash: TxHash) -> Result<TransactionReceipt, Error> {
+        self.feeder_gateway("get_transaction_receipt")
+            .query_param("transactionHash", &format!("{hash:#x}"))
+            .send()
+            .await
+    }
+
+    pub async fn get_transaction_status(
+        &self,
+        hash: TxHash,
+    ) -> Result<TransactionStatusResponse, Error> {
+        self.feeder_gateway("get_transaction_status")
+            .query_param("transactionHash", &format!("{hash:#x}"))
+            .send()
+            .await
+    }
+
+    pub async fn get_contract_addresses(&self) -> Result<ContractAddresses, Error> {
+        self.feeder_gateway("get_contract_addresses").send().await
+    }
+
     pub async fn get_public_key(&self) -> Result<SequencerPublicKey, Error> {
         self.feeder_gateway("get_public_key").send().await
     }
@@ -310,7 +349,7 @@ impl<Method> RequestBuilder<'_, Method> {
     async fn send<T: DeserializeOwned>(self) -> Result<T, Error> {
         let request = self.build()?;
 
-        let response = reqwest::Client::new().execute(request).await?;
+        let response = self.client.http.execute(request).await?;
 
         let status_code = response.status().as_u16();
         let body = response.text().await?;
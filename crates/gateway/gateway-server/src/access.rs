@@ -0,0 +1,94 @@
+//! Authentication and rate limiting of the transaction submission endpoint.
+//!
+//! Submitting a transaction through `/gateway/add_transaction` is subject to the same access
+//! control and rate limits as the equivalent RPC method (eg, `starknet_addInvokeTransaction`), so
+//! that the gateway can't be used to bypass them. See [`katana_rpc_server::middleware::auth`] and
+//! [`katana_rpc_server::middleware::rate_limit`] for how callers are authenticated and identified.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use katana_rpc_server::middleware::auth::{Access, AuthConfig};
+use katana_rpc_server::middleware::rate_limit::{ClientId, RateLimiter};
+use katana_rpc_server::PeerAddr;
+use tracing::debug;
+
+use crate::handlers::ApiError;
+
+/// The access control of the transaction submission endpoint.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AccessControl {
+    auth: Option<Arc<AuthConfig>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    trust_proxy_headers: bool,
+}
+
+impl AccessControl {
+    pub(crate) fn new(auth: Option<AuthConfig>, rate_limiter: Option<Arc<RateLimiter>>) -> Self {
+        let trust_proxy_headers =
+            rate_limiter.as_ref().is_some_and(|limiter| limiter.config().trusts_proxy_headers());
+        Self { auth: auth.map(Arc::new), rate_limiter, trust_proxy_headers }
+    }
+
+    /// Checks that `caller` is allowed to call `method` and hasn't exceeded its rate limit.
+    ///
+    /// Calls that aren't allowed don't count towards the rate limit.
+    pub(crate) fn check(&self, caller: &Caller, method: &str) -> Result<(), ApiError> {
+        if self.auth.is_some() && !caller.access.as_ref().is_some_and(|a| a.allows(method)) {
+            return Err(ApiError::Forbidden(format!("Not allowed to call method: {method}")));
+        }
+
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.try_acquire(&caller.client, method).map_err(|retry_after| {
+                ApiError::RateLimited { retry_after_ms: retry_after.as_millis() as u64 }
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The caller of a request, resolved by [`identify_caller`].
+#[derive(Debug, Clone)]
+pub(crate) struct Caller {
+    /// The access granted by the caller's credentials, if authentication is enabled.
+    access: Option<Access>,
+    client: ClientId,
+}
+
+/// Middleware that attaches the [`Caller`] of a request to its extensions.
+///
+/// Requests with invalid credentials are rejected.
+pub(crate) async fn identify_caller(
+    State(control): State<AccessControl>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    if let Some(ConnectInfo(addr)) = req.extensions().get::<ConnectInfo<SocketAddr>>().copied() {
+        req.extensions_mut().insert(PeerAddr(addr));
+    }
+
+    let access = match &control.auth {
+        Some(auth) => match auth.authenticate(req.headers()) {
+            Ok(access) => {
+                // The client id is derived from the access of authenticated callers.
+                req.extensions_mut().insert(access.clone());
+                Some(access)
+            }
+            Err(error) => {
+                debug!(target: "gateway", %error, "Rejected unauthenticated request.");
+                return ApiError::Unauthorized(error.to_string()).into_response();
+            }
+        },
+        None => None,
+    };
+
+    let client =
+        ClientId::from_request(req.extensions(), req.headers(), control.trust_proxy_headers);
+    req.extensions_mut().insert(Caller { access, client });
+
+    next.run(req).await
+}
@@ -1,24 +1,42 @@
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Json, Response};
+use axum::Extension;
+use katana_chain_spec::SettlementLayer;
 use katana_gateway_types::{
-    Block, ConfirmedReceipt, ConfirmedTransaction, ContractClass, ErrorCode, GatewayError,
-    ReceiptBody, StateUpdate, StateUpdateWithBlock,
+    AddDeclareTransactionResponse, AddDeployAccountTransactionResponse,
+    AddInvokeTransactionResponse, AddTransactionResponse, Block, BlockSignature,
+    BroadcastedTransaction, BroadcastedTxConversionError, ConfirmedReceipt, ConfirmedTransaction,
+    ContractAddresses, ContractClass, ErrorCode, ExecutionStatus, GatewayError, PreConfirmedBlock,
+    ReceiptBody, SequencerPublicKey, StateUpdate, StateUpdateWithBlock, TransactionInfo,
+    TransactionReceipt, TransactionStatus, TransactionStatusResponse,
 };
 use katana_pool_api::TransactionPool;
 use katana_primitives::block::{BlockHash, BlockIdOrTag, BlockNumber};
 use katana_primitives::class::{ClassHash, CompiledClass, ContractClassCompilationError};
+use katana_primitives::receipt::Receipt;
+use katana_primitives::transaction::{TxHash, TxWithHash};
+use katana_primitives::Felt;
 use katana_provider::{ProviderFactory, ProviderRO, ProviderRW};
 use katana_provider_api::block::{
-    BlockHashProvider, BlockIdReader, BlockProvider, BlockStatusProvider,
+    BlockHashProvider, BlockIdReader, BlockProvider, BlockSignatureProvider, BlockStatusProvider,
 };
 use katana_provider_api::transaction::ReceiptProvider;
 use katana_rpc_api::error::starknet::StarknetApiError;
 use katana_rpc_server::starknet::{PendingBlockProvider, StarknetApi};
+use katana_rpc_types::{
+    BroadcastedTxWithChainId, ExecutionResult, GetBlockWithReceiptsResponse,
+    GetBlockWithTxHashesResponse, ReceiptBlockInfo, TxStatus,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use starknet::core::types::ResourcePrice;
 
+use crate::access::{AccessControl, Caller};
+
+/// Response code returned when a transaction is accepted by `/gateway/add_transaction`.
+const TRANSACTION_RECEIVED: &str = "TRANSACTION_RECEIVED";
+
 /// Shared application state containing the backend
 pub struct AppState<Pool, PP, PF>
 where
@@ -29,6 +47,10 @@ where
     <PF as ProviderFactory>::ProviderMut: ProviderRW,
 {
     pub api: StarknetApi<Pool, PP, PF>,
+    /// The public key of the key the sequencer signs its blocks with, if block signing is enabled.
+    pub public_key: Option<SequencerPublicKey>,
+    /// The access control of the transaction submission endpoint.
+    pub(crate) access: AccessControl,
 }
 
 impl<Pool, PP, PF> Clone for AppState<Pool, PP, PF>
@@ -40,7 +62,7 @@ where
     <PF as ProviderFactory>::ProviderMut: ProviderRW,
 {
     fn clone(&self) -> Self {
-        Self { api: self.api.clone() }
+        Self { api: self.api.clone(), public_key: self.public_key, access: self.access.clone() }
     }
 }
 
@@ -118,6 +140,81 @@ where
             })
             .await?
     }
+
+    async fn get_block_signature(&self, id: BlockIdOrTag) -> Result<BlockSignature, ApiError> {
+        self.api
+            .on_io_blocking_task(move |this| {
+                let provider = this.storage().provider();
+
+                let Some(num) = provider.convert_block_id(id)? else {
+                    return Err(ApiError::gateway_error(
+                        ErrorCode::BlockNotFound,
+                        "Block not found",
+                    ));
+                };
+
+                let Some(block_hash) = provider.block_hash_by_num(num)? else {
+                    return Err(ApiError::gateway_error(
+                        ErrorCode::BlockNotFound,
+                        "Block not found",
+                    ));
+                };
+
+                let Some(signature) = provider.block_signature(num.into())? else {
+                    return Err(ApiError::gateway_error(
+                        ErrorCode::NotFound,
+                        format!("Block {num} is not signed"),
+                    ));
+                };
+
+                Ok(BlockSignature { block_hash, signature: [signature.r, signature.s] })
+            })
+            .await?
+    }
+}
+
+impl<P, PP, PF> AppState<P, PP, PF>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    /// Returns the status of a transaction, or `None` if the transaction is unknown.
+    async fn transaction_status(&self, hash: TxHash) -> Result<Option<TxStatus>, ApiError> {
+        match self.api.transaction_status(hash).await {
+            Ok(status) => Ok(Some(status)),
+            Err(StarknetApiError::TxnHashNotFound) => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns the hash, number and index of the block that includes an executed transaction.
+    ///
+    /// The block hash is `None` if the transaction is in the pre-confirmed block.
+    async fn transaction_location(
+        &self,
+        hash: TxHash,
+        block: &ReceiptBlockInfo,
+    ) -> Result<(Option<BlockHash>, BlockNumber, Option<u64>), ApiError> {
+        let (block_hash, block_number, block_id) = match *block {
+            ReceiptBlockInfo::PreConfirmed { block_number } => {
+                (None, block_number, BlockIdOrTag::PreConfirmed)
+            }
+            ReceiptBlockInfo::Block { block_hash, block_number } => {
+                (Some(block_hash), block_number, BlockIdOrTag::Hash(block_hash))
+            }
+        };
+
+        let transactions = match self.api.block_with_tx_hashes(block_id).await? {
+            GetBlockWithTxHashesResponse::Block(block) => block.transactions,
+            GetBlockWithTxHashesResponse::PreConfirmed(block) => block.transactions,
+        };
+
+        let index = transactions.iter().position(|tx| *tx == hash).map(|index| index as u64);
+        Ok((block_hash, block_number, index))
+    }
 }
 
 /// Query parameters for block endpoints
@@ -172,6 +269,13 @@ pub struct ClassQuery {
     pub block_query: BlockIdQuery,
 }
 
+/// Query parameters for the transaction endpoints
+#[derive(Debug, Deserialize)]
+pub struct TransactionQuery {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: TxHash,
+}
+
 /// Handler for `/health` endpoint
 ///
 /// Returns health status of the gateway.
@@ -284,6 +388,339 @@ where
         .map(Json)
 }
 
+/// Handler for `/gateway/add_transaction` endpoint
+///
+/// Validates the transaction and adds it to the transaction pool.
+pub async fn add_transaction<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+    Extension(caller): Extension<Caller>,
+    Json(body): Json<Value>,
+) -> Result<Json<AddTransactionResponse>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    P::Transaction: From<BroadcastedTxWithChainId>,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    let tx = serde_json::from_value::<BroadcastedTransaction>(body)
+        .map_err(|error| ApiError::gateway_error(ErrorCode::MalformedRequest, error.to_string()))?;
+
+    // The transaction is subject to the same access control as its equivalent RPC method.
+    let method = match &tx {
+        BroadcastedTransaction::Invoke(_) => "starknet_addInvokeTransaction",
+        BroadcastedTransaction::Declare(_) => "starknet_addDeclareTransaction",
+        BroadcastedTransaction::DeployAccount(_) => "starknet_addDeployAccountTransaction",
+    };
+    state.access.check(&caller, method)?;
+
+    let response = match tx {
+        BroadcastedTransaction::Invoke(tx) => {
+            let res = state.api.add_invoke_tx(tx.try_into()?).await?;
+            AddTransactionResponse::Invoke(AddInvokeTransactionResponse {
+                transaction_hash: res.transaction_hash,
+                code: TRANSACTION_RECEIVED.to_string(),
+            })
+        }
+
+        BroadcastedTransaction::Declare(tx) => {
+            let res = state.api.add_declare_tx(tx.try_into()?).await?;
+            AddTransactionResponse::Declare(AddDeclareTransactionResponse {
+                transaction_hash: res.transaction_hash,
+                class_hash: res.class_hash,
+                code: TRANSACTION_RECEIVED.to_string(),
+            })
+        }
+
+        BroadcastedTransaction::DeployAccount(tx) => {
+            let res = state.api.add_deploy_account_tx(tx.try_into()?).await?;
+            AddTransactionResponse::DeployAccount(AddDeployAccountTransactionResponse {
+                transaction_hash: res.transaction_hash,
+                code: TRANSACTION_RECEIVED.to_string(),
+            })
+        }
+    };
+
+    Ok(Json(response))
+}
+
+/// Handler for `/feeder_gateway/get_transaction` endpoint
+///
+/// Returns the transaction and its location. Unknown transactions are reported as `NOT_RECEIVED`.
+pub async fn get_transaction<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+    Query(params): Query<TransactionQuery>,
+) -> Result<Json<TransactionInfo>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    let hash = params.transaction_hash;
+
+    let mut info = TransactionInfo {
+        status: TransactionStatus::NotReceived,
+        finality_status: Some(TransactionStatus::NotReceived),
+        execution_status: None,
+        block_hash: None,
+        block_number: None,
+        transaction_index: None,
+        transaction: None,
+    };
+
+    let Some(status) = state.transaction_status(hash).await? else {
+        return Ok(Json(info));
+    };
+
+    info.status = TransactionStatus::from(&status);
+    info.finality_status = Some(info.status);
+
+    // Transactions that are still in the pool have yet to be executed.
+    if matches!(status, TxStatus::Received | TxStatus::Candidate) {
+        return Ok(Json(info));
+    }
+
+    let transaction = state.api.transaction(hash).await?;
+    let receipt = state.api.receipt(hash).await?;
+    let (block_hash, block_number, index) =
+        state.transaction_location(hash, &receipt.block).await?;
+
+    info.execution_status = Some(receipt.receipt.execution_result().into());
+    info.block_hash = block_hash;
+    info.block_number = Some(block_number);
+    info.transaction_index = index;
+    info.transaction = Some(TxWithHash::from(transaction).into());
+
+    Ok(Json(info))
+}
+
+/// Handler for `/feeder_gateway/get_transaction_receipt` endpoint
+///
+/// Returns the receipt of a transaction. The receipt body is omitted if the transaction hasn't
+/// been executed yet.
+pub async fn get_transaction_receipt<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+    Query(params): Query<TransactionQuery>,
+) -> Result<Json<TransactionReceipt>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    let hash = params.transaction_hash;
+
+    let mut receipt = TransactionReceipt {
+        status: TransactionStatus::NotReceived,
+        finality_status: Some(TransactionStatus::NotReceived),
+        block_hash: None,
+        block_number: None,
+        transaction_index: None,
+        transaction_hash: hash,
+        body: None,
+    };
+
+    let Some(status) = state.transaction_status(hash).await? else {
+        return Ok(Json(receipt));
+    };
+
+    receipt.status = TransactionStatus::from(&status);
+    receipt.finality_status = Some(receipt.status);
+
+    if matches!(status, TxStatus::Received | TxStatus::Candidate) {
+        return Ok(Json(receipt));
+    }
+
+    let rpc_receipt = state.api.receipt(hash).await?;
+    let (block_hash, block_number, index) =
+        state.transaction_location(hash, &rpc_receipt.block).await?;
+
+    receipt.block_hash = block_hash;
+    receipt.block_number = Some(block_number);
+    receipt.transaction_index = index;
+    receipt.body = Some(Receipt::from(rpc_receipt.receipt).into());
+
+    Ok(Json(receipt))
+}
+
+/// Handler for `/feeder_gateway/get_transaction_status` endpoint
+///
+/// Returns the finality and execution status of a transaction.
+pub async fn get_transaction_status<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+    Query(params): Query<TransactionQuery>,
+) -> Result<Json<TransactionStatusResponse>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    let hash = params.transaction_hash;
+
+    let Some(status) = state.transaction_status(hash).await? else {
+        return Ok(Json(TransactionStatusResponse {
+            tx_status: TransactionStatus::NotReceived,
+            finality_status: Some(TransactionStatus::NotReceived),
+            execution_status: None,
+            block_hash: None,
+            tx_revert_reason: None,
+        }));
+    };
+
+    let tx_status = TransactionStatus::from(&status);
+
+    let (execution_result, block_hash) = match status {
+        TxStatus::Received | TxStatus::Candidate => (None, None),
+        TxStatus::PreConfirmed(result) => (Some(result), None),
+        TxStatus::AcceptedOnL2(result) | TxStatus::AcceptedOnL1(result) => {
+            let receipt = state.api.receipt(hash).await?;
+            let block_hash = match receipt.block {
+                ReceiptBlockInfo::Block { block_hash, .. } => Some(block_hash),
+                ReceiptBlockInfo::PreConfirmed { .. } => None,
+            };
+            (Some(result), block_hash)
+        }
+    };
+
+    let tx_revert_reason = match &execution_result {
+        Some(ExecutionResult::Reverted { reason, .. }) => Some(reason.clone()),
+        _ => None,
+    };
+
+    Ok(Json(TransactionStatusResponse {
+        tx_status,
+        finality_status: Some(tx_status),
+        execution_status: execution_result.as_ref().map(ExecutionStatus::from),
+        block_hash,
+        tx_revert_reason,
+    }))
+}
+
+/// Handler for `/feeder_gateway/get_signature` endpoint
+///
+/// Returns the sequencer signature of a block, if block signing is enabled.
+pub async fn get_signature<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+    Query(params): Query<BlockIdQuery>,
+) -> Result<Json<BlockSignature>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    let block_id = params.block_id()?;
+
+    if state.public_key.is_none() {
+        return Err(block_signing_disabled());
+    }
+
+    let signature = state.get_block_signature(block_id).await?;
+    Ok(Json(signature))
+}
+
+/// Handler for `/feeder_gateway/get_public_key` endpoint
+///
+/// Returns the public key that block signatures can be verified against.
+pub async fn get_public_key<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+) -> Result<Json<SequencerPublicKey>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    state.public_key.map(Json).ok_or_else(block_signing_disabled)
+}
+
+fn block_signing_disabled() -> ApiError {
+    ApiError::gateway_error(ErrorCode::NotFound, "Block signing is not enabled on this node")
+}
+
+/// Handler for `/feeder_gateway/get_contract_addresses` endpoint
+///
+/// Returns the address of the core contract the chain settles to.
+pub async fn get_contract_addresses<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+) -> Result<Json<ContractAddresses>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    let starknet = match state.api.chain_spec().settlement() {
+        Some(SettlementLayer::Ethereum { core_contract, .. }) => {
+            Felt::from_bytes_be_slice(core_contract.as_slice())
+        }
+        Some(SettlementLayer::Starknet { core_contract, .. }) => (*core_contract).into(),
+        Some(SettlementLayer::Sovereign { .. }) | None => {
+            return Err(ApiError::gateway_error(
+                ErrorCode::NotFound,
+                "The chain doesn't settle to a core contract",
+            ));
+        }
+    };
+
+    Ok(Json(ContractAddresses { starknet, gps_statement_verifier: None }))
+}
+
+/// Handler for `/feeder_gateway/get_preconfirmed_block` endpoint
+///
+/// Returns the block that is currently being built by the sequencer. If a block number is
+/// specified, it must match the number of the pre-confirmed block.
+///
+/// Katana doesn't track the state diffs of the individual pre-confirmed transactions, so the
+/// `transaction_state_diffs` of the returned block are left empty.
+pub async fn get_preconfirmed_block<P, PP, PF>(
+    State(state): State<AppState<P, PP, PF>>,
+    Query(params): Query<BlockIdQuery>,
+) -> Result<Json<PreConfirmedBlock>, ApiError>
+where
+    P: TransactionPool + Send + Sync + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    let requested = match params.block_id()? {
+        BlockIdOrTag::Number(number) => Some(number),
+        BlockIdOrTag::Latest => None,
+        _ => {
+            return Err(ApiError::gateway_error(
+                ErrorCode::MalformedRequest,
+                "Pre-confirmed blocks can only be queried by block number",
+            ));
+        }
+    };
+
+    let block = match state.api.block_with_receipts(BlockIdOrTag::PreConfirmed).await? {
+        GetBlockWithReceiptsResponse::PreConfirmed(block)
+            if requested.is_none_or(|number| number == block.block_number) =>
+        {
+            block
+        }
+        _ => {
+            return Err(ApiError::gateway_error(
+                ErrorCode::BlockNotFound,
+                "Pre-confirmed block not found",
+            ));
+        }
+    };
+
+    Ok(Json(PreConfirmedBlock::from(block)))
+}
+
 /// API error types with proper HTTP status code mapping
 #[derive(Debug, thiserror::Error, Serialize)]
 #[serde(untagged)]
@@ -293,6 +730,15 @@ pub enum ApiError {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error("Rate limit exceeded")]
+    RateLimited { retry_after_ms: u64 },
 }
 
 impl ApiError {
@@ -305,6 +751,9 @@ impl ApiError {
         match self {
             ApiError::Gateway(_) => StatusCode::BAD_REQUEST,
             ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 
@@ -317,7 +766,17 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status = self.status_code();
         let body = self.body();
-        (status, body).into_response()
+
+        match self {
+            ApiError::Unauthorized(_) => {
+                (status, [(header::WWW_AUTHENTICATE, "Bearer")], body).into_response()
+            }
+            ApiError::RateLimited { retry_after_ms } => {
+                let retry_after = retry_after_ms.div_ceil(1000).to_string();
+                (status, [(header::RETRY_AFTER, retry_after)], body).into_response()
+            }
+            _ => (status, body).into_response(),
+        }
     }
 }
 
@@ -336,6 +795,12 @@ impl From<StarknetApiError> for ApiError {
     }
 }
 
+impl From<BroadcastedTxConversionError> for ApiError {
+    fn from(value: BroadcastedTxConversionError) -> Self {
+        ApiError::gateway_error(ErrorCode::MalformedRequest, value.to_string())
+    }
+}
+
 impl From<ContractClassCompilationError> for ApiError {
     fn from(value: ContractClassCompilationError) -> Self {
         ApiError::Internal(value.to_string())
@@ -2,23 +2,36 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use axum::routing::get;
-use axum::Router;
+use axum::extract::ConnectInfo;
+use axum::routing::{get, post};
+use axum::{Extension, Router};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
 use katana_pool_api::TransactionPool;
+use katana_primitives::Felt;
 use katana_provider::{ProviderFactory, ProviderRO, ProviderRW};
+use katana_rpc_server::middleware::auth::AuthConfig;
 use katana_rpc_server::middleware::cors::Cors;
+use katana_rpc_server::middleware::rate_limit::RateLimiter;
 use katana_rpc_server::starknet::{PendingBlockProvider, StarknetApi};
+use katana_rpc_server::tls::{
+    TlsAcceptor, TlsConfig, TlsListener, DEFAULT_RELOAD_INTERVAL, HTTP_ALPN_PROTOCOLS,
+};
+use katana_rpc_types::BroadcastedTxWithChainId;
 use tokio::net::TcpListener;
 use tokio::sync::watch;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
+mod access;
 mod handlers;
 mod metrics;
 
+use access::AccessControl;
 use handlers::AppState;
 use metrics::GatewayMetricsLayer;
 
@@ -32,6 +45,9 @@ pub enum Error {
 
     #[error("feeder gateway server has already been stopped")]
     AlreadyStopped,
+
+    #[error(transparent)]
+    Tls(#[from] katana_rpc_server::tls::Error),
 }
 
 /// The feeder gateway server handle.
@@ -81,6 +97,10 @@ where
     cors: Option<Cors>,
     health_check: bool,
     metered: bool,
+    public_key: Option<Felt>,
+    tls: Option<TlsConfig>,
+    auth: Option<AuthConfig>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 
     starknet_api: StarknetApi<Pool, PP, PF>,
 }
@@ -88,6 +108,7 @@ where
 impl<Pool, PP, PF> GatewayServer<Pool, PP, PF>
 where
     Pool: TransactionPool + Send + Sync + 'static,
+    Pool::Transaction: From<BroadcastedTxWithChainId>,
     PP: PendingBlockProvider,
     PF: ProviderFactory,
     <PF as ProviderFactory>::Provider: ProviderRO,
@@ -100,6 +121,10 @@ where
             cors: None,
             health_check: false,
             metered: false,
+            public_key: None,
+            tls: None,
+            auth: None,
+            rate_limiter: None,
             starknet_api,
         }
     }
@@ -133,6 +158,42 @@ where
         self
     }
 
+    /// Sets the public key of the key the sequencer signs its blocks with.
+    ///
+    /// Block signatures and the public key are only served by the `get_signature` and
+    /// `get_public_key` endpoints when this is set.
+    pub fn public_key(mut self, public_key: Felt) -> Self {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    /// Serves the gateway over TLS according to `config`.
+    ///
+    /// See [`katana_rpc_server::tls`] for more details.
+    pub fn tls(mut self, config: TlsConfig) -> Self {
+        self.tls = Some(config);
+        self
+    }
+
+    /// Requires transaction submissions to be authenticated according to `config`.
+    ///
+    /// Submitting a transaction requires the same access as calling the equivalent RPC method, eg
+    /// `starknet_addInvokeTransaction`. The feeder gateway endpoints remain public.
+    pub fn auth(mut self, config: AuthConfig) -> Self {
+        self.auth = Some(config);
+        self
+    }
+
+    /// Rate limits the transaction submissions of each client using `limiter`.
+    ///
+    /// Submitting a transaction counts as a call to the equivalent RPC method, eg
+    /// `starknet_addInvokeTransaction`. Sharing the limiter with the RPC server makes both
+    /// servers draw from the same limits.
+    pub fn rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
     /// Start the feeder gateway server.
     pub async fn start(&self, addr: SocketAddr) -> Result<GatewayServerHandle, Error> {
         let listener = TcpListener::bind(addr).await?;
@@ -141,15 +202,24 @@ where
         let actual_addr = listener.local_addr()?;
         let (server_handle, stop_handle) = stop_channel();
 
-        tokio::spawn(async move {
-            let server = axum::serve(listener, app).with_graceful_shutdown(async {
-                stop_handle.shutdown().await;
-            });
+        if let Some(tls) = &self.tls {
+            let acceptor = TlsAcceptor::new(tls.clone(), HTTP_ALPN_PROTOCOLS)?;
+            acceptor.spawn_reloader(DEFAULT_RELOAD_INTERVAL);
 
-            if let Err(err) = server.await {
-                error!(target: "gateway", error = ?err, "Gateway server error.");
-            }
-        });
+            let listener = TlsListener::new(listener, acceptor);
+            tokio::spawn(serve_tls(listener, app, stop_handle));
+        } else {
+            let app = app.into_make_service_with_connect_info::<SocketAddr>();
+            tokio::spawn(async move {
+                let server = axum::serve(listener, app).with_graceful_shutdown(async {
+                    stop_handle.shutdown().await;
+                });
+
+                if let Err(err) = server.await {
+                    error!(target: "gateway", error = ?err, "Gateway server error.");
+                }
+            });
+        }
 
         info!(target: "gateway", addr = %actual_addr, "Gateway server started.");
 
@@ -159,14 +229,31 @@ where
     /// Create the Axum application with all routes configured
     fn create_app(&self) -> Router {
         // Create shared application state
-        let state = AppState { api: self.starknet_api.clone() };
+        let access_control = AccessControl::new(self.auth.clone(), self.rate_limiter.clone());
+        let state = AppState {
+            api: self.starknet_api.clone(),
+            public_key: self.public_key,
+            access: access_control.clone(),
+        };
+
+        let identify_caller =
+            axum::middleware::from_fn_with_state(access_control, access::identify_caller);
+        let add_transaction = post(handlers::add_transaction).layer(identify_caller);
 
         let metrics_layer = if self.metered {
             Some(GatewayMetricsLayer::new([
+                "/gateway/add_transaction",
                 "/feeder_gateway/get_block",
+                "/feeder_gateway/get_preconfirmed_block",
                 "/feeder_gateway/get_state_update",
                 "/feeder_gateway/get_class_by_hash",
                 "/feeder_gateway/get_compiled_class_by_class_hash",
+                "/feeder_gateway/get_transaction",
+                "/feeder_gateway/get_transaction_receipt",
+                "/feeder_gateway/get_transaction_status",
+                "/feeder_gateway/get_signature",
+                "/feeder_gateway/get_public_key",
+                "/feeder_gateway/get_contract_addresses",
             ]))
         } else {
             None
@@ -180,13 +267,24 @@ where
 
         let mut router = Router::new()
             .layer(middleware)
+            .route("/gateway/add_transaction", add_transaction)
             .route("/feeder_gateway/get_block", get(handlers::get_block))
+            .route("/feeder_gateway/get_preconfirmed_block", get(handlers::get_preconfirmed_block))
             .route("/feeder_gateway/get_state_update", get(handlers::get_state_update))
             .route("/feeder_gateway/get_class_by_hash", get(handlers::get_class_by_hash))
             .route(
                 "/feeder_gateway/get_compiled_class_by_class_hash",
                 get(handlers::get_compiled_class_by_class_hash),
-            );
+            )
+            .route("/feeder_gateway/get_transaction", get(handlers::get_transaction))
+            .route(
+                "/feeder_gateway/get_transaction_receipt",
+                get(handlers::get_transaction_receipt),
+            )
+            .route("/feeder_gateway/get_transaction_status", get(handlers::get_transaction_status))
+            .route("/feeder_gateway/get_signature", get(handlers::get_signature))
+            .route("/feeder_gateway/get_public_key", get(handlers::get_public_key))
+            .route("/feeder_gateway/get_contract_addresses", get(handlers::get_contract_addresses));
 
         if self.health_check {
             router =
@@ -197,6 +295,41 @@ where
     }
 }
 
+/// Serves `app` over the connections accepted by `listener` until the server is stopped.
+///
+/// This is needed because [`axum::serve`] only supports plain TCP listeners.
+async fn serve_tls(mut listener: TlsListener, app: Router, stop_handle: StopHandle) {
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            conn = listener.accept() => conn,
+            _ = stop_handle.clone().shutdown() => break,
+        };
+
+        // Attach the peer address the same way `axum::serve` does.
+        let service = app.clone().layer(Extension(ConnectInfo(remote_addr)));
+        let service = TowerToHyperService::new(service);
+        let stop_handle = stop_handle.clone();
+
+        tokio::spawn(async move {
+            let builder = auto::Builder::new(TokioExecutor::new());
+            let conn = builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+            tokio::pin!(conn);
+
+            let result = tokio::select! {
+                result = conn.as_mut() => result,
+                _ = stop_handle.shutdown() => {
+                    conn.as_mut().graceful_shutdown();
+                    conn.await
+                }
+            };
+
+            if let Err(error) = result {
+                debug!(target: "gateway", %remote_addr, %error, "Connection error.");
+            }
+        });
+    }
+}
+
 /// Server handle.
 ///
 /// When all [`StopHandle`]'s have been `dropped` or `stop` has been called
@@ -0,0 +1,312 @@
+use std::num::NonZeroU32;
+
+use katana_chain_spec::{ChainSpec, SettlementLayer};
+use katana_gateway_client::{Client, Error};
+use katana_gateway_types::{
+    BlockId, BroadcastedInvokeTx, ErrorCode, ExecutionStatus, GatewayError, TransactionStatus,
+};
+use katana_primitives::chain::ChainId;
+use katana_primitives::da::DataAvailabilityMode;
+use katana_primitives::fee::{
+    AllResourceBoundsMapping, ResourceBounds, ResourceBoundsMapping, Tip,
+};
+use katana_primitives::transaction::TxHash;
+use katana_primitives::{address, felt, Felt};
+use katana_sequencer_node::config::dev::FixedL1GasPriceConfig;
+use katana_sequencer_node::config::gateway::GatewayConfig;
+use katana_sequencer_node::config::rpc::{RpcApiKey, RpcAuthConfig, RpcRateLimitConfig};
+use katana_sequencer_node::config::Config;
+use katana_utils::{FinalityStatus, TestNode, TxWaiter};
+use starknet::signers::SigningKey;
+use url::Url;
+
+const BLOCK_SIGNING_KEY: Felt = felt!("0x1");
+
+/// Returns a test node config with the gateway enabled.
+///
+/// Account validation is disabled so that transactions can be submitted without signing them.
+fn config() -> Config {
+    let mut config = katana_utils::node::test_config();
+    config.dev.account_validation = false;
+    config.gateway = Some(GatewayConfig { port: 0, ..Default::default() });
+    config
+}
+
+fn gateway_client(node: &TestNode) -> Client {
+    let addr = node.handle().gateway().expect("gateway must be enabled").addr();
+    Client::new(
+        Url::parse(&format!("http://{addr}/gateway")).unwrap(),
+        Url::parse(&format!("http://{addr}/feeder_gateway")).unwrap(),
+    )
+}
+
+/// An invoke transaction from the first genesis account that executes no calls.
+fn invoke_tx(node: &TestNode, nonce: Felt) -> BroadcastedInvokeTx {
+    let (address, _) = node.backend().chain_spec.genesis().accounts().next().unwrap();
+    let bounds = ResourceBounds { max_amount: 10_000_000_000, max_price_per_unit: 0 };
+
+    BroadcastedInvokeTx {
+        version: Felt::THREE,
+        sender_address: *address,
+        calldata: vec![Felt::ZERO],
+        signature: Vec::new(),
+        nonce,
+        tip: Tip::new(0),
+        paymaster_data: Vec::new(),
+        account_deployment_data: Vec::new(),
+        resource_bounds: ResourceBoundsMapping::All(AllResourceBoundsMapping {
+            l1_gas: bounds,
+            l2_gas: bounds,
+            l1_data_gas: bounds,
+        }),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+    }
+}
+
+#[track_caller]
+fn assert_gateway_error(result: Result<impl std::fmt::Debug, Error>, expected: ErrorCode) {
+    match result {
+        Err(Error::Sequencer(GatewayError { code, .. })) => assert_eq!(code, expected),
+        other => panic!("expected {expected} error, got {other:?}"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn transaction_endpoints() {
+    let node = TestNode::new_with_config(config()).await;
+    let client = gateway_client(&node);
+    let rpc = node.starknet_rpc_client();
+
+    let res = client.add_invoke_transaction(invoke_tx(&node, Felt::ZERO)).await.unwrap();
+    assert_eq!(res.code, "TRANSACTION_RECEIVED");
+
+    let hash = res.transaction_hash;
+    TxWaiter::new(hash, &rpc).with_tx_status(FinalityStatus::AcceptedOnL2).await.unwrap();
+
+    let block = client.get_block(BlockId::Number(1)).await.unwrap();
+    assert_eq!(block.transactions.len(), 1);
+    assert_eq!(block.transactions[0].transaction_hash, hash);
+
+    let tx = client.get_transaction(hash).await.unwrap();
+    assert_eq!(tx.status, TransactionStatus::AcceptedOnL2);
+    assert_eq!(tx.execution_status, Some(ExecutionStatus::Succeeded));
+    assert_eq!(tx.block_hash, block.block_hash);
+    assert_eq!(tx.block_number, Some(1));
+    assert_eq!(tx.transaction_index, Some(0));
+    assert_eq!(tx.transaction.map(|tx| tx.transaction_hash), Some(hash));
+
+    let receipt = client.get_transaction_receipt(hash).await.unwrap();
+    assert_eq!(receipt.status, TransactionStatus::AcceptedOnL2);
+    assert_eq!(receipt.transaction_hash, hash);
+    assert_eq!(receipt.block_hash, block.block_hash);
+    assert_eq!(receipt.block_number, Some(1));
+    assert_eq!(receipt.transaction_index, Some(0));
+    assert!(receipt.body.is_some());
+
+    let status = client.get_transaction_status(hash).await.unwrap();
+    assert_eq!(status.tx_status, TransactionStatus::AcceptedOnL2);
+    assert_eq!(status.execution_status, Some(ExecutionStatus::Succeeded));
+    assert_eq!(status.block_hash, block.block_hash);
+    assert_eq!(status.tx_revert_reason, None);
+
+    // Unknown transactions are reported as not received.
+    let unknown = TxHash::from(0x1337u64);
+
+    let tx = client.get_transaction(unknown).await.unwrap();
+    assert_eq!(tx.status, TransactionStatus::NotReceived);
+    assert_eq!(tx.transaction, None);
+
+    let receipt = client.get_transaction_receipt(unknown).await.unwrap();
+    assert_eq!(receipt.status, TransactionStatus::NotReceived);
+    assert!(receipt.body.is_none());
+
+    let status = client.get_transaction_status(unknown).await.unwrap();
+    assert_eq!(status.tx_status, TransactionStatus::NotReceived);
+    assert_eq!(status.execution_status, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn add_transaction_rejects_malformed_request() {
+    let node = TestNode::new_with_config(config()).await;
+    let addr = node.handle().gateway().unwrap().addr();
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/gateway/add_transaction"))
+        .json(&serde_json::json!({ "type": "INVOKE_FUNCTION", "version": "0x3" }))
+        .send()
+        .await
+        .unwrap();
+
+    assert!(response.status().is_client_error());
+    let error = response.json::<GatewayError>().await.unwrap();
+    assert_eq!(error.code, ErrorCode::MalformedRequest);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn add_transaction_requires_rpc_access() {
+    let mut config = config();
+    config.rpc.auth = Some(RpcAuthConfig {
+        public_methods: vec!["starknet_chainId".to_string()],
+        api_keys: vec![RpcApiKey {
+            key: "secret".to_string(),
+            methods: vec!["starknet_addInvokeTransaction".to_string()],
+        }],
+        ..Default::default()
+    });
+
+    let node = TestNode::new_with_config(config).await;
+    let addr = node.handle().gateway().unwrap().addr();
+    let url = format!("http://{addr}/gateway/add_transaction");
+    let client = reqwest::Client::new();
+    let tx = serde_json::to_value(invoke_tx(&node, Felt::ZERO)).unwrap();
+
+    // Anonymous callers aren't allowed to call `starknet_addInvokeTransaction`.
+    let response = client.post(&url).json(&tx).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let response = client.post(&url).header("x-api-key", "wrong").json(&tx).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = client.post(&url).header("x-api-key", "secret").json(&tx).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    // The feeder gateway remains public.
+    let block = gateway_client(&node).get_block(BlockId::Number(0)).await.unwrap();
+    assert_eq!(block.block_number, Some(0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn add_transaction_is_rate_limited() {
+    let mut config = config();
+    config.rpc.rate_limit = Some(RpcRateLimitConfig {
+        requests_per_second: NonZeroU32::new(1).unwrap(),
+        burst: None,
+        method_weights: Vec::new(),
+        trust_proxy_headers: false,
+    });
+
+    let node = TestNode::new_with_config(config).await;
+    let client = gateway_client(&node);
+
+    client.add_invoke_transaction(invoke_tx(&node, Felt::ZERO)).await.unwrap();
+
+    // The RPC server and the gateway draw from the same limits.
+    let result = node.starknet_rpc_client().chain_id().await;
+    assert!(result.is_err(), "RPC call must be rate limited");
+
+    let addr = node.handle().gateway().unwrap().addr();
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/gateway/add_transaction"))
+        .json(&invoke_tx(&node, Felt::ONE))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key("retry-after"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_preconfirmed_block() {
+    let mut config = config();
+    config.sequencing.no_mining = true;
+
+    let node = TestNode::new_with_config(config).await;
+    let client = gateway_client(&node);
+    let rpc = node.starknet_rpc_client();
+
+    let hash = client.add_invoke_transaction(invoke_tx(&node, Felt::ZERO)).await.unwrap();
+    let hash = hash.transaction_hash;
+    TxWaiter::new(hash, &rpc).await.unwrap();
+
+    let block = client.get_preconfirmed_block(1).await.unwrap();
+    let hashes = block.transactions.iter().map(|tx| tx.transaction_hash).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![hash]);
+    assert_eq!(block.transaction_receipts.len(), 1);
+    assert_eq!(block.transaction_receipts[0].as_ref().map(|r| r.transaction_hash), Some(hash));
+    // Per-transaction state diffs aren't tracked.
+    assert_eq!(block.transaction_state_diffs, vec![None]);
+
+    let tx = client.get_transaction(hash).await.unwrap();
+    assert_eq!(tx.status, TransactionStatus::PreConfirmed);
+    assert_eq!(tx.block_hash, None);
+    assert_eq!(tx.block_number, Some(1));
+    assert_eq!(tx.transaction_index, Some(0));
+
+    let status = client.get_transaction_status(hash).await.unwrap();
+    assert_eq!(status.tx_status, TransactionStatus::PreConfirmed);
+    assert_eq!(status.block_hash, None);
+
+    // The requested block number must be the one of the pre-confirmed block.
+    assert_gateway_error(client.get_preconfirmed_block(2).await, ErrorCode::BlockNotFound);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_signature_endpoints() {
+    let mut config = config();
+    config.sequencing.block_signing_key = Some(BLOCK_SIGNING_KEY);
+
+    let node = TestNode::new_with_config(config).await;
+    let client = gateway_client(&node);
+    let rpc = node.starknet_rpc_client();
+
+    let public_key = client.get_public_key().await.unwrap();
+    assert_eq!(
+        public_key,
+        SigningKey::from_secret_scalar(BLOCK_SIGNING_KEY).verifying_key().scalar()
+    );
+
+    let hash = client.add_invoke_transaction(invoke_tx(&node, Felt::ZERO)).await.unwrap();
+    let hash = hash.transaction_hash;
+    TxWaiter::new(hash, &rpc).with_tx_status(FinalityStatus::AcceptedOnL2).await.unwrap();
+
+    let block = client.get_block(BlockId::Number(1)).await.unwrap();
+    let signature = client.get_signature(BlockId::Number(1)).await.unwrap();
+    assert_eq!(Some(signature.block_hash), block.block_hash);
+
+    assert_gateway_error(
+        client.get_signature(BlockId::Number(100)).await,
+        ErrorCode::BlockNotFound,
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_signature_endpoints_without_signing_key() {
+    let node = TestNode::new_with_config(config()).await;
+    let client = gateway_client(&node);
+
+    assert_gateway_error(client.get_public_key().await, ErrorCode::NotFound);
+    assert_gateway_error(client.get_signature(BlockId::Number(0)).await, ErrorCode::NotFound);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_contract_addresses() {
+    let core_contract = address!("0x1234");
+
+    let mut config = config();
+    // Use fixed gas prices so that the node doesn't sample them from the settlement layer.
+    config.dev.fixed_gas_prices = Some(FixedL1GasPriceConfig::default());
+
+    let ChainSpec::Dev(mut chain) = config.chain.as_ref().clone() else { unreachable!() };
+    chain.settlement = Some(SettlementLayer::Starknet {
+        id: ChainId::SEPOLIA,
+        rpc_url: Url::parse("http://localhost:5050").unwrap(),
+        core_contract,
+        block: 0,
+        proof_kind: Default::default(),
+    });
+    config.chain = ChainSpec::Dev(chain).into();
+
+    let node = TestNode::new_with_config(config).await;
+    let addresses = gateway_client(&node).get_contract_addresses().await.unwrap();
+    assert_eq!(addresses.starknet, core_contract.into());
+    assert_eq!(addresses.gps_statement_verifier, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_contract_addresses_without_settlement() {
+    let node = TestNode::new_with_config(config()).await;
+    let result = gateway_client(&node).get_contract_addresses().await;
+    assert_gateway_error(result, ErrorCode::NotFound);
+}
@@ -1,10 +1,11 @@
 use std::collections::BTreeSet;
 
 use crate::{
-    BlockStatus, ConfirmedStateUpdate, ConfirmedTransaction, DataAvailabilityMode, DeclareTx,
-    DeclareTxV3, DeclaredContract, DeployAccountTx, DeployAccountTxV1, DeployAccountTxV3,
-    DeployedContract, ExecutionResources, ExecutionStatus, InvokeTx, InvokeTxV3, L1HandlerTx,
-    PreConfirmedStateUpdate, ReceiptBody, StateDiff, StateUpdate, StorageDiff, TypedTransaction,
+    BlockStatus, ConfirmedReceipt, ConfirmedStateUpdate, ConfirmedTransaction,
+    DataAvailabilityMode, DeclareTx, DeclareTxV3, DeclaredContract, DeployAccountTx,
+    DeployAccountTxV1, DeployAccountTxV3, DeployedContract, ExecutionResources, ExecutionStatus,
+    InvokeTx, InvokeTxV3, L1HandlerTx, PreConfirmedBlock, PreConfirmedStateUpdate, ReceiptBody,
+    StateDiff, StateUpdate, StorageDiff, TransactionStatus, TypedTransaction,
 };
 
 // Conversions between katana-rpc to feeder gateway types
@@ -340,6 +341,67 @@ impl From<katana_primitives::block::FinalityStatus> for BlockStatus {
     }
 }
 
+impl From<&katana_rpc_types::TxStatus> for TransactionStatus {
+    fn from(value: &katana_rpc_types::TxStatus) -> Self {
+        match value {
+            katana_rpc_types::TxStatus::Received => TransactionStatus::Received,
+            katana_rpc_types::TxStatus::Candidate => TransactionStatus::Candidate,
+            katana_rpc_types::TxStatus::PreConfirmed(_) => TransactionStatus::PreConfirmed,
+            katana_rpc_types::TxStatus::AcceptedOnL2(_) => TransactionStatus::AcceptedOnL2,
+            katana_rpc_types::TxStatus::AcceptedOnL1(_) => TransactionStatus::AcceptedOnL1,
+        }
+    }
+}
+
+impl From<&katana_rpc_types::ExecutionResult> for ExecutionStatus {
+    fn from(value: &katana_rpc_types::ExecutionResult) -> Self {
+        match value {
+            katana_rpc_types::ExecutionResult::Succeeded => ExecutionStatus::Succeeded,
+            katana_rpc_types::ExecutionResult::Reverted { .. } => ExecutionStatus::Reverted,
+        }
+    }
+}
+
+impl From<katana_rpc_types::PreConfirmedBlockWithReceipts> for PreConfirmedBlock {
+    fn from(value: katana_rpc_types::PreConfirmedBlockWithReceipts) -> Self {
+        let mut transactions = Vec::with_capacity(value.transactions.len());
+        let mut transaction_receipts = Vec::with_capacity(value.transactions.len());
+
+        for (index, tx) in value.transactions.into_iter().enumerate() {
+            let transaction_hash = tx.receipt.transaction_hash;
+
+            let transaction =
+                katana_rpc_types::RpcTxWithHash { transaction_hash, transaction: tx.transaction };
+            transactions.push(katana_primitives::transaction::TxWithHash::from(transaction).into());
+
+            let body = katana_primitives::receipt::Receipt::from(tx.receipt.receipt).into();
+            let transaction_index = index as u64;
+            transaction_receipts.push(Some(ConfirmedReceipt {
+                transaction_hash,
+                transaction_index,
+                body,
+            }));
+        }
+
+        // Katana doesn't track the state diff of the individual pre-confirmed transactions.
+        let transaction_state_diffs = vec![None; transactions.len()];
+
+        Self {
+            transactions,
+            transaction_receipts,
+            transaction_state_diffs,
+            status: BlockStatus::PreConfirmed,
+            timestamp: value.timestamp,
+            sequencer_address: value.sequencer_address,
+            l1_gas_price: value.l1_gas_price,
+            l2_gas_price: value.l2_gas_price,
+            l1_data_gas_price: value.l1_data_gas_price,
+            starknet_version: value.starknet_version,
+            l1_da_mode: value.l1_da_mode,
+        }
+    }
+}
+
 impl From<StateDiff> for katana_primitives::state::StateUpdates {
     fn from(value: StateDiff) -> Self {
         let storage_updates = value
@@ -92,6 +92,17 @@ pub struct BlockSignature {
     pub signature: [Felt; 2],
 }
 
+/// Addresses of the settlement contracts returned by the `/get_contract_addresses` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractAddresses {
+    /// The core contract that the chain settles to.
+    #[serde(rename = "Starknet")]
+    pub starknet: Felt,
+    #[serde(default)]
+    #[serde(rename = "GpsStatementVerifier")]
+    pub gps_statement_verifier: Option<Felt>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PreConfirmedBlock {
     pub timestamp: u64,
@@ -1,3 +1,4 @@
+use katana_primitives::block::{BlockHash, BlockNumber};
 use katana_primitives::contract::Nonce;
 use katana_primitives::execution::{EntryPointSelector, VmResources};
 use katana_primitives::receipt::{DataAvailabilityResources, Event, GasUsed, MessageToL1};
@@ -5,6 +6,8 @@ use katana_primitives::transaction::TxHash;
 use katana_primitives::{eth, ContractAddress, Felt};
 use serde::{Deserialize, Serialize};
 
+use crate::TransactionStatus;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConfirmedReceipt {
     /// The hash of the transaction the receipt belongs to.
@@ -16,6 +19,27 @@ pub struct ConfirmedReceipt {
     pub body: ReceiptBody,
 }
 
+/// Response of the `/get_transaction_receipt` endpoint.
+///
+/// The receipt body is only present once the transaction has been executed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub status: TransactionStatus,
+    #[serde(default)]
+    pub finality_status: Option<TransactionStatus>,
+    #[serde(default)]
+    pub block_hash: Option<BlockHash>,
+    #[serde(default)]
+    pub block_number: Option<BlockNumber>,
+    #[serde(default)]
+    pub transaction_index: Option<u64>,
+    /// The hash of the transaction the receipt belongs to.
+    pub transaction_hash: TxHash,
+    /// The body of the receipt.
+    #[serde(flatten)]
+    pub body: Option<ReceiptBody>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReceiptBody {
     pub execution_resources: Option<ExecutionResources>,
@@ -71,9 +95,7 @@ impl From<ExecutionStatus> for katana_rpc_types::ExecutionResult {
                 // When converting from gateway ExecutionStatus::Reverted, we don't have the
                 // revert reason here. The caller should use the revert_error field from
                 // ReceiptBody if available.
-                katana_rpc_types::ExecutionResult::Reverted {
-                    reason: String::from("Transaction reverted"),
-                }
+                katana_rpc_types::ExecutionResult::reverted(String::from("Transaction reverted"))
             }
         }
     }
@@ -97,15 +119,15 @@ impl ReceiptBody {
     /// the `revert_error` field. If `revert_error` is present, the result is `Reverted`.
     pub fn to_execution_result(&self) -> katana_rpc_types::ExecutionResult {
         if let Some(revert_error) = &self.revert_error {
-            katana_rpc_types::ExecutionResult::Reverted { reason: revert_error.clone() }
+            katana_rpc_types::ExecutionResult::reverted(revert_error.clone())
         } else if let Some(status) = &self.execution_status {
             match status {
                 ExecutionStatus::Succeeded => katana_rpc_types::ExecutionResult::Succeeded,
                 ExecutionStatus::Reverted => {
                     // Reverted status without error message
-                    katana_rpc_types::ExecutionResult::Reverted {
-                        reason: String::from("Transaction reverted"),
-                    }
+                    katana_rpc_types::ExecutionResult::reverted(String::from(
+                        "Transaction reverted",
+                    ))
                 }
             }
         } else {
@@ -291,7 +313,7 @@ mod tests {
 
         let reverted: katana_rpc_types::ExecutionResult = ExecutionStatus::Reverted.into();
         match reverted {
-            katana_rpc_types::ExecutionResult::Reverted { reason } => {
+            katana_rpc_types::ExecutionResult::Reverted { reason, .. } => {
                 assert_eq!(reason, "Transaction reverted");
             }
             _ => panic!("Expected Reverted result"),
@@ -325,12 +347,16 @@ mod tests {
         };
 
         let result = body.to_execution_result();
-        match result {
-            katana_rpc_types::ExecutionResult::Reverted { reason } => {
+        match &result {
+            katana_rpc_types::ExecutionResult::Reverted { reason, .. } => {
                 assert_eq!(reason, "Out of gas");
             }
             _ => panic!("Expected Reverted result"),
         }
+
+        // Rendered the same way as the receipts of locally executed transactions.
+        let expected = katana_rpc_types::ExecutionResult::reverted("Out of gas".to_string());
+        assert_eq!(result, expected);
     }
 
     #[test]
@@ -7,6 +7,7 @@ use cairo_lang_starknet_classes::contract_class::ContractEntryPoints;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use katana_primitives::block::{BlockHash, BlockNumber};
 use katana_primitives::class::{ClassHash, CompiledClassHash};
 use katana_primitives::contract::Nonce;
 use katana_primitives::fee::{
@@ -26,6 +27,8 @@ use katana_primitives::transaction::{
 use katana_primitives::{ContractAddress, Felt};
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::ExecutionStatus;
+
 /// API response for an INVOKE_FUNCTION transaction
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
@@ -51,6 +54,24 @@ pub struct AddDeployAccountTransactionResponse {
     pub code: String, // TRANSACTION_RECEIVED
 }
 
+/// API response of the `/add_transaction` endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum AddTransactionResponse {
+    Declare(AddDeclareTransactionResponse),
+    Invoke(AddInvokeTransactionResponse),
+    DeployAccount(AddDeployAccountTransactionResponse),
+}
+
+/// Transaction accepted by the `/add_transaction` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BroadcastedTransaction {
+    Invoke(BroadcastedInvokeTx),
+    Declare(BroadcastedDeclareTx),
+    DeployAccount(BroadcastedDeployAccountTx),
+}
+
 /// Gateway-specific broadcasted invoke transaction.
 ///
 /// This type uses "INVOKE_FUNCTION" as the type tag for serialization to maintain compatibility
@@ -193,6 +214,97 @@ impl From<katana_rpc_types::broadcasted::BroadcastedDeployAccountTx>
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum BroadcastedTxConversionError {
+    #[error("unsupported transaction version {0:#x}")]
+    UnsupportedVersion(Felt),
+    #[error(transparent)]
+    InvalidContractClass(#[from] CompressedSierraProgramError),
+}
+
+/// Returns whether the transaction version denotes a query-only transaction.
+///
+/// Only V3 transactions are accepted through the gateway.
+fn is_query_version(version: Felt) -> Result<bool, BroadcastedTxConversionError> {
+    if version == Felt::THREE {
+        Ok(false)
+    } else if version == Felt::THREE + katana_rpc_types::broadcasted::QUERY_VERSION_OFFSET {
+        Ok(true)
+    } else {
+        Err(BroadcastedTxConversionError::UnsupportedVersion(version))
+    }
+}
+
+// Conversions from Gateway types to RPC types
+impl TryFrom<BroadcastedInvokeTx> for katana_rpc_types::broadcasted::BroadcastedInvokeTx {
+    type Error = BroadcastedTxConversionError;
+
+    fn try_from(tx: BroadcastedInvokeTx) -> Result<Self, Self::Error> {
+        Ok(Self {
+            is_query: is_query_version(tx.version)?,
+            sender_address: tx.sender_address,
+            calldata: tx.calldata,
+            signature: tx.signature,
+            nonce: tx.nonce,
+            tip: tx.tip,
+            paymaster_data: tx.paymaster_data,
+            account_deployment_data: tx.account_deployment_data,
+            resource_bounds: tx.resource_bounds,
+            nonce_data_availability_mode: tx.nonce_data_availability_mode.into(),
+            fee_data_availability_mode: tx.fee_data_availability_mode.into(),
+        })
+    }
+}
+
+impl TryFrom<BroadcastedDeclareTx> for katana_rpc_types::broadcasted::BroadcastedDeclareTx {
+    type Error = BroadcastedTxConversionError;
+
+    fn try_from(tx: BroadcastedDeclareTx) -> Result<Self, Self::Error> {
+        let is_query = is_query_version(tx.version)?;
+
+        let contract_class = Arc::unwrap_or_clone(tx.contract_class);
+        let contract_class =
+            katana_rpc_types::class::RpcSierraContractClass::try_from(contract_class)?;
+
+        Ok(Self {
+            is_query,
+            sender_address: tx.sender_address,
+            compiled_class_hash: tx.compiled_class_hash,
+            signature: tx.signature,
+            nonce: tx.nonce,
+            contract_class: contract_class.into(),
+            tip: tx.tip,
+            paymaster_data: tx.paymaster_data,
+            account_deployment_data: tx.account_deployment_data,
+            resource_bounds: tx.resource_bounds,
+            nonce_data_availability_mode: tx.nonce_data_availability_mode.into(),
+            fee_data_availability_mode: tx.fee_data_availability_mode.into(),
+        })
+    }
+}
+
+impl TryFrom<BroadcastedDeployAccountTx>
+    for katana_rpc_types::broadcasted::BroadcastedDeployAccountTx
+{
+    type Error = BroadcastedTxConversionError;
+
+    fn try_from(tx: BroadcastedDeployAccountTx) -> Result<Self, Self::Error> {
+        Ok(Self {
+            is_query: is_query_version(tx.version)?,
+            signature: tx.signature,
+            nonce: tx.nonce,
+            contract_address_salt: tx.contract_address_salt,
+            constructor_calldata: tx.constructor_calldata,
+            class_hash: tx.class_hash,
+            tip: tx.tip,
+            paymaster_data: tx.paymaster_data,
+            resource_bounds: tx.resource_bounds,
+            nonce_data_availability_mode: tx.nonce_data_availability_mode.into(),
+            fee_data_availability_mode: tx.fee_data_availability_mode.into(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum CompressedSierraProgramError {
     #[error("failed to serialize sierra program to JSON: {0}")]
@@ -285,6 +397,71 @@ impl TryFrom<CompressedSierraClass> for katana_rpc_types::class::RpcSierraContra
     }
 }
 
+/// The status of a transaction as reported by the feeder gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    #[serde(rename = "NOT_RECEIVED")]
+    NotReceived,
+
+    #[serde(rename = "RECEIVED")]
+    Received,
+
+    #[serde(rename = "CANDIDATE")]
+    Candidate,
+
+    #[serde(rename = "PRE_CONFIRMED")]
+    PreConfirmed,
+
+    #[serde(rename = "REJECTED")]
+    Rejected,
+
+    #[serde(rename = "REVERTED")]
+    Reverted,
+
+    #[serde(rename = "ACCEPTED_ON_L2")]
+    AcceptedOnL2,
+
+    #[serde(rename = "ACCEPTED_ON_L1")]
+    AcceptedOnL1,
+}
+
+/// Response of the `/get_transaction_status` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatusResponse {
+    pub tx_status: TransactionStatus,
+    #[serde(default)]
+    pub finality_status: Option<TransactionStatus>,
+    #[serde(default)]
+    pub execution_status: Option<ExecutionStatus>,
+    #[serde(default)]
+    pub block_hash: Option<BlockHash>,
+    /// The revert reason, only present if the transaction was reverted.
+    #[serde(default)]
+    pub tx_revert_reason: Option<String>,
+}
+
+/// Response of the `/get_transaction` endpoint.
+///
+/// Only the `status` field is guaranteed to be present. The location of the transaction is only
+/// known once it has been executed, and the transaction itself is omitted if the gateway has never
+/// received it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionInfo {
+    pub status: TransactionStatus,
+    #[serde(default)]
+    pub finality_status: Option<TransactionStatus>,
+    #[serde(default)]
+    pub execution_status: Option<ExecutionStatus>,
+    #[serde(default)]
+    pub block_hash: Option<BlockHash>,
+    #[serde(default)]
+    pub block_number: Option<BlockNumber>,
+    #[serde(default)]
+    pub transaction_index: Option<u64>,
+    #[serde(default)]
+    pub transaction: Option<ConfirmedTransaction>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConfirmedTransaction {
     pub transaction_hash: TxHash,
@@ -1026,4 +1203,54 @@ mod tests {
         );
         assert_eq!(gateway_tx.fee_data_availability_mode, rpc_tx.fee_data_availability_mode.into());
     }
+
+    #[test]
+    fn test_conversion_to_rpc_query_invoke_tx() {
+        let rpc_tx = katana_rpc_types::broadcasted::BroadcastedInvokeTx {
+            sender_address: address!("0x123"),
+            calldata: vec![Felt::ONE, Felt::TWO],
+            signature: vec![Felt::THREE],
+            nonce: Felt::ONE,
+            paymaster_data: vec![],
+            tip: Tip::from(0),
+            account_deployment_data: vec![],
+            resource_bounds: ResourceBoundsMapping::L1Gas(
+                katana_primitives::fee::L1GasResourceBoundsMapping {
+                    l1_gas: ResourceBounds { max_amount: 100, max_price_per_unit: 200 },
+                    l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+                },
+            ),
+            fee_data_availability_mode: katana_primitives::da::DataAvailabilityMode::L1,
+            nonce_data_availability_mode: katana_primitives::da::DataAvailabilityMode::L2,
+            is_query: true,
+        };
+
+        // rpc -> gateway -> rpc
+        let gateway_tx: BroadcastedInvokeTx = rpc_tx.clone().into();
+        let json = serde_json::to_value(&gateway_tx).unwrap();
+        let gateway_tx = match serde_json::from_value::<BroadcastedTransaction>(json).unwrap() {
+            BroadcastedTransaction::Invoke(tx) => tx,
+            tx => panic!("expected invoke transaction, got {tx:?}"),
+        };
+        let converted =
+            katana_rpc_types::broadcasted::BroadcastedInvokeTx::try_from(gateway_tx).unwrap();
+
+        assert!(converted.is_query);
+        assert_eq!(converted.sender_address, rpc_tx.sender_address);
+        assert_eq!(converted.calldata, rpc_tx.calldata);
+        assert_eq!(converted.signature, rpc_tx.signature);
+        assert_eq!(converted.nonce, rpc_tx.nonce);
+        assert_eq!(converted.resource_bounds, rpc_tx.resource_bounds);
+        assert_eq!(converted.fee_data_availability_mode, rpc_tx.fee_data_availability_mode);
+        assert_eq!(converted.nonce_data_availability_mode, rpc_tx.nonce_data_availability_mode);
+
+        // only v3 transactions are accepted
+        let mut gateway_tx: BroadcastedInvokeTx = rpc_tx.into();
+        gateway_tx.version = Felt::ONE;
+        let result = katana_rpc_types::broadcasted::BroadcastedInvokeTx::try_from(gateway_tx);
+        let error = result.expect_err("should reject non-v3 transactions");
+        assert!(
+            matches!(error, BroadcastedTxConversionError::UnsupportedVersion(v) if v == Felt::ONE)
+        );
+    }
 }
@@ -10,7 +10,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .file_descriptor_set_path(out_dir.join("starknet_descriptor.bin"))
         // Allow clippy lints on generated code for enum variant naming and size patterns
         .type_attribute(".", "#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]")
-        .compile(&["proto/starknet.proto"], &["proto"])?;
+        .compile(
+            &[
+                "proto/starknet.proto",
+                "proto/katana.proto",
+                "proto/dev.proto",
+                "proto/txpool.proto",
+            ],
+            &["proto"],
+        )?;
 
     println!("cargo:rerun-if-changed=proto");
 
@@ -0,0 +1,182 @@
+//! Authentication and rate limiting of the gRPC calls.
+//!
+//! A gRPC call is subject to the same access control and rate limits as the equivalent JSON-RPC
+//! method, so that the gRPC server can't be used to bypass them. The JSON-RPC method is derived
+//! from the package and method of the call, eg `/starknet.Starknet/GetNonce` is checked as
+//! `starknet_getNonce` and `/dev.Dev/GenerateBlock` as `dev_generateBlock`.
+//!
+//! See [`katana_rpc_server::middleware::auth`] and [`katana_rpc_server::middleware::rate_limit`]
+//! for how callers are authenticated and identified.
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use katana_rpc_server::middleware::auth::AuthConfig;
+use katana_rpc_server::middleware::rate_limit::{ClientId, RateLimiter};
+use katana_rpc_server::PeerAddr;
+use tonic::body::BoxBody;
+use tonic::transport::server::TcpConnectInfo;
+use tonic::Status;
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// Prefix of the paths of the reflection service, which only describes the served services.
+const REFLECTION_PATH_PREFIX: &str = "/grpc.reflection.";
+
+/// Layer applying the access control of the RPC server to the gRPC calls.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AccessControlLayer {
+    auth: Option<Arc<AuthConfig>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl AccessControlLayer {
+    pub(crate) fn set_auth(&mut self, auth: AuthConfig) {
+        self.auth = Some(Arc::new(auth));
+    }
+
+    pub(crate) fn set_rate_limiter(&mut self, limiter: Arc<RateLimiter>) {
+        self.rate_limiter = Some(limiter);
+    }
+
+    /// Checks that the caller of `req` is allowed to make the call and hasn't exceeded its rate
+    /// limit.
+    ///
+    /// Calls that aren't allowed don't count towards the rate limit.
+    fn check<B>(&self, req: &http::Request<B>) -> Result<(), Status> {
+        if self.auth.is_none() && self.rate_limiter.is_none() {
+            return Ok(());
+        }
+
+        let path = req.uri().path();
+        if path.starts_with(REFLECTION_PATH_PREFIX) {
+            return Ok(());
+        }
+
+        let method = rpc_method_name(path)
+            .ok_or_else(|| Status::unimplemented(format!("Unknown method: {path}")))?;
+
+        // The RPC middlewares work with the `http` 1.x types, unlike tonic.
+        let headers = to_rpc_headers(req.headers());
+        let mut extensions = http_v1::Extensions::new();
+
+        if let Some(addr) = req.extensions().get::<TcpConnectInfo>().and_then(|i| i.remote_addr()) {
+            extensions.insert(PeerAddr(addr));
+        }
+
+        if let Some(auth) = &self.auth {
+            let access = auth.authenticate(&headers).map_err(|error| {
+                debug!(target: "grpc", %error, "Rejected unauthenticated request.");
+                Status::unauthenticated(error.to_string())
+            })?;
+
+            if !access.allows(&method) {
+                return Err(Status::permission_denied(format!(
+                    "Not allowed to call method: {method}"
+                )));
+            }
+
+            // The client id is derived from the access of authenticated callers.
+            extensions.insert(access);
+        }
+
+        if let Some(limiter) = &self.rate_limiter {
+            let trust_proxy = limiter.config().trusts_proxy_headers();
+            let client = ClientId::from_request(&extensions, &headers, trust_proxy);
+
+            limiter.try_acquire(&client, &method).map_err(|retry_after| {
+                Status::resource_exhausted(format!(
+                    "Rate limit exceeded, retry after {}ms",
+                    retry_after.as_millis()
+                ))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for AccessControlLayer {
+    type Service = AccessControl<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessControl { inner, layer: self.clone() }
+    }
+}
+
+/// Service created by [`AccessControlLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct AccessControl<S> {
+    inner: S,
+    layer: AccessControlLayer,
+}
+
+impl<S, B> Service<http::Request<B>> for AccessControl<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        match self.layer.check(&req) {
+            Ok(()) => self.inner.call(req).boxed(),
+            Err(status) => futures::future::ready(Ok(status.to_http())).boxed(),
+        }
+    }
+}
+
+/// Returns the JSON-RPC method equivalent to the gRPC call at `path`.
+///
+/// The path of a gRPC call is `/<package>.<service>/<method>`, and the package of each service is
+/// named after the namespace of the equivalent JSON-RPC methods.
+fn rpc_method_name(path: &str) -> Option<String> {
+    let (service, method) = path.strip_prefix('/')?.split_once('/')?;
+    let (package, _) = service.split_once('.')?;
+
+    let mut chars = method.chars();
+    let first = chars.next()?.to_ascii_lowercase();
+
+    Some(format!("{package}_{first}{}", chars.as_str()))
+}
+
+/// Converts the headers of a gRPC request to the header map used by the RPC middlewares.
+fn to_rpc_headers(headers: &http::HeaderMap) -> http_v1::HeaderMap {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = http_v1::HeaderName::from_bytes(name.as_str().as_bytes()).ok()?;
+            let value = http_v1::HeaderValue::from_bytes(value.as_bytes()).ok()?;
+            Some((name, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rpc_method_name;
+
+    #[test]
+    fn rpc_method_names() {
+        let name = |path| rpc_method_name(path).unwrap();
+
+        assert_eq!(name("/starknet.Starknet/GetNonce"), "starknet_getNonce");
+        assert_eq!(
+            name("/starknet.StarknetWrite/AddInvokeTransaction"),
+            "starknet_addInvokeTransaction"
+        );
+        assert_eq!(name("/dev.Dev/GenerateBlock"), "dev_generateBlock");
+        assert_eq!(name("/txpool.TxPool/ContentFrom"), "txpool_contentFrom");
+
+        assert_eq!(rpc_method_name("/starknet.Starknet"), None);
+        assert_eq!(rpc_method_name("/Starknet/GetNonce"), None);
+    }
+}
@@ -2,10 +2,23 @@
 
 use std::time::Duration;
 
-use tonic::transport::{Channel, Endpoint, Uri};
-use tonic::{Request, Response, Status};
-
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint, Uri};
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::protos::dev::dev_client::DevClient;
+use crate::protos::dev::{
+    GenerateBlockRequest, GenerateBlockResponse, IncreaseNextBlockTimestampRequest,
+    IncreaseNextBlockTimestampResponse, PredeployedAccountsRequest, PredeployedAccountsResponse,
+    SetNextBlockTimestampRequest, SetNextBlockTimestampResponse, SetStorageAtRequest,
+    SetStorageAtResponse,
+};
+use crate::protos::katana::katana_client::KatanaClient;
+use crate::protos::katana::{
+    AddDeclareTransactionSyncRequest, AddDeployAccountTransactionSyncRequest,
+    AddInvokeTransactionSyncRequest, AddTransactionSyncResponse,
+};
 use crate::protos::starknet::starknet_client::StarknetClient;
+use crate::protos::starknet::starknet_ext_client::StarknetExtClient;
 use crate::protos::starknet::starknet_trace_client::StarknetTraceClient;
 use crate::protos::starknet::starknet_write_client::StarknetWriteClient;
 use crate::protos::starknet::{
@@ -16,17 +29,28 @@ use crate::protos::starknet::{
     ChainIdRequest, ChainIdResponse, EstimateFeeRequest, EstimateFeeResponse,
     EstimateMessageFeeRequest, GetBlockRequest, GetBlockTransactionCountResponse,
     GetBlockWithReceiptsResponse, GetBlockWithTxHashesResponse, GetBlockWithTxsResponse,
-    GetClassAtRequest, GetClassAtResponse, GetClassHashAtRequest, GetClassHashAtResponse,
-    GetClassRequest, GetClassResponse, GetCompiledCasmRequest, GetCompiledCasmResponse,
-    GetEventsRequest, GetEventsResponse, GetNonceRequest, GetNonceResponse, GetStateUpdateResponse,
-    GetStorageAtRequest, GetStorageAtResponse, GetStorageProofRequest, GetStorageProofResponse,
+    GetBlocksRequest, GetBlocksResponse, GetClassAtRequest, GetClassAtResponse,
+    GetClassHashAtRequest, GetClassHashAtResponse, GetClassRequest, GetClassResponse,
+    GetCompiledCasmRequest, GetCompiledCasmResponse, GetEventsRequest, GetEventsResponse,
+    GetNonceRequest, GetNonceResponse, GetStateUpdateResponse, GetStorageAtRequest,
+    GetStorageAtResponse, GetStorageProofRequest, GetStorageProofResponse,
     GetTransactionByBlockIdAndIndexRequest, GetTransactionByBlockIdAndIndexResponse,
     GetTransactionByHashRequest, GetTransactionByHashResponse, GetTransactionReceiptRequest,
     GetTransactionReceiptResponse, GetTransactionStatusRequest, GetTransactionStatusResponse,
+    GetTransactionsRequest, GetTransactionsResponse, PendingTransaction,
     SimulateTransactionsRequest, SimulateTransactionsResponse, SpecVersionRequest,
-    SpecVersionResponse, SyncingRequest, SyncingResponse, TraceBlockTransactionsRequest,
-    TraceBlockTransactionsResponse, TraceTransactionRequest, TraceTransactionResponse,
+    SpecVersionResponse, SubscribeEventsRequest, SubscribeNewHeadsRequest,
+    SubscribePendingTransactionsRequest, SubscribeTransactionStatusRequest, SyncingRequest,
+    SyncingResponse, TraceBlockTransactionsRequest, TraceBlockTransactionsResponse,
+    TraceTransactionRequest, TraceTransactionResponse, TransactionNumberRequest,
+    TransactionNumberResponse,
 };
+use crate::protos::txpool::tx_pool_client::TxPoolClient;
+use crate::protos::txpool::{
+    ContentFromRequest, ContentRequest, ContentResponse, InspectRequest, InspectResponse,
+    StatusRequest, StatusResponse,
+};
+use crate::protos::types::{BlockHeader, EmittedEvent};
 
 /// The default request timeout.
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
@@ -65,6 +89,7 @@ pub struct GrpcClientBuilder {
     endpoint: String,
     timeout: Duration,
     connect_timeout: Duration,
+    tls: Option<ClientTlsConfig>,
 }
 
 impl GrpcClientBuilder {
@@ -74,6 +99,7 @@ impl GrpcClientBuilder {
             endpoint: endpoint.into(),
             timeout: DEFAULT_TIMEOUT,
             connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            tls: None,
         }
     }
 
@@ -89,15 +115,25 @@ impl GrpcClientBuilder {
         self
     }
 
+    /// Connects over TLS using `config`, eg to trust a private CA or to present a client
+    /// certificate to servers requiring mutual TLS. The endpoint must use the `https` scheme.
+    pub fn tls(mut self, config: ClientTlsConfig) -> Self {
+        self.tls = Some(config);
+        self
+    }
+
     /// Connects to the gRPC server and returns a client.
     pub async fn connect(self) -> Result<GrpcClient, Error> {
         let uri: Uri = self.endpoint.parse()?;
 
-        let channel = Endpoint::from(uri)
-            .timeout(self.timeout)
-            .connect_timeout(self.connect_timeout)
-            .connect()
-            .await?;
+        let mut endpoint =
+            Endpoint::from(uri).timeout(self.timeout).connect_timeout(self.connect_timeout);
+
+        if let Some(tls) = self.tls {
+            endpoint = endpoint.tls_config(tls)?;
+        }
+
+        let channel = endpoint.connect().await?;
 
         Ok(GrpcClient::from_channel(channel))
     }
@@ -109,6 +145,10 @@ pub struct GrpcClient {
     starknet: StarknetClient<Channel>,
     starknet_write: StarknetWriteClient<Channel>,
     starknet_trace: StarknetTraceClient<Channel>,
+    starknet_ext: StarknetExtClient<Channel>,
+    katana: KatanaClient<Channel>,
+    dev: DevClient<Channel>,
+    txpool: TxPoolClient<Channel>,
 }
 
 impl GrpcClient {
@@ -140,7 +180,11 @@ impl GrpcClient {
         Self {
             starknet: StarknetClient::new(channel.clone()),
             starknet_write: StarknetWriteClient::new(channel.clone()),
-            starknet_trace: StarknetTraceClient::new(channel),
+            starknet_trace: StarknetTraceClient::new(channel.clone()),
+            starknet_ext: StarknetExtClient::new(channel.clone()),
+            katana: KatanaClient::new(channel.clone()),
+            dev: DevClient::new(channel.clone()),
+            txpool: TxPoolClient::new(channel),
         }
     }
 
@@ -349,6 +393,42 @@ impl GrpcClient {
         self.starknet.get_storage_proof(request.into()).await
     }
 
+    // ============================================================
+    // Subscription Methods (Starknet service)
+    // ============================================================
+
+    /// Stream the header of every new block, starting from the given block id.
+    pub async fn subscribe_new_heads(
+        &mut self,
+        request: impl Into<Request<SubscribeNewHeadsRequest>>,
+    ) -> Result<Response<Streaming<BlockHeader>>, Status> {
+        self.starknet.subscribe_new_heads(request.into()).await
+    }
+
+    /// Stream the events matching the given filter, starting from the given block id.
+    pub async fn subscribe_events(
+        &mut self,
+        request: impl Into<Request<SubscribeEventsRequest>>,
+    ) -> Result<Response<Streaming<EmittedEvent>>, Status> {
+        self.starknet.subscribe_events(request.into()).await
+    }
+
+    /// Stream the status of a transaction every time it changes.
+    pub async fn subscribe_transaction_status(
+        &mut self,
+        request: impl Into<Request<SubscribeTransactionStatusRequest>>,
+    ) -> Result<Response<Streaming<GetTransactionStatusResponse>>, Status> {
+        self.starknet.subscribe_transaction_status(request.into()).await
+    }
+
+    /// Stream the transactions entering the pool.
+    pub async fn subscribe_pending_transactions(
+        &mut self,
+        request: impl Into<Request<SubscribePendingTransactionsRequest>>,
+    ) -> Result<Response<Streaming<PendingTransaction>>, Status> {
+        self.starknet.subscribe_pending_transactions(request.into()).await
+    }
+
     // ============================================================
     // Write Methods (StarknetWrite service)
     // ============================================================
@@ -404,4 +484,140 @@ impl GrpcClient {
     ) -> Result<Response<TraceBlockTransactionsResponse>, Status> {
         self.starknet_trace.trace_block_transactions(request.into()).await
     }
+
+    // ============================================================
+    // List Methods (StarknetExt service)
+    // ============================================================
+
+    /// Get the blocks within the given range.
+    pub async fn get_blocks(
+        &mut self,
+        request: impl Into<Request<GetBlocksRequest>>,
+    ) -> Result<Response<GetBlocksResponse>, Status> {
+        self.starknet_ext.get_blocks(request.into()).await
+    }
+
+    /// Get the transactions, along with their receipts, within the given range.
+    pub async fn get_transactions(
+        &mut self,
+        request: impl Into<Request<GetTransactionsRequest>>,
+    ) -> Result<Response<GetTransactionsResponse>, Status> {
+        self.starknet_ext.get_transactions(request.into()).await
+    }
+
+    /// Get the number of the most recent accepted transaction.
+    pub async fn transaction_number(
+        &mut self,
+        request: impl Into<Request<TransactionNumberRequest>>,
+    ) -> Result<Response<TransactionNumberResponse>, Status> {
+        self.starknet_ext.transaction_number(request.into()).await
+    }
+
+    // ============================================================
+    // Katana Methods (Katana service)
+    // ============================================================
+
+    /// Submit a new invoke transaction and wait until its receipt is available.
+    pub async fn add_invoke_transaction_sync(
+        &mut self,
+        request: impl Into<Request<AddInvokeTransactionSyncRequest>>,
+    ) -> Result<Response<AddTransactionSyncResponse>, Status> {
+        self.katana.add_invoke_transaction_sync(request.into()).await
+    }
+
+    /// Submit a new declare transaction and wait until its receipt is available.
+    pub async fn add_declare_transaction_sync(
+        &mut self,
+        request: impl Into<Request<AddDeclareTransactionSyncRequest>>,
+    ) -> Result<Response<AddTransactionSyncResponse>, Status> {
+        self.katana.add_declare_transaction_sync(request.into()).await
+    }
+
+    /// Submit a new deploy account transaction and wait until its receipt is available.
+    pub async fn add_deploy_account_transaction_sync(
+        &mut self,
+        request: impl Into<Request<AddDeployAccountTransactionSyncRequest>>,
+    ) -> Result<Response<AddTransactionSyncResponse>, Status> {
+        self.katana.add_deploy_account_transaction_sync(request.into()).await
+    }
+
+    // ============================================================
+    // Dev Methods (Dev service)
+    // ============================================================
+
+    /// Mine a new block with the transactions currently in the pool.
+    pub async fn generate_block(
+        &mut self,
+        request: impl Into<Request<GenerateBlockRequest>>,
+    ) -> Result<Response<GenerateBlockResponse>, Status> {
+        self.dev.generate_block(request.into()).await
+    }
+
+    /// Set the timestamp of the next block.
+    pub async fn set_next_block_timestamp(
+        &mut self,
+        request: impl Into<Request<SetNextBlockTimestampRequest>>,
+    ) -> Result<Response<SetNextBlockTimestampResponse>, Status> {
+        self.dev.set_next_block_timestamp(request.into()).await
+    }
+
+    /// Increase the timestamp of the next block by the given number of seconds.
+    pub async fn increase_next_block_timestamp(
+        &mut self,
+        request: impl Into<Request<IncreaseNextBlockTimestampRequest>>,
+    ) -> Result<Response<IncreaseNextBlockTimestampResponse>, Status> {
+        self.dev.increase_next_block_timestamp(request.into()).await
+    }
+
+    /// Set the value of the storage at the given address and key.
+    pub async fn set_storage_at(
+        &mut self,
+        request: impl Into<Request<SetStorageAtRequest>>,
+    ) -> Result<Response<SetStorageAtResponse>, Status> {
+        self.dev.set_storage_at(request.into()).await
+    }
+
+    /// Get the accounts predeployed in the genesis block.
+    pub async fn predeployed_accounts(
+        &mut self,
+        request: impl Into<Request<PredeployedAccountsRequest>>,
+    ) -> Result<Response<PredeployedAccountsResponse>, Status> {
+        self.dev.predeployed_accounts(request.into()).await
+    }
+
+    // ============================================================
+    // TxPool Methods (TxPool service)
+    // ============================================================
+
+    /// Get the number of pending and queued transactions in the pool.
+    pub async fn txpool_status(
+        &mut self,
+        request: impl Into<Request<StatusRequest>>,
+    ) -> Result<Response<StatusResponse>, Status> {
+        self.txpool.status(request.into()).await
+    }
+
+    /// Get all the transactions in the pool.
+    pub async fn txpool_content(
+        &mut self,
+        request: impl Into<Request<ContentRequest>>,
+    ) -> Result<Response<ContentResponse>, Status> {
+        self.txpool.content(request.into()).await
+    }
+
+    /// Get the transactions in the pool sent by the given address.
+    pub async fn txpool_content_from(
+        &mut self,
+        request: impl Into<Request<ContentFromRequest>>,
+    ) -> Result<Response<ContentResponse>, Status> {
+        self.txpool.content_from(request.into()).await
+    }
+
+    /// Get a textual summary of all the transactions in the pool.
+    pub async fn txpool_inspect(
+        &mut self,
+        request: impl Into<Request<InspectRequest>>,
+    ) -> Result<Response<InspectResponse>, Status> {
+        self.txpool.inspect(request.into()).await
+    }
 }
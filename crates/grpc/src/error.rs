@@ -2,6 +2,7 @@
 //!
 //! This module provides conversion from Starknet API errors to gRPC status codes.
 
+use katana_rpc_api::error::dev::DevApiError;
 use katana_rpc_api::error::starknet::StarknetApiError;
 use tonic::{Code, Status};
 
@@ -140,7 +141,19 @@ pub fn to_status(err: StarknetApiError) -> Status {
     }
 }
 
-/// Extension trait to easily convert Results with StarknetApiError to gRPC Results.
+/// Converts a [`DevApiError`] to a [`tonic::Status`].
+pub fn dev_error_to_status(err: DevApiError) -> Status {
+    match err {
+        DevApiError::PendingTransactions | DevApiError::NotForked => {
+            Status::new(Code::FailedPrecondition, err.to_string())
+        }
+        DevApiError::UnexpectedError(..) | DevApiError::ResetForkFailed(..) => {
+            Status::new(Code::Internal, err.to_string())
+        }
+    }
+}
+
+/// Extension trait to easily convert Results with API errors to gRPC Results.
 #[allow(clippy::result_large_err)]
 pub trait IntoGrpcResult<T> {
     /// Converts the result to a gRPC result.
@@ -152,3 +165,9 @@ impl<T> IntoGrpcResult<T> for Result<T, StarknetApiError> {
         self.map_err(to_status)
     }
 }
+
+impl<T> IntoGrpcResult<T> for Result<T, DevApiError> {
+    fn into_grpc_result(self) -> Result<T, Status> {
+        self.map_err(dev_error_to_status)
+    }
+}
@@ -0,0 +1,116 @@
+//! Dev service handler implementation.
+
+use std::sync::Arc;
+
+use katana_primitives::ContractAddress;
+use katana_provider::{ProviderFactory, ProviderRO, ProviderRW};
+use katana_rpc_server::dev::DevApi;
+use tonic::{Request, Response, Status};
+
+use crate::error::IntoGrpcResult;
+use crate::protos::dev::dev_server::Dev;
+use crate::protos::dev::{
+    GenerateBlockRequest, GenerateBlockResponse, IncreaseNextBlockTimestampRequest,
+    IncreaseNextBlockTimestampResponse, PredeployedAccountsRequest, PredeployedAccountsResponse,
+    SetNextBlockTimestampRequest, SetNextBlockTimestampResponse, SetStorageAtRequest,
+    SetStorageAtResponse,
+};
+
+/// The handler for the Dev gRPC service.
+///
+/// This struct wraps `DevApi` from `katana-rpc-server` and implements the gRPC service trait by
+/// delegating to the underlying API.
+pub struct DevService<PF>
+where
+    PF: ProviderFactory,
+{
+    api: Arc<DevApi<PF>>,
+}
+
+impl<PF> DevService<PF>
+where
+    PF: ProviderFactory,
+{
+    pub fn new(api: DevApi<PF>) -> Self {
+        Self { api: Arc::new(api) }
+    }
+}
+
+impl<PF> std::fmt::Debug for DevService<PF>
+where
+    PF: ProviderFactory,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DevService").finish_non_exhaustive()
+    }
+}
+
+impl<PF> Clone for DevService<PF>
+where
+    PF: ProviderFactory,
+{
+    fn clone(&self) -> Self {
+        Self { api: self.api.clone() }
+    }
+}
+
+#[tonic::async_trait]
+impl<PF> Dev for DevService<PF>
+where
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    <PF as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    async fn generate_block(
+        &self,
+        _request: Request<GenerateBlockRequest>,
+    ) -> Result<Response<GenerateBlockResponse>, Status> {
+        self.api.generate_block();
+        Ok(Response::new(GenerateBlockResponse {}))
+    }
+
+    async fn set_next_block_timestamp(
+        &self,
+        request: Request<SetNextBlockTimestampRequest>,
+    ) -> Result<Response<SetNextBlockTimestampResponse>, Status> {
+        let timestamp = request.into_inner().timestamp;
+        self.api.set_next_block_timestamp(timestamp).into_grpc_result()?;
+        Ok(Response::new(SetNextBlockTimestampResponse {}))
+    }
+
+    async fn increase_next_block_timestamp(
+        &self,
+        request: Request<IncreaseNextBlockTimestampRequest>,
+    ) -> Result<Response<IncreaseNextBlockTimestampResponse>, Status> {
+        let offset = request.into_inner().offset;
+        self.api.increase_next_block_timestamp(offset).into_grpc_result()?;
+        Ok(Response::new(IncreaseNextBlockTimestampResponse {}))
+    }
+
+    async fn set_storage_at(
+        &self,
+        request: Request<SetStorageAtRequest>,
+    ) -> Result<Response<SetStorageAtResponse>, Status> {
+        let req = request.into_inner();
+
+        let contract_address = req
+            .contract_address
+            .ok_or_else(|| Status::invalid_argument("Missing contract_address"))?;
+        let contract_address = ContractAddress::try_from(contract_address)?;
+        let key = req.key.ok_or_else(|| Status::invalid_argument("Missing key"))?.try_into()?;
+        let value =
+            req.value.ok_or_else(|| Status::invalid_argument("Missing value"))?.try_into()?;
+
+        self.api.set_storage_at(contract_address, key, value).into_grpc_result()?;
+
+        Ok(Response::new(SetStorageAtResponse {}))
+    }
+
+    async fn predeployed_accounts(
+        &self,
+        _request: Request<PredeployedAccountsRequest>,
+    ) -> Result<Response<PredeployedAccountsResponse>, Status> {
+        let accounts = self.api.predeployed_accounts().into_iter().map(Into::into).collect();
+        Ok(Response::new(PredeployedAccountsResponse { accounts }))
+    }
+}
@@ -1,8 +1,13 @@
 //! gRPC service handlers.
 //!
 //! This module contains the implementations of the gRPC services defined in the proto files.
-//! The handlers delegate to the underlying `StarknetApi` implementation for business logic.
+//! The handlers delegate to the underlying JSON-RPC API implementations for business logic.
 
+mod dev;
 mod starknet;
+mod subscription;
+mod txpool;
 
+pub use self::dev::DevService;
 pub use self::starknet::StarknetService;
+pub use self::txpool::TxPoolService;
@@ -1,8 +1,10 @@
 //! Starknet service handler implementation.
 
-use katana_pool::api::TransactionPool;
+use katana_core::backend::MinedBlockSubscription;
+use katana_pool::api::{PoolOrd, TransactionPool};
+use katana_primitives::block::BlockIdOrTag;
 use katana_primitives::transaction::TxHash;
-use katana_primitives::Felt;
+use katana_primitives::{ContractAddress, Felt};
 use katana_provider::{ProviderFactory, ProviderRO};
 use katana_rpc_api::starknet::RPC_SPEC_VERSION;
 use katana_rpc_server::starknet::{PendingBlockProvider, StarknetApi};
@@ -11,8 +13,15 @@ use katana_rpc_types::trie::ContractStorageKeys;
 use katana_rpc_types::{BroadcastedTxWithChainId, FunctionCall};
 use tonic::{Request, Response, Status};
 
+use super::subscription::{self, EventsSubscriptionFilter, SubscriptionStream};
 use crate::conversion::{block_id_from_proto, confirmed_block_id_from_proto};
 use crate::error::IntoGrpcResult;
+use crate::protos::katana::katana_server::Katana;
+use crate::protos::katana::{
+    AddDeclareTransactionSyncRequest, AddDeployAccountTransactionSyncRequest,
+    AddInvokeTransactionSyncRequest, AddTransactionSyncResponse,
+};
+use crate::protos::starknet::starknet_ext_server::StarknetExt;
 use crate::protos::starknet::starknet_server::Starknet;
 use crate::protos::starknet::starknet_trace_server::StarknetTrace;
 use crate::protos::starknet::starknet_write_server::StarknetWrite;
@@ -24,18 +33,30 @@ use crate::protos::starknet::{
     ChainIdRequest, ChainIdResponse, EstimateFeeRequest, EstimateFeeResponse,
     EstimateMessageFeeRequest, GetBlockRequest, GetBlockTransactionCountResponse,
     GetBlockWithReceiptsResponse, GetBlockWithTxHashesResponse, GetBlockWithTxsResponse,
-    GetClassAtRequest, GetClassAtResponse, GetClassHashAtRequest, GetClassHashAtResponse,
-    GetClassRequest, GetClassResponse, GetCompiledCasmRequest, GetCompiledCasmResponse,
-    GetEventsRequest, GetEventsResponse, GetNonceRequest, GetNonceResponse, GetStateUpdateResponse,
-    GetStorageAtRequest, GetStorageAtResponse, GetStorageProofRequest, GetStorageProofResponse,
+    GetBlocksRequest, GetBlocksResponse, GetClassAtRequest, GetClassAtResponse,
+    GetClassHashAtRequest, GetClassHashAtResponse, GetClassRequest, GetClassResponse,
+    GetCompiledCasmRequest, GetCompiledCasmResponse, GetEventsRequest, GetEventsResponse,
+    GetNonceRequest, GetNonceResponse, GetStateUpdateResponse, GetStorageAtRequest,
+    GetStorageAtResponse, GetStorageProofRequest, GetStorageProofResponse,
     GetTransactionByBlockIdAndIndexRequest, GetTransactionByBlockIdAndIndexResponse,
     GetTransactionByHashRequest, GetTransactionByHashResponse, GetTransactionReceiptRequest,
     GetTransactionReceiptResponse, GetTransactionStatusRequest, GetTransactionStatusResponse,
+    GetTransactionsRequest, GetTransactionsResponse, PendingTransaction,
     SimulateTransactionsRequest, SimulateTransactionsResponse, SpecVersionRequest,
-    SpecVersionResponse, SyncingRequest, SyncingResponse, TraceBlockTransactionsRequest,
-    TraceBlockTransactionsResponse, TraceTransactionRequest, TraceTransactionResponse,
+    SpecVersionResponse, SubscribeEventsRequest, SubscribeNewHeadsRequest,
+    SubscribePendingTransactionsRequest, SubscribeTransactionStatusRequest, SyncingRequest,
+    SyncingResponse, TraceBlockTransactionsRequest, TraceBlockTransactionsResponse,
+    TraceTransactionRequest, TraceTransactionResponse, TransactionNumberRequest,
+    TransactionNumberResponse,
+};
+use crate::protos::types::{
+    BlockHeader, EmittedEvent, Transaction as ProtoTx,
+    TransactionReceipt as ProtoTransactionReceipt,
 };
-use crate::protos::types::{Transaction as ProtoTx, TransactionReceipt as ProtoTransactionReceipt};
+
+/// The maximum number of items served per page by the list endpoints when the node doesn't limit
+/// the page size.
+const DEFAULT_LIST_CHUNK_SIZE: u64 = 1024;
 
 /// The main handler for Starknet gRPC services.
 ///
@@ -48,6 +69,8 @@ where
     PF: ProviderFactory,
 {
     pub(crate) api: StarknetApi<Pool, PP, PF>,
+    /// Notifies the subscriptions about newly mined blocks.
+    blocks: Option<MinedBlockSubscription>,
 }
 
 impl<Pool, PP, PF> StarknetService<Pool, PP, PF>
@@ -57,7 +80,32 @@ where
     PF: ProviderFactory,
 {
     pub fn new(api: StarknetApi<Pool, PP, PF>) -> Self {
-        Self { api }
+        Self { api, blocks: None }
+    }
+
+    /// Enables the block-based subscriptions (new heads, events and transaction status), which
+    /// are otherwise rejected as unimplemented.
+    pub fn with_block_subscription(mut self, blocks: MinedBlockSubscription) -> Self {
+        self.blocks = Some(blocks);
+        self
+    }
+
+    fn block_subscription(&self) -> Result<MinedBlockSubscription, Status> {
+        self.blocks.clone().ok_or_else(|| {
+            Status::unimplemented("Block subscriptions are not supported by this node")
+        })
+    }
+
+    /// Returns the number of items served per page by the list endpoints.
+    ///
+    /// A zero chunk size means the client didn't pick one, in which case the maximum is used.
+    fn list_chunk_size(&self, requested: u64) -> u64 {
+        let max = self.api.config().max_event_page_size.unwrap_or(DEFAULT_LIST_CHUNK_SIZE);
+        if requested == 0 {
+            max
+        } else {
+            requested.min(max)
+        }
     }
 }
 
@@ -79,7 +127,7 @@ where
     PF: ProviderFactory,
 {
     fn clone(&self) -> Self {
-        Self { api: self.api.clone() }
+        Self { api: self.api.clone(), blocks: self.blocks.clone() }
     }
 }
 
@@ -91,10 +139,16 @@ where
 impl<Pool, PP, PF> Starknet for StarknetService<Pool, PP, PF>
 where
     Pool: TransactionPool + 'static,
+    <Pool::Ordering as PoolOrd>::PriorityValue: Send + Sync,
     PP: PendingBlockProvider,
     PF: ProviderFactory,
     <PF as ProviderFactory>::Provider: ProviderRO,
 {
+    type SubscribeNewHeadsStream = SubscriptionStream<BlockHeader>;
+    type SubscribeEventsStream = SubscriptionStream<EmittedEvent>;
+    type SubscribeTransactionStatusStream = SubscriptionStream<GetTransactionStatusResponse>;
+    type SubscribePendingTransactionsStream = SubscriptionStream<PendingTransaction>;
+
     async fn spec_version(
         &self,
         _request: Request<SpecVersionRequest>,
@@ -168,21 +222,7 @@ where
 
         let status = self.api.transaction_status(tx_hash).await.into_grpc_result()?;
 
-        let (finality_status, execution_status) = match status {
-            katana_rpc_types::TxStatus::Received => ("RECEIVED".to_string(), String::new()),
-            katana_rpc_types::TxStatus::Candidate => ("CANDIDATE".to_string(), String::new()),
-            katana_rpc_types::TxStatus::PreConfirmed(exec) => {
-                ("PRE_CONFIRMED".to_string(), execution_result_to_string(&exec))
-            }
-            katana_rpc_types::TxStatus::AcceptedOnL2(exec) => {
-                ("ACCEPTED_ON_L2".to_string(), execution_result_to_string(&exec))
-            }
-            katana_rpc_types::TxStatus::AcceptedOnL1(exec) => {
-                ("ACCEPTED_ON_L1".to_string(), execution_result_to_string(&exec))
-            }
-        };
-
-        Ok(Response::new(GetTransactionStatusResponse { finality_status, execution_status }))
+        Ok(Response::new(status.into()))
     }
 
     async fn get_transaction_by_hash(
@@ -338,6 +378,8 @@ where
             .call_contract(
                 FunctionCall { calldata, entry_point_selector, contract_address },
                 block_id,
+                None,
+                None,
             )
             .await
             .into_grpc_result()?;
@@ -476,6 +518,68 @@ where
 
         Ok(Response::new(GetStorageProofResponse { proof: Some(result.into()) }))
     }
+
+    async fn subscribe_new_heads(
+        &self,
+        request: Request<SubscribeNewHeadsRequest>,
+    ) -> Result<Response<Self::SubscribeNewHeadsStream>, Status> {
+        let blocks = self.block_subscription()?;
+        let block_id = subscription_start_block(request.into_inner().block_id)?;
+        let stream = subscription::new_heads(self.api.clone(), blocks, block_id).await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn subscribe_events(
+        &self,
+        request: Request<SubscribeEventsRequest>,
+    ) -> Result<Response<Self::SubscribeEventsStream>, Status> {
+        let blocks = self.block_subscription()?;
+        let req = request.into_inner();
+        let block_id = subscription_start_block(req.block_id)?;
+
+        let address = req.from_address.map(ContractAddress::try_from).transpose()?;
+        // Each key at position i matches exactly, as in `GetEvents`
+        let keys = if req.keys.is_empty() {
+            None
+        } else {
+            let keys = req.keys.into_iter().map(|k| Ok(vec![Felt::try_from(k)?]));
+            Some(keys.collect::<Result<Vec<_>, Status>>()?)
+        };
+
+        let filter = EventsSubscriptionFilter { address, keys };
+        let stream = subscription::events(self.api.clone(), blocks, filter, block_id).await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn subscribe_transaction_status(
+        &self,
+        request: Request<SubscribeTransactionStatusRequest>,
+    ) -> Result<Response<Self::SubscribeTransactionStatusStream>, Status> {
+        let blocks = self.block_subscription()?;
+        let tx_hash = request
+            .into_inner()
+            .transaction_hash
+            .ok_or_else(|| Status::invalid_argument("Missing transaction_hash"))?
+            .try_into()?;
+
+        let stream = subscription::transaction_status(self.api.clone(), blocks, tx_hash);
+        Ok(Response::new(stream))
+    }
+
+    async fn subscribe_pending_transactions(
+        &self,
+        request: Request<SubscribePendingTransactionsRequest>,
+    ) -> Result<Response<Self::SubscribePendingTransactionsStream>, Status> {
+        let senders = request
+            .into_inner()
+            .sender_address
+            .into_iter()
+            .map(ContractAddress::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let stream = subscription::pending_transactions(self.api.clone(), senders);
+        Ok(Response::new(stream))
+    }
 }
 
 /////////////////////////////////////////////////////////////////////////
@@ -536,6 +640,109 @@ where
     }
 }
 
+/////////////////////////////////////////////////////////////////////////
+/// Katana Service Implementation
+/////////////////////////////////////////////////////////////////////////
+
+#[tonic::async_trait]
+impl<Pool, PoolTx, PP, PF> Katana for StarknetService<Pool, PP, PF>
+where
+    Pool: TransactionPool<Transaction = PoolTx> + Send + Sync + 'static,
+    PoolTx: From<BroadcastedTxWithChainId>,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+{
+    async fn add_invoke_transaction_sync(
+        &self,
+        request: Request<AddInvokeTransactionSyncRequest>,
+    ) -> Result<Response<AddTransactionSyncResponse>, Status> {
+        let AddInvokeTransactionSyncRequest { transaction } = request.into_inner();
+
+        let tx = transaction.ok_or(Status::invalid_argument("missing transaction"))?;
+        let receipt = self.api.add_invoke_tx_sync(tx.try_into()?).await.into_grpc_result()?;
+
+        Ok(Response::new(AddTransactionSyncResponse {
+            receipt: Some(ProtoTransactionReceipt::from(&receipt)),
+        }))
+    }
+
+    async fn add_declare_transaction_sync(
+        &self,
+        request: Request<AddDeclareTransactionSyncRequest>,
+    ) -> Result<Response<AddTransactionSyncResponse>, Status> {
+        let AddDeclareTransactionSyncRequest { transaction } = request.into_inner();
+
+        let tx = transaction.ok_or(Status::invalid_argument("missing transaction"))?;
+        let receipt = self.api.add_declare_tx_sync(tx.try_into()?).await.into_grpc_result()?;
+
+        Ok(Response::new(AddTransactionSyncResponse {
+            receipt: Some(ProtoTransactionReceipt::from(&receipt)),
+        }))
+    }
+
+    async fn add_deploy_account_transaction_sync(
+        &self,
+        request: Request<AddDeployAccountTransactionSyncRequest>,
+    ) -> Result<Response<AddTransactionSyncResponse>, Status> {
+        let AddDeployAccountTransactionSyncRequest { transaction } = request.into_inner();
+
+        let tx = transaction.ok_or(Status::invalid_argument("missing transaction"))?;
+        let receipt =
+            self.api.add_deploy_account_tx_sync(tx.try_into()?).await.into_grpc_result()?;
+
+        Ok(Response::new(AddTransactionSyncResponse {
+            receipt: Some(ProtoTransactionReceipt::from(&receipt)),
+        }))
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////
+/// Starknet Ext Service Implementation
+/////////////////////////////////////////////////////////////////////////
+
+#[tonic::async_trait]
+impl<Pool, PP, PF> StarknetExt for StarknetService<Pool, PP, PF>
+where
+    Pool: TransactionPool + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+{
+    async fn get_blocks(
+        &self,
+        request: Request<GetBlocksRequest>,
+    ) -> Result<Response<GetBlocksResponse>, Status> {
+        let mut request: katana_rpc_types::list::GetBlocksRequest = request.into_inner().into();
+        let chunk_size = &mut request.result_page_request.chunk_size;
+        *chunk_size = self.list_chunk_size(*chunk_size);
+
+        let result = self.api.blocks(request).await.into_grpc_result()?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn get_transactions(
+        &self,
+        request: Request<GetTransactionsRequest>,
+    ) -> Result<Response<GetTransactionsResponse>, Status> {
+        let mut request: katana_rpc_types::list::GetTransactionsRequest =
+            request.into_inner().into();
+        let chunk_size = &mut request.result_page_request.chunk_size;
+        *chunk_size = self.list_chunk_size(*chunk_size);
+
+        let result = self.api.transactions(request).await.into_grpc_result()?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn transaction_number(
+        &self,
+        _request: Request<TransactionNumberRequest>,
+    ) -> Result<Response<TransactionNumberResponse>, Status> {
+        let transaction_number = self.api.total_transactions().await.into_grpc_result()?;
+        Ok(Response::new(TransactionNumberResponse { transaction_number }))
+    }
+}
+
 /////////////////////////////////////////////////////////////////////////
 /// Starknet Trace Service Implementation
 /////////////////////////////////////////////////////////////////////////
@@ -583,9 +790,9 @@ where
     }
 }
 
-fn execution_result_to_string(exec: &katana_rpc_types::ExecutionResult) -> String {
-    match exec {
-        katana_rpc_types::ExecutionResult::Succeeded => "SUCCEEDED".to_string(),
-        katana_rpc_types::ExecutionResult::Reverted { .. } => "REVERTED".to_string(),
-    }
+/// Returns the block a subscription starts from, which defaults to the latest block.
+fn subscription_start_block(
+    block_id: Option<crate::proto::BlockId>,
+) -> Result<BlockIdOrTag, Status> {
+    block_id.map(BlockIdOrTag::try_from).transpose().map(|id| id.unwrap_or(BlockIdOrTag::Latest))
 }
@@ -0,0 +1,364 @@
+//! Server-streaming subscription handlers.
+//!
+//! Each subscription is driven by a background task that pushes items into a bounded channel,
+//! which is then exposed to tonic as the response stream. The tasks are woken up by the blocks
+//! mined by the node's backend and by the transactions entering the pool, and stop as soon as the
+//! client goes away.
+
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use katana_core::backend::MinedBlockSubscription;
+use katana_pool::api::{PoolOrd, PoolTransaction, TransactionPool};
+use katana_primitives::block::{BlockIdOrTag, BlockNumber};
+use katana_primitives::transaction::TxHash;
+use katana_primitives::{ContractAddress, Felt};
+use katana_provider::{ProviderFactory, ProviderRO};
+use katana_rpc_api::error::starknet::StarknetApiError;
+use katana_rpc_server::starknet::{PendingBlockProvider, StarknetApi};
+use katana_rpc_types::block::GetBlockWithTxHashesResponse;
+use katana_rpc_types::event::{EventFilter, EventFilterWithPage, ResultPageRequest};
+use katana_rpc_types::TxStatus;
+use tokio::sync::mpsc;
+use tonic::Status;
+
+use crate::error::{to_status, IntoGrpcResult};
+use crate::protos::starknet::{GetTransactionStatusResponse, PendingTransaction};
+use crate::protos::types::{BlockHeader, EmittedEvent};
+
+/// The maximum number of blocks in the past that a subscription can start from.
+const MAX_BLOCKS_BACK: u64 = 1024;
+
+/// The number of items buffered per subscription before the producing task waits for the client
+/// to catch up.
+const SUBSCRIPTION_BUFFER_SIZE: usize = 128;
+
+/// The number of events fetched per page when the node doesn't limit the page size.
+const DEFAULT_EVENTS_CHUNK_SIZE: u64 = 1024;
+
+/// The response stream of a subscription.
+pub(crate) type SubscriptionStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// The filter of an events subscription.
+#[derive(Debug, Clone)]
+pub(crate) struct EventsSubscriptionFilter {
+    pub(crate) address: Option<ContractAddress>,
+    pub(crate) keys: Option<Vec<Vec<Felt>>>,
+}
+
+/// Streams the header of every block starting from `start`.
+pub(crate) async fn new_heads<Pool, PP, PF>(
+    api: StarknetApi<Pool, PP, PF>,
+    mut blocks: MinedBlockSubscription,
+    start: BlockIdOrTag,
+) -> Result<SubscriptionStream<BlockHeader>, Status>
+where
+    Pool: TransactionPool + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+{
+    let mut next = resolve_start_block(&api, start).await?;
+    let (tx, rx) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+
+    tokio::spawn(async move {
+        loop {
+            let latest = match api.latest_block_number().await.into_grpc_result() {
+                Ok(latest) => latest.block_number,
+                Err(status) => return send_error(&tx, status).await,
+            };
+
+            while next <= latest {
+                let block = api.block_with_tx_hashes(BlockIdOrTag::Number(next)).await;
+                let header = match block.into_grpc_result() {
+                    Ok(GetBlockWithTxHashesResponse::Block(block)) => BlockHeader::from(&block),
+                    Ok(GetBlockWithTxHashesResponse::PreConfirmed(block)) => {
+                        BlockHeader::from(&block)
+                    }
+                    Err(status) => return send_error(&tx, status).await,
+                };
+
+                if tx.send(Ok(header)).await.is_err() {
+                    return;
+                }
+
+                next += 1;
+            }
+
+            tokio::select! {
+                _ = tx.closed() => return,
+                res = blocks.changed() => {
+                    if res.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(into_stream(rx))
+}
+
+/// Streams the events matching `filter` that are emitted in every block starting from `start`.
+pub(crate) async fn events<Pool, PP, PF>(
+    api: StarknetApi<Pool, PP, PF>,
+    mut blocks: MinedBlockSubscription,
+    filter: EventsSubscriptionFilter,
+    start: BlockIdOrTag,
+) -> Result<SubscriptionStream<EmittedEvent>, Status>
+where
+    Pool: TransactionPool + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+{
+    let mut next = resolve_start_block(&api, start).await?;
+    let chunk_size = api.config().max_event_page_size.unwrap_or(DEFAULT_EVENTS_CHUNK_SIZE);
+    let (tx, rx) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+
+    tokio::spawn(async move {
+        loop {
+            let latest = match api.latest_block_number().await.into_grpc_result() {
+                Ok(latest) => latest.block_number,
+                Err(status) => return send_error(&tx, status).await,
+            };
+
+            while next <= latest {
+                let mut continuation_token = None;
+
+                loop {
+                    let page = EventFilterWithPage {
+                        event_filter: EventFilter {
+                            from_block: Some(BlockIdOrTag::Number(next)),
+                            to_block: Some(BlockIdOrTag::Number(next)),
+                            address: filter.address,
+                            keys: filter.keys.clone(),
+                        },
+                        result_page_request: ResultPageRequest { continuation_token, chunk_size },
+                    };
+
+                    let page = match api.events(page).await.into_grpc_result() {
+                        Ok(page) => page,
+                        Err(status) => return send_error(&tx, status).await,
+                    };
+
+                    for event in page.events {
+                        if tx.send(Ok(event.into())).await.is_err() {
+                            return;
+                        }
+                    }
+
+                    continuation_token = page.continuation_token;
+                    if continuation_token.is_none() {
+                        break;
+                    }
+                }
+
+                next += 1;
+            }
+
+            tokio::select! {
+                _ = tx.closed() => return,
+                res = blocks.changed() => {
+                    if res.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(into_stream(rx))
+}
+
+/// Streams the status of the transaction every time it changes.
+///
+/// The stream ends once the transaction is accepted on L1, as its status can no longer change
+/// afterwards. Nothing is streamed while the transaction is unknown to the node.
+pub(crate) fn transaction_status<Pool, PP, PF>(
+    api: StarknetApi<Pool, PP, PF>,
+    mut blocks: MinedBlockSubscription,
+    hash: TxHash,
+) -> SubscriptionStream<GetTransactionStatusResponse>
+where
+    Pool: TransactionPool + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+{
+    let mut pool_txs = api.pool().add_listener();
+    let (tx, rx) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+
+    tokio::spawn(async move {
+        let mut last_status = None;
+
+        loop {
+            match api.transaction_status(hash).await {
+                Ok(status) => {
+                    let is_final = matches!(status, TxStatus::AcceptedOnL1(..));
+                    let status = GetTransactionStatusResponse::from(status);
+
+                    if last_status.as_ref() != Some(&status) {
+                        last_status = Some(status.clone());
+                        if tx.send(Ok(status)).await.is_err() {
+                            return;
+                        }
+                    }
+
+                    if is_final {
+                        return;
+                    }
+                }
+
+                Err(StarknetApiError::TxnHashNotFound) => {}
+                Err(error) => return send_error(&tx, to_status(error)).await,
+            }
+
+            tokio::select! {
+                _ = tx.closed() => return,
+                res = blocks.changed() => {
+                    if res.is_err() {
+                        return;
+                    }
+                }
+                _ = pool_txs.next() => {}
+            }
+        }
+    });
+
+    into_stream(rx)
+}
+
+/// Streams the transactions entering the pool.
+///
+/// Only the transactions sent by `senders` are streamed, unless it's empty.
+pub(crate) fn pending_transactions<Pool, PP, PF>(
+    api: StarknetApi<Pool, PP, PF>,
+    senders: Vec<ContractAddress>,
+) -> SubscriptionStream<PendingTransaction>
+where
+    Pool: TransactionPool + 'static,
+    <Pool::Ordering as PoolOrd>::PriorityValue: Send + Sync,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+{
+    let pool_txs = api.pool().subscribe().map(|pending| (pending.tx.hash(), pending.tx.sender()));
+    forward_pending_transactions(pool_txs, senders, SUBSCRIPTION_BUFFER_SIZE)
+}
+
+/// Forwards the `(hash, sender)` of the transactions yielded by `pool_txs` to a stream buffering
+/// up to `buffer_size` items.
+///
+/// The pool doesn't bound its subscriptions, so `pool_txs` is drained without waiting for the
+/// client. A client that lets the buffer fill up is lagging behind: its stream ends with a
+/// `RESOURCE_EXHAUSTED` error instead of transactions piling up in memory.
+fn forward_pending_transactions<S>(
+    mut pool_txs: S,
+    senders: Vec<ContractAddress>,
+    buffer_size: usize,
+) -> SubscriptionStream<PendingTransaction>
+where
+    S: Stream<Item = (TxHash, ContractAddress)> + Send + Unpin + 'static,
+{
+    let (tx, rx) = mpsc::channel(buffer_size);
+
+    tokio::spawn(async move {
+        loop {
+            let (hash, sender) = tokio::select! {
+                _ = tx.closed() => return,
+                pending = pool_txs.next() => match pending {
+                    Some(pending) => pending,
+                    None => return,
+                },
+            };
+
+            if senders.is_empty() || senders.contains(&sender) {
+                // The last slot of the buffer is kept for the lagging error.
+                if tx.capacity() <= 1 {
+                    let status = Status::resource_exhausted(
+                        "pending transactions subscription is lagging behind",
+                    );
+                    return send_error(&tx, status).await;
+                }
+
+                let pending = PendingTransaction {
+                    transaction_hash: Some(hash.into()),
+                    sender_address: Some(Felt::from(sender).into()),
+                };
+
+                if tx.try_send(Ok(pending)).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    into_stream(rx)
+}
+
+/// Resolves the block a subscription starts from, making sure it's not too far in the past.
+async fn resolve_start_block<Pool, PP, PF>(
+    api: &StarknetApi<Pool, PP, PF>,
+    block_id: BlockIdOrTag,
+) -> Result<BlockNumber, Status>
+where
+    Pool: TransactionPool + 'static,
+    PP: PendingBlockProvider,
+    PF: ProviderFactory,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+{
+    let start = match api.block_with_tx_hashes(block_id).await.into_grpc_result()? {
+        GetBlockWithTxHashesResponse::Block(block) => block.block_number,
+        GetBlockWithTxHashesResponse::PreConfirmed(block) => block.block_number,
+    };
+
+    let latest = api.latest_block_number().await.into_grpc_result()?.block_number;
+    if latest.saturating_sub(start) > MAX_BLOCKS_BACK {
+        return Err(to_status(StarknetApiError::TooManyBlocksBack));
+    }
+
+    Ok(start)
+}
+
+async fn send_error<T>(tx: &mpsc::Sender<Result<T, Status>>, status: Status) {
+    let _ = tx.send(Err(status)).await;
+}
+
+fn into_stream<T: Send + 'static>(
+    mut rx: mpsc::Receiver<Result<T, Status>>,
+) -> SubscriptionStream<T> {
+    Box::pin(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::channel::mpsc::unbounded;
+    use katana_primitives::address;
+    use tonic::Code;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn lagging_pending_transactions_subscriber_is_closed() {
+        let (pool_tx, pool_rx) = unbounded();
+        let mut stream = forward_pending_transactions(pool_rx, Vec::new(), 4);
+
+        // Never read by the client while the transactions are entering the pool.
+        for i in 0..10u64 {
+            pool_tx.unbounded_send((Felt::from(i), address!("0x1"))).unwrap();
+        }
+
+        // All but the last slot of the buffer are filled with transactions.
+        for i in 0..3u64 {
+            let pending = stream.next().await.unwrap().unwrap();
+            assert_eq!(pending.transaction_hash, Some(Felt::from(i).into()));
+        }
+
+        let status = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert!(stream.next().await.is_none());
+
+        // The subscription to the pool must be dropped along with the stream.
+        assert!(pool_tx.is_closed());
+    }
+}
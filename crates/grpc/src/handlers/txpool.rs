@@ -0,0 +1,80 @@
+//! TxPool service handler implementation.
+
+use std::sync::Arc;
+
+use katana_pool::api::TransactionPool;
+use katana_primitives::ContractAddress;
+use katana_rpc_server::txpool::TxPoolApi;
+use tonic::{Request, Response, Status};
+
+use crate::protos::txpool::tx_pool_server::TxPool;
+use crate::protos::txpool::{
+    ContentFromRequest, ContentRequest, ContentResponse, InspectRequest, InspectResponse,
+    StatusRequest, StatusResponse,
+};
+
+/// The handler for the TxPool gRPC service.
+///
+/// This struct wraps `TxPoolApi` from `katana-rpc-server` and implements the gRPC service trait
+/// by delegating to the underlying API.
+pub struct TxPoolService<P> {
+    api: Arc<TxPoolApi<P>>,
+}
+
+impl<P> TxPoolService<P> {
+    pub fn new(api: TxPoolApi<P>) -> Self {
+        Self { api: Arc::new(api) }
+    }
+}
+
+impl<P> std::fmt::Debug for TxPoolService<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxPoolService").finish_non_exhaustive()
+    }
+}
+
+impl<P> Clone for TxPoolService<P> {
+    fn clone(&self) -> Self {
+        Self { api: self.api.clone() }
+    }
+}
+
+#[tonic::async_trait]
+impl<P> TxPool for TxPoolService<P>
+where
+    P: TransactionPool + 'static,
+{
+    async fn status(
+        &self,
+        _request: Request<StatusRequest>,
+    ) -> Result<Response<StatusResponse>, Status> {
+        Ok(Response::new(self.api.status().into()))
+    }
+
+    async fn content(
+        &self,
+        _request: Request<ContentRequest>,
+    ) -> Result<Response<ContentResponse>, Status> {
+        Ok(Response::new(self.api.content(None).into()))
+    }
+
+    async fn content_from(
+        &self,
+        request: Request<ContentFromRequest>,
+    ) -> Result<Response<ContentResponse>, Status> {
+        let address = request
+            .into_inner()
+            .address
+            .ok_or_else(|| Status::invalid_argument("Missing address"))?;
+        let address = ContractAddress::try_from(address)?;
+
+        Ok(Response::new(self.api.content(Some(address)).into()))
+    }
+
+    async fn inspect(
+        &self,
+        _request: Request<InspectRequest>,
+    ) -> Result<Response<InspectResponse>, Status> {
+        Ok(Response::new(self.api.inspect().into()))
+    }
+}
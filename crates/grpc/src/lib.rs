@@ -10,13 +10,18 @@
 //! ```ignore
 //! use katana_grpc::{GrpcServer, StarknetServer, StarknetService};
 //!
-//! let service = StarknetService::new(/* ... */);
+//! let service = StarknetService::new(/* ... */)
+//!     // Required by the block-based subscriptions
+//!     .with_block_subscription(backend.subscribe_blocks());
 //! let server = GrpcServer::new()
 //!     .service(StarknetServer::new(service))
 //!     .start("127.0.0.1:5051".parse()?)
 //!     .await?;
 //! ```
 //!
+//! Use [`GrpcServer::tls`] to serve over TLS, optionally requiring clients to present a
+//! certificate (ie, mutual TLS).
+//!
 //! # Client
 //!
 //! Use [`GrpcClient`] to connect to a gRPC server:
@@ -36,9 +41,24 @@
 //! let response = client.chain_id(ChainIdRequest {}).await?;
 //! println!("Chain ID: {}", response.into_inner().chain_id);
 //! ```
+//!
+//! # Subscriptions
+//!
+//! The `Subscribe*` methods are server-streaming and push updates as blocks are mined and
+//! transactions enter the pool, instead of having to poll for them:
+//!
+//! ```ignore
+//! use katana_grpc::proto::SubscribeNewHeadsRequest;
+//!
+//! let mut heads = client.subscribe_new_heads(SubscribeNewHeadsRequest::default()).await?;
+//! while let Some(header) = heads.get_mut().message().await? {
+//!     println!("New block: {}", header.block_number);
+//! }
+//! ```
 
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+mod access;
 mod client;
 mod error;
 mod handlers;
@@ -48,10 +68,14 @@ mod server;
 // Client exports
 pub use client::{Error as ClientError, GrpcClient, GrpcClientBuilder};
 // Server exports
-pub use handlers::StarknetService;
+pub use handlers::{DevService, StarknetService, TxPoolService};
+pub use protos::dev::dev_server::DevServer;
+pub use protos::katana::katana_server::KatanaServer;
+pub use protos::starknet::starknet_ext_server::StarknetExtServer;
 pub use protos::starknet::starknet_server::StarknetServer;
 pub use protos::starknet::starknet_trace_server::StarknetTraceServer;
 pub use protos::starknet::starknet_write_server::StarknetWriteServer;
+pub use protos::txpool::tx_pool_server::TxPoolServer;
 pub use server::{GrpcServer, GrpcServerHandle};
 
 /// Protocol buffer generated types.
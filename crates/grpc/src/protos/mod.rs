@@ -11,3 +11,18 @@ pub mod starknet {
     pub const FILE_DESCRIPTOR_SET: &[u8] =
         tonic::include_file_descriptor_set!("starknet_descriptor");
 }
+
+/// Katana service definitions from katana.proto
+pub mod katana {
+    tonic::include_proto!("katana");
+}
+
+/// Dev service definitions from dev.proto
+pub mod dev {
+    tonic::include_proto!("dev");
+}
+
+/// TxPool service definitions from txpool.proto
+pub mod txpool {
+    tonic::include_proto!("txpool");
+}
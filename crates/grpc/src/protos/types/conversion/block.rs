@@ -113,11 +113,7 @@ impl From<katana_rpc_types::block::GetBlockWithTxHashesResponse> for GetBlockWit
             RpcResponse::Block(block) => GetBlockWithTxHashesResponse {
                 result: Some(
                     crate::protos::starknet::get_block_with_tx_hashes_response::Result::Block(
-                        ProtoBlockWithTxHashes {
-                            status: finality_status_to_proto(block.status),
-                            header: Some(ProtoBlockHeader::from(&block)),
-                            transactions: block.transactions.to_proto_felts(),
-                        },
+                        block.into(),
                     ),
                 ),
             },
@@ -135,6 +131,17 @@ impl From<katana_rpc_types::block::GetBlockWithTxHashesResponse> for GetBlockWit
     }
 }
 
+/// Convert block with tx hashes to proto.
+impl From<katana_rpc_types::block::BlockWithTxHashes> for ProtoBlockWithTxHashes {
+    fn from(block: katana_rpc_types::block::BlockWithTxHashes) -> Self {
+        ProtoBlockWithTxHashes {
+            status: finality_status_to_proto(block.status),
+            header: Some(ProtoBlockHeader::from(&block)),
+            transactions: block.transactions.to_proto_felts(),
+        }
+    }
+}
+
 /// Convert block with txs response to proto.
 impl From<katana_rpc_types::block::MaybePreConfirmedBlock> for GetBlockWithTxsResponse {
     fn from(response: katana_rpc_types::block::MaybePreConfirmedBlock) -> Self {
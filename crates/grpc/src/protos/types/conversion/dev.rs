@@ -0,0 +1,20 @@
+//! Dev type conversions.
+
+use katana_primitives::Felt;
+use katana_rpc_types::account::Account;
+
+use crate::protos::common::Felt as ProtoFelt;
+use crate::protos::dev::PredeployedAccount;
+
+/// Convert Account to proto
+impl From<Account> for PredeployedAccount {
+    fn from(account: Account) -> Self {
+        PredeployedAccount {
+            address: Some(ProtoFelt::from(Felt::from(account.address))),
+            public_key: Some(account.public_key.into()),
+            private_key: account.private_key.map(ProtoFelt::from),
+            class_hash: Some(account.class_hash.into()),
+            balance: account.balance.to_be_bytes::<32>().to_vec(),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! List endpoint type conversions.
+
+use katana_rpc_types::list::{
+    GetBlocksRequest, GetBlocksResponse, GetTransactionsRequest, GetTransactionsResponse,
+    TransactionListItem,
+};
+use katana_rpc_types::ResultPageRequest;
+
+use crate::protos::starknet::{
+    GetBlocksRequest as ProtoGetBlocksRequest, GetBlocksResponse as ProtoGetBlocksResponse,
+    GetTransactionsRequest as ProtoGetTransactionsRequest,
+    GetTransactionsResponse as ProtoGetTransactionsResponse,
+};
+use crate::protos::types::{
+    BlockWithTxHashes as ProtoBlockWithTxHashes, Transaction as ProtoTx,
+    TransactionReceipt as ProtoTransactionReceipt, TransactionWithReceipt,
+};
+
+/// Convert GetBlocksRequest from proto
+impl From<ProtoGetBlocksRequest> for GetBlocksRequest {
+    fn from(req: ProtoGetBlocksRequest) -> Self {
+        GetBlocksRequest {
+            from: req.from,
+            to: req.to.map(|bound| bound.value),
+            result_page_request: page_request(req.continuation_token, req.chunk_size),
+        }
+    }
+}
+
+/// Convert GetBlocksResponse to proto
+impl From<GetBlocksResponse> for ProtoGetBlocksResponse {
+    fn from(response: GetBlocksResponse) -> Self {
+        ProtoGetBlocksResponse {
+            blocks: response.blocks.into_iter().map(ProtoBlockWithTxHashes::from).collect(),
+            continuation_token: response.continuation_token.unwrap_or_default(),
+        }
+    }
+}
+
+/// Convert GetTransactionsRequest from proto
+impl From<ProtoGetTransactionsRequest> for GetTransactionsRequest {
+    fn from(req: ProtoGetTransactionsRequest) -> Self {
+        GetTransactionsRequest {
+            from: req.from,
+            to: req.to.map(|bound| bound.value),
+            result_page_request: page_request(req.continuation_token, req.chunk_size),
+        }
+    }
+}
+
+/// Convert GetTransactionsResponse to proto
+impl From<GetTransactionsResponse> for ProtoGetTransactionsResponse {
+    fn from(response: GetTransactionsResponse) -> Self {
+        ProtoGetTransactionsResponse {
+            transactions: response
+                .transactions
+                .into_iter()
+                .map(TransactionWithReceipt::from)
+                .collect(),
+            continuation_token: response.continuation_token.unwrap_or_default(),
+        }
+    }
+}
+
+/// Convert TransactionListItem to proto
+impl From<TransactionListItem> for TransactionWithReceipt {
+    fn from(item: TransactionListItem) -> Self {
+        TransactionWithReceipt {
+            receipt: Some(ProtoTransactionReceipt::from(&item.receipt)),
+            transaction: Some(ProtoTx::from(item.transaction)),
+        }
+    }
+}
+
+fn page_request(continuation_token: String, chunk_size: u32) -> ResultPageRequest {
+    let continuation_token =
+        if continuation_token.is_empty() { None } else { Some(continuation_token) };
+    ResultPageRequest { continuation_token, chunk_size: chunk_size as u64 }
+}
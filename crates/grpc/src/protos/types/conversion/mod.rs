@@ -7,12 +7,15 @@
 //! Conversions are implemented using `From` and `TryFrom` traits for idiomatic Rust.
 
 mod block;
+mod dev;
 mod event;
+mod list;
 mod receipt;
 mod state;
 mod trace;
 mod transaction;
 mod trie;
+mod txpool;
 
 pub use block::*;
 #[allow(unused_imports)]
@@ -80,7 +80,7 @@ fn receipt_from_rpc_receipt(
         katana_rpc_types::receipt::ExecutionResult::Succeeded => {
             ("SUCCEEDED".to_string(), String::new())
         }
-        katana_rpc_types::receipt::ExecutionResult::Reverted { reason } => {
+        katana_rpc_types::receipt::ExecutionResult::Reverted { reason, .. } => {
             ("REVERTED".to_string(), reason.clone())
         }
     };
@@ -650,3 +650,33 @@ impl From<&PrimitiveResourceBoundsMapping> for ResourceBoundsMapping {
         }
     }
 }
+
+/// Convert TxStatus to proto
+impl From<katana_rpc_types::TxStatus> for proto::GetTransactionStatusResponse {
+    fn from(status: katana_rpc_types::TxStatus) -> Self {
+        use katana_rpc_types::TxStatus;
+
+        let (finality_status, execution_status) = match status {
+            TxStatus::Received => ("RECEIVED".to_string(), String::new()),
+            TxStatus::Candidate => ("CANDIDATE".to_string(), String::new()),
+            TxStatus::PreConfirmed(exec) => {
+                ("PRE_CONFIRMED".to_string(), execution_result_to_string(&exec))
+            }
+            TxStatus::AcceptedOnL2(exec) => {
+                ("ACCEPTED_ON_L2".to_string(), execution_result_to_string(&exec))
+            }
+            TxStatus::AcceptedOnL1(exec) => {
+                ("ACCEPTED_ON_L1".to_string(), execution_result_to_string(&exec))
+            }
+        };
+
+        proto::GetTransactionStatusResponse { finality_status, execution_status }
+    }
+}
+
+fn execution_result_to_string(exec: &katana_rpc_types::ExecutionResult) -> String {
+    match exec {
+        katana_rpc_types::ExecutionResult::Succeeded => "SUCCEEDED".to_string(),
+        katana_rpc_types::ExecutionResult::Reverted { .. } => "REVERTED".to_string(),
+    }
+}
@@ -0,0 +1,61 @@
+//! Transaction pool type conversions.
+
+use std::collections::BTreeMap;
+
+use katana_primitives::{ContractAddress, Felt};
+use katana_rpc_types::txpool::{TxPoolContent, TxPoolInspect, TxPoolStatus, TxPoolTransaction};
+
+use crate::protos::common::Felt as ProtoFelt;
+use crate::protos::txpool::{
+    ContentResponse, InspectResponse, PoolTransaction, PoolTransactionSummary, StatusResponse,
+};
+
+/// Convert TxPoolStatus to proto
+impl From<TxPoolStatus> for StatusResponse {
+    fn from(status: TxPoolStatus) -> Self {
+        StatusResponse { pending: status.pending, queued: status.queued }
+    }
+}
+
+/// Convert TxPoolContent to proto
+impl From<TxPoolContent> for ContentResponse {
+    fn from(content: TxPoolContent) -> Self {
+        let flatten = |txs: BTreeMap<ContractAddress, BTreeMap<Felt, TxPoolTransaction>>| {
+            txs.into_values().flat_map(BTreeMap::into_values).map(PoolTransaction::from).collect()
+        };
+
+        ContentResponse { pending: flatten(content.pending), queued: flatten(content.queued) }
+    }
+}
+
+/// Convert TxPoolTransaction to proto
+impl From<TxPoolTransaction> for PoolTransaction {
+    fn from(tx: TxPoolTransaction) -> Self {
+        PoolTransaction {
+            hash: Some(tx.hash.into()),
+            nonce: Some(tx.nonce.into()),
+            sender: Some(ProtoFelt::from(Felt::from(tx.sender))),
+            max_fee: Some(Felt::from(tx.max_fee).into()),
+            tip: tx.tip,
+        }
+    }
+}
+
+/// Convert TxPoolInspect to proto
+impl From<TxPoolInspect> for InspectResponse {
+    fn from(inspect: TxPoolInspect) -> Self {
+        let flatten = |txs: BTreeMap<ContractAddress, BTreeMap<Felt, String>>| {
+            txs.into_iter()
+                .flat_map(|(sender, txs)| {
+                    txs.into_iter().map(move |(nonce, summary)| PoolTransactionSummary {
+                        sender: Some(ProtoFelt::from(Felt::from(sender))),
+                        nonce: Some(nonce.into()),
+                        summary,
+                    })
+                })
+                .collect()
+        };
+
+        InspectResponse { pending: flatten(inspect.pending), queued: flatten(inspect.queued) }
+    }
+}
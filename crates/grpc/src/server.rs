@@ -1,19 +1,35 @@
 //! gRPC server implementation.
 
+use std::io;
 use std::net::SocketAddr;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
+use futures::stream::BoxStream;
+use futures::{FutureExt, StreamExt};
+use katana_rpc_server::middleware::auth::AuthConfig;
+use katana_rpc_server::middleware::rate_limit::RateLimiter;
+use katana_rpc_server::tls::{
+    TlsAcceptor, TlsConfig, TlsListener, TlsStream, DEFAULT_RELOAD_INTERVAL,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
 use tokio::sync::watch;
-use tonic::transport::server::Routes;
+use tonic::transport::server::{Connected, Routes, TcpConnectInfo, TcpIncoming};
 use tonic::transport::Server;
 use tracing::{error, info};
 
+use crate::access::AccessControlLayer;
 use crate::protos::starknet::FILE_DESCRIPTOR_SET;
 
 /// The default timeout for an request.
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
 
+/// The ALPN protocols advertised by the server. gRPC is only served over HTTP/2.
+const ALPN_PROTOCOLS: &[&[u8]] = &[b"h2"];
+
 /// Error type for gRPC server operations.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -36,6 +52,10 @@ pub enum Error {
     /// Error from creating the TCP incoming stream.
     #[error("Failed to create TCP incoming stream: {0}")]
     Incoming(String),
+
+    /// Error from loading the TLS certificates.
+    #[error(transparent)]
+    Tls(#[from] katana_rpc_server::tls::Error),
 }
 
 /// Handle to a running gRPC server.
@@ -77,12 +97,21 @@ pub struct GrpcServer {
     routes: Routes,
     /// Request timeout.
     timeout: Duration,
+    /// TLS configuration. The server is served over plaintext if `None`.
+    tls: Option<TlsConfig>,
+    /// Authentication and rate limiting of the calls.
+    access: AccessControlLayer,
 }
 
 impl GrpcServer {
     /// Creates a new gRPC server builder with the given configuration.
     pub fn new() -> Self {
-        Self { routes: Routes::default(), timeout: DEFAULT_TIMEOUT }
+        Self {
+            routes: Routes::default(),
+            timeout: DEFAULT_TIMEOUT,
+            tls: None,
+            access: AccessControlLayer::default(),
+        }
     }
 
     /// Set the timeout for the server. Default is 20 seconds.
@@ -91,6 +120,31 @@ impl GrpcServer {
         self
     }
 
+    /// Serves the gRPC services over TLS according to `config`.
+    ///
+    /// See [`katana_rpc_server::tls`] for more details.
+    pub fn tls(mut self, config: TlsConfig) -> Self {
+        self.tls = Some(config);
+        self
+    }
+
+    /// Authenticates the callers and restricts the methods they can call, like the RPC server does.
+    ///
+    /// Each call is checked as its equivalent JSON-RPC method, eg `/starknet.Starknet/GetNonce` as
+    /// `starknet_getNonce`.
+    pub fn auth(mut self, config: AuthConfig) -> Self {
+        self.access.set_auth(config);
+        self
+    }
+
+    /// Rate limits the calls made by each client.
+    ///
+    /// The limiter can be shared with the RPC server for both to draw from the same limits.
+    pub fn rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.access.set_rate_limiter(limiter);
+        self
+    }
+
     pub fn service<S>(mut self, service: S) -> Self
     where
         S: tower_service::Service<
@@ -129,20 +183,28 @@ impl GrpcServer {
         let listener = tokio::net::TcpListener::bind(addr).await?;
         let actual_addr = listener.local_addr()?;
 
-        let incoming = tonic::transport::server::TcpIncoming::from_listener(listener, true, None)
-            .map_err(|e| Error::Incoming(e.to_string()))?;
-
-        let mut builder = Server::builder().timeout(self.timeout);
+        let mut builder = Server::builder().timeout(self.timeout).layer(self.access.clone());
         let server = builder.add_routes(self.routes.clone()).add_service(reflection_service);
 
-        // Start the server with the already-bound listener
+        let shutdown = async move {
+            let _ = shutdown_rx.changed().await;
+        };
+
+        // Serve with the already-bound listener
+        let serve = if let Some(tls) = &self.tls {
+            let acceptor = TlsAcceptor::new(tls.clone(), ALPN_PROTOCOLS)?;
+            acceptor.spawn_reloader(DEFAULT_RELOAD_INTERVAL);
+
+            let incoming = tls_incoming(TlsListener::new(listener, acceptor));
+            server.serve_with_incoming_shutdown(incoming, shutdown).boxed()
+        } else {
+            let incoming = TcpIncoming::from_listener(listener, true, None)
+                .map_err(|e| Error::Incoming(e.to_string()))?;
+            server.serve_with_incoming_shutdown(incoming, shutdown).boxed()
+        };
+
         tokio::spawn(async move {
-            if let Err(error) = server
-                .serve_with_incoming_shutdown(incoming, async move {
-                    let _ = shutdown_rx.changed().await;
-                })
-                .await
-            {
+            if let Err(error) = serve.await {
                 error!(target: "grpc", %error, "gRPC server error");
             }
         });
@@ -158,3 +220,67 @@ impl Default for GrpcServer {
         Self::new()
     }
 }
+
+/// Returns the stream of the TLS connections accepted by `listener`.
+fn tls_incoming(listener: TlsListener) -> BoxStream<'static, io::Result<TlsConnection>> {
+    futures::stream::unfold(listener, |mut listener| async move {
+        let (stream, _) = listener.accept().await;
+        Some((Ok(TlsConnection(stream)), listener))
+    })
+    .boxed()
+}
+
+/// A TLS connection accepted by the server.
+///
+/// This only exists to implement [`Connected`], which tonic requires from the connections it
+/// serves.
+struct TlsConnection(TlsStream<TcpStream>);
+
+impl Connected for TlsConnection {
+    type ConnectInfo = TcpConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        let (stream, _) = self.0.get_ref();
+        stream.connect_info()
+    }
+}
+
+impl AsyncRead for TlsConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for TlsConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.0.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
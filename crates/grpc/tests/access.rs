@@ -0,0 +1,80 @@
+use std::num::NonZeroU32;
+
+use katana_grpc::proto::dev::GenerateBlockRequest;
+use katana_grpc::proto::txpool::StatusRequest;
+use katana_grpc::proto::{BlockNumberRequest, ChainIdRequest};
+use katana_grpc::GrpcClient;
+use katana_sequencer_node::config::rpc::{RpcApiKey, RpcAuthConfig, RpcRateLimitConfig};
+use katana_sequencer_node::config::Config;
+use katana_utils::node::{test_config, TestNode};
+use tonic::{Code, Request};
+
+async fn setup(config: Config) -> (TestNode, GrpcClient) {
+    let node = TestNode::new_with_config(config).await;
+
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let grpc = GrpcClient::connect(format!("http://{grpc_addr}"))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    (node, grpc)
+}
+
+fn with_api_key<T>(message: T, key: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("x-api-key", key.parse().unwrap());
+    request
+}
+
+#[tokio::test]
+async fn grpc_calls_require_rpc_access() {
+    let mut config = test_config();
+    config.rpc.auth = Some(RpcAuthConfig {
+        public_methods: vec!["starknet_*".to_string()],
+        api_keys: vec![RpcApiKey { key: "secret".to_string(), methods: vec!["dev_*".to_string()] }],
+        ..Default::default()
+    });
+
+    let (_node, mut grpc) = setup(config).await;
+
+    // Public methods can be called anonymously.
+    grpc.block_number(Request::new(BlockNumberRequest {})).await.unwrap();
+
+    // The dev and txpool services are subject to the same access control as their RPC APIs.
+    let status = grpc.generate_block(Request::new(GenerateBlockRequest {})).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+
+    let status = grpc.txpool_status(Request::new(StatusRequest {})).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+
+    let request = with_api_key(GenerateBlockRequest {}, "wrong");
+    let status = grpc.generate_block(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    grpc.generate_block(with_api_key(GenerateBlockRequest {}, "secret")).await.unwrap();
+
+    let status = grpc.txpool_status(with_api_key(StatusRequest {}, "secret")).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn grpc_calls_are_rate_limited() {
+    let mut config = test_config();
+    config.rpc.rate_limit = Some(RpcRateLimitConfig {
+        requests_per_second: NonZeroU32::new(1).unwrap(),
+        burst: None,
+        method_weights: Vec::new(),
+        trust_proxy_headers: false,
+    });
+
+    let (node, mut grpc) = setup(config).await;
+
+    grpc.chain_id(Request::new(ChainIdRequest {})).await.unwrap();
+
+    let status = grpc.chain_id(Request::new(ChainIdRequest {})).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+
+    // The RPC server and the gRPC server draw from the same limits.
+    let result = node.starknet_rpc_client().chain_id().await;
+    assert!(result.is_err(), "RPC call must be rate limited");
+}
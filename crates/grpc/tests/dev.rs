@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use katana_grpc::proto::dev::{
+    GenerateBlockRequest, IncreaseNextBlockTimestampRequest, PredeployedAccountsRequest,
+    SetNextBlockTimestampRequest, SetStorageAtRequest,
+};
+use katana_grpc::proto::txpool::{
+    ContentFromRequest, ContentRequest, InspectRequest, StatusRequest,
+};
+use katana_grpc::proto::{
+    get_block_with_tx_hashes_response, BlockHeader, BlockNumberRequest, GetBlockRequest,
+    GetStorageAtRequest,
+};
+use katana_grpc::GrpcClient;
+use katana_primitives::Felt;
+use katana_rpc_api::dev::DevApiClient;
+use katana_utils::node::{test_config, TestNode};
+use starknet::accounts::Account;
+use starknet::core::types::Call;
+use starknet::core::utils::get_selector_from_name;
+use tonic::{Code, Request};
+
+async fn setup(no_mining: bool) -> (TestNode, GrpcClient) {
+    let mut config = test_config();
+    config.sequencing.no_mining = no_mining;
+    let node = TestNode::new_with_config(config).await;
+
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let grpc = GrpcClient::connect(format!("http://{grpc_addr}"))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    (node, grpc)
+}
+
+fn felt_to_proto(felt: Felt) -> katana_grpc::proto::Felt {
+    katana_grpc::proto::Felt { value: felt.to_bytes_be().to_vec() }
+}
+
+fn proto_to_felt(proto: &katana_grpc::proto::Felt) -> Felt {
+    Felt::from_bytes_be_slice(&proto.value)
+}
+
+async fn block_number(grpc: &mut GrpcClient) -> u64 {
+    grpc.block_number(Request::new(BlockNumberRequest {}))
+        .await
+        .expect("grpc block_number failed")
+        .into_inner()
+        .block_number
+}
+
+/// Mines the pending block and returns its number.
+async fn generate_block(grpc: &mut GrpcClient) -> u64 {
+    let before = block_number(grpc).await;
+    grpc.generate_block(Request::new(GenerateBlockRequest {}))
+        .await
+        .expect("grpc generate_block failed");
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let number = block_number(grpc).await;
+            if number != before {
+                return number;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for the block to be mined")
+}
+
+async fn block_header(grpc: &mut GrpcClient, number: u64) -> BlockHeader {
+    let block = grpc
+        .get_block_with_tx_hashes(Request::new(GetBlockRequest {
+            block_id: Some(katana_grpc::proto::BlockId {
+                identifier: Some(katana_grpc::proto::block_id::Identifier::Number(number)),
+            }),
+        }))
+        .await
+        .expect("grpc get_block_with_tx_hashes failed")
+        .into_inner();
+
+    match block.result {
+        Some(get_block_with_tx_hashes_response::Result::Block(block)) => {
+            block.header.expect("missing block header")
+        }
+        _ => panic!("expected a confirmed block"),
+    }
+}
+
+#[tokio::test]
+async fn test_predeployed_accounts() {
+    let (node, mut grpc) = setup(false).await;
+
+    let rpc_accounts =
+        node.rpc_http_client().predeployed_accounts().await.expect("rpc predeployed_accounts");
+
+    let grpc_accounts = grpc
+        .predeployed_accounts(Request::new(PredeployedAccountsRequest {}))
+        .await
+        .expect("grpc predeployed_accounts failed")
+        .into_inner()
+        .accounts;
+
+    assert_eq!(grpc_accounts.len(), rpc_accounts.len());
+    for (grpc, rpc) in grpc_accounts.iter().zip(&rpc_accounts) {
+        assert_eq!(proto_to_felt(grpc.address.as_ref().unwrap()), Felt::from(rpc.address));
+        assert_eq!(proto_to_felt(grpc.public_key.as_ref().unwrap()), rpc.public_key);
+        assert_eq!(grpc.private_key.as_ref().map(proto_to_felt), rpc.private_key);
+        assert_eq!(grpc.balance, rpc.balance.to_be_bytes::<32>().to_vec());
+    }
+}
+
+#[tokio::test]
+async fn test_set_storage_at() {
+    let (_node, mut grpc) = setup(false).await;
+
+    let address = Felt::from(0x1337u64);
+    let key = Felt::from(0x1u64);
+    let value = Felt::from(0xdeadu64);
+
+    grpc.set_storage_at(Request::new(SetStorageAtRequest {
+        contract_address: Some(felt_to_proto(address)),
+        key: Some(felt_to_proto(key)),
+        value: Some(felt_to_proto(value)),
+    }))
+    .await
+    .expect("grpc set_storage_at failed");
+
+    let storage = grpc
+        .get_storage_at(Request::new(GetStorageAtRequest {
+            block_id: Some(katana_grpc::proto::BlockId {
+                identifier: Some(katana_grpc::proto::block_id::Identifier::Tag(
+                    katana_grpc::proto::BlockTag::Latest as i32,
+                )),
+            }),
+            contract_address: Some(felt_to_proto(address)),
+            key: Some(felt_to_proto(key)),
+        }))
+        .await
+        .expect("grpc get_storage_at failed")
+        .into_inner();
+
+    assert_eq!(proto_to_felt(storage.value.as_ref().unwrap()), value);
+
+    let status = grpc
+        .set_storage_at(Request::new(SetStorageAtRequest {
+            contract_address: Some(felt_to_proto(address)),
+            key: None,
+            value: Some(felt_to_proto(value)),
+        }))
+        .await
+        .expect_err("missing key must be rejected");
+
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_txpool_and_generate_block() {
+    let (node, mut grpc) = setup(true).await;
+
+    grpc.set_next_block_timestamp(Request::new(SetNextBlockTimestampRequest {
+        timestamp: 1_900_000_000,
+    }))
+    .await
+    .expect("grpc set_next_block_timestamp failed");
+
+    // Without mining, the next block is opened as soon as the current one is closed. So the new
+    // timestamp applies to the block opened after the one being built when it was set.
+    generate_block(&mut grpc).await;
+
+    let account = node.account();
+    let sender = account.address();
+
+    let call = Call {
+        to: Felt::from_hex_unchecked(
+            "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d",
+        ),
+        selector: get_selector_from_name("transfer").unwrap(),
+        calldata: vec![Felt::ONE, Felt::ONE, Felt::ZERO],
+    };
+    let tx_hash = account.execute_v3(vec![call]).send().await.unwrap().transaction_hash;
+
+    let status = grpc.txpool_status(Request::new(StatusRequest {})).await.unwrap().into_inner();
+    assert_eq!(status.pending, 1);
+    assert_eq!(status.queued, 0);
+
+    let content = grpc.txpool_content(Request::new(ContentRequest {})).await.unwrap().into_inner();
+    assert_eq!(content.pending.len(), 1);
+    assert!(content.queued.is_empty());
+    assert_eq!(proto_to_felt(content.pending[0].hash.as_ref().unwrap()), tx_hash);
+    assert_eq!(proto_to_felt(content.pending[0].sender.as_ref().unwrap()), sender);
+
+    let content = grpc
+        .txpool_content_from(Request::new(ContentFromRequest {
+            address: Some(felt_to_proto(Felt::ONE)),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(content.pending.is_empty());
+
+    let inspect = grpc.txpool_inspect(Request::new(InspectRequest {})).await.unwrap().into_inner();
+    assert_eq!(inspect.pending.len(), 1);
+    assert!(inspect.pending[0].summary.contains(&format!("hash={tx_hash:#x}")));
+
+    let number = generate_block(&mut grpc).await;
+    let header = block_header(&mut grpc, number).await;
+    assert_eq!(header.timestamp, 1_900_000_000);
+
+    let status = grpc.txpool_status(Request::new(StatusRequest {})).await.unwrap().into_inner();
+    assert_eq!(status.pending, 0);
+}
+
+#[tokio::test]
+async fn test_increase_next_block_timestamp() {
+    let (_node, mut grpc) = setup(true).await;
+
+    // Close the block opened at startup, so that the next one is opened after the increase.
+    let first = generate_block(&mut grpc).await;
+
+    grpc.increase_next_block_timestamp(Request::new(IncreaseNextBlockTimestampRequest {
+        offset: 1000,
+    }))
+    .await
+    .expect("grpc increase_next_block_timestamp failed");
+
+    generate_block(&mut grpc).await;
+    let second = generate_block(&mut grpc).await;
+
+    let first = block_header(&mut grpc, first).await.timestamp;
+    let second = block_header(&mut grpc, second).await.timestamp;
+
+    // Allow for the time elapsed while mining the blocks.
+    assert!(
+        (first + 1000..first + 1060).contains(&second),
+        "expected timestamp {second} to be increased by 1000 from {first}"
+    );
+}
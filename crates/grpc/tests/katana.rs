@@ -0,0 +1,235 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+use katana_grpc::proto::katana::{
+    AddDeclareTransactionSyncRequest, AddDeployAccountTransactionSyncRequest,
+    AddInvokeTransactionSyncRequest,
+};
+use katana_grpc::proto::{
+    BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
+    BroadcastedInvokeTransaction, TransactionReceipt,
+};
+use katana_grpc::GrpcClient;
+use katana_primitives::block::BlockIdOrTag;
+use katana_primitives::class::CompiledClass;
+use katana_primitives::{felt, Felt};
+use katana_rpc_types::broadcasted::{
+    BroadcastedDeclareTx, BroadcastedDeployAccountTx, BroadcastedInvokeTx,
+};
+use katana_utils::node::{test_config, TestNode};
+use starknet::accounts::{
+    Account, AccountFactory, ConnectedAccount, OpenZeppelinAccountFactory as OZAccountFactory,
+};
+use starknet::core::types::contract::SierraClass;
+use starknet::core::types::{Call, FlattenedSierraClass};
+use starknet::core::utils::get_selector_from_name;
+use starknet::signers::{LocalWallet, SigningKey};
+use tonic::{Code, Request};
+
+fn felt_to_proto(felt: Felt) -> katana_grpc::proto::Felt {
+    katana_grpc::proto::Felt { value: felt.to_bytes_be().to_vec() }
+}
+
+fn proto_to_felt(proto: &katana_grpc::proto::Felt) -> Felt {
+    Felt::from_bytes_be_slice(&proto.value)
+}
+
+/// Converts a starknet-rs request into its Katana counterpart through their common JSON format.
+fn convert_broadcasted_tx<T, U>(tx: T) -> U
+where
+    T: serde::Serialize,
+    U: serde::de::DeserializeOwned,
+{
+    let value = serde_json::to_value(tx).expect("failed to serialize tx");
+    serde_json::from_value(value).expect("failed to deserialize tx")
+}
+
+fn declaration_params(path: &PathBuf) -> (FlattenedSierraClass, Felt) {
+    let class: SierraClass = serde_json::from_reader(File::open(path).unwrap()).unwrap();
+    let flattened = class.flatten().unwrap();
+
+    let class: ContractClass = serde_json::from_reader(File::open(path).unwrap()).unwrap();
+    let casm = CasmContractClass::from_contract_class(class, true, usize::MAX).unwrap();
+    let compiled: CompiledClass =
+        serde_json::from_str(&serde_json::to_string(&casm).unwrap()).unwrap();
+
+    (flattened, compiled.class_hash().unwrap())
+}
+
+#[tokio::test]
+async fn test_add_transactions_sync() {
+    let mut config = test_config();
+    config.dev.fee = false;
+    config.dev.account_validation = false;
+
+    let node = TestNode::new_with_config(config).await;
+    let provider = node.starknet_rpc_client();
+    let account = node.account();
+
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let mut grpc = GrpcClient::connect(format!("http://{grpc_addr}"))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    // -----------------------------------------------------------------------
+    // AddInvokeTransactionSync
+
+    let call = Call {
+        to: Felt::from_hex_unchecked(
+            "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d",
+        ),
+        selector: get_selector_from_name("transfer").unwrap(),
+        calldata: vec![Felt::ONE, Felt::ONE, Felt::ZERO],
+    };
+
+    let fee = account.execute_v3(vec![call.clone()]).estimate_fee().await.unwrap();
+    let nonce = account.get_nonce().await.unwrap();
+
+    let prepared_invoke = account
+        .execute_v3(vec![call])
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed)
+        .l1_gas_price(fee.l1_gas_price)
+        .l2_gas(fee.l2_gas_consumed)
+        .l2_gas_price(fee.l2_gas_price)
+        .l1_data_gas(fee.l1_data_gas_consumed)
+        .l1_data_gas_price(fee.l1_data_gas_price)
+        .tip(0)
+        .prepared()
+        .unwrap();
+
+    let invoke_tx: BroadcastedInvokeTx =
+        convert_broadcasted_tx(prepared_invoke.get_invoke_request(false, false).await.unwrap());
+
+    let receipt = grpc
+        .add_invoke_transaction_sync(Request::new(AddInvokeTransactionSyncRequest {
+            transaction: Some(BroadcastedInvokeTransaction::from(&invoke_tx)),
+        }))
+        .await
+        .expect("grpc add_invoke_transaction_sync failed")
+        .into_inner()
+        .receipt
+        .expect("missing receipt");
+
+    assert_eq!(receipt.r#type, "INVOKE");
+    let hash = proto_to_felt(receipt.transaction_hash.as_ref().unwrap());
+    let expected = provider.get_transaction_receipt(hash).await.unwrap();
+    assert_eq!(receipt, TransactionReceipt::from(&expected));
+
+    // -----------------------------------------------------------------------
+    // AddDeclareTransactionSync
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../rpc/rpc-server/tests/test_data/cairo1_contract.json");
+    let (contract, compiled_class_hash) = declaration_params(&path);
+
+    let fee = account
+        .declare_v3(contract.clone().into(), compiled_class_hash)
+        .estimate_fee()
+        .await
+        .unwrap();
+    let nonce = account.get_nonce().await.unwrap();
+
+    let prepared_declare = account
+        .declare_v3(contract.into(), compiled_class_hash)
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed)
+        .l1_gas_price(fee.l1_gas_price)
+        .l2_gas(fee.l2_gas_consumed)
+        .l2_gas_price(fee.l2_gas_price)
+        .l1_data_gas(fee.l1_data_gas_consumed)
+        .l1_data_gas_price(fee.l1_data_gas_price)
+        .tip(0)
+        .prepared()
+        .unwrap();
+
+    let declare_tx: BroadcastedDeclareTx =
+        convert_broadcasted_tx(prepared_declare.get_declare_request(false, false).await.unwrap());
+
+    let receipt = grpc
+        .add_declare_transaction_sync(Request::new(AddDeclareTransactionSyncRequest {
+            transaction: Some(BroadcastedDeclareTransaction::from(&declare_tx)),
+        }))
+        .await
+        .expect("grpc add_declare_transaction_sync failed")
+        .into_inner()
+        .receipt
+        .expect("missing receipt");
+
+    assert_eq!(receipt.r#type, "DECLARE");
+    let hash = proto_to_felt(receipt.transaction_hash.as_ref().unwrap());
+    let expected = provider.get_transaction_receipt(hash).await.unwrap();
+    assert_eq!(receipt, TransactionReceipt::from(&expected));
+
+    // -----------------------------------------------------------------------
+    // AddDeployAccountTransactionSync
+
+    let chain_id = provider.chain_id().await.unwrap();
+    let signer = LocalWallet::from(SigningKey::from_random());
+    let class_hash = provider
+        .get_class_hash_at(BlockIdOrTag::PreConfirmed, account.address().into())
+        .await
+        .unwrap();
+    let salt = felt!("0x123");
+
+    let factory =
+        OZAccountFactory::new(class_hash, chain_id, &signer, account.provider()).await.unwrap();
+
+    let deploy_account_tx = factory.deploy_v3(salt);
+    let deployed_address = deploy_account_tx.address();
+
+    let fee = deploy_account_tx.estimate_fee().await.unwrap();
+    let nonce = deploy_account_tx.fetch_nonce().await.unwrap();
+
+    let prepared_deploy = factory
+        .deploy_v3(salt)
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed)
+        .l1_gas_price(fee.l1_gas_price)
+        .l2_gas(fee.l2_gas_consumed)
+        .l2_gas_price(fee.l2_gas_price)
+        .l1_data_gas(fee.l1_data_gas_consumed)
+        .l1_data_gas_price(fee.l1_data_gas_price)
+        .tip(0)
+        .prepared()
+        .unwrap();
+
+    let deploy_tx: BroadcastedDeployAccountTx =
+        convert_broadcasted_tx(prepared_deploy.get_deploy_request(false, false).await.unwrap());
+
+    let receipt = grpc
+        .add_deploy_account_transaction_sync(Request::new(AddDeployAccountTransactionSyncRequest {
+            transaction: Some(BroadcastedDeployAccountTransaction::from(&deploy_tx)),
+        }))
+        .await
+        .expect("grpc add_deploy_account_transaction_sync failed")
+        .into_inner()
+        .receipt
+        .expect("missing receipt");
+
+    assert_eq!(receipt.r#type, "DEPLOY_ACCOUNT");
+    assert_eq!(receipt.contract_address, Some(felt_to_proto(deployed_address)));
+    let hash = proto_to_felt(receipt.transaction_hash.as_ref().unwrap());
+    let expected = provider.get_transaction_receipt(hash).await.unwrap();
+    assert_eq!(receipt, TransactionReceipt::from(&expected));
+}
+
+#[tokio::test]
+async fn test_add_transaction_sync_without_transaction() {
+    let node = TestNode::new_with_config(test_config()).await;
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let mut grpc = GrpcClient::connect(format!("http://{grpc_addr}"))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    let status = grpc
+        .add_invoke_transaction_sync(Request::new(AddInvokeTransactionSyncRequest {
+            transaction: None,
+        }))
+        .await
+        .expect_err("missing transaction must be rejected");
+
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
@@ -1,14 +1,21 @@
+use katana_executor::blockifier::cache::ClassCache;
+use katana_executor::ExecutionFlags;
+use katana_grpc::proto::dev::GenerateBlockRequest;
 use katana_grpc::proto::{
     BlockHashAndNumberRequest, BlockNumberRequest, BlockTag, CallRequest, ChainIdRequest,
-    EstimateFeeRequest, EstimateMessageFeeRequest, GetBlockRequest, GetClassAtRequest,
-    GetClassHashAtRequest, GetClassRequest, GetCompiledCasmRequest, GetEventsRequest,
-    GetNonceRequest, GetStorageAtRequest, GetStorageProofRequest,
+    EstimateFeeRequest, EstimateMessageFeeRequest, GetBlockRequest, GetBlocksRequest,
+    GetClassAtRequest, GetClassHashAtRequest, GetClassRequest, GetCompiledCasmRequest,
+    GetEventsRequest, GetNonceRequest, GetStorageAtRequest, GetStorageProofRequest,
     GetTransactionByBlockIdAndIndexRequest, GetTransactionByHashRequest,
-    GetTransactionReceiptRequest, GetTransactionStatusRequest, SpecVersionRequest, SyncingRequest,
+    GetTransactionReceiptRequest, GetTransactionStatusRequest, GetTransactionsRequest, ListBound,
+    SpecVersionRequest, SubscribeEventsRequest, SubscribeNewHeadsRequest,
+    SubscribePendingTransactionsRequest, SubscribeTransactionStatusRequest, SyncingRequest,
+    TransactionNumberRequest,
 };
-use katana_grpc::GrpcClient;
+use katana_grpc::{GrpcClient, GrpcServer, StarknetServer, StarknetService};
 use katana_primitives::block::BlockIdOrTag;
 use katana_primitives::{ContractAddress, Felt};
+use katana_rpc_server::starknet::{RpcCache, StarknetApi, StarknetApiConfig};
 use katana_rpc_types::block::{
     GetBlockWithReceiptsResponse, GetBlockWithTxHashesResponse, MaybePreConfirmedBlock,
 };
@@ -16,7 +23,10 @@ use katana_rpc_types::state_update::StateUpdate;
 use katana_rpc_types::transaction::{RpcTx, TxStatus};
 use katana_rpc_types::{EventFilter, ExecutionResult, FunctionCall, SyncingResponse};
 use katana_starknet::rpc::StarknetRpcClient;
-use katana_utils::node::TestNode;
+use katana_tasks::TaskManager;
+use katana_utils::node::{test_config, TestNode};
+use starknet::accounts::Account;
+use starknet::core::types::Call;
 use starknet::core::utils::get_selector_from_name;
 use tonic::{Code, Request};
 
@@ -1018,3 +1028,376 @@ async fn test_get_block_with_txs_by_hash() {
     );
     assert_eq!(grpc_block.transactions.len(), rpc_block.transactions.len());
 }
+
+#[tokio::test]
+async fn test_subscriptions() {
+    let (node, mut grpc, _rpc) = setup().await;
+
+    let strk = Felt::from_hex_unchecked(
+        "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d",
+    );
+    let transfer = get_selector_from_name("Transfer").unwrap();
+
+    let latest = grpc
+        .block_number(Request::new(BlockNumberRequest {}))
+        .await
+        .expect("grpc block_number failed")
+        .into_inner()
+        .block_number;
+
+    let mut heads = grpc
+        .subscribe_new_heads(Request::new(SubscribeNewHeadsRequest { block_id: None }))
+        .await
+        .expect("grpc subscribe_new_heads failed")
+        .into_inner();
+
+    let mut events = grpc
+        .subscribe_events(Request::new(SubscribeEventsRequest {
+            from_address: Some(felt_to_proto(strk)),
+            keys: vec![felt_to_proto(transfer)],
+            block_id: None,
+        }))
+        .await
+        .expect("grpc subscribe_events failed")
+        .into_inner();
+
+    let mut pending_txs = grpc
+        .subscribe_pending_transactions(Request::new(SubscribePendingTransactionsRequest {
+            sender_address: vec![],
+        }))
+        .await
+        .expect("grpc subscribe_pending_transactions failed")
+        .into_inner();
+
+    // Without a block id, the subscription starts from the latest block.
+    let head = heads.message().await.expect("stream failed").expect("stream ended");
+    assert_eq!(head.block_number, latest);
+
+    let call = Call {
+        to: strk,
+        selector: get_selector_from_name("transfer").unwrap(),
+        calldata: vec![Felt::ONE, Felt::ONE, Felt::ZERO],
+    };
+    let tx_hash = node.account().execute_v3(vec![call]).send().await.unwrap().transaction_hash;
+
+    let mut statuses = grpc
+        .subscribe_transaction_status(Request::new(SubscribeTransactionStatusRequest {
+            transaction_hash: Some(felt_to_proto(tx_hash)),
+        }))
+        .await
+        .expect("grpc subscribe_transaction_status failed")
+        .into_inner();
+
+    tokio::time::timeout(std::time::Duration::from_secs(30), async {
+        let pending = pending_txs.message().await.expect("stream failed").expect("stream ended");
+        assert_eq!(proto_to_felt(pending.transaction_hash.as_ref().unwrap()), tx_hash);
+
+        let head = heads.message().await.expect("stream failed").expect("stream ended");
+        assert_eq!(head.block_number, latest + 1);
+
+        // Skip the transfers emitted in the latest block before the subscription.
+        loop {
+            let event = events.message().await.expect("stream failed").expect("stream ended");
+            assert_eq!(proto_to_felt(event.from_address.as_ref().unwrap()), strk);
+            assert_eq!(proto_to_felt(&event.keys[0]), transfer);
+
+            if proto_to_felt(event.transaction_hash.as_ref().unwrap()) == tx_hash {
+                assert_eq!(event.block_number, latest + 1);
+                break;
+            }
+        }
+
+        loop {
+            let status = statuses.message().await.expect("stream failed").expect("stream ended");
+            if status.finality_status == "ACCEPTED_ON_L2" {
+                assert_eq!(status.execution_status, "SUCCEEDED");
+                break;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for subscription updates");
+}
+
+#[tokio::test]
+async fn test_subscribe_pending_transactions_with_sender_filter() {
+    let (node, mut grpc, _rpc) = setup().await;
+    let sender = genesis_address(&node);
+
+    let mut sender_txs = grpc
+        .subscribe_pending_transactions(Request::new(SubscribePendingTransactionsRequest {
+            sender_address: vec![felt_to_proto(sender)],
+        }))
+        .await
+        .expect("grpc subscribe_pending_transactions failed")
+        .into_inner();
+
+    let mut other_txs = grpc
+        .subscribe_pending_transactions(Request::new(SubscribePendingTransactionsRequest {
+            sender_address: vec![felt_to_proto(Felt::from(0x1337u64))],
+        }))
+        .await
+        .expect("grpc subscribe_pending_transactions failed")
+        .into_inner();
+
+    let strk = Felt::from_hex_unchecked(
+        "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d",
+    );
+    let call = Call {
+        to: strk,
+        selector: get_selector_from_name("transfer").unwrap(),
+        calldata: vec![Felt::ONE, Felt::ONE, Felt::ZERO],
+    };
+    let tx_hash = node.account().execute_v3(vec![call]).send().await.unwrap().transaction_hash;
+
+    let pending = tokio::time::timeout(std::time::Duration::from_secs(30), sender_txs.message())
+        .await
+        .expect("timed out waiting for pending transaction")
+        .expect("stream failed")
+        .expect("stream ended");
+
+    assert_eq!(proto_to_felt(pending.transaction_hash.as_ref().unwrap()), tx_hash);
+    assert_eq!(proto_to_felt(pending.sender_address.as_ref().unwrap()), sender);
+
+    // Transactions from other senders are filtered out.
+    let other = tokio::time::timeout(std::time::Duration::from_secs(1), other_txs.message()).await;
+    assert!(other.is_err(), "unexpected pending transaction: {other:?}");
+}
+
+#[tokio::test]
+async fn test_subscription_too_many_blocks_back() {
+    let node = TestNode::new_with_config(test_config()).await;
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let mut grpc = GrpcClient::connect(format!("http://{grpc_addr}"))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    // Subscriptions can start at most 1024 blocks in the past.
+    for _ in 0..1025 {
+        grpc.generate_block(Request::new(GenerateBlockRequest {}))
+            .await
+            .expect("grpc generate_block failed");
+    }
+
+    let result = grpc
+        .subscribe_new_heads(Request::new(SubscribeNewHeadsRequest {
+            block_id: grpc_block_id_number(0),
+        }))
+        .await;
+    assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+
+    let result = grpc
+        .subscribe_events(Request::new(SubscribeEventsRequest {
+            from_address: None,
+            keys: vec![],
+            block_id: grpc_block_id_number(0),
+        }))
+        .await;
+    assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+
+    // The oldest block that can still be subscribed from.
+    let result = grpc
+        .subscribe_new_heads(Request::new(SubscribeNewHeadsRequest {
+            block_id: grpc_block_id_number(1),
+        }))
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_subscriptions_without_block_subscription() {
+    let node = TestNode::new_with_config(test_config()).await;
+    let inner = node.handle().node();
+    let backend = node.backend();
+
+    let config = StarknetApiConfig {
+        max_event_page_size: None,
+        max_proof_keys: None,
+        max_call_gas: None,
+        max_concurrent_estimate_fee_requests: None,
+        simulation_flags: ExecutionFlags::new(),
+        versioned_constant_overrides: None,
+    };
+
+    let api = StarknetApi::new(
+        backend.chain_spec.clone(),
+        inner.pool().clone(),
+        TaskManager::current().task_spawner(),
+        inner.block_producer().clone(),
+        backend.gas_oracle.clone(),
+        config,
+        inner.provider().clone(),
+        RpcCache::new(),
+        ClassCache::new().unwrap(),
+    );
+
+    // A service that isn't notified of the mined blocks.
+    let server = GrpcServer::new().service(StarknetServer::new(StarknetService::new(api)));
+    let handle = server.start("127.0.0.1:0".parse().unwrap()).await.unwrap();
+    let mut grpc = GrpcClient::connect(format!("http://{}", handle.addr()))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    let result =
+        grpc.subscribe_new_heads(Request::new(SubscribeNewHeadsRequest { block_id: None })).await;
+    assert_eq!(result.unwrap_err().code(), Code::Unimplemented);
+
+    let result = grpc
+        .subscribe_events(Request::new(SubscribeEventsRequest {
+            from_address: None,
+            keys: vec![],
+            block_id: None,
+        }))
+        .await;
+    assert_eq!(result.unwrap_err().code(), Code::Unimplemented);
+
+    let result = grpc
+        .subscribe_transaction_status(Request::new(SubscribeTransactionStatusRequest {
+            transaction_hash: Some(felt_to_proto(Felt::ONE)),
+        }))
+        .await;
+    assert_eq!(result.unwrap_err().code(), Code::Unimplemented);
+
+    // Pending transactions are streamed from the pool, so they don't need the mined blocks.
+    let result = grpc
+        .subscribe_pending_transactions(Request::new(SubscribePendingTransactionsRequest {
+            sender_address: vec![],
+        }))
+        .await;
+    assert!(result.is_ok());
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn test_list_blocks_and_transactions() {
+    let (_node, mut grpc, rpc) = setup().await;
+
+    let latest = rpc.block_number().await.expect("rpc block_number failed").block_number;
+
+    let total = grpc
+        .transaction_number(Request::new(TransactionNumberRequest {}))
+        .await
+        .expect("grpc transaction_number failed")
+        .into_inner()
+        .transaction_number;
+    assert!(total > 0, "Expected transactions after migration");
+
+    // Walk all the blocks in pages of 2.
+    let mut blocks = Vec::new();
+    let mut token = String::new();
+    loop {
+        let resp = grpc
+            .get_blocks(Request::new(GetBlocksRequest {
+                from: 0,
+                to: None,
+                chunk_size: 2,
+                continuation_token: token,
+            }))
+            .await
+            .expect("grpc get_blocks failed")
+            .into_inner();
+
+        assert!(resp.blocks.len() <= 2);
+        blocks.extend(resp.blocks);
+
+        if resp.continuation_token.is_empty() {
+            break;
+        }
+        token = resp.continuation_token;
+    }
+
+    assert_eq!(blocks.len() as u64, latest + 1);
+    for (i, block) in blocks.iter().enumerate() {
+        assert_eq!(block.header.as_ref().unwrap().block_number, i as u64);
+    }
+
+    let block_txs: usize = blocks.iter().map(|block| block.transactions.len()).sum();
+    assert_eq!(block_txs as u64, total);
+
+    // Without a chunk size, the node's max page size is used.
+    let resp = grpc
+        .get_blocks(Request::new(GetBlocksRequest {
+            from: 0,
+            to: None,
+            chunk_size: 0,
+            continuation_token: String::new(),
+        }))
+        .await
+        .expect("grpc get_blocks failed")
+        .into_inner();
+
+    assert_eq!(resp.blocks.len(), blocks.len());
+    assert!(resp.continuation_token.is_empty());
+
+    // A bounded range only returns the requested items.
+    let resp = grpc
+        .get_transactions(Request::new(GetTransactionsRequest {
+            from: 0,
+            to: Some(ListBound { value: 1 }),
+            chunk_size: 100,
+            continuation_token: String::new(),
+        }))
+        .await
+        .expect("grpc get_transactions failed")
+        .into_inner();
+
+    assert_eq!(resp.transactions.len(), 2);
+    assert!(resp.continuation_token.is_empty());
+
+    let flattened = blocks.iter().flat_map(|block| &block.transactions);
+    for (item, hash) in resp.transactions.iter().zip(flattened) {
+        let receipt = item.receipt.as_ref().unwrap();
+        assert_eq!(receipt.transaction_hash.as_ref(), Some(hash));
+    }
+}
+
+#[tokio::test]
+async fn test_list_chunk_size_is_capped() {
+    let mut config = test_config();
+    config.rpc.max_event_page_size = Some(1);
+
+    let node = TestNode::new_with_config(config).await;
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let mut grpc = GrpcClient::connect(format!("http://{grpc_addr}"))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    grpc.generate_block(Request::new(GenerateBlockRequest {}))
+        .await
+        .expect("grpc generate_block failed");
+
+    for chunk_size in [0, 100] {
+        let resp = grpc
+            .get_blocks(Request::new(GetBlocksRequest {
+                from: 0,
+                to: None,
+                chunk_size,
+                continuation_token: String::new(),
+            }))
+            .await
+            .expect("grpc get_blocks failed")
+            .into_inner();
+
+        assert_eq!(resp.blocks.len(), 1);
+        assert!(!resp.continuation_token.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_list_requires_explorer() {
+    let mut config = test_config();
+    config.rpc.explorer = false;
+
+    let node = TestNode::new_with_config(config).await;
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let mut grpc = GrpcClient::connect(format!("http://{grpc_addr}"))
+        .await
+        .expect("failed to connect to gRPC server");
+
+    let status = grpc
+        .transaction_number(Request::new(TransactionNumberRequest {}))
+        .await
+        .expect_err("the list API must not be served without the explorer");
+    assert_eq!(status.code(), Code::Unimplemented);
+}
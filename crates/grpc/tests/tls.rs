@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use katana_grpc::proto::ChainIdRequest;
+use katana_grpc::GrpcClient;
+use katana_rpc_server::tls::TlsConfig;
+use katana_utils::node::{test_config, TestNode};
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+use tonic::Request;
+
+fn test_data(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../rpc/rpc-server/tests/test_data/tls")
+        .join(name)
+}
+
+fn client_tls(identity: bool) -> ClientTlsConfig {
+    let ca = std::fs::read(test_data("ca.pem")).unwrap();
+    let tls =
+        ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca)).domain_name("localhost");
+
+    if identity {
+        let cert = std::fs::read(test_data("client.pem")).unwrap();
+        let key = std::fs::read(test_data("client.key")).unwrap();
+        tls.identity(Identity::from_pem(cert, key))
+    } else {
+        tls
+    }
+}
+
+#[tokio::test]
+async fn test_mutual_tls() {
+    let mut config = test_config();
+    config.grpc.as_mut().expect("grpc not enabled").tls = Some(TlsConfig {
+        cert: test_data("server.pem"),
+        key: test_data("server.key"),
+        client_ca: Some(test_data("ca.pem")),
+    });
+
+    let node = TestNode::new_with_config(config).await;
+    let grpc_addr = *node.grpc_addr().expect("grpc not enabled");
+    let endpoint = format!("https://localhost:{}", grpc_addr.port());
+
+    let chain_id = chain_id(&endpoint, Some(client_tls(true))).await.unwrap();
+    assert!(!chain_id.is_empty());
+
+    // Clients without a certificate are rejected.
+    assert!(chain_id(&endpoint, Some(client_tls(false))).await.is_err());
+
+    // So are plaintext clients.
+    assert!(chain_id(&format!("http://{grpc_addr}"), None).await.is_err());
+}
+
+async fn chain_id(
+    endpoint: &str,
+    tls: Option<ClientTlsConfig>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut builder = GrpcClient::builder(endpoint);
+    if let Some(tls) = tls {
+        builder = builder.tls(tls);
+    }
+
+    let mut grpc = builder.connect().await?;
+    let response = grpc.chain_id(Request::new(ChainIdRequest {})).await?;
+
+    Ok(response.into_inner().chain_id)
+}
@@ -179,7 +179,8 @@ pub struct Katana {
     block_time: Option<u64>,
     data_dir: Option<PathBuf>,
     l1_provider: Option<String>,
-    fork_block_number: Option<u64>,
+    fork_url: Option<Url>,
+    fork_block: Option<u64>,
     messaging: Option<PathBuf>,
 
     // Metrics options
@@ -194,6 +195,10 @@ pub struct Katana {
     rpc_max_call_gas: Option<u64>,
     http_cors_domain: Option<String>,
 
+    // gRPC options
+    grpc: bool,
+    grpc_port: Option<u16>,
+
     // Dev options
     seed: Option<u64>,
     accounts: Option<u16>,
@@ -208,6 +213,9 @@ pub struct Katana {
     strk_gas_price: Option<u64>,
     genesis: Option<PathBuf>,
 
+    // Paymaster options
+    paymaster: bool,
+
     // Cartridge options
     enable_cartridge_paymaster: bool,
     cartridge_api_url: Option<String>,
@@ -306,12 +314,24 @@ impl Katana {
         self
     }
 
-    /// Sets the fork block number which will be used when the `katana` instance is launched.
-    pub const fn fork_block_number(mut self, fork_block_number: u64) -> Self {
-        self.fork_block_number = Some(fork_block_number);
+    /// Sets the RPC URL of the network to fork from when the `katana` instance is launched.
+    pub fn fork_url(mut self, url: Url) -> Self {
+        self.fork_url = Some(url);
+        self
+    }
+
+    /// Sets the block number to fork the network at. Requires [`Katana::fork_url`] to be set.
+    pub const fn fork_block(mut self, block: u64) -> Self {
+        self.fork_block = Some(block);
         self
     }
 
+    /// Sets the fork block number which will be used when the `katana` instance is launched.
+    #[deprecated(note = "Use `fork_block` instead")]
+    pub const fn fork_block_number(self, fork_block_number: u64) -> Self {
+        self.fork_block(fork_block_number)
+    }
+
     /// Sets the messaging configuration path which will be used when the `katana` instance is
     /// launched.
     pub fn messaging<T: Into<PathBuf>>(mut self, messaging: T) -> Self {
@@ -361,6 +381,24 @@ impl Katana {
         self
     }
 
+    /// Enables or disables the gRPC server.
+    pub const fn grpc(mut self, enable: bool) -> Self {
+        self.grpc = enable;
+        self
+    }
+
+    /// Enables the gRPC server and sets the port it will listen on.
+    pub const fn grpc_port(mut self, port: u16) -> Self {
+        self.grpc_port = Some(port);
+        self
+    }
+
+    /// Enables or disables the paymaster service.
+    pub const fn paymaster(mut self, enable: bool) -> Self {
+        self.paymaster = enable;
+        self
+    }
+
     /// Sets the seed for randomness of accounts to be predeployed.
     pub const fn seed(mut self, seed: u64) -> Self {
         self.seed = Some(seed);
@@ -532,8 +570,16 @@ impl Katana {
             cmd.args(["--log.stdout.format", "json"]);
         }
 
-        if let Some(fork_block_number) = self.fork_block_number {
-            cmd.args(["--fork", "--fork.block"]).arg(fork_block_number.to_string());
+        if let Some(url) = self.fork_url {
+            cmd.arg("--fork.provider").arg(url.as_str());
+        }
+
+        if let Some(block) = self.fork_block {
+            cmd.arg("--fork.block").arg(block.to_string());
+        }
+
+        if let Some(genesis) = self.genesis {
+            cmd.arg("--genesis").arg(genesis);
         }
 
         if let Some(messaging) = self.messaging {
@@ -587,6 +633,35 @@ impl Katana {
             cmd.arg("--validate-max-steps").arg(validate_max_steps.to_string());
         }
 
+        // Need to make sure that the `--grpc` is not being set twice.
+        let mut grpc_enabled = false;
+
+        if self.grpc {
+            cmd.arg("--grpc");
+            grpc_enabled = true;
+        }
+
+        if let Some(port) = self.grpc_port {
+            if !grpc_enabled {
+                cmd.arg("--grpc");
+            }
+
+            cmd.arg("--grpc.port").arg(port.to_string());
+        }
+
+        // The cartridge paymaster requires the paymaster service to be enabled.
+        if self.paymaster || self.enable_cartridge_paymaster {
+            cmd.arg("--paymaster");
+        }
+
+        if let Some(url) = self.cartridge_api_url {
+            cmd.arg("--cartridge.api").arg(url);
+        }
+
+        if self.enable_cartridge_paymaster {
+            cmd.arg("--cartridge.paymaster");
+        }
+
         let mut child = cmd.spawn().map_err(Error::SpawnError)?;
         let stdout = child.stdout.as_mut().ok_or(Error::NoStderr)?;
 
@@ -600,14 +675,6 @@ impl Katana {
         // id) if not specified
         let mut chain_id: Felt = self.chain_id.unwrap_or(short_string!("KATANA"));
 
-        if let Some(url) = self.cartridge_api_url {
-            cmd.arg("--cartridge.api-url").arg(url);
-        }
-
-        if self.enable_cartridge_paymaster {
-            cmd.arg("--cartridge.paymaster");
-        }
-
         loop {
             if start + Duration::from_millis(self.timeout.unwrap_or(KATANA_STARTUP_TIMEOUT_MILLIS))
                 <= Instant::now()
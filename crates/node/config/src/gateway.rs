@@ -3,6 +3,8 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use crate::tls::TlsConfig;
+
 pub const DEFAULT_GATEWAY_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
 pub const DEFAULT_GATEWAY_PORT: u16 = 5051;
 pub const DEFAULT_GATEWAY_TIMEOUT_SECS: u64 = 20;
@@ -19,6 +21,8 @@ pub struct GatewayConfig {
     /// If `None`, requests will wait indefinitely. If `Some`, requests made to the gateway
     /// server will timeout after the specified duration has elapsed.
     pub timeout: Option<Duration>,
+    /// TLS termination. If `None`, the server is served over plain HTTP.
+    pub tls: Option<TlsConfig>,
 }
 
 impl GatewayConfig {
@@ -34,6 +38,7 @@ impl Default for GatewayConfig {
             addr: DEFAULT_GATEWAY_ADDR,
             port: DEFAULT_GATEWAY_PORT,
             timeout: Some(Duration::from_secs(DEFAULT_GATEWAY_TIMEOUT_SECS)),
+            tls: None,
         }
     }
 }
@@ -5,3 +5,4 @@ pub mod db;
 pub mod gateway;
 pub mod metrics;
 pub mod rpc;
+pub mod tls;
@@ -1,10 +1,13 @@
 use std::collections::HashSet;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::num::NonZeroU32;
 use std::time::Duration;
 
 use http::HeaderValue;
 use serde::{Deserialize, Serialize};
 
+use crate::tls::TlsConfig;
+
 pub const DEFAULT_RPC_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
 pub const DEFAULT_RPC_PORT: u16 = 5050;
 
@@ -14,6 +17,10 @@ pub const DEFAULT_RPC_MAX_EVENT_PAGE_SIZE: u64 = 1024;
 pub const DEFAULT_RPC_MAX_PROOF_KEYS: u64 = 100;
 /// Default maximum gas for the `starknet_call` RPC method.
 pub const DEFAULT_RPC_MAX_CALL_GAS: u64 = 1_000_000_000;
+/// Default methods that can be called without credentials when authentication is enabled.
+pub const DEFAULT_RPC_PUBLIC_METHODS: &[&str] = &["starknet_*", "node_*"];
+/// Default methods that JWTs without a `methods` claim are allowed to call.
+pub const DEFAULT_RPC_JWT_METHODS: &[&str] = &["*"];
 
 /// List of RPC modules supported by Katana.
 #[derive(
@@ -55,6 +62,12 @@ pub struct RpcConfig {
     pub max_proof_keys: Option<u64>,
     pub max_event_page_size: Option<u64>,
     pub max_call_gas: Option<u64>,
+    /// Authentication and access control. If `None`, every method is publicly accessible.
+    pub auth: Option<RpcAuthConfig>,
+    /// Per-client rate limiting. If `None`, calls aren't rate limited.
+    pub rate_limit: Option<RpcRateLimitConfig>,
+    /// TLS termination. If `None`, the server is served over plain HTTP.
+    pub tls: Option<TlsConfig>,
 }
 
 impl RpcConfig {
@@ -81,7 +94,130 @@ impl Default for RpcConfig {
             max_event_page_size: Some(DEFAULT_RPC_MAX_EVENT_PAGE_SIZE),
             max_proof_keys: Some(DEFAULT_RPC_MAX_PROOF_KEYS),
             max_call_gas: Some(DEFAULT_RPC_MAX_CALL_GAS),
+            auth: None,
+            rate_limit: None,
+            tls: None,
+        }
+    }
+}
+
+/// Authentication and access control configuration for the RPC server.
+///
+/// Methods are described by patterns, which are either an exact method name (eg,
+/// `dev_generateBlock`) or a prefix followed by a wildcard (eg, `starknet_*`).
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcAuthConfig {
+    /// Methods that can be called without any credentials.
+    pub public_methods: Vec<String>,
+    /// Static API keys and the methods they're allowed to call.
+    pub api_keys: Vec<RpcApiKey>,
+    /// Secret used to verify HS256 JSON Web Tokens. JWT authentication is disabled if `None`.
+    pub jwt_secret: Option<String>,
+    /// Methods that JWTs without a `methods` claim are allowed to call.
+    pub jwt_methods: Vec<String>,
+}
+
+/// A static API key and the methods it's allowed to call.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcApiKey {
+    pub key: String,
+    pub methods: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid API key: must not be empty")]
+pub struct InvalidRpcApiKeyError;
+
+impl RpcApiKey {
+    /// Used as the value parser for `clap`.
+    ///
+    /// The value is in the form of `<KEY>[:<METHODS>]`, where `<METHODS>` is a comma-separated
+    /// list of method patterns. A key without methods is allowed to call every method.
+    pub fn parse(value: &str) -> Result<Self, InvalidRpcApiKeyError> {
+        let (key, methods) = match value.split_once(':') {
+            Some((key, methods)) => {
+                let methods = methods
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|m| !m.is_empty())
+                    .map(String::from)
+                    .collect();
+                (key.trim(), methods)
+            }
+            None => (value.trim(), vec!["*".to_string()]),
+        };
+
+        if key.is_empty() {
+            return Err(InvalidRpcApiKeyError);
+        }
+
+        Ok(Self { key: key.to_string(), methods })
+    }
+}
+
+impl std::fmt::Debug for RpcAuthConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the JWT secret.
+        f.debug_struct("RpcAuthConfig")
+            .field("public_methods", &self.public_methods)
+            .field("api_keys", &self.api_keys)
+            .field("jwt_secret", &self.jwt_secret.as_ref().map(|_| "<redacted>"))
+            .field("jwt_methods", &self.jwt_methods)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for RpcApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the key.
+        f.debug_struct("RpcApiKey")
+            .field("key", &"<redacted>")
+            .field("methods", &self.methods)
+            .finish()
+    }
+}
+
+/// Per-client rate limiting configuration for the RPC server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcRateLimitConfig {
+    /// The number of call weight units each client can spend per second.
+    pub requests_per_second: NonZeroU32,
+    /// The maximum number of weight units a client can spend at once. Defaults to
+    /// `requests_per_second` if `None`.
+    pub burst: Option<NonZeroU32>,
+    /// Weights of specific methods, overriding the default ones.
+    pub method_weights: Vec<RpcMethodWeight>,
+    /// Whether to identify clients using the `X-Forwarded-For` and `X-Real-IP` headers.
+    pub trust_proxy_headers: bool,
+}
+
+/// The number of weight units consumed by each call to a method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcMethodWeight {
+    pub method: String,
+    pub weight: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid method weight '{0}': expected <METHOD>=<WEIGHT>")]
+pub struct InvalidRpcMethodWeightError(String);
+
+impl RpcMethodWeight {
+    /// Used as the value parser for `clap`.
+    ///
+    /// The value is in the form of `<METHOD>=<WEIGHT>` (eg, `starknet_getEvents=10`).
+    pub fn parse(value: &str) -> Result<Self, InvalidRpcMethodWeightError> {
+        let error = || InvalidRpcMethodWeightError(value.to_string());
+
+        let (method, weight) = value.split_once('=').ok_or_else(error)?;
+        let method = method.trim();
+        let weight = weight.trim().parse().map_err(|_| error())?;
+
+        if method.is_empty() {
+            return Err(error());
         }
+
+        Ok(Self { method: method.to_string(), weight })
     }
 }
 
@@ -223,4 +359,29 @@ mod tests {
     fn test_parse_invalid() {
         assert!(RpcModulesList::parse("invalid").is_err());
     }
+
+    #[test]
+    fn test_parse_method_weight() {
+        let weight = RpcMethodWeight::parse("starknet_getEvents=10").unwrap();
+        assert_eq!(weight.method, "starknet_getEvents");
+        assert_eq!(weight.weight, 10);
+
+        assert!(RpcMethodWeight::parse("starknet_getEvents").is_err());
+        assert!(RpcMethodWeight::parse("starknet_getEvents=").is_err());
+        assert!(RpcMethodWeight::parse("=10").is_err());
+    }
+
+    #[test]
+    fn test_parse_api_key() {
+        let key = RpcApiKey::parse("secret").unwrap();
+        assert_eq!(key.key, "secret");
+        assert_eq!(key.methods, vec!["*"]);
+
+        let key = RpcApiKey::parse("secret:dev_*, katana_getTransactions").unwrap();
+        assert_eq!(key.key, "secret");
+        assert_eq!(key.methods, vec!["dev_*", "katana_getTransactions"]);
+
+        assert!(RpcApiKey::parse("").is_err());
+        assert!(RpcApiKey::parse(":dev_*").is_err());
+    }
 }
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// TLS configuration of a server.
+///
+/// The certificates are reloaded whenever their files change, so that they can be rotated without
+/// restarting the node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// Path to the PEM-encoded certificate chain of the server.
+    pub cert: PathBuf,
+    /// Path to the PEM-encoded private key of the server.
+    pub key: PathBuf,
+    /// Path to the PEM-encoded CA certificates used to verify client certificates.
+    ///
+    /// If set, clients must present a certificate signed by one of these CAs (ie, mutual TLS).
+    pub client_ca: Option<PathBuf>,
+}
+
+/// TLS configuration of a client.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientTlsConfig {
+    /// Path to the PEM-encoded CA certificates used to verify the server certificate, in addition
+    /// to the default trusted CAs.
+    pub ca: Option<PathBuf>,
+    /// The certificate presented to servers requiring mutual TLS.
+    pub identity: Option<ClientIdentity>,
+}
+
+/// Certificate of a TLS client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientIdentity {
+    /// Path to the PEM-encoded certificate chain of the client.
+    pub cert: PathBuf,
+    /// Path to the PEM-encoded private key of the client.
+    pub key: PathBuf,
+}
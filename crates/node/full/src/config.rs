@@ -1 +1 @@
-pub use katana_node_config::{build_info, db, gateway, metrics, rpc};
+pub use katana_node_config::{build_info, db, gateway, metrics, rpc, tls};
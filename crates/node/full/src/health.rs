@@ -0,0 +1,80 @@
+use futures::future::BoxFuture;
+use katana_primitives::block::BlockNumber;
+use katana_provider::api::block::BlockNumberProvider;
+use katana_provider::{DbProviderFactory, ProviderFactory};
+use katana_rpc_server::health::{ComponentHealth, HealthIndicator};
+use serde_json::json;
+
+use crate::tip_watcher::TipSubscription;
+
+/// The default number of blocks the node may lag behind the chain tip while still being ready.
+pub const DEFAULT_MAX_SYNC_LAG: u64 = 64;
+
+/// Checks that the sync pipeline is keeping up with the chain tip.
+#[derive(Debug)]
+pub struct SyncCheck {
+    provider: DbProviderFactory,
+    tip: TipSubscription,
+    /// The block the pipeline stops syncing at, if any.
+    max_tip: Option<BlockNumber>,
+    max_lag: u64,
+}
+
+impl SyncCheck {
+    pub fn new(
+        provider: DbProviderFactory,
+        tip: TipSubscription,
+        max_tip: Option<BlockNumber>,
+    ) -> Self {
+        Self { provider, tip, max_tip, max_lag: DEFAULT_MAX_SYNC_LAG }
+    }
+
+    /// Sets the number of blocks the node may lag behind the chain tip. Default is 64.
+    pub fn max_lag(mut self, max_lag: u64) -> Self {
+        self.max_lag = max_lag;
+        self
+    }
+}
+
+impl HealthIndicator for SyncCheck {
+    fn name(&self) -> &str {
+        "sync"
+    }
+
+    fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+        Box::pin(async move {
+            let synced = match self.provider.provider().latest_number() {
+                Ok(block) => block,
+                Err(error) => {
+                    return ComponentHealth::unhealthy(format!(
+                        "failed to read latest synced block: {error}"
+                    ));
+                }
+            };
+
+            // The tip watcher hasn't fetched the tip yet.
+            let tip = self.tip.tip();
+            if tip == 0 {
+                return ComponentHealth::degraded("chain tip is not known yet")
+                    .with_details(json!({ "synced_block": synced }));
+            }
+
+            let target = self.max_tip.map_or(tip, |max| tip.min(max));
+            let lag = target.saturating_sub(synced);
+
+            let details = json!({
+                "synced_block": synced,
+                "chain_tip": tip,
+                "target_block": target,
+                "lag": lag,
+            });
+
+            if lag > self.max_lag {
+                ComponentHealth::unhealthy(format!("node is {lag} blocks behind the chain tip"))
+                    .with_details(details)
+            } else {
+                ComponentHealth::healthy().with_details(details)
+            }
+        })
+    }
+}
@@ -8,12 +8,13 @@ use std::collections::HashSet;
 use std::future::IntoFuture;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use config::build_info::BuildInfo;
 use config::db::DbConfig;
 use config::gateway::GatewayConfig;
 use config::metrics::MetricsConfig;
 use config::rpc::{RpcConfig, RpcModuleKind};
+use config::tls::ClientTlsConfig;
 use http::header::CONTENT_TYPE;
 use http::Method;
 use jsonrpsee::RpcModule;
@@ -29,33 +30,43 @@ use katana_metrics::sys::DiskReporter;
 use katana_metrics::{MetricsServer, MetricsServerHandle, Report};
 use katana_pipeline::{Pipeline, PipelineHandle};
 use katana_pool::ordering::TipOrdering;
+use katana_primitives::Felt;
 use katana_provider::DbProviderFactory;
 use katana_rpc_api::katana::KatanaApiServer;
 use katana_rpc_api::node::NodeApiServer;
 use katana_rpc_api::starknet::StarknetApiServer;
+use katana_rpc_server::health::{DatabaseCheck, HealthChecks, TaskCheck};
+use katana_rpc_server::middleware::auth::AuthConfig;
 use katana_rpc_server::middleware::cors::Cors;
+use katana_rpc_server::middleware::rate_limit::{RateLimitConfig, RateLimiter};
 use katana_rpc_server::node::NodeApi;
 use katana_rpc_server::starknet::{RpcCache, StarknetApi, StarknetApiConfig};
+use katana_rpc_server::tls::load_client_config;
 use katana_rpc_server::{RpcServer, RpcServerHandle};
 use katana_rpc_types::node::NodeInfo;
 use katana_stage::blocks::{BatchBlockDownloader, JsonRpcBlockDownloader};
 use katana_stage::classes::{GatewayClassDownloader, JsonRpcClassDownloader};
 use katana_stage::{Blocks, Classes, IndexHistory, StateTrie};
-use katana_tasks::TaskManager;
+use katana_starknet::rpc::StarknetRpcClient;
+use katana_tasks::{TaskManager, TaskStatus};
 use tracing::{error, info};
 use url::Url;
 
 use crate::pending::PreconfStateFactory;
 
 mod exit;
+pub mod health;
 mod pending;
 mod pool;
 pub mod tip_watcher;
+pub mod upstream;
 
 use exit::NodeStoppedFuture;
+use health::SyncCheck;
 use tip_watcher::ChainTipWatcher;
+use upstream::Upstream;
 
-use crate::pool::{FullNodePool, GatewayProxyValidator};
+use crate::pool::{FullNodePool, UpstreamProxyValidator};
 
 #[derive(
     Debug,
@@ -165,6 +176,11 @@ pub struct Config {
     pub metrics: Option<MetricsConfig>,
     pub gateway_api_key: Option<String>,
     pub network: Network,
+    /// Chain spec of a custom chain (eg a Katana appchain) to follow instead of [`Self::network`].
+    ///
+    /// Following a custom chain requires an explicit sync source, as there is no default
+    /// upstream to sync from.
+    pub chain: Option<katana_chain_spec::rollup::ChainSpec>,
     pub gateway: Option<GatewayConfig>,
     pub sync: SyncConfig,
     /// Build-time identity. See [`BuildInfo`] docs.
@@ -186,6 +202,14 @@ pub struct SyncConfig {
     pub stages: SyncStagesList,
     /// Per-stage configuration.
     pub stage: StageConfig,
+    /// Public key of the upstream sequencer.
+    ///
+    /// When set, the signature of every synced block is verified against this key. Only
+    /// supported when syncing from a feeder gateway.
+    pub sequencer_public_key: Option<Felt>,
+    /// TLS configuration used to connect to the sync source, eg to trust a private CA or to
+    /// authenticate to a source requiring mutual TLS.
+    pub tls: Option<ClientTlsConfig>,
 }
 
 /// Per-stage configuration options.
@@ -216,6 +240,9 @@ pub enum SyncSource {
     /// Custom feeder gateway base URL instead of the default network gateway.
     Gateway(Url),
     /// JSON-RPC endpoint URL.
+    ///
+    /// The chain tip, the pre-confirmed block and transaction submission go through the same
+    /// endpoint.
     JsonRpc(Url),
 }
 
@@ -229,9 +256,10 @@ pub struct Node {
     pub pipeline: Pipeline,
     pub rpc_server: RpcServer,
     pub gateway_server: Option<GatewayServer<FullNodePool, PreconfStateFactory, DbProviderFactory>>,
-    pub gateway_client: SequencerGateway,
+    pub upstream: Upstream,
     pub metrics_server: Option<MetricsServer<Prometheus>>,
-    pub chain_tip_watcher: ChainTipWatcher<SequencerGateway>,
+    pub chain_tip_watcher: ChainTipWatcher<Upstream>,
+    pub pipeline_status: TaskStatus,
 }
 
 impl Node {
@@ -262,28 +290,76 @@ impl Node {
 
         let storage_provider = DbProviderFactory::new(db.clone());
 
-        // --- build gateway client
+        // --- build chain spec
 
-        let gateway_client = if let Some(SyncSource::Gateway(ref base_url)) = config.sync.source {
-            let gateway = base_url.join("gateway").expect("valid URL join");
-            let feeder_gateway = base_url.join("feeder_gateway").expect("valid URL join");
-            SequencerGateway::new(gateway, feeder_gateway)
-        } else {
-            match config.network {
-                Network::Mainnet => SequencerGateway::mainnet(),
-                Network::Sepolia => SequencerGateway::sepolia(),
+        let chain_spec = match &config.chain {
+            Some(chain) => ChainSpec::Rollup(chain.clone()),
+            None => match config.network {
+                Network::Mainnet => ChainSpec::mainnet(),
+                Network::Sepolia => ChainSpec::sepolia(),
+            },
+        };
+        let chain_spec = Arc::new(chain_spec);
+        let chain_id = chain_spec.id();
+
+        // --- build upstream client
+
+        if config.chain.is_some() && config.sync.source.is_none() {
+            anyhow::bail!("a sync source must be specified when following a custom chain");
+        }
+
+        let upstream_tls = match &config.sync.tls {
+            Some(tls) => {
+                Some(load_client_config(tls).context("failed to load the sync source TLS config")?)
             }
+            None => None,
         };
 
-        let gateway_client = if let Some(ref key) = config.gateway_api_key {
-            gateway_client.with_api_key(key.clone())
-        } else {
-            gateway_client
+        let upstream = match &config.sync.source {
+            Some(SyncSource::JsonRpc(rpc_url)) => {
+                let rpc_url = rpc_url.clone();
+                let rpc_client = match upstream_tls {
+                    Some(tls) => StarknetRpcClient::new_with_tls(rpc_url, tls),
+                    None => StarknetRpcClient::new(rpc_url),
+                };
+
+                Upstream::JsonRpc(rpc_client)
+            }
+
+            source => {
+                let gateway_client = match source {
+                    Some(SyncSource::Gateway(base_url)) => {
+                        let gateway = base_url.join("gateway").expect("valid URL join");
+                        let feeder_gateway =
+                            base_url.join("feeder_gateway").expect("valid URL join");
+                        SequencerGateway::new(gateway, feeder_gateway)
+                    }
+                    _ => match config.network {
+                        Network::Mainnet => SequencerGateway::mainnet(),
+                        Network::Sepolia => SequencerGateway::sepolia(),
+                    },
+                };
+
+                let gateway_client = if let Some(ref key) = config.gateway_api_key {
+                    gateway_client.with_api_key(key.clone())
+                } else {
+                    gateway_client
+                };
+
+                let gateway_client = match upstream_tls {
+                    Some(tls) => gateway_client
+                        .with_tls(tls)
+                        .context("failed to build the gateway client")?,
+                    None => gateway_client,
+                };
+
+                Upstream::Gateway(gateway_client)
+            }
         };
 
         // --- build transaction pool
 
-        let validator = GatewayProxyValidator::new(gateway_client.clone());
+        let validator = UpstreamProxyValidator::new(upstream.clone());
         let pool = FullNodePool::new(validator, TipOrdering::new());
 
         // --- build pipeline
@@ -300,47 +376,82 @@ impl Node {
             pruning: config.pruning.clone(),
         });
 
-        let chain_id = match config.network {
-            Network::Mainnet => katana_primitives::chain::ChainId::MAINNET,
-            Network::Sepolia => katana_primitives::chain::ChainId::SEPOLIA,
-        };
-
         let stages = &config.sync.stages;
 
-        if let Some(SyncSource::JsonRpc(ref rpc_url)) = config.sync.source {
-            let rpc_client = katana_starknet::rpc::StarknetRpcClient::new(rpc_url.clone());
-
-            if stages.contains(&SyncStageKind::Blocks) {
-                let block_downloader =
-                    JsonRpcBlockDownloader::new_json_rpc(rpc_client.clone(), blocks_batch_size);
-                pipeline.add_stage(Blocks::new(
-                    storage_provider.clone(),
-                    block_downloader,
-                    chain_id,
-                    task_spawner.clone(),
-                ));
-            }
+        // Only check the genesis block against the chain spec of a custom chain, as the genesis of
+        // the public networks isn't part of their chain spec.
+        let genesis = config.chain.as_ref().map(|chain| chain.block());
+
+        match &upstream {
+            Upstream::JsonRpc(rpc_client) => {
+                if config.sync.sequencer_public_key.is_some() {
+                    anyhow::bail!(
+                        "block signature verification is only supported when syncing from a \
+                         feeder gateway"
+                    );
+                }
 
-            if stages.contains(&SyncStageKind::Classes) {
-                let class_downloader = JsonRpcClassDownloader::new(rpc_client, classes_batch_size);
-                pipeline.add_stage(Classes::new(storage_provider.clone(), class_downloader));
-            }
-        } else {
-            if stages.contains(&SyncStageKind::Blocks) {
-                let block_downloader =
-                    BatchBlockDownloader::new_gateway(gateway_client.clone(), blocks_batch_size);
-                pipeline.add_stage(Blocks::new(
-                    storage_provider.clone(),
-                    block_downloader,
-                    chain_id,
-                    task_spawner.clone(),
-                ));
+                if stages.contains(&SyncStageKind::Blocks) {
+                    let block_downloader =
+                        JsonRpcBlockDownloader::new_json_rpc(rpc_client.clone(), blocks_batch_size);
+
+                    let mut stage = Blocks::new(
+                        storage_provider.clone(),
+                        block_downloader,
+                        chain_id,
+                        task_spawner.clone(),
+                    );
+
+                    if let Some(genesis) = genesis {
+                        stage = stage.with_genesis(genesis);
+                    }
+
+                    pipeline.add_stage(stage);
+                }
+
+                if stages.contains(&SyncStageKind::Classes) {
+                    let class_downloader =
+                        JsonRpcClassDownloader::new(rpc_client.clone(), classes_batch_size);
+                    pipeline.add_stage(Classes::new(storage_provider.clone(), class_downloader));
+                }
             }
 
-            if stages.contains(&SyncStageKind::Classes) {
-                let class_downloader =
-                    GatewayClassDownloader::new(gateway_client.clone(), classes_batch_size);
-                pipeline.add_stage(Classes::new(storage_provider.clone(), class_downloader));
+            Upstream::Gateway(gateway_client) => {
+                if stages.contains(&SyncStageKind::Blocks) {
+                    let public_key = config.sync.sequencer_public_key;
+
+                    let block_downloader = if public_key.is_some() {
+                        BatchBlockDownloader::new_gateway_with_signatures(
+                            gateway_client.clone(),
+                            blocks_batch_size,
+                        )
+                    } else {
+                        BatchBlockDownloader::new_gateway(gateway_client.clone(), blocks_batch_size)
+                    };
+
+                    let mut stage = Blocks::new(
+                        storage_provider.clone(),
+                        block_downloader,
+                        chain_id,
+                        task_spawner.clone(),
+                    );
+
+                    if let Some(public_key) = public_key {
+                        stage = stage.with_sequencer_public_key(public_key);
+                    }
+
+                    if let Some(genesis) = genesis {
+                        stage = stage.with_genesis(genesis);
+                    }
+
+                    pipeline.add_stage(stage);
+                }
+
+                if stages.contains(&SyncStageKind::Classes) {
+                    let class_downloader =
+                        GatewayClassDownloader::new(gateway_client.clone(), classes_batch_size);
+                    pipeline.add_stage(Classes::new(storage_provider.clone(), class_downloader));
+                }
             }
         }
 
@@ -352,13 +463,13 @@ impl Node {
             pipeline.add_stage(StateTrie::new(storage_provider.clone(), task_spawner.clone()));
         }
 
-        // -- build chain tip watcher using gateway client
+        // -- build chain tip watcher using the upstream client
 
-        let chain_tip_watcher = ChainTipWatcher::new(gateway_client.clone());
+        let chain_tip_watcher = ChainTipWatcher::new(upstream.clone());
 
         let preconf_factory = PreconfStateFactory::new(
             storage_provider.clone(),
-            gateway_client.clone(),
+            upstream.clone(),
             pipeline_handle.subscribe_blocks(),
             chain_tip_watcher.subscribe(),
         );
@@ -386,12 +497,6 @@ impl Node {
             versioned_constant_overrides: None,
         };
 
-        let chain_spec = match config.network {
-            Network::Mainnet => ChainSpec::mainnet(),
-            Network::Sepolia => ChainSpec::sepolia(),
-        };
-        let chain_spec = Arc::new(chain_spec);
-
         let starknet_api = StarknetApi::new(
             chain_spec.clone(),
             pool.clone(),
@@ -426,9 +531,28 @@ impl Node {
             rpc_modules.merge(NodeApiServer::into_rpc(NodeApi::new(info)))?;
         }
 
+        // --- build health checks
+
+        let pipeline_status = TaskStatus::new();
+
+        let sync_check = SyncCheck::new(
+            storage_provider.clone(),
+            chain_tip_watcher.subscribe(),
+            config.sync.max_tip,
+        );
+
+        let health_checks = HealthChecks::new()
+            .readiness(DatabaseCheck::new(storage_provider.clone()))
+            .readiness(sync_check)
+            .liveness(TaskCheck::new("pipeline", pipeline_status.clone()));
+
         #[allow(unused_mut)]
-        let mut rpc_server =
-            RpcServer::new().metrics(true).health_check(true).cors(cors).module(rpc_modules)?;
+        let mut rpc_server = RpcServer::new()
+            .metrics(true)
+            .health_check(true)
+            .health_checks(health_checks)
+            .cors(cors)
+            .module(rpc_modules)?;
 
         #[cfg(feature = "explorer")]
         {
@@ -451,6 +575,30 @@ impl Node {
             rpc_server = rpc_server.max_response_body_size(max_response_body_size);
         }
 
+        let auth = match &config.rpc.auth {
+            Some(auth) => {
+                Some(AuthConfig::try_from(auth).context("invalid RPC authentication config")?)
+            }
+            None => None,
+        };
+        let rate_limiter = config
+            .rpc
+            .rate_limit
+            .as_ref()
+            .map(|rate_limit| Arc::new(RateLimiter::new(RateLimitConfig::from(rate_limit))));
+
+        if let Some(auth) = &auth {
+            rpc_server = rpc_server.auth(auth.clone());
+        }
+
+        if let Some(limiter) = &rate_limiter {
+            rpc_server = rpc_server.rate_limiter(limiter.clone());
+        }
+
+        if let Some(tls) = &config.rpc.tls {
+            rpc_server = rpc_server.tls(tls.clone());
+        }
+
         // --- build feeder gateway server (optional)
 
         let gateway_server = if let Some(gw_config) = &config.gateway {
@@ -462,6 +610,24 @@ impl Node {
                 server = server.timeout(timeout);
             }
 
+            if let Some(tls) = &gw_config.tls {
+                server = server.tls(tls.clone());
+            }
+
+            // Serve the upstream signatures that were verified while syncing.
+            if let Some(public_key) = config.sync.sequencer_public_key {
+                server = server.public_key(public_key);
+            }
+
+            // Transactions submitted through the gateway are subject to the RPC access control.
+            if let Some(auth) = auth {
+                server = server.auth(auth);
+            }
+
+            if let Some(limiter) = rate_limiter {
+                server = server.rate_limiter(limiter);
+            }
+
             Some(server)
         } else {
             None
@@ -490,9 +656,10 @@ impl Node {
             rpc_server,
             gateway_server,
             task_manager,
-            gateway_client,
+            upstream,
             metrics_server,
             chain_tip_watcher,
+            pipeline_status,
             config: Arc::new(config),
         })
     }
@@ -523,7 +690,7 @@ impl Node {
             .build_task()
             .graceful_shutdown()
             .name("Pipeline")
-            .spawn(self.pipeline.into_future());
+            .spawn(self.pipeline_status.track(self.pipeline.into_future()));
 
         // -- start chain tip watcher task
 
@@ -2,18 +2,18 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{anyhow, Result};
-use katana_gateway_client::Client;
-use katana_gateway_types::{ConfirmedTransaction, ErrorCode, PreConfirmedBlock, StateDiff};
+use katana_gateway_types::{ConfirmedTransaction, PreConfirmedBlock};
 use katana_pipeline::PipelineBlockSubscription;
 use katana_primitives::block::BlockNumber;
 use katana_primitives::state::StateUpdates;
 use katana_provider::api::state::StateFactoryProvider;
 use katana_provider::{DbProviderFactory, ProviderFactory};
 use parking_lot::Mutex;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::pending::state::PreconfStateProvider;
 use crate::tip_watcher::TipSubscription;
+use crate::upstream::{Error as UpstreamError, Upstream};
 
 mod provider;
 pub mod state;
@@ -22,7 +22,7 @@ pub mod state;
 pub struct PreconfStateFactory {
     // from pipeline
     latest_synced_block: PipelineBlockSubscription,
-    gateway_client: Client,
+    upstream: Upstream,
     storage_provider: DbProviderFactory,
 
     // shared state
@@ -32,7 +32,7 @@ pub struct PreconfStateFactory {
 impl PreconfStateFactory {
     pub fn new(
         storage_provider: DbProviderFactory,
-        gateway_client: Client,
+        upstream: Upstream,
         latest_synced_block: PipelineBlockSubscription,
         tip_subscription: TipSubscription,
     ) -> Self {
@@ -41,9 +41,10 @@ impl PreconfStateFactory {
         let mut worker = PreconfBlockWatcher {
             interval: DEFAULT_INTERVAL,
             latest_block: tip_subscription,
-            gateway_client: gateway_client.clone(),
+            upstream: upstream.clone(),
             latest_synced_block: latest_synced_block.clone(),
             shared_preconf_block: shared_preconf_block.clone(),
+            missing_state_diffs_reported: false,
         };
 
         tokio::spawn(async move {
@@ -52,7 +53,7 @@ impl PreconfStateFactory {
             }
         });
 
-        Self { gateway_client, latest_synced_block, shared_preconf_block, storage_provider }
+        Self { upstream, latest_synced_block, shared_preconf_block, storage_provider }
     }
 
     pub fn state(&self) -> PreconfStateProvider {
@@ -68,7 +69,7 @@ impl PreconfStateFactory {
             base,
             preconf_block_id,
             preconf_state_updates,
-            gateway: self.gateway_client.clone(),
+            upstream: self.upstream.clone(),
         }
     }
 
@@ -114,7 +115,7 @@ const DEFAULT_INTERVAL: Duration = Duration::from_millis(1000);
 #[derive(Debug)]
 struct PreconfBlockWatcher {
     interval: Duration,
-    gateway_client: Client,
+    upstream: Upstream,
 
     // from pipeline
     latest_synced_block: PipelineBlockSubscription,
@@ -123,6 +124,10 @@ struct PreconfBlockWatcher {
 
     // shared state
     shared_preconf_block: SharedPreconfBlockData,
+
+    /// Whether the upstream has been reported to not serve the pre-confirmed state diffs, to only
+    /// warn about it once.
+    missing_state_diffs_reported: bool,
 }
 
 impl PreconfBlockWatcher {
@@ -132,44 +137,7 @@ impl PreconfBlockWatcher {
 
         loop {
             if current_preconf_block_num >= self.latest_block.tip() {
-                match self.gateway_client.get_preconfirmed_block(current_preconf_block_num).await {
-                    Ok(preconf_block) => {
-                        let preconf_state_diff: StateUpdates = preconf_block
-                            .transaction_state_diffs
-                            .clone()
-                            .into_iter()
-                            .fold(StateDiff::default(), |acc, diff| {
-                                if let Some(diff) = diff {
-                                    acc.merge(diff)
-                                } else {
-                                    acc
-                                }
-                            })
-                            .into();
-
-                        // update shared state
-                        let mut shared_data_lock = self.shared_preconf_block.inner.lock();
-                        if let Some(block) = shared_data_lock.as_mut() {
-                            block.preconf_block = preconf_block;
-                            block.preconf_block_id = current_preconf_block_num;
-                            block.preconf_state_updates = preconf_state_diff;
-                        } else {
-                            *shared_data_lock = Some(PreconfBlockData {
-                                preconf_block,
-                                preconf_state_updates: preconf_state_diff,
-                                preconf_block_id: current_preconf_block_num,
-                            })
-                        }
-                    }
-
-                    // this could either be because the latest block is still not synced to the
-                    // chain's tip, in which case we just skip to the next
-                    // iteration.
-                    Err(katana_gateway_client::Error::Sequencer(error))
-                        if error.code == ErrorCode::BlockNotFound => {}
-
-                    Err(err) => return Err(anyhow!(err)),
-                }
+                self.update_preconf_block(current_preconf_block_num).await?;
             } else {
                 if let Err(err) = self.latest_synced_block.changed().await {
                     return Err(anyhow!(err));
@@ -187,4 +155,125 @@ impl PreconfBlockWatcher {
             tokio::time::sleep(self.interval).await
         }
     }
+
+    /// Fetches the pre-confirmed block `block_number` from the upstream and updates the shared
+    /// state with it.
+    async fn update_preconf_block(&mut self, block_number: BlockNumber) -> Result<()> {
+        match self.upstream.preconfirmed_block(block_number).await {
+            Ok(Some((preconf_block, preconf_state_diff))) => {
+                // update shared state
+                let mut shared_data_lock = self.shared_preconf_block.inner.lock();
+                if let Some(block) = shared_data_lock.as_mut() {
+                    block.preconf_block = preconf_block;
+                    block.preconf_block_id = block_number;
+                    block.preconf_state_updates = preconf_state_diff;
+                } else {
+                    *shared_data_lock = Some(PreconfBlockData {
+                        preconf_block,
+                        preconf_state_updates: preconf_state_diff,
+                        preconf_block_id: block_number,
+                    })
+                }
+            }
+
+            // this could either be because the latest block is still not synced to the
+            // chain's tip, in which case we just skip to the next
+            // iteration.
+            Ok(None) => {}
+
+            // The upstream can't be followed beyond its confirmed blocks, which are still synced.
+            // So the pre-confirmed block is treated as not existing yet rather than giving up on
+            // it altogether.
+            Err(UpstreamError::MissingStateDiffs) => {
+                if !self.missing_state_diffs_reported {
+                    self.missing_state_diffs_reported = true;
+                    warn!(block = %block_number, "{}", UpstreamError::MissingStateDiffs);
+                }
+
+                *self.shared_preconf_block.inner.lock() = None;
+            }
+
+            Err(err) => return Err(anyhow!(err)),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_gateway_client::Client as SequencerGateway;
+    use katana_pipeline::Pipeline;
+    use katana_primitives::da::DataAvailabilityMode;
+    use katana_primitives::fee::{
+        AllResourceBoundsMapping, ResourceBounds, ResourceBoundsMapping, Tip,
+    };
+    use katana_primitives::Felt;
+    use katana_rpc_types::BroadcastedInvokeTx;
+    use katana_utils::{TestNode, TxWaiter};
+    use url::Url;
+
+    use super::*;
+    use crate::config::gateway::GatewayConfig;
+    use crate::tip_watcher::ChainTipWatcher;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn gateway_upstream_without_preconfirmed_state_diffs() {
+        let mut config = katana_utils::node::test_config();
+        config.dev.account_validation = false;
+        config.sequencing.no_mining = true;
+        config.gateway = Some(GatewayConfig { port: 0, ..Default::default() });
+
+        let node = TestNode::new_with_config(config).await;
+        let client = node.starknet_rpc_client();
+
+        let (sender, _) = node.backend().chain_spec.genesis().accounts().next().unwrap();
+        let bounds = ResourceBounds { max_amount: 10_000_000_000, max_price_per_unit: 0 };
+
+        let tx = BroadcastedInvokeTx {
+            sender_address: *sender,
+            calldata: vec![Felt::ZERO],
+            signature: Vec::new(),
+            nonce: Felt::ZERO,
+            paymaster_data: Vec::new(),
+            tip: Tip::new(0),
+            account_deployment_data: Vec::new(),
+            resource_bounds: ResourceBoundsMapping::All(AllResourceBoundsMapping {
+                l1_gas: bounds,
+                l2_gas: bounds,
+                l1_data_gas: bounds,
+            }),
+            fee_data_availability_mode: DataAvailabilityMode::L1,
+            nonce_data_availability_mode: DataAvailabilityMode::L1,
+            is_query: false,
+        };
+
+        let hash = client.add_invoke_transaction(tx).await.unwrap().transaction_hash;
+        TxWaiter::new(hash, &client).await.unwrap();
+
+        // Katana's gateway serves the pre-confirmed block without the transactions' state diffs.
+        let addr = node.handle().gateway().expect("gateway must be enabled").addr();
+        let upstream = Upstream::Gateway(SequencerGateway::new(
+            Url::parse(&format!("http://{addr}/gateway")).unwrap(),
+            Url::parse(&format!("http://{addr}/feeder_gateway")).unwrap(),
+        ));
+
+        let (_, pipeline) = Pipeline::new(DbProviderFactory::new_in_memory(), 10);
+        let mut watcher = PreconfBlockWatcher {
+            interval: DEFAULT_INTERVAL,
+            latest_block: ChainTipWatcher::new(upstream.clone()).subscribe(),
+            upstream,
+            latest_synced_block: pipeline.subscribe_blocks(),
+            shared_preconf_block: SharedPreconfBlockData::default(),
+            missing_state_diffs_reported: false,
+        };
+
+        // The watcher must keep going, as if there was no pre-confirmed block.
+        watcher.update_preconf_block(1).await.unwrap();
+        assert!(watcher.missing_state_diffs_reported);
+        assert!(watcher.shared_preconf_block.inner.lock().is_none());
+
+        watcher.update_preconf_block(1).await.unwrap();
+        assert!(watcher.shared_preconf_block.inner.lock().is_none());
+    }
 }
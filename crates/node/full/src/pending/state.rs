@@ -1,6 +1,5 @@
 use std::fmt::Debug;
 
-use katana_gateway_types::{ErrorCode, GatewayError};
 use katana_primitives::block::BlockNumber;
 use katana_primitives::class::{ClassHash, CompiledClassHash, ContractClass};
 use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
@@ -8,15 +7,16 @@ use katana_primitives::state::StateUpdates;
 use katana_provider::api::contract::ContractClassProvider;
 use katana_provider::api::state::{StateProofProvider, StateProvider, StateRootProvider};
 use katana_provider::{ProviderError, ProviderResult};
-use katana_rpc_types::ConversionError;
 use tokio::runtime;
 
+use crate::upstream::Upstream;
+
 #[allow(unused)]
 pub struct PreconfStateProvider {
     pub base: Box<dyn StateProvider>,
     pub preconf_block_id: Option<BlockNumber>,
     pub preconf_state_updates: Option<StateUpdates>,
-    pub gateway: katana_gateway_client::Client,
+    pub upstream: Upstream,
 }
 
 impl StateProvider for PreconfStateProvider {
@@ -83,28 +83,9 @@ impl ContractClassProvider for PreconfStateProvider {
         let result = runtime::Builder::new_current_thread()
             .build()
             .unwrap()
-            .block_on(self.gateway.get_class(hash, katana_gateway_types::BlockId::Pending));
-
-        match result {
-            Ok(class) => {
-                let class = class
-                    .try_into()
-                    .map_err(|e: ConversionError| ProviderError::Other(e.to_string()))?;
-                Ok(Some(class))
-            }
+            .block_on(self.upstream.preconfirmed_class(hash));
 
-            Err(error) => {
-                if let katana_gateway_client::Error::Sequencer(GatewayError {
-                    code: ErrorCode::UndeclaredClass,
-                    ..
-                }) = error
-                {
-                    Ok(None)
-                } else {
-                    Err(ProviderError::Other(error.to_string()))
-                }
-            }
-        }
+        result.map_err(|error| ProviderError::Other(error.to_string()))
     }
 
     fn compiled_class_hash_of_class_hash(
@@ -132,7 +113,7 @@ impl Debug for PreconfStateProvider {
         f.debug_struct("PreconfStateProvider")
             .field("preconf_block_id", &self.preconf_block_id)
             .field("preconf_state_updates", &self.preconf_state_updates)
-            .field("gateway", &self.gateway)
+            .field("upstream", &self.upstream)
             .finish_non_exhaustive()
     }
 }
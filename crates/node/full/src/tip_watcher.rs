@@ -130,6 +130,25 @@ impl ChainTipProvider for katana_gateway_client::Client {
     }
 }
 
+/// Implementation of [`ChainTipProvider`] for a JSON-RPC node.
+///
+/// This fetches the latest block number known by the node.
+impl ChainTipProvider for katana_starknet::rpc::StarknetRpcClient {
+    fn latest_number(&self) -> BoxFuture<'_, Result<BlockNumber>> {
+        Box::pin(async move {
+            let response = (|| async { self.block_number().await })
+                .retry(ExponentialBuilder::default())
+                .when(|error| error.is_retryable())
+                .notify(|error, dur| {
+                    warn!(%error, "Failed to fetch latest block, retrying in {}s...", dur.as_secs());
+                })
+                .await?;
+
+            Ok(response.block_number)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::{AtomicU64, Ordering};
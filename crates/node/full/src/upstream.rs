@@ -0,0 +1,265 @@
+use futures::future::BoxFuture;
+use katana_gateway_client::Client as SequencerGateway;
+use katana_gateway_types::{ErrorCode, GatewayError, PreConfirmedBlock, StateDiff};
+use katana_primitives::block::{BlockIdOrTag, BlockNumber};
+use katana_primitives::class::{ClassHash, ContractClass};
+use katana_primitives::state::StateUpdates;
+use katana_rpc_types::{
+    BroadcastedTx, ConversionError, GetBlockWithReceiptsResponse, GetBlockWithTxHashesResponse,
+    StateUpdate,
+};
+use katana_starknet::rpc::{StarknetApiError, StarknetRpcClient, StarknetRpcClientError};
+
+use crate::tip_watcher::ChainTipProvider;
+
+/// The node that the full node follows.
+///
+/// Besides downloading blocks and classes, the full node relies on its upstream for tracking the
+/// chain tip, following the pre-confirmed block, and forwarding the transactions it receives.
+#[derive(Debug, Clone)]
+pub enum Upstream {
+    /// A feeder gateway, eg the Starknet sequencer or a Katana sequencer with its gateway enabled.
+    ///
+    /// The pre-confirmed block can only be followed if the gateway serves the state diffs of the
+    /// pre-confirmed transactions, which Katana's gateway doesn't.
+    Gateway(SequencerGateway),
+    /// A JSON-RPC node, eg a Katana sequencer.
+    JsonRpc(StarknetRpcClient),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Gateway(#[from] katana_gateway_client::Error),
+
+    #[error(transparent)]
+    JsonRpc(#[from] StarknetRpcClientError),
+
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+
+    #[error(
+        "the upstream gateway doesn't serve the state diffs of pre-confirmed transactions; follow \
+         it through its JSON-RPC endpoint instead"
+    )]
+    MissingStateDiffs,
+}
+
+impl Upstream {
+    /// Returns the pre-confirmed block with the given number along with its aggregated state
+    /// diff.
+    ///
+    /// Returns `None` if the upstream isn't currently building a block with that number.
+    pub async fn preconfirmed_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<(PreConfirmedBlock, StateUpdates)>, Error> {
+        match self {
+            Self::Gateway(client) => match client.get_preconfirmed_block(block_number).await {
+                Ok(block) => {
+                    // Every executed transaction must come with its state diff, otherwise the
+                    // aggregated diff would silently miss some of the pre-confirmed state.
+                    let is_missing_diffs = block
+                        .transaction_receipts
+                        .iter()
+                        .zip(&block.transaction_state_diffs)
+                        .any(|(receipt, diff)| receipt.is_some() && diff.is_none());
+
+                    if is_missing_diffs {
+                        return Err(Error::MissingStateDiffs);
+                    }
+
+                    let state_updates: StateUpdates = block
+                        .transaction_state_diffs
+                        .clone()
+                        .into_iter()
+                        .fold(StateDiff::default(), |acc, diff| {
+                            if let Some(diff) = diff {
+                                acc.merge(diff)
+                            } else {
+                                acc
+                            }
+                        })
+                        .into();
+
+                    Ok(Some((block, state_updates)))
+                }
+
+                Err(katana_gateway_client::Error::Sequencer(error))
+                    if error.code == ErrorCode::BlockNotFound =>
+                {
+                    Ok(None)
+                }
+
+                Err(error) => Err(error.into()),
+            },
+
+            Self::JsonRpc(client) => {
+                let block = match client.get_block_with_receipts(BlockIdOrTag::PreConfirmed).await?
+                {
+                    GetBlockWithReceiptsResponse::PreConfirmed(block)
+                        if block.block_number == block_number =>
+                    {
+                        block
+                    }
+                    _ => return Ok(None),
+                };
+
+                let state_updates =
+                    match client.get_state_update(BlockIdOrTag::PreConfirmed).await? {
+                        StateUpdate::PreConfirmed(update) => update.state_diff.into(),
+                        StateUpdate::Confirmed(..) => return Ok(None),
+                    };
+
+                // The block and its state update are fetched separately, so the upstream may have
+                // closed the block or added transactions to it in between. Treat the block as not
+                // yet available unless it's still the same, and retry on the next poll.
+                match client.get_block_with_tx_hashes(BlockIdOrTag::PreConfirmed).await? {
+                    GetBlockWithTxHashesResponse::PreConfirmed(latest)
+                        if latest.block_number == block.block_number
+                            && latest.transactions.len() == block.transactions.len() => {}
+                    _ => return Ok(None),
+                }
+
+                Ok(Some((block.into(), state_updates)))
+            }
+        }
+    }
+
+    /// Returns the class with the given hash as seen by the pre-confirmed block.
+    ///
+    /// Returns `None` if the class hasn't been declared.
+    pub async fn preconfirmed_class(
+        &self,
+        hash: ClassHash,
+    ) -> Result<Option<ContractClass>, Error> {
+        match self {
+            Self::Gateway(client) => {
+                match client.get_class(hash, katana_gateway_types::BlockId::Pending).await {
+                    Ok(class) => Ok(Some(class.try_into()?)),
+
+                    Err(katana_gateway_client::Error::Sequencer(GatewayError {
+                        code: ErrorCode::UndeclaredClass,
+                        ..
+                    })) => Ok(None),
+
+                    Err(error) => Err(error.into()),
+                }
+            }
+
+            Self::JsonRpc(client) => match client.get_class(BlockIdOrTag::PreConfirmed, hash).await
+            {
+                Ok(class) => Ok(Some(class.try_into()?)),
+
+                Err(StarknetRpcClientError::Starknet(StarknetApiError::ClassHashNotFound)) => {
+                    Ok(None)
+                }
+
+                Err(error) => Err(error.into()),
+            },
+        }
+    }
+
+    /// Submits a transaction to the upstream.
+    pub async fn add_transaction(&self, tx: BroadcastedTx) -> Result<(), Error> {
+        match self {
+            Self::Gateway(client) => match tx {
+                BroadcastedTx::Invoke(tx) => {
+                    client.add_invoke_transaction(tx.into()).await?;
+                }
+                BroadcastedTx::Declare(tx) => {
+                    client.add_declare_transaction(tx.into()).await?;
+                }
+                BroadcastedTx::DeployAccount(tx) => {
+                    client.add_deploy_account_transaction(tx.into()).await?;
+                }
+            },
+
+            Self::JsonRpc(client) => match tx {
+                BroadcastedTx::Invoke(tx) => {
+                    client.add_invoke_transaction(tx).await?;
+                }
+                BroadcastedTx::Declare(tx) => {
+                    client.add_declare_transaction(tx).await?;
+                }
+                BroadcastedTx::DeployAccount(tx) => {
+                    client.add_deploy_account_transaction(tx).await?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl ChainTipProvider for Upstream {
+    fn latest_number(&self) -> BoxFuture<'_, anyhow::Result<BlockNumber>> {
+        match self {
+            Self::Gateway(client) => client.latest_number(),
+            Self::JsonRpc(client) => client.latest_number(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::da::DataAvailabilityMode;
+    use katana_primitives::fee::{
+        AllResourceBoundsMapping, ResourceBounds, ResourceBoundsMapping, Tip,
+    };
+    use katana_primitives::Felt;
+    use katana_rpc_types::BroadcastedInvokeTx;
+    use katana_utils::{TestNode, TxWaiter};
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn json_rpc_upstream() {
+        let mut config = katana_utils::node::test_config();
+        config.dev.account_validation = false;
+        config.sequencing.no_mining = true;
+
+        let node = TestNode::new_with_config(config).await;
+        let client = node.starknet_rpc_client();
+        let upstream = Upstream::JsonRpc(node.starknet_rpc_client());
+
+        assert_eq!(upstream.latest_number().await.unwrap(), 0);
+
+        let (sender, _) = node.backend().chain_spec.genesis().accounts().next().unwrap();
+        let bounds = ResourceBounds { max_amount: 10_000_000_000, max_price_per_unit: 0 };
+
+        let tx = BroadcastedInvokeTx {
+            sender_address: *sender,
+            calldata: vec![Felt::ZERO],
+            signature: Vec::new(),
+            nonce: Felt::ZERO,
+            paymaster_data: Vec::new(),
+            tip: Tip::new(0),
+            account_deployment_data: Vec::new(),
+            resource_bounds: ResourceBoundsMapping::All(AllResourceBoundsMapping {
+                l1_gas: bounds,
+                l2_gas: bounds,
+                l1_data_gas: bounds,
+            }),
+            fee_data_availability_mode: DataAvailabilityMode::L1,
+            nonce_data_availability_mode: DataAvailabilityMode::L1,
+            is_query: false,
+        };
+
+        let hash = client.add_invoke_transaction(tx).await.unwrap().transaction_hash;
+        TxWaiter::new(hash, &client).await.unwrap();
+
+        // The pre-confirmed transaction is followed along with the state it changed.
+        let (block, state_updates) = upstream.preconfirmed_block(1).await.unwrap().unwrap();
+        let hashes = block.transactions.iter().map(|tx| tx.transaction_hash).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![hash]);
+        assert_eq!(state_updates.nonce_updates.get(sender), Some(&Felt::ONE));
+
+        // Only the block that is currently being built is pre-confirmed.
+        assert!(upstream.preconfirmed_block(2).await.unwrap().is_none());
+        assert_eq!(upstream.latest_number().await.unwrap(), 0);
+
+        let class = upstream.preconfirmed_class(Felt::from(0x1337u64)).await.unwrap();
+        assert!(class.is_none());
+    }
+}
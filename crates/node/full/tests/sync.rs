@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+
+use katana_chain_spec::{rollup, ChainSpec, FeeContracts, SettlementLayer};
+use katana_full_node::config::db::DbConfig;
+use katana_full_node::config::gateway::GatewayConfig;
+use katana_full_node::config::rpc::RpcConfig;
+use katana_full_node::config::tls::{ClientIdentity, ClientTlsConfig, TlsConfig};
+use katana_full_node::{Config, Node, SyncConfig, SyncSource, SyncStagesList};
+use katana_primitives::chain::ChainId;
+use katana_primitives::{felt, Felt};
+use katana_provider::api::block::{BlockNumberProvider, BlockSignatureProvider};
+use katana_provider::ProviderFactory;
+use katana_utils::TestNode;
+use starknet::signers::SigningKey;
+use url::Url;
+
+const BLOCK_SIGNING_KEY: Felt = felt!("0x1");
+
+/// A rollup chain spec built from the genesis of the default test node.
+fn rollup_chain_spec() -> rollup::ChainSpec {
+    let config = katana_utils::node::test_config();
+    let ChainSpec::Dev(chain) = config.chain.as_ref() else { unreachable!() };
+
+    // The classes are declared by the genesis transactions of rollup chains instead.
+    let mut genesis = chain.genesis.clone();
+    genesis.classes = Default::default();
+
+    rollup::ChainSpec {
+        id: ChainId::parse("KATANA").unwrap(),
+        genesis,
+        settlement: SettlementLayer::Sovereign {},
+        fee_contracts: FeeContracts::default(),
+    }
+}
+
+fn tls_test_data(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../rpc/rpc-server/tests/test_data/tls")
+        .join(name)
+}
+
+/// The config of a full node syncing the blocks of `chain` from `source`.
+fn full_node_config(chain: rollup::ChainSpec, source: SyncSource, db_dir: &Path) -> Config {
+    Config {
+        db: DbConfig { dir: Some(db_dir.to_path_buf()), migrate: false },
+        rpc: RpcConfig { port: 0, ..Default::default() },
+        pruning: Default::default(),
+        metrics: None,
+        gateway_api_key: None,
+        network: Default::default(),
+        chain: Some(chain),
+        gateway: None,
+        sync: SyncConfig {
+            source: Some(source),
+            stages: SyncStagesList::parse("blocks").unwrap(),
+            ..Default::default()
+        },
+        build_info: Default::default(),
+    }
+}
+
+/// Syncs a full node from the genesis block of a sequencer signing its blocks, verifying every
+/// block signature against the sequencer's public key.
+#[tokio::test(flavor = "multi_thread")]
+async fn sync_signed_blocks_from_genesis() {
+    let chain = rollup_chain_spec();
+
+    let mut config = katana_utils::node::test_config();
+    config.chain = ChainSpec::Rollup(chain.clone()).into();
+    config.gateway = Some(GatewayConfig { port: 0, ..Default::default() });
+    config.sequencing.block_signing_key = Some(BLOCK_SIGNING_KEY);
+
+    let sequencer = TestNode::new_with_config(config).await;
+    let gateway_addr = sequencer.handle().gateway().expect("gateway must be enabled").addr();
+
+    let db_dir = tempfile::tempdir().unwrap();
+    let public_key = SigningKey::from_secret_scalar(BLOCK_SIGNING_KEY).verifying_key().scalar();
+
+    let source = SyncSource::Gateway(Url::parse(&format!("http://{gateway_addr}")).unwrap());
+    let mut config = full_node_config(chain, source, db_dir.path());
+    config.sync.sequencer_public_key = Some(public_key);
+
+    let mut node = Node::build(config).expect("failed to build full node");
+    node.pipeline.execute(0).await.expect("failed to sync the genesis block");
+
+    let provider = node.provider.provider();
+    assert_eq!(provider.latest_number().unwrap(), 0);
+
+    // The verified genesis signature must be the one of the sequencer.
+    let expected = sequencer.backend().storage.provider().block_signature(0.into()).unwrap();
+    assert!(expected.is_some());
+    assert_eq!(provider.block_signature(0.into()).unwrap(), expected);
+}
+
+/// Syncs a full node from a sequencer gateway requiring mutual TLS.
+#[tokio::test(flavor = "multi_thread")]
+async fn sync_from_gateway_over_mutual_tls() {
+    let chain = rollup_chain_spec();
+
+    let mut config = katana_utils::node::test_config();
+    config.chain = ChainSpec::Rollup(chain.clone()).into();
+    config.gateway = Some(GatewayConfig {
+        port: 0,
+        tls: Some(TlsConfig {
+            cert: tls_test_data("server.pem"),
+            key: tls_test_data("server.key"),
+            client_ca: Some(tls_test_data("ca.pem")),
+        }),
+        ..Default::default()
+    });
+
+    let sequencer = TestNode::new_with_config(config).await;
+    let gateway_addr = sequencer.handle().gateway().expect("gateway must be enabled").addr();
+
+    let db_dir = tempfile::tempdir().unwrap();
+    let url = Url::parse(&format!("https://localhost:{}", gateway_addr.port())).unwrap();
+
+    let mut config = full_node_config(chain, SyncSource::Gateway(url), db_dir.path());
+    config.sync.tls = Some(ClientTlsConfig {
+        ca: Some(tls_test_data("ca.pem")),
+        identity: Some(ClientIdentity {
+            cert: tls_test_data("client.pem"),
+            key: tls_test_data("client.key"),
+        }),
+    });
+
+    let mut node = Node::build(config).expect("failed to build full node");
+    node.pipeline.execute(0).await.expect("failed to sync the genesis block");
+
+    assert_eq!(node.provider.provider().latest_number().unwrap(), 0);
+}
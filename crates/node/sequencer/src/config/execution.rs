@@ -13,6 +13,8 @@ pub struct ExecutionConfig {
     pub max_recursion_depth: usize,
     #[cfg(feature = "native")]
     pub compile_native: bool,
+    /// Whether to execute the transactions of a block in parallel.
+    pub parallel_execution: bool,
 }
 
 impl std::default::Default for ExecutionConfig {
@@ -23,6 +25,7 @@ impl std::default::Default for ExecutionConfig {
             validation_max_steps: DEFAULT_VALIDATION_MAX_STEPS,
             #[cfg(feature = "native")]
             compile_native: DEFAULT_ENABLE_NATIVE_COMPILATION,
+            parallel_execution: false,
         }
     }
 }
@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+
+pub use katana_fork::BackendConfig as ForkBackendConfig;
 use katana_primitives::block::BlockHashOrNumber;
 use url::Url;
 
@@ -13,4 +16,17 @@ pub struct ForkingConfig {
     /// This is typically enabled in developer mode so predeployed dev accounts are available.
     /// Disable it for strict lazy-fetch forking where local state roots must match the remote.
     pub init_dev_genesis: bool,
+    /// Whether to fork from the pre-confirmed block of the forked network.
+    ///
+    /// If enabled, the node forks at the parent of the pre-confirmed block and applies the
+    /// pre-confirmed state diff in a new local block. Cannot be used together with `block`.
+    pub pre_confirmed: bool,
+    /// The directory where the state fetched from the forked network is persisted.
+    ///
+    /// The cache is keyed by the forked network's chain id and the fork block, so that subsequent
+    /// runs forking the same network at the same block read from disk instead of the network. If
+    /// `None`, the fetched state is only kept in memory.
+    pub cache_dir: Option<PathBuf>,
+    /// How requests are sent to the forked network, ie retries, rate limiting and fallback URLs.
+    pub backend: ForkBackendConfig,
 }
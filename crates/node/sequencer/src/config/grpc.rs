@@ -3,6 +3,8 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::time::Duration;
 
+use katana_node_config::tls::TlsConfig;
+
 /// Default gRPC server listening address.
 pub const DEFAULT_GRPC_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
 
@@ -21,6 +23,8 @@ pub struct GrpcConfig {
     pub port: u16,
     /// Request timeout in seconds.
     pub timeout: Option<Duration>,
+    /// TLS termination. If `None`, the server is served over plaintext HTTP/2.
+    pub tls: Option<TlsConfig>,
 }
 
 impl GrpcConfig {
@@ -36,6 +40,7 @@ impl Default for GrpcConfig {
             addr: DEFAULT_GRPC_ADDR,
             port: DEFAULT_GRPC_PORT,
             timeout: Some(Duration::from_secs(DEFAULT_GRPC_TIMEOUT_SECS)),
+            tls: None,
         }
     }
 }
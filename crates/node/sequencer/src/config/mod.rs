@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 // Re-export shared config types
-pub use katana_node_config::{build_info, db, gateway, metrics, rpc};
+pub use katana_node_config::{build_info, db, gateway, metrics, rpc, tls};
 
 // Sequencer-specific config modules
 pub mod dev;
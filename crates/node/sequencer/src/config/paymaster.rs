@@ -31,15 +31,38 @@ pub struct CartridgeApiConfig {
     pub vrf: Option<VrfConfig>,
 }
 
-/// Configuration for connecting to a VRF service.
+/// Configuration for the VRF service.
 ///
-/// The node treats the VRF as an external service - it simply connects to the
-/// provided URL. Whether the service is managed externally or as a sidecar process
-/// is not a concern of the [`Node`](crate::Node).
+/// The node either connects to a VRF server at the provided URL, or generates the VRF proofs
+/// itself. Whether a remote VRF server is managed externally or as a sidecar process is not a
+/// concern of the [`Node`](crate::Node).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VrfConfig {
-    /// The VRF service URL.
-    pub url: Url,
+    /// Where the VRF proofs are generated.
+    pub provider: VrfProviderConfig,
     /// The address of the VRF account contract.
     pub vrf_account: ContractAddress,
 }
+
+impl VrfConfig {
+    /// Returns the VRF service URL, if the VRF proofs are not generated in-process.
+    pub fn url(&self) -> Option<&Url> {
+        match &self.provider {
+            VrfProviderConfig::Remote(url) => Some(url),
+            VrfProviderConfig::InProcess { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VrfProviderConfig {
+    /// Connects to the VRF service at the given URL.
+    Remote(Url),
+    /// Generates the VRF proofs natively within the node process.
+    InProcess {
+        /// The private key of the VRF account. (used for signing the VRF outside executions)
+        vrf_account_private_key: Felt,
+        /// The VRF secret key.
+        secret_key: u64,
+    },
+}
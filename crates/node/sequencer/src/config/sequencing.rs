@@ -1,7 +1,10 @@
 use katana_executor::BlockLimits;
+use katana_gas_price_oracle::{DynamicGasPriceConfig, SampledPriceOracleConfig};
+use katana_primitives::Felt;
+use url::Url;
 
 /// Configurations related to block production.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Clone, Default, PartialEq, Eq)]
 pub struct SequencingConfig {
     /// The time in milliseconds for a block to be produced.
     pub block_time: Option<u64>,
@@ -21,16 +24,77 @@ pub struct SequencingConfig {
     /// See <https://docs.starknet.io/chain-info/#current_limits>.
     pub block_cairo_steps_limit: Option<u64>,
 
+    /// The maximum amount of L2 gas in a block.
+    pub block_l2_gas_limit: Option<u64>,
+
+    /// The maximum number of transactions in a block.
+    pub block_txs_limit: Option<u64>,
+
+    /// The maximum size of the state diff of a block, in number of felts.
+    pub block_state_diff_size_limit: Option<u64>,
+
+    /// The maximum amount of L1 data gas in a block.
+    pub block_l1_data_gas_limit: Option<u64>,
+
+    /// The maximum number of events emitted in a block.
+    pub block_events_limit: Option<u64>,
+
     /// Disable state trie computation during block production.
     ///
     /// When true, block headers carry `state_root = Felt::ZERO` and no
     /// class/contract/storage trie writes are performed. Block hashes
     /// will differ from a normal run and storage proofs become unavailable.
     pub no_state_trie: bool,
+
+    /// Private key used to sign every produced block.
+    ///
+    /// When set, the sequencer signs the hash and state diff commitment of each block it commits.
+    /// Blocks are left unsigned otherwise.
+    pub block_signing_key: Option<Felt>,
+
+    /// Adjust the L2 gas prices of every block based on the gas used by the previous block.
+    ///
+    /// When not set, the L2 gas prices are the ones of the configured gas price oracle.
+    pub dynamic_l2_gas_price: Option<DynamicGasPriceConfig>,
+
+    /// How the gas prices are sampled from the settlement layer.
+    pub gas_price_sampling: SampledPriceOracleConfig,
+
+    /// RPC URLs of the settlement layer to sample the gas prices from when the settlement layer's
+    /// own RPC URL fails, in order of priority.
+    pub gas_price_fallback_urls: Vec<Url>,
+}
+
+impl std::fmt::Debug for SequencingConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SequencingConfig")
+            .field("block_time", &self.block_time)
+            .field("no_mining", &self.no_mining)
+            .field("block_cairo_steps_limit", &self.block_cairo_steps_limit)
+            .field("block_l2_gas_limit", &self.block_l2_gas_limit)
+            .field("block_txs_limit", &self.block_txs_limit)
+            .field("block_state_diff_size_limit", &self.block_state_diff_size_limit)
+            .field("block_l1_data_gas_limit", &self.block_l1_data_gas_limit)
+            .field("block_events_limit", &self.block_events_limit)
+            .field("no_state_trie", &self.no_state_trie)
+            // never print the private key
+            .field("block_signing_key", &self.block_signing_key.map(|_| "<redacted>"))
+            .field("dynamic_l2_gas_price", &self.dynamic_l2_gas_price)
+            .field("gas_price_sampling", &self.gas_price_sampling)
+            .field("gas_price_fallback_urls", &self.gas_price_fallback_urls)
+            .finish()
+    }
 }
 
 impl SequencingConfig {
     pub fn block_limits(&self) -> BlockLimits {
-        BlockLimits { cairo_steps: self.block_cairo_steps_limit.unwrap_or(50_000_000) }
+        BlockLimits {
+            cairo_steps: self.block_cairo_steps_limit.unwrap_or(50_000_000),
+            l2_gas: self.block_l2_gas_limit,
+            transactions: self.block_txs_limit,
+            state_diff_size: self.block_state_diff_size_limit,
+            l1_data_gas: self.block_l1_data_gas_limit,
+            events: self.block_events_limit,
+        }
     }
 }
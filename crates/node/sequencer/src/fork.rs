@@ -0,0 +1,420 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use futures::lock::Mutex;
+use jsonrpsee::core::async_trait;
+use katana_core::backend::Backend;
+use katana_core::service::block_producer::BlockProducer;
+use katana_pool::api::TransactionPool;
+use katana_pool::TxPool;
+use katana_primitives::block::{BlockIdOrTag, BlockNumber, ConfirmedBlockIdOrTag, GasPrices};
+use katana_primitives::cairo::ShortString;
+use katana_primitives::class::ContractClass;
+use katana_primitives::env::BlockEnv;
+use katana_primitives::receipt::Receipt;
+use katana_primitives::state::StateUpdatesWithClasses;
+use katana_primitives::transaction::TxWithHash;
+use katana_primitives::version::{StarknetVersion, CURRENT_STARKNET_VERSION};
+use katana_primitives::Felt;
+use katana_provider::providers::fork::ForkStateCache;
+use katana_provider::{ForkProviderFactory, ProviderFactory, ProviderRO, ProviderRW};
+use katana_rpc_server::dev::ForkResetter;
+use katana_rpc_server::starknet::RpcCache;
+use katana_rpc_types::block::{
+    BlockWithTxHashes, PreConfirmedBlockWithReceipts, PreConfirmedBlockWithTxHashes,
+    RpcTxWithReceipt,
+};
+use katana_rpc_types::state_update::StateUpdate;
+use katana_rpc_types::{GetBlockWithReceiptsResponse, GetBlockWithTxHashesResponse, RpcTxWithHash};
+use katana_starknet::rpc::StarknetRpcClient as StarknetClient;
+use num_traits::ToPrimitive;
+use tracing::info;
+use url::Url;
+
+/// How long to wait for the block producer to finish its ongoing work before re-forking.
+const BLOCK_PRODUCER_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How many times to try fetching a consistent view of the pre-confirmed block.
+const PRE_CONFIRMED_FETCH_ATTEMPTS: usize = 3;
+
+/// The block of the forked network a chain is forked at.
+#[derive(Debug)]
+pub(crate) struct ForkPoint {
+    pub(crate) chain_id: Felt,
+    pub(crate) block: BlockWithTxHashes,
+    /// The pre-confirmed block built on top of `block`, if forking from the pre-confirmed state.
+    pub(crate) pre_confirmed: Option<PreConfirmedState>,
+}
+
+/// The pre-confirmed block of the forked network and the state changes made by its transactions.
+#[derive(Debug)]
+pub(crate) struct PreConfirmedState {
+    pub(crate) block: PreConfirmedBlockWithReceipts,
+    pub(crate) states: StateUpdatesWithClasses,
+}
+
+impl ForkPoint {
+    /// Fetches the fork point from the network `client` is connected to.
+    ///
+    /// If `block` is `None`, the latest block of the network is used. If it is
+    /// [`BlockIdOrTag::PreConfirmed`], the chain is forked at the parent of the pre-confirmed block
+    /// and the pre-confirmed state is fetched along with it.
+    pub(crate) async fn fetch(
+        client: &StarknetClient,
+        block: Option<BlockIdOrTag>,
+    ) -> Result<Self> {
+        let chain_id = client.chain_id().await.context("failed to fetch forked network id")?;
+
+        // If the fork block is not specified, we use the latest accepted block on the forked
+        // network.
+        let mut pre_confirmed = None;
+        let block_id = match block {
+            Some(BlockIdOrTag::PreConfirmed) => {
+                let state = PreConfirmedState::fetch(client).await?;
+                let parent = state
+                    .block
+                    .block_number
+                    .checked_sub(1)
+                    .context("the pre-confirmed block of the forked network has no parent")?;
+
+                pre_confirmed = Some(state);
+                BlockIdOrTag::Number(parent)
+            }
+
+            Some(id) => id,
+
+            None => {
+                let res = client.block_number().await?;
+                BlockIdOrTag::Number(res.block_number)
+            }
+        };
+
+        // if the id is not in ASCII encoding, we display the chain id as is in hex.
+        match ShortString::try_from(chain_id) {
+            Ok(id) => {
+                info!(chain = %id, block = ?block_id, "Forking chain.");
+            }
+
+            Err(_) => {
+                let id = format!("{chain_id:#x}");
+                info!(chain = %id, block = ?block_id, "Forking chain.");
+            }
+        };
+
+        let block = client
+            .get_block_with_tx_hashes(block_id)
+            .await
+            .context("failed to fetch forked block")?;
+
+        let GetBlockWithTxHashesResponse::Block(block) = block else {
+            bail!("forking a pending block is not allowed")
+        };
+
+        Ok(Self { chain_id, block, pre_confirmed })
+    }
+
+    /// Adjusts the genesis of `chain_spec` so that the chain continues from the fork point.
+    pub(crate) fn apply(&self, chain_spec: &mut katana_chain_spec::dev::ChainSpec) {
+        let forked_block = &self.block;
+
+        chain_spec.id = self.chain_id.into();
+
+        // adjust the genesis to match the forked block
+        chain_spec.genesis.timestamp = forked_block.timestamp;
+        chain_spec.genesis.number = forked_block.block_number + 1;
+        chain_spec.genesis.state_root = Default::default();
+        chain_spec.genesis.parent_hash = forked_block.parent_hash;
+        chain_spec.genesis.sequencer_address = forked_block.sequencer_address;
+
+        // TODO: remove gas price from genesis
+        let eth_l1_gas_price =
+            forked_block.l1_gas_price.price_in_wei.to_u128().expect("should fit in u128");
+        let strk_l1_gas_price =
+            forked_block.l1_gas_price.price_in_fri.to_u128().expect("should fit in u128");
+        chain_spec.genesis.gas_prices =
+            unsafe { GasPrices::new_unchecked(eth_l1_gas_price, strk_l1_gas_price) };
+    }
+}
+
+impl PreConfirmedState {
+    async fn fetch(client: &StarknetClient) -> Result<Self> {
+        for _ in 0..PRE_CONFIRMED_FETCH_ATTEMPTS {
+            let block = client
+                .get_block_with_receipts(BlockIdOrTag::PreConfirmed)
+                .await
+                .context("failed to fetch pre-confirmed block")?;
+
+            let GetBlockWithReceiptsResponse::PreConfirmed(block) = block else {
+                bail!("forked network returned a confirmed block for the pre-confirmed block")
+            };
+
+            let state_update = client
+                .get_state_update(BlockIdOrTag::PreConfirmed)
+                .await
+                .context("failed to fetch pre-confirmed state update")?;
+
+            let StateUpdate::PreConfirmed(state_update) = state_update else {
+                bail!(
+                    "forked network returned a confirmed state update for the pre-confirmed block"
+                )
+            };
+
+            // The pre-confirmed block may have been confirmed, or have had more transactions added
+            // to it, in between the requests, in which case the state update doesn't match the
+            // transactions of the block.
+            let current = fetch_pre_confirmed_block(client).await?;
+            if current.block_number != block.block_number
+                || current.transactions.len() != block.transactions.len()
+            {
+                continue;
+            }
+
+            let mut states = StateUpdatesWithClasses {
+                state_updates: state_update.state_diff.into(),
+                ..Default::default()
+            };
+
+            let updates = &states.state_updates;
+            let declared =
+                updates.declared_classes.keys().chain(&updates.deprecated_declared_classes);
+
+            for hash in declared.copied().collect::<Vec<_>>() {
+                let class = client
+                    .get_class(BlockIdOrTag::PreConfirmed, hash)
+                    .await
+                    .with_context(|| format!("failed to fetch class {hash:#x}"))?;
+
+                states.classes.insert(hash, ContractClass::try_from(class)?);
+            }
+
+            info!(
+                block = %block.block_number,
+                transactions = %block.transactions.len(),
+                "Fetched pre-confirmed state."
+            );
+
+            return Ok(Self { block, states });
+        }
+
+        bail!("the pre-confirmed block of the forked network kept changing while fetching it")
+    }
+
+    /// Returns the transactions of the pre-confirmed block along with their receipts.
+    pub(crate) fn transactions(&self) -> Vec<(TxWithHash, Receipt)> {
+        self.block
+            .transactions
+            .iter()
+            .cloned()
+            .map(|RpcTxWithReceipt { transaction, receipt }| {
+                let hash = receipt.transaction_hash;
+                let tx = RpcTxWithHash { transaction_hash: hash, transaction };
+                (TxWithHash::from(tx), Receipt::from(receipt.receipt))
+            })
+            .collect()
+    }
+
+    /// Returns the environment of the block that carries over the pre-confirmed state into the
+    /// forked chain.
+    pub(crate) fn block_env(&self, number: BlockNumber) -> BlockEnv {
+        let block = &self.block;
+        BlockEnv {
+            number,
+            timestamp: block.timestamp,
+            l2_gas_prices: block.l2_gas_price.clone().into(),
+            l1_gas_prices: block.l1_gas_price.clone().into(),
+            l1_data_gas_prices: block.l1_data_gas_price.clone().into(),
+            sequencer_address: block.sequencer_address,
+            starknet_version: StarknetVersion::parse(&block.starknet_version)
+                .unwrap_or(CURRENT_STARKNET_VERSION),
+        }
+    }
+}
+
+async fn fetch_pre_confirmed_block(
+    client: &StarknetClient,
+) -> Result<PreConfirmedBlockWithTxHashes> {
+    let block = client
+        .get_block_with_tx_hashes(BlockIdOrTag::PreConfirmed)
+        .await
+        .context("failed to fetch pre-confirmed block")?;
+
+    match block {
+        GetBlockWithTxHashesResponse::PreConfirmed(block) => Ok(block),
+        GetBlockWithTxHashesResponse::Block(..) => {
+            bail!("forked network returned a confirmed block for the pre-confirmed block")
+        }
+    }
+}
+
+/// Re-forks the chain of a running forked node.
+///
+/// The storage is shared with the rest of the node, so re-forking it is visible to every
+/// component. The other components that hold state derived from the chain (ie the block producer,
+/// the pool and the RPC cache) are reset along with it.
+#[allow(missing_debug_implementations)]
+pub(crate) struct ForkController<P>
+where
+    P: ProviderFactory,
+{
+    /// The URL of the currently forked network. The lock also prevents concurrent resets.
+    url: Mutex<Url>,
+    init_dev_genesis: bool,
+    /// The directory of the persistent fork state cache, if enabled.
+    cache_dir: Option<PathBuf>,
+    /// The fork state caches opened so far, by fork block.
+    ///
+    /// The database of a cache can only be opened once per process, so the caches are kept open
+    /// to be reused when re-forking at a block the node was already forked at. The chain id is
+    /// fixed for the life of the node, so the block alone identifies a cache.
+    caches: Mutex<HashMap<BlockNumber, ForkStateCache>>,
+    /// The chain spec the node was started with, which the genesis of the re-forked chain is
+    /// derived from.
+    chain_spec: katana_chain_spec::dev::ChainSpec,
+    storage: ForkProviderFactory,
+    backend: Arc<Backend<P>>,
+    block_producer: BlockProducer<P>,
+    pool: TxPool,
+    rpc_cache: RpcCache,
+    /// The fork block number reported by the TEE API, if enabled.
+    tee_fork_block_number: Option<Arc<AtomicU64>>,
+}
+
+impl<P> ForkController<P>
+where
+    P: ProviderFactory,
+    <P as ProviderFactory>::Provider: ProviderRO,
+    <P as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        url: Url,
+        init_dev_genesis: bool,
+        cache_dir: Option<PathBuf>,
+        chain_spec: katana_chain_spec::dev::ChainSpec,
+        storage: ForkProviderFactory,
+        backend: Arc<Backend<P>>,
+        block_producer: BlockProducer<P>,
+        pool: TxPool,
+        rpc_cache: RpcCache,
+        tee_fork_block_number: Option<Arc<AtomicU64>>,
+    ) -> Self {
+        let caches = storage.cache().map(|cache| (storage.block(), cache)).into_iter().collect();
+
+        Self {
+            url: Mutex::new(url),
+            init_dev_genesis,
+            cache_dir,
+            caches: Mutex::new(caches),
+            chain_spec,
+            storage,
+            backend,
+            block_producer,
+            pool,
+            rpc_cache,
+            tee_fork_block_number,
+        }
+    }
+
+    async fn reset(&self, url: Option<Url>, block: Option<ConfirmedBlockIdOrTag>) -> Result<()> {
+        let mut current_url = self.url.lock().await;
+        let url = url.unwrap_or_else(|| current_url.clone());
+
+        let client = StarknetClient::new(url.clone());
+        let fork = ForkPoint::fetch(&client, block.map(to_block_id)).await?;
+
+        // The chain id is fixed for the life of the node, as many components depend on it.
+        if fork.chain_id != self.backend.chain_spec.id().id() {
+            return Err(anyhow!(
+                "cannot fork a network with a different chain id: expected {:#x}, got {:#x}",
+                self.backend.chain_spec.id().id(),
+                fork.chain_id
+            ));
+        }
+
+        let mut chain_spec = self.chain_spec.clone();
+        fork.apply(&mut chain_spec);
+
+        let block = fork.block.block_number;
+        let cache = match &self.cache_dir {
+            Some(dir) => {
+                let mut caches = self.caches.lock().await;
+                let cache = match caches.entry(block) {
+                    Entry::Occupied(entry) => entry.get().clone(),
+                    Entry::Vacant(entry) => {
+                        let cache = ForkStateCache::open(dir, chain_spec.id, block)
+                            .context("failed to open fork state cache")?;
+                        entry.insert(cache).clone()
+                    }
+                };
+                Some(cache)
+            }
+            None => None,
+        };
+
+        // The block producer only makes progress when polled by the sequencing task, which can't
+        // happen while we hold the lock.
+        let deadline = Instant::now() + BLOCK_PRODUCER_IDLE_TIMEOUT;
+        let mut producer = loop {
+            let producer = self.block_producer.producer.write();
+            if !producer.is_busy() {
+                break producer;
+            }
+
+            drop(producer);
+
+            if Instant::now() > deadline {
+                bail!("timed out waiting for the ongoing block production to finish");
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+
+        self.storage.reset(block, client, cache)?;
+        self.backend
+            .init_dev_genesis(&chain_spec, !self.init_dev_genesis)
+            .context("failed to initialize genesis")?;
+
+        self.pool.clear();
+        self.rpc_cache.clear();
+        producer.reset()?;
+
+        if let Some(number) = &self.tee_fork_block_number {
+            number.store(block, Ordering::Release);
+        }
+
+        *current_url = url;
+        info!(%block, "Fork reset.");
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<P> ForkResetter for ForkController<P>
+where
+    P: ProviderFactory,
+    <P as ProviderFactory>::Provider: ProviderRO,
+    <P as ProviderFactory>::ProviderMut: ProviderRW,
+{
+    async fn reset_fork(
+        &self,
+        url: Option<Url>,
+        block: Option<ConfirmedBlockIdOrTag>,
+    ) -> anyhow::Result<()> {
+        self.reset(url, block).await
+    }
+}
+
+fn to_block_id(block: ConfirmedBlockIdOrTag) -> BlockIdOrTag {
+    match block {
+        ConfirmedBlockIdOrTag::Hash(hash) => BlockIdOrTag::Hash(hash),
+        ConfirmedBlockIdOrTag::Number(number) => BlockIdOrTag::Number(number),
+        ConfirmedBlockIdOrTag::Latest => BlockIdOrTag::Latest,
+        ConfirmedBlockIdOrTag::L1Accepted => BlockIdOrTag::L1Accepted,
+    }
+}
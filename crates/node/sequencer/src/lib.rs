@@ -2,11 +2,14 @@
 
 pub mod config;
 pub mod exit;
+mod fork;
 
 use std::future::IntoFuture;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result};
 use config::rpc::RpcModuleKind;
 use config::Config;
 use http::header::CONTENT_TYPE;
@@ -30,10 +33,11 @@ use katana_metrics::sys::DiskReporter;
 use katana_metrics::{MetricsServer, MetricsServerHandle, Report};
 use katana_pool::ordering::FiFo;
 use katana_pool::TxPool;
-use katana_primitives::block::{BlockHashOrNumber, GasPrices};
-use katana_primitives::cairo::ShortString;
+use katana_primitives::block::{BlockIdOrTag, GasPrices};
 use katana_primitives::env::VersionedConstantsOverrides;
 use katana_primitives::Felt;
+use katana_provider::api::block::BlockNumberProvider;
+use katana_provider::providers::fork::ForkStateCache;
 use katana_provider::{
     DbProviderFactory, ForkProviderFactory, ProviderFactory, ProviderRO, ProviderRW,
 };
@@ -49,10 +53,16 @@ use katana_rpc_api::starknet_ext::StarknetApiExtServer;
 use katana_rpc_api::tee::TeeApiServer;
 use katana_rpc_server::cartridge::{CartridgeApi, CartridgeConfig};
 use katana_rpc_server::dev::DevApi;
+use katana_rpc_server::health::{
+    BlockProductionCheck, DatabaseCheck, GasOracleCheck, HealthChecks, PaymasterCheck, TaskCheck,
+    VrfCheck, DEFAULT_BLOCK_PRODUCTION_STALL_THRESHOLD,
+};
+use katana_rpc_server::middleware::auth::AuthConfig;
 use katana_rpc_server::middleware::cartridge::{ControllerDeploymentLayer, VrfLayer};
 use katana_rpc_server::middleware::cors::Cors;
 use katana_rpc_server::middleware::logger::RpcLoggerLayer;
 use katana_rpc_server::middleware::metrics::RpcServerMetricsLayer;
+use katana_rpc_server::middleware::rate_limit::{RateLimitConfig, RateLimiter};
 use katana_rpc_server::node::NodeApi;
 use katana_rpc_server::paymaster::PaymasterProxy;
 use katana_rpc_server::starknet::{RpcCache, StarknetApi, StarknetApiConfig};
@@ -60,16 +70,17 @@ use katana_rpc_server::starknet::{RpcCache, StarknetApi, StarknetApiConfig};
 use katana_rpc_server::tee::TeeApi;
 use katana_rpc_server::{RpcServer, RpcServerHandle, RpcServiceBuilder};
 use katana_rpc_types::node::NodeInfo;
-use katana_rpc_types::GetBlockWithTxHashesResponse;
 use katana_stage::Sequencing;
 use katana_starknet::rpc::StarknetRpcClient as StarknetClient;
-use katana_tasks::TaskManager;
+use katana_tasks::{TaskManager, TaskStatus};
 use num_traits::ToPrimitive;
 use starknet::signers::SigningKey;
+use tokio::sync::watch;
 use tower::layer::util::{Identity, Stack};
 use tracing::info;
 
 use crate::exit::NodeStoppedFuture;
+use crate::fork::{ForkController, ForkPoint};
 
 /// The concrete type of the RPC middleware stack used by the node.
 type NodeRpcMiddleware<PF> = Stack<
@@ -105,6 +116,8 @@ where
     block_producer: BlockProducer<P>,
     gateway_server: Option<GatewayServer<TxPool, BlockProducer<P>, P>>,
     metrics_server: Option<MetricsServer<Prometheus>>,
+    messaging_status: TaskStatus,
+    gas_oracle_status: TaskStatus,
 }
 
 impl<P> Node<P>
@@ -118,6 +131,17 @@ where
     /// This returns a [`Node`] instance which can be launched with the all the necessary components
     /// configured.
     pub fn build_with_provider(db: katana_db::Db, provider: P, config: Config) -> Result<Node<P>> {
+        Self::build_inner(db, provider, config, None)
+    }
+
+    /// Builds the node components. `fork` must be the same as `provider` if the node is forked,
+    /// which enables re-forking the chain through the dev API.
+    fn build_inner(
+        db: katana_db::Db,
+        provider: P,
+        config: Config,
+        fork: Option<ForkProviderFactory>,
+    ) -> Result<Node<P>> {
         if config.metrics.is_some() {
             // Metrics recorder must be initialized before calling any of the metrics macros, in
             // order for it to be registered.
@@ -163,7 +187,7 @@ where
         };
 
         let executor_factory = {
-            let factory = BlockifierFactory::new(
+            let mut factory = BlockifierFactory::new(
                 overrides,
                 execution_flags.clone(),
                 config.sequencing.block_limits(),
@@ -171,6 +195,10 @@ where
                 config.chain.clone(),
             );
 
+            if config.execution.parallel_execution {
+                factory = factory.with_parallel_execution(task_spawner.cpu_bound().pool());
+            }
+
             Arc::new(factory) as Arc<dyn ExecutorFactory>
         };
 
@@ -184,12 +212,23 @@ where
                 prices.l1_data_gas_prices.clone(),
             )
         } else if let Some(settlement) = config.chain.settlement() {
+            let sampling = config.sequencing.gas_price_sampling.clone();
+            let fallback_urls = config.sequencing.gas_price_fallback_urls.iter().cloned();
+
             match settlement {
                 SettlementLayer::Starknet { rpc_url, .. } => {
-                    GasPriceOracle::sampled_starknet(rpc_url.clone())
+                    let samplers = std::iter::once(rpc_url.clone())
+                        .chain(fallback_urls)
+                        .map(GasPriceOracle::starknet_sampler)
+                        .collect();
+                    GasPriceOracle::sampled(samplers, sampling)
                 }
                 SettlementLayer::Ethereum { rpc_url, .. } => {
-                    GasPriceOracle::sampled_ethereum(rpc_url.clone())
+                    let samplers = std::iter::once(rpc_url.clone())
+                        .chain(fallback_urls)
+                        .map(GasPriceOracle::ethereum_sampler)
+                        .collect();
+                    GasPriceOracle::sampled(samplers, sampling)
                 }
                 SettlementLayer::Sovereign { .. } => {
                     GasPriceOracle::Fixed(FixedPriceOracle::default())
@@ -199,11 +238,18 @@ where
             GasPriceOracle::Fixed(FixedPriceOracle::default())
         };
 
+        let gas_oracle = match &config.sequencing.dynamic_l2_gas_price {
+            Some(dynamic) => GasPriceOracle::dynamic(gas_oracle, dynamic.clone()),
+            None => gas_oracle,
+        };
+
         // Get cfg_env before moving executor_factory into Backend
         let versioned_constant_overrides = executor_factory.overrides().cloned();
 
         // --- build backend
 
+        let block_signer = config.sequencing.block_signing_key.map(SigningKey::from_secret_scalar);
+
         let block_context_generator = BlockContextGenerator::default().into();
         let backend = Arc::new(Backend {
             gas_oracle: gas_oracle.clone(),
@@ -212,6 +258,8 @@ where
             block_context_generator,
             chain_spec: config.chain.clone(),
             no_state_trie: config.sequencing.no_state_trie,
+            block_signer: block_signer.clone(),
+            block_notifier: watch::channel(None).0,
         });
 
         let skip_dev_genesis =
@@ -253,9 +301,12 @@ where
                 "argent-version".parse().unwrap(),
             ]);
 
-        if let Some(cfg) = &config.paymaster {
+        let paymaster_proxy = if let Some(cfg) = &config.paymaster {
             let proxy = PaymasterProxy::new(cfg.url.clone(), cfg.api_key.clone())?;
-            rpc_modules.merge(proxy.into_rpc())?;
+            rpc_modules.merge(proxy.clone().into_rpc())?;
+            Some(proxy)
+        } else {
+            None
         };
 
         // --- build starknet api
@@ -270,6 +321,7 @@ where
         };
 
         let chain_spec = backend.chain_spec.clone();
+        let rpc_cache = RpcCache::new();
 
         let starknet_api = StarknetApi::new(
             chain_spec.clone(),
@@ -279,7 +331,7 @@ where
             gas_oracle.clone(),
             starknet_api_cfg,
             provider.clone(),
-            RpcCache::new(),
+            rpc_cache.clone(),
             class_cache.clone(),
         );
 
@@ -296,8 +348,35 @@ where
             rpc_modules.merge(KatanaApiServer::into_rpc(starknet_api.clone()))?;
         }
 
+        // The block the chain is forked at, as reported in TEE attestations. It changes when the
+        // fork is reset.
+        let tee_fork_block_number = config
+            .tee
+            .as_ref()
+            .and_then(|tee| tee.fork_block_number)
+            .map(|number| Arc::new(AtomicU64::new(number)));
+
         if config.rpc.apis.contains(&RpcModuleKind::Dev) {
-            let api = DevApi::new(backend.clone(), block_producer.clone(), pool.clone());
+            let mut api = DevApi::new(backend.clone(), block_producer.clone(), pool.clone());
+
+            if let (Some(storage), Some(forking), ChainSpec::Dev(chain_spec)) =
+                (&fork, &config.forking, config.chain.as_ref())
+            {
+                let controller = ForkController::new(
+                    forking.url.clone(),
+                    forking.init_dev_genesis,
+                    forking.cache_dir.clone(),
+                    chain_spec.clone(),
+                    storage.clone(),
+                    backend.clone(),
+                    block_producer.clone(),
+                    pool.clone(),
+                    rpc_cache.clone(),
+                    tee_fork_block_number.clone(),
+                );
+                api = api.with_fork(Arc::new(controller));
+            }
+
             rpc_modules.merge(DevApiServer::into_rpc(api))?;
         }
 
@@ -351,15 +430,29 @@ where
                 );
 
                 let vrf_layer = if let Some(vrf) = &cartridge_api_cfg.vrf {
-                    use katana_rpc_server::cartridge::{VrfService, VrfServiceConfig};
+                    use cartridge::vrf::InProcessVrf;
+                    use katana_rpc_server::cartridge::{VrfProvider, VrfService, VrfServiceConfig};
                     use url::Url;
 
+                    use crate::config::paymaster::VrfProviderConfig;
+
                     let rpc_url = Url::parse(&format!("http://{}", config.rpc.socket_addr()))
                         .expect("valid rpc url");
 
+                    let provider = match &vrf.provider {
+                        VrfProviderConfig::Remote(url) => VrfProvider::Remote(url.clone()),
+                        VrfProviderConfig::InProcess { vrf_account_private_key, secret_key } => {
+                            VrfProvider::InProcess(InProcessVrf::new(
+                                vrf.vrf_account,
+                                *vrf_account_private_key,
+                                *secret_key,
+                            ))
+                        }
+                    };
+
                     let vrf_service = VrfService::new(VrfServiceConfig {
                         rpc_url,
-                        service_url: vrf.url.clone(),
+                        provider,
                         vrf_contract: vrf.vrf_account,
                     });
 
@@ -420,7 +513,7 @@ where
                 let api = TeeApi::new(
                     provider.clone(),
                     tee_provider,
-                    tee_config.fork_block_number,
+                    tee_fork_block_number.clone(),
                     &backend.chain_spec,
                 );
                 rpc_modules.merge(TeeApiServer::into_rpc(api))?;
@@ -429,6 +522,43 @@ where
             }
         }
 
+        // --- build health checks
+
+        let messaging_status = TaskStatus::new();
+        let gas_oracle_status = TaskStatus::new();
+
+        // Blocks are only produced when there are transactions to include, so block production is
+        // considered stalled if transactions are left pending for much longer than the block time.
+        let stall_threshold = match config.sequencing.block_time {
+            Some(block_time) => Some(
+                (Duration::from_millis(block_time) * 3)
+                    .max(DEFAULT_BLOCK_PRODUCTION_STALL_THRESHOLD),
+            ),
+            None if config.sequencing.no_mining => None,
+            None => Some(DEFAULT_BLOCK_PRODUCTION_STALL_THRESHOLD),
+        };
+
+        let mut health_checks = HealthChecks::new()
+            .readiness(DatabaseCheck::new(provider.clone()))
+            .liveness(BlockProductionCheck::new(provider.clone(), pool.clone(), stall_threshold))
+            .liveness(GasOracleCheck::new(gas_oracle.clone(), gas_oracle_status.clone()));
+
+        if config.messaging.is_some() {
+            health_checks =
+                health_checks.liveness(TaskCheck::new("messaging", messaging_status.clone()));
+        }
+
+        if let Some(proxy) = paymaster_proxy {
+            health_checks = health_checks.readiness(PaymasterCheck::new(proxy));
+        }
+
+        let vrf = config.paymaster.as_ref().and_then(|p| p.cartridge_api.as_ref()?.vrf.as_ref());
+        if let Some(vrf) = vrf {
+            if let crate::config::paymaster::VrfProviderConfig::Remote(url) = &vrf.provider {
+                health_checks = health_checks.readiness(VrfCheck::new(url.clone()));
+            }
+        }
+
         // --- build rpc middleware
 
         let rpc_middleware = RpcServiceBuilder::new()
@@ -443,6 +573,7 @@ where
             .cors(cors)
             .metrics(true)
             .health_check(true)
+            .health_checks(health_checks)
             .rpc_middleware(rpc_middleware)
             .module(rpc_modules)?;
 
@@ -467,12 +598,39 @@ where
             rpc_server = rpc_server.max_response_body_size(max_response_body_size);
         }
 
+        let auth = match &config.rpc.auth {
+            Some(auth) => {
+                Some(AuthConfig::try_from(auth).context("invalid RPC authentication config")?)
+            }
+            None => None,
+        };
+        let rate_limiter = config
+            .rpc
+            .rate_limit
+            .as_ref()
+            .map(|rate_limit| Arc::new(RateLimiter::new(RateLimitConfig::from(rate_limit))));
+
+        if let Some(auth) = &auth {
+            rpc_server = rpc_server.auth(auth.clone());
+        }
+
+        if let Some(limiter) = &rate_limiter {
+            rpc_server = rpc_server.rate_limiter(limiter.clone());
+        }
+
+        if let Some(tls) = &config.rpc.tls {
+            rpc_server = rpc_server.tls(tls.clone());
+        }
+
         // --- build gRPC server (optional)
 
         #[cfg(feature = "grpc")]
         let grpc_server = if let Some(grpc_config) = &config.grpc {
+            #[cfg(feature = "explorer")]
+            use katana_grpc::StarknetExtServer;
             use katana_grpc::{
-                StarknetServer, StarknetService, StarknetTraceServer, StarknetWriteServer,
+                DevServer, DevService, KatanaServer, StarknetServer, StarknetService,
+                StarknetTraceServer, StarknetWriteServer, TxPoolServer, TxPoolService,
             };
 
             let mut server = GrpcServer::new();
@@ -481,12 +639,43 @@ where
                 server = server.timeout(timeout);
             }
 
-            let svc = StarknetService::new(starknet_api.clone());
+            if let Some(tls) = &grpc_config.tls {
+                server = server.tls(tls.clone());
+            }
+
+            // The gRPC services are subject to the same access control as the RPC server.
+            if let Some(auth) = &auth {
+                server = server.auth(auth.clone());
+            }
+
+            if let Some(limiter) = &rate_limiter {
+                server = server.rate_limiter(limiter.clone());
+            }
+
+            let svc = StarknetService::new(starknet_api.clone())
+                .with_block_subscription(backend.subscribe_blocks());
 
             server = server
                 .service(StarknetServer::new(svc.clone()))
                 .service(StarknetTraceServer::new(svc.clone()))
-                .service(StarknetWriteServer::new(svc.clone()));
+                .service(StarknetWriteServer::new(svc.clone()))
+                .service(KatanaServer::new(svc.clone()));
+
+            // Like its JSON-RPC counterpart, the list API is only served for the explorer.
+            #[cfg(feature = "explorer")]
+            if config.rpc.explorer {
+                server = server.service(StarknetExtServer::new(svc));
+            }
+
+            if config.rpc.apis.contains(&RpcModuleKind::Dev) {
+                let api = DevApi::new(backend.clone(), block_producer.clone(), pool.clone());
+                server = server.service(DevServer::new(DevService::new(api)));
+            }
+
+            if config.rpc.apis.contains(&RpcModuleKind::TxPool) {
+                let api = katana_rpc_server::txpool::TxPoolApi::new(pool.clone());
+                server = server.service(TxPoolServer::new(TxPoolService::new(api)));
+            }
 
             Some(server)
         } else {
@@ -504,6 +693,23 @@ where
                 server = server.timeout(timeout);
             }
 
+            if let Some(tls) = &gw_config.tls {
+                server = server.tls(tls.clone());
+            }
+
+            if let Some(signer) = &block_signer {
+                server = server.public_key(signer.verifying_key().scalar());
+            }
+
+            // Transactions submitted through the gateway are subject to the RPC access control.
+            if let Some(auth) = auth {
+                server = server.auth(auth);
+            }
+
+            if let Some(limiter) = rate_limiter {
+                server = server.rate_limiter(limiter);
+            }
+
             Some(server)
         } else {
             None
@@ -535,6 +741,8 @@ where
             gateway_server,
             block_producer,
             metrics_server,
+            messaging_status,
+            gas_oracle_status,
             config: Arc::new(config),
             task_manager,
         })
@@ -581,66 +789,44 @@ impl Node<ForkProviderFactory> {
         let db = katana_db::Db::in_memory()?;
 
         let client = StarknetClient::new(cfg.url.clone());
-        let chain_id = client.chain_id().await.context("failed to fetch forked network id")?;
-
-        // If the fork block number is not specified, we use the latest accepted block on the forked
-        // network.
-        let block_id = if let Some(id) = cfg.block {
-            id
+        let block = if cfg.pre_confirmed {
+            Some(BlockIdOrTag::PreConfirmed)
         } else {
-            let res = client.block_number().await?;
-            BlockHashOrNumber::Num(res.block_number)
+            cfg.block.map(Into::into)
         };
 
-        // if the id is not in ASCII encoding, we display the chain id as is in hex.
-        match ShortString::try_from(chain_id) {
-            Ok(id) => {
-                info!(chain = %id, block = %block_id, "Forking chain.");
-            }
-
-            Err(_) => {
-                let id = format!("{chain_id:#x}");
-                info!(chain = %id, block = %block_id, "Forking chain.");
-            }
-        };
-
-        let block = client
-            .get_block_with_tx_hashes(block_id.into())
+        let mut fork = ForkPoint::fetch(&client, block).await?;
+        cfg.backend
+            .check_fallback_urls(fork.chain_id)
             .await
-            .context("failed to fetch forked block")?;
-
-        let GetBlockWithTxHashesResponse::Block(forked_block) = block else {
-            bail!("forking a pending block is not allowed")
-        };
+            .context("invalid fork fallback url")?;
 
+        let pre_confirmed = fork.pre_confirmed.take();
+        let forked_block = &fork.block;
         let block_num = forked_block.block_number;
-        let genesis_block_num = block_num + 1;
 
         // Store fork block number in TEE config so report_data includes it
         if let Some(ref mut tee_config) = config.tee {
             tee_config.fork_block_number = Some(block_num);
         }
 
-        chain_spec.id = chain_id.into();
-
-        // adjust the genesis to match the forked block
-        chain_spec.genesis.timestamp = forked_block.timestamp;
-        chain_spec.genesis.number = genesis_block_num;
-        chain_spec.genesis.state_root = Default::default();
-        chain_spec.genesis.parent_hash = forked_block.parent_hash;
-        chain_spec.genesis.sequencer_address = forked_block.sequencer_address;
-
-        // TODO: remove gas price from genesis
-        let eth_l1_gas_price =
-            forked_block.l1_gas_price.price_in_wei.to_u128().expect("should fit in u128");
-        let strk_l1_gas_price =
-            forked_block.l1_gas_price.price_in_fri.to_u128().expect("should fit in u128");
-        chain_spec.genesis.gas_prices =
-            unsafe { GasPrices::new_unchecked(eth_l1_gas_price, strk_l1_gas_price) };
+        fork.apply(chain_spec);
 
         // TODO: convert this to block number instead of BlockHashOrNumber so that it is easier to
         // check if the requested block is within the supported range or not.
-        let provider_factory = ForkProviderFactory::new(db.clone(), block_num, client.clone());
+        let mut provider_factory = ForkProviderFactory::new_with_config(
+            db.clone(),
+            block_num,
+            client.clone(),
+            cfg.backend.clone(),
+        );
+
+        if let Some(dir) = &cfg.cache_dir {
+            let cache = ForkStateCache::open(dir, chain_spec.id, block_num)
+                .context("failed to open fork state cache")?;
+            info!(target: "node", path = %cache.db().path().display(), "Using persistent fork state cache.");
+            provider_factory = provider_factory.with_cache(cache);
+        }
 
         // update the genesis block with the forked block's data
         // we dont update the `l1_gas_price` bcs its already done when we set the `gas_prices` in
@@ -658,7 +844,22 @@ impl Node<ForkProviderFactory> {
 
         block.header.l1_da_mode = forked_block.l1_da_mode;
 
-        Self::build_with_provider(db, provider_factory, config)
+        let node = Self::build_inner(db, provider_factory.clone(), config, Some(provider_factory))?;
+
+        // Carry over the pre-confirmed state of the forked network into the first block on top of
+        // the forked chain's genesis.
+        if let Some(pre_confirmed) = pre_confirmed {
+            let number = node.backend.storage.provider().latest_number()? + 1;
+            let block_env = pre_confirmed.block_env(number);
+            let transactions = pre_confirmed.transactions();
+            node.backend.mine_block_with_states(&block_env, transactions, pre_confirmed.states)?;
+
+            // The block producer was initialized on top of the state before the pre-confirmed
+            // block was mined.
+            node.block_producer.producer.write().reset()?;
+        }
+
+        Ok(node)
     }
 }
 
@@ -720,7 +921,8 @@ where
             self.task_manager.task_spawner(),
             block_producer.clone(),
             self.config.messaging.clone(),
-        );
+        )
+        .messaging_status(self.messaging_status.clone());
 
         self.task_manager
             .task_spawner()
@@ -766,7 +968,7 @@ where
                 .build_task()
                 .graceful_shutdown()
                 .name("gas oracle")
-                .spawn(worker);
+                .spawn(self.gas_oracle_status.track(worker));
         }
 
         info!(target: "node", "Gas price oracle worker started.");
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use katana_primitives::block::{GasPrice, GasPrices};
+use parking_lot::Mutex;
+
+use crate::GasPriceOracle;
+
+/// The default maximum change of the L2 gas price between two blocks, as a fraction of the price
+/// (ie 1/8 = 12.5%). Same as EIP-1559.
+pub const DEFAULT_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// Configuration of the [`DynamicPriceOracle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicGasPriceConfig {
+    /// The amount of L2 gas used per block at which the price stays the same.
+    pub target_gas: u64,
+    /// The lowest the L2 gas prices can go.
+    pub min_prices: GasPrices,
+    /// The highest the L2 gas prices can go.
+    pub max_prices: GasPrices,
+    /// Bounds how much the price can change between two blocks. The price changes by at most
+    /// `1 / max_change_denominator` of itself, which happens when a block uses either twice the
+    /// target or no gas at all.
+    pub max_change_denominator: u64,
+}
+
+impl Default for DynamicGasPriceConfig {
+    fn default() -> Self {
+        Self {
+            target_gas: 0,
+            min_prices: GasPrices::MIN,
+            max_prices: GasPrices::MAX,
+            max_change_denominator: DEFAULT_MAX_CHANGE_DENOMINATOR,
+        }
+    }
+}
+
+/// A gas price oracle that adjusts the L2 gas prices after every block based on the amount of L2
+/// gas the block used, similar to Ethereum's EIP-1559 base fee.
+///
+/// When a block uses more gas than [the target](DynamicGasPriceConfig::target_gas), the prices go
+/// up proportionally to how far above the target the usage is. When it uses less, they go down.
+///
+/// The L1 gas prices are provided by the wrapped oracle.
+#[derive(Debug, Clone)]
+pub struct DynamicPriceOracle {
+    base: Box<GasPriceOracle>,
+    config: DynamicGasPriceConfig,
+    l2_gas_prices: Arc<Mutex<GasPrices>>,
+}
+
+impl DynamicPriceOracle {
+    /// Creates a new oracle on top of `base`.
+    ///
+    /// The initial L2 gas prices are the ones of `base`, within the configured bounds.
+    pub fn new(base: GasPriceOracle, config: DynamicGasPriceConfig) -> Self {
+        let initial = base.l2_gas_prices();
+        let l2_gas_prices = GasPrices::new(
+            clamp(initial.eth.get(), &config.min_prices.eth, &config.max_prices.eth),
+            clamp(initial.strk.get(), &config.min_prices.strk, &config.max_prices.strk),
+        );
+
+        let l2_gas_prices = Arc::new(Mutex::new(l2_gas_prices));
+        Self { base: Box::new(base), config, l2_gas_prices }
+    }
+
+    /// Returns the oracle providing the L1 gas prices.
+    pub fn base(&self) -> &GasPriceOracle {
+        &self.base
+    }
+
+    /// Returns the L2 gas prices for the next block.
+    pub fn l2_gas_prices(&self) -> GasPrices {
+        self.l2_gas_prices.lock().clone()
+    }
+
+    /// Updates the L2 gas prices based on the amount of L2 gas used by the last block.
+    pub fn update(&self, l2_gas_used: u64) {
+        let mut prices = self.l2_gas_prices.lock();
+        let config = &self.config;
+
+        *prices = GasPrices::new(
+            clamp(
+                next_price(prices.eth.get(), l2_gas_used, config),
+                &config.min_prices.eth,
+                &config.max_prices.eth,
+            ),
+            clamp(
+                next_price(prices.strk.get(), l2_gas_used, config),
+                &config.min_prices.strk,
+                &config.max_prices.strk,
+            ),
+        );
+    }
+}
+
+/// Computes the price for the next block, before applying the bounds.
+fn next_price(price: u128, gas_used: u64, config: &DynamicGasPriceConfig) -> u128 {
+    let target = config.target_gas.max(1) as u128;
+    let denominator = config.max_change_denominator.max(1) as u128;
+    let gas_used = gas_used as u128;
+
+    if gas_used > target {
+        let delta = price.saturating_mul(gas_used - target) / target / denominator;
+        // Like EIP-1559, the price always goes up when the target is exceeded, even when the
+        // price is too low for the delta to be non-zero.
+        price.saturating_add(delta.max(1))
+    } else {
+        let delta = price.saturating_mul(target - gas_used) / target / denominator;
+        price.saturating_sub(delta)
+    }
+}
+
+fn clamp(price: u128, min: &GasPrice, max: &GasPrice) -> GasPrice {
+    let price = price.clamp(min.get(), max.get().max(min.get()));
+    GasPrice::try_from(price).unwrap_or(GasPrice::MIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::block::GasPrices;
+
+    use super::{DynamicGasPriceConfig, DynamicPriceOracle};
+    use crate::GasPriceOracle;
+
+    fn oracle(initial: u128, min: u128, max: u128) -> DynamicPriceOracle {
+        let prices = |price| unsafe { GasPrices::new_unchecked(price, price) };
+        let base = GasPriceOracle::fixed(prices(initial), GasPrices::MIN, GasPrices::MIN);
+        let config = DynamicGasPriceConfig {
+            target_gas: 1_000,
+            min_prices: prices(min),
+            max_prices: prices(max),
+            ..Default::default()
+        };
+        DynamicPriceOracle::new(base, config)
+    }
+
+    #[test]
+    fn adjusts_to_block_utilization() {
+        let oracle = oracle(800, 1, 10_000);
+        assert_eq!(oracle.l2_gas_prices().strk.get(), 800);
+
+        // at the target, the price doesn't change
+        oracle.update(1_000);
+        assert_eq!(oracle.l2_gas_prices().strk.get(), 800);
+
+        // twice the target, the price increases by the maximum of 12.5%
+        oracle.update(2_000);
+        assert_eq!(oracle.l2_gas_prices().strk.get(), 900);
+
+        // half the target, the price decreases by 6.25%
+        oracle.update(500);
+        assert_eq!(oracle.l2_gas_prices().eth.get(), 844);
+
+        // empty block, the price decreases by the maximum of 12.5%
+        oracle.update(0);
+        assert_eq!(oracle.l2_gas_prices().eth.get(), 739);
+    }
+
+    #[test]
+    fn price_stays_within_bounds() {
+        // the initial price is clamped
+        let oracle = oracle(5, 10, 20);
+        assert_eq!(oracle.l2_gas_prices().eth.get(), 10);
+
+        oracle.update(0);
+        assert_eq!(oracle.l2_gas_prices().eth.get(), 10);
+
+        for _ in 0..10 {
+            oracle.update(u64::MAX);
+        }
+        assert_eq!(oracle.l2_gas_prices().eth.get(), 20);
+    }
+
+    #[test]
+    fn price_increases_when_too_low_to_change() {
+        let oracle = oracle(1, 1, 100);
+        oracle.update(1_001);
+        assert_eq!(oracle.l2_gas_prices().eth.get(), 2);
+    }
+}
@@ -1,19 +1,24 @@
 use std::fmt::Debug;
-use std::future::Future;
 
+use futures::future::BoxFuture;
 use katana_primitives::block::GasPrices;
 use url::Url;
 
+mod dynamic;
 mod fixed;
 mod sampled;
 
+pub use dynamic::{DynamicGasPriceConfig, DynamicPriceOracle, DEFAULT_MAX_CHANGE_DENOMINATOR};
 pub use fixed::{
     FixedPriceOracle, DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE,
     DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE, DEFAULT_STRK_L1_GAS_PRICE,
     DEFAULT_STRK_L2_GAS_PRICE,
 };
 use sampled::ethereum::EthereumSampler;
-pub use sampled::{SampledPriceOracle, Sampler};
+pub use sampled::{
+    Aggregation, InvalidAggregation, SampledPriceOracle, SampledPriceOracleConfig, SampledPrices,
+    Sampler, SamplerHealth, DEFAULT_MAX_STALENESS, DEFAULT_SAMPLING_INTERVAL, DEFAULT_WINDOW_SIZE,
+};
 
 use crate::sampled::starknet::{StarknetGatewaySampler, StarknetJsonRpcSampler};
 
@@ -21,6 +26,7 @@ use crate::sampled::starknet::{StarknetGatewaySampler, StarknetJsonRpcSampler};
 pub enum GasPriceOracle {
     Fixed(fixed::FixedPriceOracle),
     Sampled(sampled::SampledPriceOracle<Box<dyn Sampler + 'static>>),
+    Dynamic(dynamic::DynamicPriceOracle),
 }
 
 impl GasPriceOracle {
@@ -36,6 +42,24 @@ impl GasPriceOracle {
         ))
     }
 
+    /// Creates a new gas oracle that samples the gas prices from `samplers`, in order of priority.
+    ///
+    /// The first sampler is the primary source of the gas prices, and the others are only used
+    /// when the ones before them fail.
+    pub fn sampled(samplers: Vec<Box<dyn Sampler>>, config: SampledPriceOracleConfig) -> Self {
+        Self::Sampled(sampled::SampledPriceOracle::with_config(samplers, config))
+    }
+
+    /// Creates a new [`Sampler`] of the gas prices of an Ethereum chain.
+    pub fn ethereum_sampler(url: Url) -> Box<dyn Sampler> {
+        Box::new(EthereumSampler::new_http(url))
+    }
+
+    /// Creates a new [`Sampler`] of the gas prices of a Starknet chain via RPC.
+    pub fn starknet_sampler(url: Url) -> Box<dyn Sampler> {
+        Box::new(StarknetJsonRpcSampler::new_http(url))
+    }
+
     /// Creates a new gas oracle that samples the gas prices from an Ethereum chain.
     pub fn sampled_ethereum(url: Url) -> Self {
         let sampler: Box<dyn Sampler> = Box::new(EthereumSampler::new_http(url));
@@ -58,11 +82,28 @@ impl GasPriceOracle {
         Self::Sampled(oracle)
     }
 
+    /// Creates a new gas oracle that adjusts the L2 gas prices based on the utilization of the
+    /// blocks, and takes the L1 gas prices from `base`.
+    pub fn dynamic(base: GasPriceOracle, config: DynamicGasPriceConfig) -> Self {
+        Self::Dynamic(DynamicPriceOracle::new(base, config))
+    }
+
+    /// Reports the amount of L2 gas used by the last mined block.
+    ///
+    /// This is a no-op unless the oracle is [dynamic](GasPriceOracle::Dynamic), in which case the
+    /// L2 gas prices for the next block are updated.
+    pub fn record_l2_gas_used(&self, l2_gas_used: u64) {
+        if let Self::Dynamic(dynamic) = self {
+            dynamic.update(l2_gas_used);
+        }
+    }
+
     /// Returns the current L1 gas prices.
     pub fn l1_gas_prices(&self) -> GasPrices {
         match self {
             GasPriceOracle::Fixed(fixed) => fixed.l1_gas_prices().clone(),
-            GasPriceOracle::Sampled(sampled) => sampled.avg_l1_gas_prices(),
+            GasPriceOracle::Sampled(sampled) => sampled.l1_gas_prices(),
+            GasPriceOracle::Dynamic(dynamic) => dynamic.base().l1_gas_prices(),
         }
     }
 
@@ -70,7 +111,8 @@ impl GasPriceOracle {
     pub fn l1_data_gas_prices(&self) -> GasPrices {
         match self {
             GasPriceOracle::Fixed(fixed) => fixed.l1_data_gas_prices().clone(),
-            GasPriceOracle::Sampled(sampled) => sampled.avg_l1_data_gas_prices(),
+            GasPriceOracle::Sampled(sampled) => sampled.l1_data_gas_prices(),
+            GasPriceOracle::Dynamic(dynamic) => dynamic.base().l1_data_gas_prices(),
         }
     }
 
@@ -78,14 +120,16 @@ impl GasPriceOracle {
     pub fn l2_gas_prices(&self) -> GasPrices {
         match self {
             GasPriceOracle::Fixed(fixed) => fixed.l2_gas_prices().clone(),
-            GasPriceOracle::Sampled(sampled) => sampled.avg_l2_gas_prices(),
+            GasPriceOracle::Sampled(sampled) => sampled.l2_gas_prices(),
+            GasPriceOracle::Dynamic(dynamic) => dynamic.l2_gas_prices(),
         }
     }
 
-    pub fn run_worker(&self) -> Option<impl Future<Output = ()> + 'static> {
+    pub fn run_worker(&self) -> Option<BoxFuture<'static, ()>> {
         match self {
             Self::Fixed(..) => None,
-            Self::Sampled(sampled) => Some(sampled.run_worker()),
+            Self::Sampled(sampled) => Some(Box::pin(sampled.run_worker())),
+            Self::Dynamic(dynamic) => dynamic.base().run_worker(),
         }
     }
 
@@ -1,15 +1,18 @@
 use katana_primitives::block::GasPrices;
 
+use super::Aggregation;
+
 /// Gas prices buffer.
 ///
 /// The buffer is implemented as a sliding window buffer i.e., once the buffer is full, the oldest
 /// price is removed when a new gas price is inserted.
 #[derive(Debug, Clone)]
-pub struct GasPricesBuffer<const N: usize>(SlidingWindowBuffer<GasPrices, N>);
+pub struct GasPricesBuffer(SlidingWindowBuffer<GasPrices>);
 
-impl<const N: usize> GasPricesBuffer<N> {
-    pub fn new() -> Self {
-        Self(SlidingWindowBuffer::new())
+impl GasPricesBuffer {
+    /// Creates a new buffer holding at most `capacity` prices.
+    pub fn new(capacity: usize) -> Self {
+        Self(SlidingWindowBuffer::new(capacity))
     }
 
     pub fn push(&mut self, prices: GasPrices) {
@@ -28,6 +31,60 @@ impl<const N: usize> GasPricesBuffer<N> {
 
         unsafe { GasPrices::new_unchecked(eth_avg, strk_avg) }
     }
+
+    /// Calculate the median gas prices from the buffer.
+    ///
+    /// With an even number of prices, the median is the average of the two middle prices, rounded
+    /// up.
+    pub fn median(&self) -> GasPrices {
+        self.aggregate_with(|sorted| {
+            let mid = sorted.len() / 2;
+            if sorted.len() % 2 == 0 {
+                let (a, b) = (sorted[mid - 1], sorted[mid]);
+                a / 2 + b / 2 + (a % 2 + b % 2).div_ceil(2)
+            } else {
+                sorted[mid]
+            }
+        })
+    }
+
+    /// Calculate the `p`-th percentile gas prices from the buffer, using the nearest-rank method.
+    ///
+    /// `p` is capped at 100, which is the highest price.
+    pub fn percentile(&self, p: u8) -> GasPrices {
+        let p = p.min(100) as usize;
+        self.aggregate_with(|sorted| {
+            let rank = (p * sorted.len()).div_ceil(100).max(1);
+            sorted[rank - 1]
+        })
+    }
+
+    /// Aggregates the gas prices from the buffer using the given method.
+    pub fn aggregate(&self, aggregation: Aggregation) -> GasPrices {
+        match aggregation {
+            Aggregation::Average => self.average(),
+            Aggregation::Median => self.median(),
+            Aggregation::Percentile(p) => self.percentile(p),
+        }
+    }
+
+    /// Applies `f` to the sorted ETH and STRK prices separately.
+    fn aggregate_with(&self, f: impl Fn(&[u128]) -> u128) -> GasPrices {
+        if self.0.is_empty() {
+            return GasPrices::MIN;
+        }
+
+        let mut eth = self.0.iter().map(|p| p.eth.get()).collect::<Vec<_>>();
+        let mut strk = self.0.iter().map(|p| p.strk.get()).collect::<Vec<_>>();
+        eth.sort_unstable();
+        strk.sort_unstable();
+
+        // # SAFETY
+        //
+        // The aggregated value is always one of the prices, or in between two of them, which are
+        // all non-zero.
+        unsafe { GasPrices::new_unchecked(f(&eth), f(&strk)) }
+    }
 }
 
 /// Calculate the sum of gas prices from an iterator of GasPrices.
@@ -54,7 +111,7 @@ fn sum_gas_prices<'a, I: Iterator<Item = &'a GasPrices>>(iter: I) -> GasPrices {
 /// # Examples
 ///
 /// ```
-/// let mut buffer = SlidingWindowBuffer::<i32, 3>::new();
+/// let mut buffer = SlidingWindowBuffer::<i32>::new(3);
 ///
 /// // Buffer grows until capacity
 /// buffer.push(1); // [1]
@@ -66,19 +123,26 @@ fn sum_gas_prices<'a, I: Iterator<Item = &'a GasPrices>>(iter: I) -> GasPrices {
 /// assert_eq!(evicted, Some(1));
 /// ```
 #[derive(Debug, Clone)]
-pub struct SlidingWindowBuffer<T, const N: usize> {
+pub struct SlidingWindowBuffer<T> {
     /// The total number of elements currently in the buffer.
     len: usize,
     /// Index of the oldest element in the circular buffer.
     head: usize,
     /// The internal buffer where `None` represents an empty slot.
-    buffer: [Option<T>; N],
+    buffer: Vec<Option<T>>,
 }
 
-impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
-    /// Creates a new empty sliding window buffer.
-    pub const fn new() -> Self {
-        Self { buffer: [const { None }; N], head: 0, len: 0 }
+impl<T: Clone> SlidingWindowBuffer<T> {
+    /// Creates a new empty sliding window buffer holding at most `capacity` elements.
+    ///
+    /// A capacity of zero is treated as one.
+    pub fn new(capacity: usize) -> Self {
+        Self { buffer: vec![None; capacity.max(1)], head: 0, len: 0 }
+    }
+
+    /// Returns the maximum number of elements the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
     }
 
     /// Pushes a new element into the buffer.
@@ -98,22 +162,23 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
     /// # Examples
     ///
     /// ```
-    /// let mut buffer = SlidingWindowBuffer::<i32, 2>::new();
+    /// let mut buffer = SlidingWindowBuffer::<i32>::new(2);
     /// assert_eq!(buffer.push(1), None); // Buffer not full
     /// assert_eq!(buffer.push(2), None); // Buffer now full
     /// assert_eq!(buffer.push(3), Some(1)); // Evicts oldest (1)
     /// ```
     pub fn push(&mut self, sample: T) -> Option<T> {
-        let evicted = if self.len == N {
+        let capacity = self.capacity();
+        let evicted = if self.len == capacity {
             let old_head = self.head;
-            self.head = (self.head + 1) % N;
+            self.head = (self.head + 1) % capacity;
             self.buffer[old_head].take()
         } else {
             self.len += 1;
             None
         };
 
-        let insert_pos = (self.head + self.len - 1) % N;
+        let insert_pos = (self.head + self.len - 1) % capacity;
         self.buffer[insert_pos] = Some(sample);
         evicted
     }
@@ -131,7 +196,7 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
     /// # Examples
     ///
     /// ```
-    /// let mut buffer = SlidingWindowBuffer::<i32, 3>::new();
+    /// let mut buffer = SlidingWindowBuffer::<i32>::new(3);
     /// buffer.push(1);
     /// buffer.push(2);
     /// assert_eq!(buffer.pop(), Some(1)); // Removes oldest
@@ -145,7 +210,7 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
         }
 
         let old_head = self.head;
-        self.head = (self.head + 1) % N;
+        self.head = (self.head + 1) % self.capacity();
         self.len -= 1;
         self.buffer[old_head].take()
     }
@@ -155,7 +220,7 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
     /// # Examples
     ///
     /// ```
-    /// let mut buffer = SlidingWindowBuffer::<i32, 5>::new();
+    /// let mut buffer = SlidingWindowBuffer::<i32>::new(5);
     /// assert_eq!(buffer.len(), 0);
     /// buffer.push(1);
     /// assert_eq!(buffer.len(), 1);
@@ -169,7 +234,7 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
     /// # Examples
     ///
     /// ```
-    /// let mut buffer = SlidingWindowBuffer::<_, 3>::new();
+    /// let mut buffer = SlidingWindowBuffer::<_>::new(3);
     /// assert!(buffer.is_empty());
     /// buffer.push(1);
     /// assert!(!buffer.is_empty());
@@ -186,7 +251,7 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
     /// # Examples
     ///
     /// ```
-    /// let mut buffer = SlidingWindowBuffer::<i32, 4>::new();
+    /// let mut buffer = SlidingWindowBuffer::<i32>::new(4);
     /// buffer.push(1);
     /// buffer.push(2);
     /// buffer.push(3);
@@ -194,7 +259,7 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
     /// let values: Vec<i32> = buffer.iter().copied().collect();
     /// assert_eq!(values, vec![1, 2, 3]); // Oldest to newest
     /// ```
-    pub fn iter(&self) -> Iter<'_, T, N> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter { buffer: self, index: 0 }
     }
 }
@@ -203,12 +268,12 @@ impl<T: Clone, const N: usize> SlidingWindowBuffer<T, N> {
 ///
 /// This iterator yields references to elements in the buffer from oldest to newest.
 /// It is created by the [`SlidingWindowBuffer::iter`] method.
-pub struct Iter<'a, T, const N: usize> {
-    buffer: &'a SlidingWindowBuffer<T, N>,
+pub struct Iter<'a, T> {
+    buffer: &'a SlidingWindowBuffer<T>,
     index: usize,
 }
 
-impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -216,7 +281,7 @@ impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
             return None;
         }
 
-        let idx = (self.buffer.head + self.index) % N;
+        let idx = (self.buffer.head + self.index) % self.buffer.buffer.len();
         self.index += 1;
         self.buffer.buffer[idx].as_ref()
     }
@@ -227,7 +292,7 @@ impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
     }
 }
 
-impl<T, const N: usize> ExactSizeIterator for Iter<'_, T, N> {
+impl<T> ExactSizeIterator for Iter<'_, T> {
     fn len(&self) -> usize {
         self.buffer.len.saturating_sub(self.index)
     }
@@ -240,7 +305,7 @@ mod tests {
     #[test]
     fn buffer_size_limit() {
         const BUFFER_SIZE: usize = 5;
-        let mut buffer = SlidingWindowBuffer::<u128, BUFFER_SIZE>::new();
+        let mut buffer = SlidingWindowBuffer::<u128>::new(BUFFER_SIZE);
 
         // Fill up buffer
         for value in 0..BUFFER_SIZE {
@@ -264,14 +329,14 @@ mod tests {
 
     #[test]
     fn gas_prices_buffer_average_empty() {
-        let buffer = GasPricesBuffer::<5>::new();
+        let buffer = GasPricesBuffer::new(5);
         let average = buffer.average();
         assert_eq!(average, GasPrices::MIN);
     }
 
     #[test]
     fn gas_prices_buffer_average_single_element() {
-        let mut buffer = GasPricesBuffer::<5>::new();
+        let mut buffer = GasPricesBuffer::new(5);
 
         let gas_price = unsafe { GasPrices::new_unchecked(100, 200) };
         buffer.push(gas_price);
@@ -283,7 +348,7 @@ mod tests {
 
     #[test]
     fn gas_prices_buffer_average_multiple_elements() {
-        let mut buffer = GasPricesBuffer::<5>::new();
+        let mut buffer = GasPricesBuffer::new(5);
 
         // Add test gas prices
         let prices = [
@@ -304,7 +369,7 @@ mod tests {
 
     #[test]
     fn gas_prices_buffer_average_ceiling_division() {
-        let mut buffer = GasPricesBuffer::<5>::new();
+        let mut buffer = GasPricesBuffer::new(5);
 
         // Add prices that don't divide evenly
         let prices =
@@ -322,7 +387,7 @@ mod tests {
 
     #[test]
     fn gas_prices_buffer_average_large_numbers() {
-        let mut buffer = GasPricesBuffer::<5>::new();
+        let mut buffer = GasPricesBuffer::new(5);
 
         let max_val = u128::MAX / 2; // Use half of max to avoid overflow
         let prices = unsafe { [GasPrices::new_unchecked(max_val, max_val), GasPrices::MIN] };
@@ -339,9 +404,52 @@ mod tests {
         assert_eq!(average.strk.get(), expected_strk);
     }
 
+    #[test]
+    fn gas_prices_buffer_median() {
+        let mut buffer = GasPricesBuffer::new(5);
+        assert_eq!(buffer.median(), GasPrices::MIN);
+
+        // Add prices out of order, with an outlier
+        for (eth, strk) in [(300, 30), (100, 10), (10_000, 1_000)] {
+            buffer.push(unsafe { GasPrices::new_unchecked(eth, strk) });
+        }
+
+        let median = buffer.median();
+        assert_eq!(median.eth.get(), 300);
+        assert_eq!(median.strk.get(), 30);
+
+        // With an even number of prices, the two middle prices are averaged and rounded up
+        buffer.push(unsafe { GasPrices::new_unchecked(200, 25) });
+
+        let median = buffer.median();
+        assert_eq!(median.eth.get(), 250);
+        assert_eq!(median.strk.get(), 28);
+    }
+
+    #[test]
+    fn gas_prices_buffer_percentile() {
+        let mut buffer = GasPricesBuffer::new(10);
+        assert_eq!(buffer.percentile(90), GasPrices::MIN);
+
+        for price in (1..=10).rev() {
+            buffer.push(unsafe { GasPrices::new_unchecked(price * 10, price) });
+        }
+
+        assert_eq!(buffer.percentile(0).eth.get(), 10);
+        assert_eq!(buffer.percentile(25).eth.get(), 30);
+        assert_eq!(buffer.percentile(50).eth.get(), 50);
+        assert_eq!(buffer.percentile(90).strk.get(), 9);
+        assert_eq!(buffer.percentile(100).strk.get(), 10);
+        assert_eq!(buffer.percentile(255).strk.get(), 10);
+
+        assert_eq!(buffer.aggregate(Aggregation::Percentile(50)), buffer.percentile(50));
+        assert_eq!(buffer.aggregate(Aggregation::Median), buffer.median());
+        assert_eq!(buffer.aggregate(Aggregation::Average), buffer.average());
+    }
+
     #[test]
     fn sliding_window_iterator_empty_buffer() {
-        let buffer = SlidingWindowBuffer::<i32, 5>::new();
+        let buffer = SlidingWindowBuffer::<i32>::new(5);
         let mut iter = buffer.iter();
 
         assert_eq!(iter.next(), None);
@@ -354,7 +462,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_partial_buffer() {
-        let mut buffer = SlidingWindowBuffer::<i32, 5>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(5);
         buffer.push(1);
         buffer.push(2);
         buffer.push(3);
@@ -383,7 +491,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_full_buffer_no_wrap() {
-        let mut buffer = SlidingWindowBuffer::<i32, 5>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(5);
         for i in 1..=5 {
             buffer.push(i);
         }
@@ -397,7 +505,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_with_wraparound() {
-        let mut buffer = SlidingWindowBuffer::<i32, 3>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(3);
 
         // Fill the buffer
         buffer.push(1);
@@ -420,7 +528,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_complex_wraparound() {
-        let mut buffer = SlidingWindowBuffer::<i32, 4>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(4);
 
         // Create a complex wraparound scenario
         for i in 1..=10 {
@@ -442,7 +550,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_after_pop() {
-        let mut buffer = SlidingWindowBuffer::<i32, 5>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(5);
 
         // Fill buffer
         for i in 1..=5 {
@@ -468,7 +576,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_multiple_iterations() {
-        let mut buffer = SlidingWindowBuffer::<i32, 3>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(3);
         buffer.push(1);
         buffer.push(2);
         buffer.push(3);
@@ -482,7 +590,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_size_hint_accuracy() {
-        let mut buffer = SlidingWindowBuffer::<i32, 4>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(4);
 
         // Test size hint at different stages
         let iter = buffer.iter();
@@ -516,7 +624,7 @@ mod tests {
 
     #[test]
     fn sliding_window_iterator_edge_case_single_element() {
-        let mut buffer = SlidingWindowBuffer::<i32, 1>::new();
+        let mut buffer = SlidingWindowBuffer::<i32>::new(1);
 
         buffer.push(1);
         assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1]);
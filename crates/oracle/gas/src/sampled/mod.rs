@@ -1,20 +1,32 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::future::Future;
+use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use backon::{ExponentialBuilder, Retryable};
 use buffer::GasPricesBuffer;
 use futures::future::BoxFuture;
+use katana_metrics::metrics::{Counter, Gauge};
+use katana_metrics::Metrics;
 use katana_primitives::block::GasPrices;
 use parking_lot::Mutex;
 use tracing::{error, warn};
 
+use crate::fixed::FixedPriceOracle;
+
 mod buffer;
 pub mod ethereum;
 pub mod starknet;
 
-const DEFAULT_SAMPLING_INTERVAL: Duration = Duration::from_secs(60);
+/// The default interval at which the gas prices are sampled.
+pub const DEFAULT_SAMPLING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The default number of samples the gas prices are aggregated over.
+pub const DEFAULT_WINDOW_SIZE: usize = 60;
+
+/// The default duration after which the samples are considered stale.
+pub const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(10 * 60);
 
 /// Trait for sampling gas prices from different blockchain networks.
 #[auto_impl::auto_impl(Box)]
@@ -23,6 +35,88 @@ pub trait Sampler: Debug + Send + Sync {
     fn sample(&self) -> BoxFuture<'_, anyhow::Result<SampledPrices>>;
 }
 
+/// How the samples are aggregated into a single price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Aggregation {
+    /// The average of the samples.
+    #[default]
+    Average,
+    /// The median of the samples.
+    Median,
+    /// The given percentile of the samples, from 0 to 100.
+    Percentile(u8),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid aggregation '{0}': expected 'average', 'median' or 'p<0-100>'")]
+pub struct InvalidAggregation(String);
+
+impl FromStr for Aggregation {
+    type Err = InvalidAggregation;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "average" | "avg" => Ok(Self::Average),
+            "median" => Ok(Self::Median),
+            _ => s
+                .strip_prefix('p')
+                .and_then(|p| p.parse::<u8>().ok())
+                .filter(|p| *p <= 100)
+                .map(Self::Percentile)
+                .ok_or_else(|| InvalidAggregation(s.to_string())),
+        }
+    }
+}
+
+impl Display for Aggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Average => write!(f, "average"),
+            Self::Median => write!(f, "median"),
+            Self::Percentile(p) => write!(f, "p{p}"),
+        }
+    }
+}
+
+/// Configuration of the [`SampledPriceOracle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampledPriceOracleConfig {
+    /// The interval at which the gas prices are sampled.
+    pub interval: Duration,
+    /// The number of most recent samples the gas prices are aggregated over.
+    pub window_size: usize,
+    /// How the samples are aggregated.
+    pub aggregation: Aggregation,
+    /// If no sample could be taken for this long, the samples are considered stale and the
+    /// fallback prices are used instead.
+    pub max_staleness: Duration,
+    /// The prices used when the samples are stale.
+    pub fallback_prices: SampledPrices,
+}
+
+impl Default for SampledPriceOracleConfig {
+    fn default() -> Self {
+        let fixed = FixedPriceOracle::default();
+        Self {
+            interval: DEFAULT_SAMPLING_INTERVAL,
+            window_size: DEFAULT_WINDOW_SIZE,
+            aggregation: Aggregation::default(),
+            max_staleness: DEFAULT_MAX_STALENESS,
+            fallback_prices: SampledPrices {
+                l2_gas_prices: fixed.l2_gas_prices().clone(),
+                l1_gas_prices: fixed.l1_gas_prices().clone(),
+                l1_data_gas_prices: fixed.l1_data_gas_prices().clone(),
+            },
+        }
+    }
+}
+
+/// A gas price oracle that periodically samples the gas prices from other networks.
+///
+/// The samplers are tried in order on every sampling round, so that the first one is the primary
+/// source and the others are only used when the ones before them fail. If no sampler succeeds for
+/// longer than [`max_staleness`](SampledPriceOracleConfig::max_staleness), the oracle returns the
+/// configured fallback prices until one does.
 #[derive(Debug)]
 pub struct SampledPriceOracle<S: Sampler> {
     inner: Arc<SampledPriceOracleInner<S>>,
@@ -34,32 +128,101 @@ impl<S: Sampler> Clone for SampledPriceOracle<S> {
     }
 }
 
-#[derive(Debug)]
 struct SampledPriceOracleInner<S> {
     samples: Mutex<Samples>,
+    samplers: Vec<SamplerEntry<S>>,
+    config: SampledPriceOracleConfig,
+    metrics: GasOracleMetrics,
+}
+
+impl<S: Debug> Debug for SampledPriceOracleInner<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SampledPriceOracleInner")
+            .field("samples", &self.samples)
+            .field("samplers", &self.samplers)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+struct SamplerEntry<S> {
     sampler: S,
+    health: Mutex<SamplerHealth>,
+    metrics: SamplerMetrics,
+}
+
+impl<S: Debug> Debug for SamplerEntry<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SamplerEntry")
+            .field("sampler", &self.sampler)
+            .field("health", &self.health)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The health of a sampler.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SamplerHealth {
+    /// When the sampler last returned prices.
+    pub last_success: Option<Instant>,
+    /// The number of times the sampler failed in a row.
+    pub consecutive_failures: u64,
+}
+
+impl SamplerHealth {
+    /// Returns `true` if the last attempt to sample from the sampler succeeded.
+    pub fn is_healthy(&self) -> bool {
+        self.last_success.is_some() && self.consecutive_failures == 0
+    }
 }
 
 impl<S: Sampler> SampledPriceOracle<S> {
+    /// Creates a new oracle sampling from a single sampler, with the default configuration.
     pub fn new(sampler: S) -> Self {
-        let samples = Mutex::new(Samples::new());
-        let inner = Arc::new(SampledPriceOracleInner { samples, sampler });
+        Self::with_config(vec![sampler], SampledPriceOracleConfig::default())
+    }
+
+    /// Creates a new oracle sampling from `samplers`, in order of priority.
+    pub fn with_config(samplers: Vec<S>, config: SampledPriceOracleConfig) -> Self {
+        let samplers = samplers
+            .into_iter()
+            .enumerate()
+            .map(|(index, sampler)| SamplerEntry {
+                sampler,
+                health: Mutex::default(),
+                metrics: SamplerMetrics::new_with_labels(&[("sampler", index.to_string())]),
+            })
+            .collect();
+
+        let samples = Mutex::new(Samples::new(config.window_size));
+        let metrics = GasOracleMetrics::default();
+        let inner = Arc::new(SampledPriceOracleInner { samples, samplers, config, metrics });
         Self { inner }
     }
 
-    /// Returns the average l2 gas prices of the samples.
-    pub fn avg_l2_gas_prices(&self) -> GasPrices {
-        self.inner.samples.lock().l2_gas_prices.average()
+    /// Returns the aggregated l2 gas prices of the samples.
+    pub fn l2_gas_prices(&self) -> GasPrices {
+        self.inner.prices().l2_gas_prices
     }
 
-    /// Returns the average l1 gas prices of the samples.
-    pub fn avg_l1_gas_prices(&self) -> GasPrices {
-        self.inner.samples.lock().l1_gas_prices.average()
+    /// Returns the aggregated l1 gas prices of the samples.
+    pub fn l1_gas_prices(&self) -> GasPrices {
+        self.inner.prices().l1_gas_prices
     }
 
-    /// Returns the average l1 data gas prices of the samples.
-    pub fn avg_l1_data_gas_prices(&self) -> GasPrices {
-        self.inner.samples.lock().l1_data_gas_prices.average()
+    /// Returns the aggregated l1 data gas prices of the samples.
+    pub fn l1_data_gas_prices(&self) -> GasPrices {
+        self.inner.prices().l1_data_gas_prices
+    }
+
+    /// Returns `true` if the samples are stale, in which case the fallback prices are used.
+    pub fn is_stale(&self) -> bool {
+        self.inner.samples.lock().is_stale(self.inner.config.max_staleness)
+    }
+
+    /// Returns the health of every sampler, in order of priority.
+    pub fn sampler_health(&self) -> Vec<SamplerHealth> {
+        self.inner.samplers.iter().map(|entry| entry.health.lock().clone()).collect()
     }
 }
 
@@ -68,59 +231,338 @@ impl<S: Sampler + 'static> SampledPriceOracle<S> {
     pub fn run_worker(&self) -> impl Future<Output = ()> + 'static {
         let inner = self.inner.clone();
 
-        // every 60 seconds, Starknet samples the base price of gas and data gas on L1
-        let mut interval = tokio::time::interval(DEFAULT_SAMPLING_INTERVAL);
+        // by default, every 60 seconds, like Starknet samples the base price of gas and data gas
+        // on L1
+        let mut interval = tokio::time::interval(inner.config.interval);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         async move {
             loop {
                 interval.tick().await;
 
-                let request = || async { inner.sampler.sample().await };
                 let backoff = ExponentialBuilder::default().with_min_delay(Duration::from_secs(3));
-                let future = request.retry(backoff).notify(|error, _| {
-                    warn!(target: "gas_oracle", %error, "Retrying gas prices sampling.");
-                });
-
-                match future.await {
-                    Ok(prices) => {
-                        let mut buffers = inner.samples.lock();
-                        buffers.l2_gas_prices.push(prices.l2_gas_prices);
-                        buffers.l1_gas_prices.push(prices.l1_gas_prices);
-                        buffers.l1_data_gas_prices.push(prices.l1_data_gas_prices);
-                    }
+                match inner.sample_with_retry(backoff).await {
+                    Ok(prices) => inner.samples.lock().push(prices),
                     Err(error) => {
                         error!(target: "gas_oracle", %error, "Failed to sample gas prices.")
                     }
                 }
+
+                inner.record_metrics();
+            }
+        }
+    }
+}
+
+impl<S: Sampler> SampledPriceOracleInner<S> {
+    /// Samples the gas prices from the first sampler that succeeds, retrying with an exponential
+    /// backoff if all of them fail.
+    ///
+    /// The health of the samplers is only recorded once all the attempts are done, so that a
+    /// sampler failing on every attempt counts as a single failure rather than one per attempt.
+    async fn sample_with_retry(
+        &self,
+        backoff: ExponentialBuilder,
+    ) -> anyhow::Result<SampledPrices> {
+        let outcomes = Mutex::new(vec![None; self.samplers.len()]);
+
+        let request = || self.try_sample(&outcomes);
+        let future = request.retry(backoff).notify(|error, _| {
+            warn!(target: "gas_oracle", %error, "Retrying gas prices sampling.");
+        });
+
+        let result = future.await;
+        self.record_outcomes(outcomes.into_inner());
+        result
+    }
+
+    /// Tries every sampler in order until one succeeds, storing whether each sampler that was
+    /// tried succeeded in `outcomes`.
+    async fn try_sample(
+        &self,
+        outcomes: &Mutex<Vec<Option<bool>>>,
+    ) -> anyhow::Result<SampledPrices> {
+        let mut last_error = None;
+
+        for (index, entry) in self.samplers.iter().enumerate() {
+            match entry.sampler.sample().await {
+                Ok(prices) => {
+                    outcomes.lock()[index] = Some(true);
+                    return Ok(prices);
+                }
+                Err(error) => {
+                    outcomes.lock()[index] = Some(false);
+                    warn!(target: "gas_oracle", sampler = index, %error, "Failed to sample gas prices from sampler.");
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("no gas price samplers configured")))
+    }
+
+    /// Records the latest outcome of every sampler that was tried.
+    fn record_outcomes(&self, outcomes: Vec<Option<bool>>) {
+        for (entry, outcome) in self.samplers.iter().zip(outcomes) {
+            if let Some(success) = outcome {
+                entry.record(success);
             }
         }
     }
+
+    /// Returns the aggregated prices, or the fallback prices if the samples are stale.
+    fn prices(&self) -> SampledPrices {
+        let samples = self.samples.lock();
+        if samples.is_stale(self.config.max_staleness) {
+            self.config.fallback_prices.clone()
+        } else {
+            samples.aggregate(self.config.aggregation)
+        }
+    }
+
+    fn record_metrics(&self) {
+        let prices = self.prices();
+        let metrics = &self.metrics;
+
+        metrics.l2_gas_price_wei.set(prices.l2_gas_prices.eth.get() as f64);
+        metrics.l2_gas_price_fri.set(prices.l2_gas_prices.strk.get() as f64);
+        metrics.l1_gas_price_wei.set(prices.l1_gas_prices.eth.get() as f64);
+        metrics.l1_gas_price_fri.set(prices.l1_gas_prices.strk.get() as f64);
+        metrics.l1_data_gas_price_wei.set(prices.l1_data_gas_prices.eth.get() as f64);
+        metrics.l1_data_gas_price_fri.set(prices.l1_data_gas_prices.strk.get() as f64);
+
+        let stale = self.samples.lock().is_stale(self.config.max_staleness);
+        metrics.stale.set(if stale { 1.0 } else { 0.0 });
+    }
 }
 
-/// The default sample size for the gas prices buffer.
-const SAMPLE_SIZE: usize = 60;
+impl<S> SamplerEntry<S> {
+    fn record(&self, success: bool) {
+        let mut health = self.health.lock();
+
+        if success {
+            health.last_success = Some(Instant::now());
+            health.consecutive_failures = 0;
+            self.metrics.samples_succeeded.increment(1);
+        } else {
+            health.consecutive_failures += 1;
+            self.metrics.samples_failed.increment(1);
+        }
+
+        self.metrics.healthy.set(if health.is_healthy() { 1.0 } else { 0.0 });
+    }
+}
 
 #[derive(Debug, Clone)]
 struct Samples {
-    l2_gas_prices: GasPricesBuffer<SAMPLE_SIZE>,
-    l1_gas_prices: GasPricesBuffer<SAMPLE_SIZE>,
-    l1_data_gas_prices: GasPricesBuffer<SAMPLE_SIZE>,
+    l2_gas_prices: GasPricesBuffer,
+    l1_gas_prices: GasPricesBuffer,
+    l1_data_gas_prices: GasPricesBuffer,
+    last_sampled_at: Option<Instant>,
 }
 
 impl Samples {
-    fn new() -> Self {
+    fn new(window_size: usize) -> Self {
         Self {
-            l2_gas_prices: GasPricesBuffer::new(),
-            l1_gas_prices: GasPricesBuffer::new(),
-            l1_data_gas_prices: GasPricesBuffer::new(),
+            l2_gas_prices: GasPricesBuffer::new(window_size),
+            l1_gas_prices: GasPricesBuffer::new(window_size),
+            l1_data_gas_prices: GasPricesBuffer::new(window_size),
+            last_sampled_at: None,
+        }
+    }
+
+    fn push(&mut self, prices: SampledPrices) {
+        self.l2_gas_prices.push(prices.l2_gas_prices);
+        self.l1_gas_prices.push(prices.l1_gas_prices);
+        self.l1_data_gas_prices.push(prices.l1_data_gas_prices);
+        self.last_sampled_at = Some(Instant::now());
+    }
+
+    fn is_stale(&self, max_staleness: Duration) -> bool {
+        self.last_sampled_at.is_none_or(|at| at.elapsed() > max_staleness)
+    }
+
+    fn aggregate(&self, aggregation: Aggregation) -> SampledPrices {
+        SampledPrices {
+            l2_gas_prices: self.l2_gas_prices.aggregate(aggregation),
+            l1_gas_prices: self.l1_gas_prices.aggregate(aggregation),
+            l1_data_gas_prices: self.l1_data_gas_prices.aggregate(aggregation),
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct SampledPrices {
     pub l2_gas_prices: GasPrices,
     pub l1_gas_prices: GasPrices,
     pub l1_data_gas_prices: GasPrices,
 }
+
+/// Metrics for the sampled gas prices.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "gas_oracle")]
+struct GasOracleMetrics {
+    /// The current L2 gas price in wei
+    l2_gas_price_wei: Gauge,
+    /// The current L2 gas price in fri
+    l2_gas_price_fri: Gauge,
+    /// The current L1 gas price in wei
+    l1_gas_price_wei: Gauge,
+    /// The current L1 gas price in fri
+    l1_gas_price_fri: Gauge,
+    /// The current L1 data gas price in wei
+    l1_data_gas_price_wei: Gauge,
+    /// The current L1 data gas price in fri
+    l1_data_gas_price_fri: Gauge,
+    /// Whether the samples are stale and the fallback prices are used (1) or not (0)
+    stale: Gauge,
+}
+
+/// Metrics for a single gas price sampler.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "gas_oracle.sampler")]
+struct SamplerMetrics {
+    /// The number of successful samples
+    samples_succeeded: Counter,
+    /// The number of failed samples
+    samples_failed: Counter,
+    /// Whether the last sample succeeded (1) or not (0)
+    healthy: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use backon::ExponentialBuilder;
+    use futures::future::BoxFuture;
+    use katana_primitives::block::GasPrices;
+
+    use super::{
+        Aggregation, SampledPriceOracle, SampledPriceOracleConfig, SampledPrices, Sampler,
+    };
+
+    #[derive(Debug, Default)]
+    struct MockSampler {
+        price: Option<u128>,
+        calls: AtomicUsize,
+    }
+
+    impl MockSampler {
+        fn ok(price: u128) -> Self {
+            Self { price: Some(price), ..Default::default() }
+        }
+
+        fn failing() -> Self {
+            Self::default()
+        }
+    }
+
+    impl Sampler for MockSampler {
+        fn sample(&self) -> BoxFuture<'_, anyhow::Result<SampledPrices>> {
+            Box::pin(async move {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                let price = self.price.ok_or_else(|| anyhow::anyhow!("unavailable"))?;
+                let prices = unsafe { GasPrices::new_unchecked(price, price) };
+                Ok(SampledPrices {
+                    l2_gas_prices: prices.clone(),
+                    l1_gas_prices: prices.clone(),
+                    l1_data_gas_prices: prices,
+                })
+            })
+        }
+    }
+
+    fn no_retry() -> ExponentialBuilder {
+        ExponentialBuilder::default().with_max_times(0)
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_sampler() {
+        let samplers = vec![MockSampler::failing(), MockSampler::ok(100), MockSampler::ok(200)];
+        let oracle = SampledPriceOracle::with_config(samplers, Default::default());
+
+        let prices = oracle.inner.sample_with_retry(no_retry()).await.unwrap();
+        oracle.inner.samples.lock().push(prices);
+
+        assert_eq!(oracle.l1_gas_prices().eth.get(), 100);
+        assert_eq!(oracle.inner.samplers[2].sampler.calls.load(Ordering::SeqCst), 0);
+
+        let health = oracle.sampler_health();
+        assert!(!health[0].is_healthy());
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert!(health[1].is_healthy());
+        assert_eq!(health[2].last_success, None);
+    }
+
+    #[tokio::test]
+    async fn fails_when_all_samplers_fail() {
+        let samplers = vec![MockSampler::failing(), MockSampler::failing()];
+        let oracle = SampledPriceOracle::with_config(samplers, Default::default());
+
+        assert!(oracle.inner.sample_with_retry(no_retry()).await.is_err());
+        assert!(oracle.sampler_health().iter().all(|health| health.consecutive_failures == 1));
+    }
+
+    #[tokio::test]
+    async fn retries_count_as_a_single_failure() {
+        let samplers = vec![MockSampler::failing(), MockSampler::failing()];
+        let oracle = SampledPriceOracle::with_config(samplers, Default::default());
+
+        let backoff = ExponentialBuilder::default()
+            .with_min_delay(Duration::from_millis(1))
+            .with_max_times(3);
+        assert!(oracle.inner.sample_with_retry(backoff).await.is_err());
+
+        for entry in &oracle.inner.samplers {
+            assert_eq!(entry.sampler.calls.load(Ordering::SeqCst), 4);
+            assert_eq!(entry.health.lock().consecutive_failures, 1);
+        }
+    }
+
+    #[test]
+    fn uses_fallback_prices_when_stale() {
+        let fallback = unsafe { GasPrices::new_unchecked(42, 43) };
+        let config = SampledPriceOracleConfig {
+            max_staleness: Duration::from_millis(100),
+            fallback_prices: SampledPrices {
+                l2_gas_prices: fallback.clone(),
+                l1_gas_prices: fallback.clone(),
+                l1_data_gas_prices: fallback.clone(),
+            },
+            ..Default::default()
+        };
+
+        let oracle = SampledPriceOracle::with_config(vec![MockSampler::ok(1)], config);
+
+        // no samples yet
+        assert!(oracle.is_stale());
+        assert_eq!(oracle.l1_gas_prices(), fallback);
+
+        let prices = unsafe { GasPrices::new_unchecked(7, 8) };
+        oracle.inner.samples.lock().push(SampledPrices {
+            l2_gas_prices: prices.clone(),
+            l1_gas_prices: prices.clone(),
+            l1_data_gas_prices: prices.clone(),
+        });
+
+        assert!(!oracle.is_stale());
+        assert_eq!(oracle.l1_gas_prices(), prices);
+
+        std::thread::sleep(Duration::from_millis(200));
+
+        assert!(oracle.is_stale());
+        assert_eq!(oracle.l1_data_gas_prices(), fallback);
+    }
+
+    #[test]
+    fn parse_aggregation() {
+        assert_eq!("average".parse::<Aggregation>().unwrap(), Aggregation::Average);
+        assert_eq!("median".parse::<Aggregation>().unwrap(), Aggregation::Median);
+        assert_eq!("p90".parse::<Aggregation>().unwrap(), Aggregation::Percentile(90));
+        assert!("p101".parse::<Aggregation>().is_err());
+        assert!("mean".parse::<Aggregation>().is_err());
+        assert_eq!(Aggregation::Percentile(75).to_string(), "p75");
+    }
+}
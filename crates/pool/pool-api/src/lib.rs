@@ -59,6 +59,9 @@ pub trait TransactionPool: Send + Sync {
 
     fn add_listener(&self) -> Receiver<TxHash>;
 
+    /// Returns a [`Subscription`] which yields the transactions added to the pool from now on.
+    fn subscribe(&self) -> Subscription<Self::Transaction, Self::Ordering>;
+
     /// Removes a list of transactions from the pool according to their hashes.
     fn remove_transactions(&self, hashes: &[TxHash]);
 
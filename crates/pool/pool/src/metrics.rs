@@ -0,0 +1,26 @@
+use katana_metrics::Metrics;
+use metrics::{Counter, Gauge, Histogram};
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "pool")]
+pub(crate) struct PoolMetrics {
+    /// The number of pending transactions in the pool.
+    pub(crate) pending_transactions: Gauge,
+    /// The number of transactions added to the pool.
+    pub(crate) added_transactions_total: Counter,
+    /// The number of transactions rejected for being invalid.
+    pub(crate) invalid_transactions_total: Counter,
+    /// The number of transactions rejected for having a nonce higher than the sender's next
+    /// nonce.
+    pub(crate) dependent_transactions_total: Counter,
+    /// The time it takes to validate a transaction.
+    pub(crate) validation_time_seconds: Histogram,
+    /// The time a transaction spends in the pool before being included in a block.
+    pub(crate) time_in_pool_seconds: Histogram,
+}
+
+impl std::fmt::Debug for PoolMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolMetrics").finish_non_exhaustive()
+    }
+}
@@ -2,6 +2,7 @@ use core::fmt;
 use std::collections::BTreeSet;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
 
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use katana_pool_api::validation::{InvalidTransactionError, ValidationOutcome, Validator};
@@ -16,6 +17,8 @@ use parking_lot::RwLock;
 use tokio::sync::mpsc;
 use tracing::{error, trace, warn, Instrument};
 
+use crate::metrics::PoolMetrics;
+
 #[derive(Debug)]
 pub struct Pool<T, V, O>
 where
@@ -42,6 +45,8 @@ struct Inner<T, V, O: PoolOrd> {
 
     /// the ordering mechanism used to order the txs in the pool
     ordering: O,
+
+    metrics: PoolMetrics,
 }
 
 impl<T, V, O> Pool<T, V, O>
@@ -59,6 +64,7 @@ where
                 transactions: Default::default(),
                 subscribers: Default::default(),
                 listeners: Default::default(),
+                metrics: PoolMetrics::default(),
             }),
         }
     }
@@ -114,12 +120,6 @@ where
         self.notify_listener(tx.tx.hash());
         self.notify_subscribers(tx);
     }
-
-    fn subscribe(&self) -> Subscription<T, O> {
-        let (subscriber, tx) = Subscription::new();
-        self.inner.subscribers.write().push(tx);
-        subscriber
-    }
 }
 
 impl<T, V, O> TransactionPool for Pool<T, V, O>
@@ -140,7 +140,11 @@ where
         let id = TxId::new(tx.sender(), tx.nonce());
 
         async move {
-            match pool.inner.validator.validate(tx).await {
+            let start = Instant::now();
+            let result = pool.inner.validator.validate(tx).await;
+            pool.inner.metrics.validation_time_seconds.record(start.elapsed().as_secs_f64());
+
+            match result {
 	            Ok(outcome) => {
 	                match outcome {
 	                    ValidationOutcome::Valid(tx) => {
@@ -149,7 +153,14 @@ where
 	                        let tx = PendingTx::new(id, tx, priority);
 
 	                        // insert the tx in the pool
-	                        pool.inner.transactions.write().insert(tx.clone());
+	                        let size = {
+	                            let mut txs = pool.inner.transactions.write();
+	                            txs.insert(tx.clone());
+	                            txs.len()
+	                        };
+
+	                        pool.inner.metrics.added_transactions_total.increment(1);
+	                        pool.inner.metrics.pending_transactions.set(size as f64);
 	                        trace!(target: "pool", "Transaction added to the pool");
 
 	                        pool.notify(tx);
@@ -161,6 +172,7 @@ where
 	                    // `getTransactionStatus`
 	                    ValidationOutcome::Invalid { error, .. } => {
 	                        warn!(target: "pool", %error, "Invalid transaction.");
+	                        pool.inner.metrics.invalid_transactions_total.increment(1);
 	                        Err(PoolError::InvalidTransaction(Box::new(error)))
 	                    }
 
@@ -168,6 +180,7 @@ where
 	                    // queue and revalidate it when the parent tx is added to the pool
 	                    ValidationOutcome::Dependent { tx, tx_nonce, current_nonce } => {
 	                        trace!(target: "pool", %tx_nonce, %current_nonce, "Dependent transaction.");
+	                        pool.inner.metrics.dependent_transactions_total.increment(1);
 	                        let err = InvalidTransactionError::InvalidNonce {
 	                            address: tx.sender(),
 	                            current_nonce,
@@ -216,10 +229,26 @@ where
         rx
     }
 
+    fn subscribe(&self) -> Subscription<T, O> {
+        let (subscriber, tx) = Subscription::new();
+        self.inner.subscribers.write().push(tx);
+        subscriber
+    }
+
     fn remove_transactions(&self, hashes: &[TxHash]) {
         // retain only transactions that aren't included in the list
         let mut txs = self.inner.transactions.write();
-        txs.retain(|t| !hashes.contains(&t.tx.hash()))
+        txs.retain(|t| {
+            if hashes.contains(&t.tx.hash()) {
+                let time_in_pool = t.added_at.elapsed().as_secs_f64();
+                self.inner.metrics.time_in_pool_seconds.record(time_in_pool);
+                false
+            } else {
+                true
+            }
+        });
+
+        self.inner.metrics.pending_transactions.set(txs.len() as f64);
     }
 
     fn size(&self) -> usize {
@@ -247,6 +276,7 @@ where
 
     fn clear(&self) {
         self.inner.transactions.write().clear();
+        self.inner.metrics.pending_transactions.set(0.0);
     }
 }
 
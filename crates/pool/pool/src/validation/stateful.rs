@@ -77,6 +77,16 @@ impl TxValidator {
         this.block_env = block_env;
         this.state = Arc::new(new_state);
     }
+
+    /// Same as [`update`](Self::update), but also forgets the nonces of the transactions that
+    /// were validated so far. This method is used when the pool is cleared and the validator needs
+    /// to start over from the new state.
+    pub fn reset(&self, new_state: Box<dyn StateProvider>, block_env: BlockEnv) {
+        let mut this = self.inner.lock();
+        this.block_env = block_env;
+        this.state = Arc::new(new_state);
+        this.pool_nonces.clear();
+    }
 }
 
 impl Debug for Inner {
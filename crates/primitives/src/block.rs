@@ -3,8 +3,11 @@ use std::num::NonZeroU128;
 use std::str::FromStr;
 
 use num_traits::ToPrimitive;
+use starknet::core::crypto::{EcdsaSignError, Signature};
 use starknet::core::types::ResourcePrice;
 use starknet::core::utils::cairo_short_string_to_felt;
+use starknet::signers::{SigningKey, VerifyingKey};
+use starknet_types_core::hash::{Pedersen, StarkHash};
 
 use crate::cairo::ShortString;
 use crate::contract::ContractAddress;
@@ -487,6 +490,49 @@ pub struct SealedBlockWithStatus {
     pub status: FinalityStatus,
 }
 
+/// A sequencer signature over a block.
+///
+/// The signed message is `pedersen(block_hash, state_diff_commitment)`, which commits to both the
+/// block header and the state changes introduced by the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockSignature {
+    pub r: Felt,
+    pub s: Felt,
+}
+
+impl BlockSignature {
+    /// Computes the message that is signed for a block.
+    pub fn message_hash(block_hash: BlockHash, state_diff_commitment: Felt) -> Felt {
+        Pedersen::hash(&block_hash, &state_diff_commitment)
+    }
+
+    /// Signs the block identified by `block_hash` and `state_diff_commitment`.
+    pub fn sign(
+        signing_key: &SigningKey,
+        block_hash: BlockHash,
+        state_diff_commitment: Felt,
+    ) -> Result<Self, EcdsaSignError> {
+        let message = Self::message_hash(block_hash, state_diff_commitment);
+        let signature = signing_key.sign(&message)?;
+        Ok(Self { r: signature.r, s: signature.s })
+    }
+
+    /// Returns `true` if this is a valid signature by `public_key` over the block identified by
+    /// `block_hash` and `state_diff_commitment`.
+    pub fn verify(
+        &self,
+        public_key: Felt,
+        block_hash: BlockHash,
+        state_diff_commitment: Felt,
+    ) -> bool {
+        let message = Self::message_hash(block_hash, state_diff_commitment);
+        let signature = Signature { r: self.r, s: self.s };
+        VerifyingKey::from_scalar(public_key).verify(&message, &signature).unwrap_or(false)
+    }
+}
+
 /// A block that can executed. This is a block whose transactions includes
 /// all the necessary information to be executed.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -698,4 +744,22 @@ mod tests {
         let actual = Header::concat_counts(0xFFFFFFFF, 0, 0, L1DataAvailabilityMode::Calldata);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn block_signature_sign_and_verify() {
+        let signing_key = SigningKey::from_secret_scalar(felt!("0x1337", crate));
+        let public_key = signing_key.verifying_key().scalar();
+
+        let block_hash = felt!("0x123", crate);
+        let state_diff_commitment = felt!("0x456", crate);
+
+        let signature =
+            BlockSignature::sign(&signing_key, block_hash, state_diff_commitment).unwrap();
+        assert!(signature.verify(public_key, block_hash, state_diff_commitment));
+
+        // signature must not be valid for a different block, state diff or public key
+        assert!(!signature.verify(public_key, felt!("0x124", crate), state_diff_commitment));
+        assert!(!signature.verify(public_key, block_hash, felt!("0x457", crate)));
+        assert!(!signature.verify(felt!("0x1", crate), block_hash, state_diff_commitment));
+    }
 }
@@ -0,0 +1,254 @@
+//! Structured representation of transaction revert errors.
+//!
+//! The revert reason of a transaction is a human-readable error stack produced by the executor,
+//! where each call that failed is represented by an `Error in ... contract (...)` frame, optionally
+//! followed by the VM program counter at which it failed, and ending with the panic data of the
+//! innermost call. [`RevertStack::parse`] recovers that structure from the error string.
+
+use crate::class::ClassHash;
+use crate::execution::EntryPointSelector;
+use crate::{ContractAddress, Felt};
+
+/// The first felt of the panic data of a `ByteArray` panic (ie `panic!("...")`).
+pub const BYTE_ARRAY_PANIC_MAGIC: Felt =
+    Felt::from_hex_unchecked("0x46a6158a16a947e5916b2a2ca68501a45e93d7110e81aa2d6438b1c57c879a3");
+
+/// The structured form of a transaction revert error.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RevertStack {
+    /// The failed calls, from the outermost call to the one that panicked.
+    pub frames: Vec<RevertFrame>,
+    /// The panic data of the innermost call.
+    pub panic_data: Vec<Felt>,
+    /// The panic data decoded as Cairo strings, if it is made of short strings or is a
+    /// `ByteArray` panic.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub panic_reason: Option<String>,
+}
+
+/// A call frame of a revert error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RevertFrame {
+    /// The address of the contract being called.
+    pub contract_address: ContractAddress,
+    /// The class of the contract being called, if known.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub class_hash: Option<ClassHash>,
+    /// The selector of the entry point being called, if known.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub selector: Option<EntryPointSelector>,
+    /// The program counter at which the call failed, if reported by the VM.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub pc: Option<u64>,
+    /// The Cairo function at [`Self::pc`], if the class has Sierra debug info.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub function: Option<String>,
+}
+
+impl RevertStack {
+    /// Parses the error stack out of a revert reason.
+    ///
+    /// Parsing is lenient: lines that aren't recognized are ignored. Returns `None` if neither a
+    /// frame nor panic data could be parsed out of the reason (eg, for plain error messages).
+    pub fn parse(reason: &str) -> Option<Self> {
+        let mut stack = Self::default();
+        let mut last_line = None;
+
+        for line in reason.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(frame) = parse_frame(line) {
+                stack.frames.push(frame);
+            } else if let Some(pc) = line.strip_prefix("Error at pc=") {
+                // Only the first pc of a frame is where it failed, the rest is the traceback.
+                if let Some(frame) = stack.frames.last_mut().filter(|f| f.pc.is_none()) {
+                    frame.pc = parse_pc(pc.trim_end_matches(':'));
+                }
+            } else {
+                last_line = Some(line);
+            }
+        }
+
+        // The panic data, if any, is at the very end of the error.
+        if let Some(line) = last_line {
+            let line =
+                line.strip_prefix("Execution failed. Failure reason:").unwrap_or(line).trim();
+            if line.starts_with("0x") || line.starts_with("(0x") {
+                stack.panic_data = parse_panic_data(line);
+                stack.panic_reason = decode_panic_data(&stack.panic_data);
+            }
+        }
+
+        (!stack.frames.is_empty() || !stack.panic_data.is_empty()).then_some(stack)
+    }
+}
+
+/// Parses a frame header, eg:
+///
+/// `0: Error in the called contract (contract address: 0x1, class hash: 0x2, selector: 0x3):`
+fn parse_frame(line: &str) -> Option<RevertFrame> {
+    let start = line.find("(contract address: ")?;
+    let fields = line[start + 1..].trim_end_matches(':').strip_suffix(')')?;
+
+    let mut contract_address = None;
+    let mut class_hash = None;
+    let mut selector = None;
+
+    for field in fields.split(", ") {
+        let (key, value) = field.split_once(": ")?;
+        let value = Felt::from_hex(value.trim()).ok();
+        match key {
+            "contract address" => contract_address = value,
+            "class hash" => class_hash = value,
+            "selector" => selector = value,
+            _ => {}
+        }
+    }
+
+    Some(RevertFrame {
+        contract_address: contract_address?.into(),
+        class_hash,
+        selector,
+        pc: None,
+        function: None,
+    })
+}
+
+/// Parses the offset of a relocatable program counter, eg `0:4835`.
+fn parse_pc(pc: &str) -> Option<u64> {
+    let (_, offset) = pc.split_once(':').unwrap_or(("", pc));
+    offset.trim().parse().ok()
+}
+
+/// Parses the felts of a formatted panic data, eg `(0x1 ('a'), 0x2)`.
+fn parse_panic_data(line: &str) -> Vec<Felt> {
+    line.split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ','))
+        .map(|token| token.trim_end_matches('.'))
+        .filter(|token| token.starts_with("0x"))
+        .filter_map(|token| Felt::from_hex(token).ok())
+        .collect()
+}
+
+/// Decodes panic data made of a `ByteArray` or of short strings.
+fn decode_panic_data(data: &[Felt]) -> Option<String> {
+    if data.first() == Some(&BYTE_ARRAY_PANIC_MAGIC) {
+        return decode_byte_array(&data[1..]);
+    }
+
+    let strings = data.iter().map(|felt| decode_short_string(*felt)).collect::<Option<Vec<_>>>()?;
+    (!strings.is_empty()).then(|| strings.join(", "))
+}
+
+/// Decodes a serialized `ByteArray`: `[num_full_words, ...full_words, pending_word, pending_len]`.
+fn decode_byte_array(data: &[Felt]) -> Option<String> {
+    let (len, rest) = data.split_first()?;
+    let len = usize::try_from(u64::try_from(*len).ok()?).ok()?;
+
+    let (words, rest) = rest.split_at_checked(len)?;
+    let [pending_word, pending_len] = rest else { return None };
+    let pending_len = usize::try_from(u64::try_from(*pending_len).ok()?).ok()?;
+    if pending_len > 31 {
+        return None;
+    }
+
+    let mut bytes = Vec::new();
+    for word in words {
+        bytes.extend_from_slice(&word.to_bytes_be()[1..]);
+    }
+    bytes.extend_from_slice(&pending_word.to_bytes_be()[32 - pending_len..]);
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Decodes a felt as a short string if all of its bytes are printable ASCII.
+fn decode_short_string(felt: Felt) -> Option<String> {
+    let bytes = felt.to_bytes_be();
+    let bytes = &bytes[bytes.iter().position(|b| *b != 0)?..];
+
+    if bytes.len() > 31 || !bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, felt};
+
+    #[test]
+    fn parse_nested_cairo1_revert() {
+        let reason = "Transaction execution has failed:
+0: Error in the called contract (contract address: 0xabc, class hash: 0xdef, selector: \
+                      0x015d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad):
+Error at pc=0:4835:
+Cairo traceback (most recent call last):
+Unknown location (pc=0:67)
+Unknown location (pc=0:1835)
+
+1: Error in the called contract (contract address: 0x123, class hash: 0x456, selector: 0x789):
+Execution failed. Failure reason:
+Error in contract (contract address: 0x123, class hash: 0x456, selector: 0x789):
+0x753235365f737562204f766572666c6f77 ('u256_sub Overflow').
+";
+
+        let stack = RevertStack::parse(reason).unwrap();
+
+        assert_eq!(stack.frames.len(), 3);
+        assert_eq!(
+            stack.frames[0],
+            RevertFrame {
+                contract_address: address!("0xabc", crate),
+                class_hash: Some(felt!("0xdef", crate)),
+                selector: Some(felt!(
+                    "0x15d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad",
+                    crate
+                )),
+                pc: Some(4835),
+                function: None,
+            }
+        );
+        assert_eq!(stack.frames[1].contract_address, address!("0x123", crate));
+        assert_eq!(stack.frames[1].pc, None);
+        assert_eq!(stack.panic_data, vec![felt!("0x753235365f737562204f766572666c6f77", crate)]);
+        assert_eq!(stack.panic_reason.as_deref(), Some("u256_sub Overflow"));
+    }
+
+    #[test]
+    fn parse_unknown_class_hash_and_selector() {
+        let reason = "Error in contract (contract address: 0x1, class hash: _, selector: UNKNOWN):
+(0x617267656e744f7574 ('argentOut'), 0x0 (''), 0x1).";
+
+        let stack = RevertStack::parse(reason).unwrap();
+
+        assert_eq!(stack.frames.len(), 1);
+        assert_eq!(stack.frames[0].class_hash, None);
+        assert_eq!(stack.frames[0].selector, None);
+        assert_eq!(
+            stack.panic_data,
+            vec![felt!("0x617267656e744f7574", crate), Felt::ZERO, Felt::ONE]
+        );
+        // Not every felt is a short string.
+        assert_eq!(stack.panic_reason, None);
+    }
+
+    #[test]
+    fn decode_byte_array_panic() {
+        let data = [
+            BYTE_ARRAY_PANIC_MAGIC,
+            Felt::ZERO,
+            felt!("0x6e6f7420656e6f7567682066756e6473", crate),
+            felt!("0x10", crate),
+        ];
+        assert_eq!(decode_panic_data(&data).as_deref(), Some("not enough funds"));
+    }
+
+    #[test]
+    fn parse_plain_message() {
+        let stack = RevertStack::parse("Insufficient max L2Gas: max amount: 10, actual used: 20.");
+        assert_eq!(stack, None);
+    }
+}
@@ -52,6 +52,26 @@ impl StateUpdates {
     }
 }
 
+/// The parts of the state that were accessed while executing a transaction.
+///
+/// Unlike [`StateUpdates`], this includes the state that was only read, but not the values that
+/// were written.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateAccesses {
+    /// A mapping of contract addresses to the storage keys that were read.
+    pub storage_reads: BTreeMap<ContractAddress, BTreeSet<StorageKey>>,
+    /// A mapping of contract addresses to the storage keys that were written.
+    pub storage_writes: BTreeMap<ContractAddress, BTreeSet<StorageKey>>,
+    /// The contracts whose nonce was read or written.
+    pub nonces: BTreeSet<ContractAddress>,
+    /// The contracts whose class hash was read or written.
+    pub class_hashes: BTreeSet<ContractAddress>,
+    /// The classes that were loaded for execution.
+    pub classes: BTreeSet<ClassHash>,
+}
+
 /// State update with declared classes artifacts.
 #[derive(Debug, Default, Clone)]
 pub struct StateUpdatesWithClasses {
@@ -1,8 +1,10 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
+use katana_primitives::block::ConfirmedBlockIdOrTag;
 use katana_primitives::contract::{StorageKey, StorageValue};
 use katana_primitives::ContractAddress;
 use katana_rpc_types::account::Account;
+use url::Url;
 
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "dev"))]
 #[cfg_attr(feature = "client", rpc(client, server, namespace = "dev"))]
@@ -29,4 +31,16 @@ pub trait DevApi {
 
     #[method(name = "predeployedAccounts")]
     async fn predeployed_accounts(&self) -> RpcResult<Vec<Account>>;
+
+    /// Re-forks the chain without restarting the node. Only available when the node is forked.
+    ///
+    /// All the local changes are dropped and the chain is forked again at `block` of the network
+    /// at `url`. If `url` is not given, the currently forked network is used. If `block` is not
+    /// given, the latest block of the network is used.
+    #[method(name = "resetFork")]
+    async fn reset_fork(
+        &self,
+        url: Option<Url>,
+        block: Option<ConfirmedBlockIdOrTag>,
+    ) -> RpcResult<()>;
 }
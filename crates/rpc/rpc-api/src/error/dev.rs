@@ -8,6 +8,10 @@ pub enum DevApiError {
     PendingTransactions,
     #[error("An unexpected error occurred: {}", .0.reason)]
     UnexpectedError(UnexpectedErrorData),
+    #[error("Node is not running in forking mode.")]
+    NotForked,
+    #[error("Failed to reset fork: {0}")]
+    ResetForkFailed(String),
 }
 
 impl DevApiError {
@@ -31,6 +35,10 @@ impl From<DevApiError> for ErrorObjectOwned {
             DevApiError::UnexpectedError(data) => {
                 ErrorObjectOwned::owned(2, err.to_string(), Some(data))
             }
+            DevApiError::NotForked => ErrorObjectOwned::owned(3, err.to_string(), None::<()>),
+            DevApiError::ResetForkFailed(..) => {
+                ErrorObjectOwned::owned(4, err.to_string(), None::<()>)
+            }
         }
     }
 }
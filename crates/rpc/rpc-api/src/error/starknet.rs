@@ -1,4 +1,5 @@
 use katana_primitives::block::BlockNumber;
+use katana_primitives::revert::RevertStack;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -130,6 +131,7 @@ impl StarknetApiError {
         StarknetApiError::TransactionExecutionError(TransactionExecutionErrorData {
             execution_error: reason.to_string(),
             transaction_index,
+            revert_error: None,
         })
     }
 
@@ -222,6 +224,9 @@ pub struct TransactionExecutionErrorData {
     pub transaction_index: u64,
     /// The revert error with the execution trace up to the point of failure.
     pub execution_error: String,
+    /// The structured form of [`Self::execution_error`]. This is a Katana extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_error: Option<RevertStack>,
 }
 
 /// Data for the [`StarknetApiError::StorageProofNotSupported`] error.
@@ -529,6 +534,7 @@ mod tests {
         StarknetApiError::TransactionExecutionError(TransactionExecutionErrorData {
             transaction_index: 1,
             execution_error: "Transaction execution error message".to_string(),
+            revert_error: None,
         }),
         41,
         "Transaction execution error",
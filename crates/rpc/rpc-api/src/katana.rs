@@ -1,9 +1,17 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
+use katana_primitives::block::BlockIdOrTag;
+use katana_primitives::transaction::TxHash;
 use katana_rpc_types::broadcasted::{
     BroadcastedDeclareTx, BroadcastedDeployAccountTx, BroadcastedInvokeTx,
 };
+use katana_rpc_types::overrides::{BlockOverride, StateOverride};
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
+use katana_rpc_types::trace::{DecodedTxTrace, SimulatedTransactionsResponse};
+use katana_rpc_types::{
+    BroadcastedTx, CallResponse, EstimateFeeSimulationFlag, FeeEstimate, FunctionCall,
+    KatanaSimulationFlag,
+};
 
 /// Katana-specific JSON-RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "katana"))]
@@ -41,4 +49,57 @@ pub trait KatanaApi {
         &self,
         deploy_account_transaction: BroadcastedDeployAccountTx,
     ) -> RpcResult<TxReceiptWithBlockInfo>;
+
+    /// Returns the execution trace of a transaction with its calls and events decoded.
+    ///
+    /// The trace has the same call tree as `starknet_traceTransaction`, but each invocation is
+    /// annotated with the function name, arguments and return values decoded using the Sierra ABI
+    /// of the class being executed. Invocations of classes without a Sierra ABI are left
+    /// undecoded.
+    #[method(name = "traceTransactionDecoded")]
+    async fn trace_transaction_decoded(
+        &self,
+        transaction_hash: TxHash,
+    ) -> RpcResult<DecodedTxTrace>;
+
+    /// Calls a contract function, with optional overrides of the state and block context.
+    ///
+    /// This is `starknet_call` executed on top of the given overrides. The overrides only apply
+    /// to this call and are never persisted.
+    #[method(name = "call")]
+    async fn call_with_overrides(
+        &self,
+        request: FunctionCall,
+        block_id: BlockIdOrTag,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> RpcResult<CallResponse>;
+
+    /// Estimates the fee of transactions, with optional overrides of the state and block context.
+    ///
+    /// This is `starknet_estimateFee` executed on top of the given overrides. The overrides only
+    /// apply to this estimation and are never persisted.
+    #[method(name = "estimateFee")]
+    async fn estimate_fee_with_overrides(
+        &self,
+        request: Vec<BroadcastedTx>,
+        simulation_flags: Vec<EstimateFeeSimulationFlag>,
+        block_id: BlockIdOrTag,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> RpcResult<Vec<FeeEstimate>>;
+
+    /// Simulates transactions, with optional overrides of the state and block context.
+    ///
+    /// This is `starknet_simulateTransactions` executed on top of the given overrides. The
+    /// overrides only apply to this simulation and are never persisted.
+    #[method(name = "simulateTransactions")]
+    async fn simulate_transactions_with_overrides(
+        &self,
+        block_id: BlockIdOrTag,
+        transactions: Vec<BroadcastedTx>,
+        simulation_flags: Vec<KatanaSimulationFlag>,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> RpcResult<SimulatedTransactionsResponse>;
 }
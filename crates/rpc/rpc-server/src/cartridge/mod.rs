@@ -55,7 +55,7 @@ use paymaster_rpc::{
 use starknet_paymaster::core::types::Call as StarknetRsCall;
 use tracing::{debug, info, trace_span, Instrument};
 use url::Url;
-pub use vrf::{VrfService, VrfServiceConfig};
+pub use vrf::{VrfProvider, VrfService, VrfServiceConfig};
 
 #[derive(Debug, Clone)]
 pub struct CartridgeConfig {
@@ -1,6 +1,6 @@
 //! VRF (Verifiable Random Function) service for Cartridge.
 
-use cartridge::vrf::{RequestContext, VrfClient};
+use cartridge::vrf::{InProcessVrf, RequestContext, VrfClient};
 use katana_primitives::chain::ChainId;
 use katana_primitives::ContractAddress;
 use katana_rpc_api::error::cartridge::CartridgeApiError;
@@ -10,33 +10,49 @@ use url::Url;
 #[derive(Debug, Clone)]
 pub struct VrfServiceConfig {
     pub rpc_url: Url,
-    pub service_url: Url,
+    pub provider: VrfProvider,
     pub vrf_contract: ContractAddress,
 }
 
+/// The backend responsible for generating VRF proofs.
+#[derive(Debug, Clone)]
+pub enum VrfProvider {
+    /// A VRF server reachable at the given URL (either external or a sidecar process).
+    Remote(Url),
+    /// VRF proofs are generated natively within the node process.
+    InProcess(InProcessVrf),
+}
+
+#[derive(Debug, Clone)]
+enum VrfBackend {
+    Remote(VrfClient),
+    InProcess(InProcessVrf),
+}
+
 #[derive(Debug, Clone)]
 pub struct VrfService {
-    client: VrfClient,
+    backend: VrfBackend,
     account_address: ContractAddress,
     rpc_url: Url,
 }
 
 impl VrfService {
     pub fn new(config: VrfServiceConfig) -> Self {
-        Self {
-            client: VrfClient::new(config.service_url),
-            account_address: config.vrf_contract,
-            rpc_url: config.rpc_url,
-        }
+        let backend = match config.provider {
+            VrfProvider::Remote(url) => VrfBackend::Remote(VrfClient::new(url)),
+            VrfProvider::InProcess(vrf) => VrfBackend::InProcess(vrf),
+        };
+
+        Self { backend, account_address: config.vrf_contract, rpc_url: config.rpc_url }
     }
 
     pub fn account_address(&self) -> ContractAddress {
         self.account_address
     }
 
-    /// Delegates outside execution to the VRF server.
+    /// Delegates outside execution to the VRF provider.
     ///
-    /// The VRF server handles seed computation, proof generation, and signing.
+    /// The VRF provider handles seed computation, proof generation, and signing.
     pub async fn outside_execution(
         &self,
         outside_execution: &SignedOutsideExecution,
@@ -47,9 +63,17 @@ impl VrfService {
             rpc_url: Some(self.rpc_url.clone()),
         };
 
-        self.client
-            .outside_execution(outside_execution, &context)
-            .await
-            .map_err(|err| CartridgeApiError::VrfExecutionFailed { reason: err.to_string() })
+        let result = match &self.backend {
+            VrfBackend::Remote(client) => client
+                .outside_execution(outside_execution, &context)
+                .await
+                .map_err(|err| err.to_string()),
+            VrfBackend::InProcess(vrf) => vrf
+                .outside_execution(outside_execution, &context)
+                .await
+                .map_err(|err| err.to_string()),
+        };
+
+        result.map_err(|reason| CartridgeApiError::VrfExecutionFailed { reason })
     }
 }
@@ -4,12 +4,28 @@ use jsonrpsee::core::{async_trait, RpcResult};
 use katana_core::backend::Backend;
 use katana_core::service::block_producer::{BlockProducer, BlockProducerMode, PendingExecutor};
 use katana_pool::TxPool;
+use katana_primitives::block::ConfirmedBlockIdOrTag;
 use katana_primitives::contract::{ContractAddress, StorageKey, StorageValue};
 use katana_provider::api::state::StateWriter;
 use katana_provider::{MutableProvider, ProviderFactory, ProviderRO, ProviderRW};
 use katana_rpc_api::dev::DevApiServer;
 use katana_rpc_api::error::dev::DevApiError;
 use katana_rpc_types::account::Account;
+use url::Url;
+
+/// Re-forks the chain of a forked node. Backs the `dev_resetFork` method.
+#[async_trait]
+pub trait ForkResetter: Send + Sync + 'static {
+    /// Drops all the local changes and forks the chain again at `block` of the network at `url`.
+    ///
+    /// If `url` is `None`, the currently forked network is used. If `block` is `None`, the latest
+    /// block of the network is used.
+    async fn reset_fork(
+        &self,
+        url: Option<Url>,
+        block: Option<ConfirmedBlockIdOrTag>,
+    ) -> anyhow::Result<()>;
+}
 
 #[allow(missing_debug_implementations)]
 pub struct DevApi<PF>
@@ -19,6 +35,7 @@ where
     backend: Arc<Backend<PF>>,
     block_producer: BlockProducer<PF>,
     pool: TxPool,
+    fork: Option<Arc<dyn ForkResetter>>,
 }
 
 impl<PF> DevApi<PF>
@@ -28,7 +45,13 @@ where
     <PF as ProviderFactory>::ProviderMut: ProviderRW,
 {
     pub fn new(backend: Arc<Backend<PF>>, block_producer: BlockProducer<PF>, pool: TxPool) -> Self {
-        Self { backend, block_producer, pool }
+        Self { backend, block_producer, pool, fork: None }
+    }
+
+    /// Enables the `dev_resetFork` method, which is only available when the node is forked.
+    pub fn with_fork(mut self, fork: Arc<dyn ForkResetter>) -> Self {
+        self.fork = Some(fork);
+        self
     }
 
     /// Returns the pending state if the sequencer is running in _interval_ mode. Otherwise `None`.
@@ -47,6 +70,16 @@ where
         }
     }
 
+    /// Mines a new block with the transactions currently in the pool.
+    pub fn generate_block(&self) {
+        self.block_producer.force_mine(&self.pool);
+    }
+
+    /// Returns the accounts predeployed in the genesis block.
+    pub fn predeployed_accounts(&self) -> Vec<Account> {
+        self.backend.chain_spec.genesis().accounts().map(|e| Account::new(*e.0, e.1)).collect()
+    }
+
     pub fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), DevApiError> {
         if self.has_pending_transactions() {
             return Err(DevApiError::PendingTransactions);
@@ -97,6 +130,17 @@ where
 
         Ok(())
     }
+
+    pub async fn reset_fork(
+        &self,
+        url: Option<Url>,
+        block: Option<ConfirmedBlockIdOrTag>,
+    ) -> Result<(), DevApiError> {
+        let Some(fork) = &self.fork else { return Err(DevApiError::NotForked) };
+        fork.reset_fork(url, block)
+            .await
+            .map_err(|error| DevApiError::ResetForkFailed(format!("{error:#}")))
+    }
 }
 
 #[async_trait]
@@ -107,7 +151,7 @@ where
     <PF as ProviderFactory>::ProviderMut: ProviderRW,
 {
     async fn generate_block(&self) -> RpcResult<()> {
-        self.block_producer.force_mine(&self.pool);
+        self.generate_block();
         Ok(())
     }
 
@@ -134,6 +178,14 @@ where
     }
 
     async fn predeployed_accounts(&self) -> RpcResult<Vec<Account>> {
-        Ok(self.backend.chain_spec.genesis().accounts().map(|e| Account::new(*e.0, e.1)).collect())
+        Ok(self.predeployed_accounts())
+    }
+
+    async fn reset_fork(
+        &self,
+        url: Option<Url>,
+        block: Option<ConfirmedBlockIdOrTag>,
+    ) -> RpcResult<()> {
+        Ok(self.reset_fork(url, block).await?)
     }
 }
@@ -0,0 +1,310 @@
+//! Health checks of the components shared by the different node implementations.
+
+use std::time::{Duration, Instant};
+
+use cartridge::vrf::VrfClient;
+use futures::future::BoxFuture;
+use katana_gas_price_oracle::{GasPriceOracle, SampledPriceOracle, Sampler};
+use katana_pool::api::TransactionPool;
+use katana_primitives::block::BlockNumber;
+use katana_provider::api::block::BlockNumberProvider;
+use katana_provider::{ProviderFactory, ProviderRO};
+use katana_rpc_api::paymaster::PaymasterApiServer;
+use katana_tasks::{TaskState, TaskStatus};
+use parking_lot::Mutex;
+use serde_json::json;
+use url::Url;
+
+use super::{ComponentHealth, HealthIndicator};
+use crate::paymaster::PaymasterProxy;
+
+/// The default time transactions may wait in the pool without any new block being produced,
+/// before block production is considered stalled.
+pub const DEFAULT_BLOCK_PRODUCTION_STALL_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Checks that the database can be read from.
+#[derive(Debug)]
+pub struct DatabaseCheck<PF> {
+    provider: PF,
+}
+
+impl<PF> DatabaseCheck<PF> {
+    pub fn new(provider: PF) -> Self {
+        Self { provider }
+    }
+}
+
+impl<PF> HealthIndicator for DatabaseCheck<PF>
+where
+    PF: ProviderFactory + Send + Sync + 'static,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+{
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+        Box::pin(async move {
+            match self.provider.provider().latest_number() {
+                Ok(block) => {
+                    ComponentHealth::healthy().with_details(json!({ "latest_block": block }))
+                }
+                Err(error) => {
+                    ComponentHealth::unhealthy(format!("failed to read database: {error}"))
+                }
+            }
+        })
+    }
+}
+
+/// Checks that blocks are being produced.
+///
+/// Block production is considered stalled if transactions have been waiting in the pool for
+/// longer than the configured threshold without any new block being produced. An idle node is
+/// never considered stalled, as blocks are only produced when there are transactions to include.
+#[derive(Debug)]
+pub struct BlockProductionCheck<PF, P> {
+    provider: PF,
+    pool: P,
+    /// `None` if blocks are only produced on demand, in which case stalls aren't detected.
+    stall_threshold: Option<Duration>,
+    progress: Mutex<Option<BlockProductionProgress>>,
+}
+
+#[derive(Debug)]
+struct BlockProductionProgress {
+    /// The latest block number as of the last check.
+    block: BlockNumber,
+    /// When `block` was first observed.
+    observed_at: Instant,
+    /// Since when the pool has had pending transactions while `block` was the latest block.
+    pending_since: Option<Instant>,
+}
+
+impl<PF, P> BlockProductionCheck<PF, P> {
+    pub fn new(provider: PF, pool: P, stall_threshold: Option<Duration>) -> Self {
+        Self { provider, pool, stall_threshold, progress: Mutex::new(None) }
+    }
+}
+
+impl<PF, P> HealthIndicator for BlockProductionCheck<PF, P>
+where
+    PF: ProviderFactory + Send + Sync + 'static,
+    <PF as ProviderFactory>::Provider: ProviderRO,
+    P: TransactionPool + 'static,
+{
+    fn name(&self) -> &str {
+        "block_production"
+    }
+
+    fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+        Box::pin(async move {
+            let latest = match self.provider.provider().latest_number() {
+                Ok(block) => block,
+                Err(error) => {
+                    return ComponentHealth::unhealthy(format!(
+                        "failed to read latest block: {error}"
+                    ));
+                }
+            };
+
+            let now = Instant::now();
+            let pending = self.pool.size();
+
+            let mut progress = self.progress.lock();
+
+            // Reset the progress whenever a new block is observed.
+            if !matches!(&*progress, Some(p) if p.block == latest) {
+                *progress = Some(BlockProductionProgress {
+                    block: latest,
+                    observed_at: now,
+                    pending_since: None,
+                });
+            }
+
+            let progress = progress.as_mut().expect("qed; set above");
+
+            if pending == 0 {
+                progress.pending_since = None;
+            } else {
+                progress.pending_since.get_or_insert(now);
+            }
+
+            let block_age = now.duration_since(progress.observed_at);
+            let pending_for = progress.pending_since.map(|since| now.duration_since(since));
+
+            let details = json!({
+                "latest_block": latest,
+                "last_block_age_secs": block_age.as_secs(),
+                "pending_transactions": pending,
+            });
+
+            match (self.stall_threshold, pending_for) {
+                (Some(threshold), Some(pending_for)) if pending_for > threshold => {
+                    ComponentHealth::unhealthy(format!(
+                        "no block produced in {}s while transactions are pending",
+                        pending_for.as_secs()
+                    ))
+                    .with_details(details)
+                }
+                _ => ComponentHealth::healthy().with_details(details),
+            }
+        })
+    }
+}
+
+/// Checks that a long-running background task is still running.
+#[derive(Debug)]
+pub struct TaskCheck {
+    name: String,
+    status: TaskStatus,
+}
+
+impl TaskCheck {
+    pub fn new(name: impl Into<String>, status: TaskStatus) -> Self {
+        Self { name: name.into(), status }
+    }
+}
+
+impl HealthIndicator for TaskCheck {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+        Box::pin(async move {
+            match self.status.state() {
+                TaskState::Running => ComponentHealth::healthy(),
+                TaskState::Pending => ComponentHealth::degraded("task hasn't started yet"),
+                TaskState::Finished => ComponentHealth::unhealthy("task is no longer running"),
+            }
+        })
+    }
+}
+
+/// Checks the gas price oracle.
+///
+/// Oracles with fixed prices are always healthy. Sampled oracles are unhealthy if their sampling
+/// worker is no longer running, and degraded if their samples are stale and the fallback prices
+/// are used.
+#[derive(Debug)]
+pub struct GasOracleCheck {
+    oracle: GasPriceOracle,
+    worker: TaskStatus,
+}
+
+impl GasOracleCheck {
+    /// Creates a new check, where `worker` tracks the task returned by
+    /// [`GasPriceOracle::run_worker`].
+    pub fn new(oracle: GasPriceOracle, worker: TaskStatus) -> Self {
+        Self { oracle, worker }
+    }
+}
+
+impl HealthIndicator for GasOracleCheck {
+    fn name(&self) -> &str {
+        "gas_oracle"
+    }
+
+    fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+        Box::pin(async move {
+            let Some(sampled) = sampled_oracle(&self.oracle) else {
+                return ComponentHealth::healthy().with_details(json!({ "mode": "fixed" }));
+            };
+
+            let samplers = sampled
+                .sampler_health()
+                .into_iter()
+                .map(|health| {
+                    let last_success = health.last_success.map(|at| at.elapsed().as_secs());
+                    json!({
+                        "healthy": health.is_healthy(),
+                        "consecutive_failures": health.consecutive_failures,
+                        "last_success_secs_ago": last_success,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let stale = sampled.is_stale();
+            let details = json!({ "mode": "sampled", "stale": stale, "samplers": samplers });
+
+            let health = if self.worker.state() == TaskState::Finished {
+                ComponentHealth::unhealthy("gas price sampling worker is no longer running")
+            } else if stale {
+                ComponentHealth::degraded("gas price samples are stale, using fallback prices")
+            } else {
+                ComponentHealth::healthy()
+            };
+
+            health.with_details(details)
+        })
+    }
+}
+
+fn sampled_oracle(oracle: &GasPriceOracle) -> Option<&SampledPriceOracle<Box<dyn Sampler>>> {
+    match oracle {
+        GasPriceOracle::Fixed(..) => None,
+        GasPriceOracle::Sampled(sampled) => Some(sampled),
+        GasPriceOracle::Dynamic(dynamic) => sampled_oracle(dynamic.base()),
+    }
+}
+
+/// Checks that the paymaster sidecar is reachable and reports itself healthy.
+#[derive(Debug)]
+pub struct PaymasterCheck {
+    proxy: PaymasterProxy,
+}
+
+impl PaymasterCheck {
+    pub fn new(proxy: PaymasterProxy) -> Self {
+        Self { proxy }
+    }
+}
+
+impl HealthIndicator for PaymasterCheck {
+    fn name(&self) -> &str {
+        "paymaster"
+    }
+
+    fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+        Box::pin(async move {
+            match PaymasterApiServer::health(&self.proxy).await {
+                Ok(true) => ComponentHealth::healthy(),
+                Ok(false) => ComponentHealth::unhealthy("paymaster reported itself unhealthy"),
+                Err(error) => ComponentHealth::unhealthy(format!(
+                    "paymaster is unreachable: {}",
+                    error.message()
+                )),
+            }
+        })
+    }
+}
+
+/// Checks that a remote VRF server is reachable.
+#[derive(Debug)]
+pub struct VrfCheck {
+    client: VrfClient,
+}
+
+impl VrfCheck {
+    pub fn new(url: Url) -> Self {
+        Self { client: VrfClient::new(url) }
+    }
+}
+
+impl HealthIndicator for VrfCheck {
+    fn name(&self) -> &str {
+        "vrf"
+    }
+
+    fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+        Box::pin(async move {
+            match self.client.health_check().await {
+                Ok(()) => ComponentHealth::healthy(),
+                Err(error) => {
+                    ComponentHealth::unhealthy(format!("VRF server is unreachable: {error}"))
+                }
+            }
+        })
+    }
+}
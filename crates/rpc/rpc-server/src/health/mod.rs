@@ -0,0 +1,392 @@
+//! Health check endpoints.
+//!
+//! Besides the simple [`HealthCheck`] endpoint, the server can expose Kubernetes-style probes
+//! backed by a set of [`HealthChecks`]:
+//!
+//! - `GET /health/live`: runs the liveness checks only. A failing liveness probe means the node is
+//!   stuck and should be restarted.
+//! - `GET /health/ready`: runs every check. A failing readiness probe means the node shouldn't
+//!   receive traffic (yet).
+//!
+//! Both endpoints respond with a per-component JSON report, with status `200 OK` unless at least
+//! one component is [unhealthy](HealthStatus::Unhealthy), in which case `503 Service Unavailable`
+//! is returned.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::future::{join_all, BoxFuture};
+use http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use http::{Method, Request, Response, StatusCode};
+use jsonrpsee::core::http_helpers::Body;
+use jsonrpsee::server::middleware::http::ProxyGetRequestLayer;
+use jsonrpsee::{Methods, ResponsePayload, RpcModule};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tower::{Layer, Service};
+
+mod checks;
+
+pub use checks::*;
+
+/// Simple health check endpoint.
+#[derive(Debug)]
+pub struct HealthCheck;
+
+impl HealthCheck {
+    const METHOD: &'static str = "health";
+    const PROXY_PATH: &'static str = "/";
+
+    pub(crate) fn proxy() -> ProxyGetRequestLayer {
+        Self::proxy_with_path(Self::PROXY_PATH)
+    }
+
+    fn proxy_with_path(path: &str) -> ProxyGetRequestLayer {
+        ProxyGetRequestLayer::new([(path, Self::METHOD)]).expect("path starts with /")
+    }
+}
+
+impl From<HealthCheck> for Methods {
+    fn from(_: HealthCheck) -> Self {
+        let mut module = RpcModule::new(());
+
+        module
+            .register_method(HealthCheck::METHOD, |_, _, _| {
+                ResponsePayload::success(json!({ "health": true }))
+            })
+            .unwrap();
+
+        module.into()
+    }
+}
+
+/// The default time a single health check is allowed to run for.
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The status of a component, or of the node as a whole.
+///
+/// Variants are ordered by severity, so that the overall status of a report is the maximum of its
+/// components' status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The component is working as expected.
+    Healthy,
+    /// The component is working but in a degraded mode (eg, using fallback values).
+    Degraded,
+    /// The component isn't working.
+    Unhealthy,
+}
+
+/// The result of checking a single component.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    /// Human readable explanation of the status, if not healthy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Component specific information.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ComponentHealth {
+    pub fn healthy() -> Self {
+        Self { status: HealthStatus::Healthy, message: None, details: None }
+    }
+
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Self { status: HealthStatus::Degraded, message: Some(message.into()), details: None }
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self { status: HealthStatus::Unhealthy, message: Some(message.into()), details: None }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// A check of the health of a single node component.
+pub trait HealthIndicator: Send + Sync + 'static {
+    /// The name of the component, used as its key in the health report.
+    fn name(&self) -> &str;
+
+    /// Checks the health of the component.
+    fn check(&self) -> BoxFuture<'_, ComponentHealth>;
+}
+
+/// The kind of probe a health report is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Probe {
+    Liveness,
+    Readiness,
+}
+
+impl Probe {
+    const LIVENESS_PATH: &'static str = "/health/live";
+    const READINESS_PATH: &'static str = "/health/ready";
+
+    fn from_path(path: &str) -> Option<Self> {
+        match path.trim_end_matches('/') {
+            Self::LIVENESS_PATH => Some(Self::Liveness),
+            Self::READINESS_PATH => Some(Self::Readiness),
+            _ => None,
+        }
+    }
+}
+
+/// The health report of the node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// The most severe status among all the components.
+    pub status: HealthStatus,
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+impl HealthReport {
+    fn into_response(self) -> Response<Body> {
+        let status = match self.status {
+            HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+            HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+        };
+
+        let body = serde_json::to_vec(&self).expect("qed; report is serializable");
+
+        Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CACHE_CONTROL, "no-store")
+            .body(Body::from(body))
+            .expect("qed; valid response")
+    }
+}
+
+#[derive(Clone)]
+struct RegisteredCheck {
+    indicator: Arc<dyn HealthIndicator>,
+    liveness: bool,
+}
+
+/// The set of checks that the health probes are made of.
+///
+/// Liveness checks are part of both probes, whereas readiness checks are only part of the
+/// readiness probe.
+#[derive(Clone)]
+pub struct HealthChecks {
+    checks: Arc<Vec<RegisteredCheck>>,
+    timeout: Duration,
+}
+
+impl HealthChecks {
+    pub fn new() -> Self {
+        Self { checks: Arc::new(Vec::new()), timeout: DEFAULT_HEALTH_CHECK_TIMEOUT }
+    }
+
+    /// Adds a check to both the liveness and readiness probes.
+    pub fn liveness(self, indicator: impl HealthIndicator) -> Self {
+        self.register(Arc::new(indicator), true)
+    }
+
+    /// Adds a check to the readiness probe only.
+    pub fn readiness(self, indicator: impl HealthIndicator) -> Self {
+        self.register(Arc::new(indicator), false)
+    }
+
+    /// Sets how long a single check may run for before the component is considered unhealthy.
+    /// Default is 5 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Runs the checks of the given probe and returns the resulting report.
+    pub async fn report(&self, probe: Probe) -> HealthReport {
+        let checks = self.checks.iter().filter(|c| c.liveness || probe == Probe::Readiness);
+
+        let results = join_all(checks.map(|c| async move {
+            let health = match tokio::time::timeout(self.timeout, c.indicator.check()).await {
+                Ok(health) => health,
+                Err(_) => ComponentHealth::unhealthy("health check timed out"),
+            };
+
+            (c.indicator.name().to_string(), health)
+        }))
+        .await;
+
+        let components: BTreeMap<_, _> = results.into_iter().collect();
+        let status = components.values().map(|c| c.status).max().unwrap_or(HealthStatus::Healthy);
+
+        HealthReport { status, components }
+    }
+
+    fn register(mut self, indicator: Arc<dyn HealthIndicator>, liveness: bool) -> Self {
+        Arc::make_mut(&mut self.checks).push(RegisteredCheck { indicator, liveness });
+        self
+    }
+}
+
+impl Default for HealthChecks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for HealthChecks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self.checks.iter().map(|c| c.indicator.name()).collect::<Vec<_>>();
+        f.debug_struct("HealthChecks")
+            .field("checks", &names)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Layer that serves the `GET /health/live` and `GET /health/ready` endpoints.
+#[derive(Debug, Clone)]
+pub struct HealthLayer {
+    checks: HealthChecks,
+}
+
+impl HealthLayer {
+    pub fn new(checks: HealthChecks) -> Self {
+        Self { checks }
+    }
+}
+
+impl<S> Layer<S> for HealthLayer {
+    type Service = HealthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HealthService { inner, checks: self.checks.clone() }
+    }
+}
+
+/// Service created by [`HealthLayer`].
+#[derive(Debug, Clone)]
+pub struct HealthService<S> {
+    inner: S,
+    checks: HealthChecks,
+}
+
+impl<S, B> Service<Request<B>> for HealthService<S>
+where
+    S: Service<Request<B>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let probe = match *req.method() {
+            Method::GET => Probe::from_path(req.uri().path()),
+            _ => None,
+        };
+
+        match probe {
+            Some(probe) => {
+                let checks = self.checks.clone();
+                Box::pin(async move { Ok(checks.report(probe).await.into_response()) })
+            }
+            None => Box::pin(self.inner.call(req)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::BoxFuture;
+    use reqwest::StatusCode;
+
+    use super::{ComponentHealth, HealthChecks, HealthIndicator, HealthReport, HealthStatus};
+    use crate::RpcServer;
+
+    struct StaticCheck(&'static str, ComponentHealth);
+
+    impl HealthIndicator for StaticCheck {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+            Box::pin(async move { self.1.clone() })
+        }
+    }
+
+    async fn get(checks: HealthChecks, path: &str) -> (StatusCode, HealthReport) {
+        let server = RpcServer::new().health_checks(checks);
+        let handle = server.start("127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+        let url = format!("http://{}{path}", handle.addr());
+        let response = reqwest::get(url).await.unwrap();
+        let status = response.status();
+        let report = response.json::<HealthReport>().await.unwrap();
+
+        (status, report)
+    }
+
+    #[tokio::test]
+    async fn readiness_includes_all_checks() {
+        let checks = HealthChecks::new()
+            .liveness(StaticCheck("a", ComponentHealth::healthy()))
+            .readiness(StaticCheck("b", ComponentHealth::degraded("slow")));
+
+        let (status, report) = get(checks, "/health/ready").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert_eq!(report.components.len(), 2);
+        assert_eq!(report.components["b"].message.as_deref(), Some("slow"));
+    }
+
+    #[tokio::test]
+    async fn liveness_excludes_readiness_checks() {
+        let checks = HealthChecks::new()
+            .liveness(StaticCheck("a", ComponentHealth::healthy()))
+            .readiness(StaticCheck("b", ComponentHealth::unhealthy("down")));
+
+        let (status, report) = get(checks.clone(), "/health/live").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert!(!report.components.contains_key("b"));
+
+        let (status, report) = get(checks, "/health/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn slow_check_times_out() {
+        struct PendingCheck;
+
+        impl HealthIndicator for PendingCheck {
+            fn name(&self) -> &str {
+                "pending"
+            }
+
+            fn check(&self) -> BoxFuture<'_, ComponentHealth> {
+                Box::pin(std::future::pending())
+            }
+        }
+
+        let checks = HealthChecks::new()
+            .liveness(PendingCheck)
+            .timeout(std::time::Duration::from_millis(100));
+
+        let (status, report) = get(checks, "/health/live").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(report.components["pending"].status, HealthStatus::Unhealthy);
+    }
+}
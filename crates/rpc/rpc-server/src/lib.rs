@@ -4,18 +4,24 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use jsonrpsee::core::middleware::RpcServiceT;
 use jsonrpsee::core::{RegisterMethodError, TEN_MB_SIZE_BYTES};
 use jsonrpsee::server::middleware::rpc::RpcService;
-use jsonrpsee::server::{Server, ServerConfig, ServerHandle};
-use jsonrpsee::{MethodResponse, RpcModule};
+use jsonrpsee::server::{
+    serve_with_graceful_shutdown, stop_channel, Server, ServerConfig, ServerHandle,
+};
+use jsonrpsee::{MethodResponse, Methods, RpcModule};
 use katana_tracing::gcloud::GoogleStackDriverMakeSpan;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio_util::either::Either;
 use tower::layer::util::Identity;
 use tower::{Layer, ServiceBuilder};
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{debug, info, warn};
 
 pub mod cartridge;
 pub mod dev;
@@ -26,14 +32,25 @@ pub mod paymaster;
 pub mod permit;
 pub mod starknet;
 pub mod tee;
+pub mod tls;
 pub mod txpool;
 
 mod utils;
-use health::HealthCheck;
+use health::{HealthCheck, HealthChecks, HealthLayer};
 pub use jsonrpsee::core::middleware::RpcServiceBuilder;
 pub use jsonrpsee::http_client::HttpClient;
 pub use katana_rpc_api as api;
+use middleware::auth::{AuthConfig, AuthLayer, AuthRpcLayer, AuthRpcService};
 use middleware::cors::Cors;
+use middleware::rate_limit::{
+    ClientIdLayer, RateLimitConfig, RateLimitRpcLayer, RateLimitRpcService, RateLimiter,
+};
+use tls::{
+    TlsAcceptor, TlsConfig, TlsListener, TlsStream, DEFAULT_RELOAD_INTERVAL, HTTP_ALPN_PROTOCOLS,
+};
+
+/// The RPC service wrapped by the custom RPC middleware (see [`RpcServer::rpc_middleware`]).
+type InnerRpcService = AuthRpcService<RateLimitRpcService<RpcService>>;
 
 /// The default maximum number of concurrent RPC connections.
 pub const DEFAULT_RPC_MAX_CONNECTIONS: u32 = 100;
@@ -59,6 +76,9 @@ pub enum Error {
 
     #[error(transparent)]
     Client(#[from] jsonrpsee::core::ClientError),
+
+    #[error(transparent)]
+    Tls(#[from] tls::Error),
 }
 
 /// The RPC server handle.
@@ -68,6 +88,8 @@ pub struct RpcServerHandle {
     addr: SocketAddr,
     /// The handle to the spawned [`jsonrpsee::server::Server`].
     handle: ServerHandle,
+    /// Whether the server is served over TLS.
+    tls: bool,
 }
 
 impl RpcServerHandle {
@@ -89,7 +111,8 @@ impl RpcServerHandle {
     /// Returns a HTTP client associated with the server.
     pub fn http_client(&self) -> Result<HttpClient, Error> {
         use jsonrpsee::http_client::HttpClientBuilder;
-        let url = format!("http://{}", self.addr);
+        let scheme = if self.tls { "https" } else { "http" };
+        let url = format!("{scheme}://{}", self.addr);
         Ok(HttpClientBuilder::default().build(url)?)
     }
 }
@@ -98,7 +121,11 @@ impl RpcServerHandle {
 pub struct RpcServer<RpcMiddleware = Identity> {
     metrics: bool,
     cors: Option<Cors>,
+    auth: Option<AuthConfig>,
+    rate_limit: Option<Arc<RateLimiter>>,
+    tls: Option<TlsConfig>,
     health_check: bool,
+    health_checks: Option<HealthChecks>,
     explorer: bool,
 
     module: RpcModule<()>,
@@ -114,9 +141,13 @@ impl RpcServer<Identity> {
     pub fn new() -> Self {
         Self {
             cors: None,
+            auth: None,
+            rate_limit: None,
+            tls: None,
             metrics: false,
             explorer: false,
             health_check: false,
+            health_checks: None,
             module: RpcModule::new(()),
             max_connections: 100,
             max_request_body_size: TEN_MB_SIZE_BYTES,
@@ -166,6 +197,14 @@ impl<RpcMiddleware> RpcServer<RpcMiddleware> {
         self
     }
 
+    /// Enables the `GET /health/live` and `GET /health/ready` probes, backed by `checks`.
+    ///
+    /// See [`health`] for more details.
+    pub fn health_checks(mut self, checks: HealthChecks) -> Self {
+        self.health_checks = Some(checks);
+        self
+    }
+
     /// Enables explorer.
     pub fn explorer(mut self, enable: bool) -> Self {
         self.explorer = enable;
@@ -177,16 +216,53 @@ impl<RpcMiddleware> RpcServer<RpcMiddleware> {
         self
     }
 
+    /// Requires requests to be authenticated according to `config`.
+    ///
+    /// See [`middleware::auth`] for more details.
+    pub fn auth(mut self, config: AuthConfig) -> Self {
+        self.auth = Some(config);
+        self
+    }
+
+    /// Rate limits the calls of each client according to `config`.
+    ///
+    /// See [`middleware::rate_limit`] for more details.
+    pub fn rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limit = Some(Arc::new(RateLimiter::new(config)));
+        self
+    }
+
+    /// Rate limits the calls of each client using `limiter`.
+    ///
+    /// This is the same as [`rate_limit`](Self::rate_limit), but allows the limits to be shared
+    /// with other servers.
+    pub fn rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limit = Some(limiter);
+        self
+    }
+
+    /// Serves the RPC over TLS according to `config`.
+    ///
+    /// See [`tls`] for more details.
+    pub fn tls(mut self, config: TlsConfig) -> Self {
+        self.tls = Some(config);
+        self
+    }
+
     /// Configure custom RPC middleware.
     pub fn rpc_middleware<T>(self, middleware: RpcServiceBuilder<T>) -> RpcServer<T> {
         RpcServer {
             rpc_middleware: middleware,
             cors: self.cors,
+            auth: self.auth,
+            rate_limit: self.rate_limit,
+            tls: self.tls,
             module: self.module,
             timeout: self.timeout,
             metrics: self.metrics,
             explorer: self.explorer,
             health_check: self.health_check,
+            health_checks: self.health_checks,
             max_connections: self.max_connections,
             max_request_body_size: self.max_request_body_size,
             max_response_body_size: self.max_response_body_size,
@@ -210,8 +286,8 @@ impl<RpcMiddleware> RpcServer<RpcMiddleware> {
 
 impl<RpcMiddleware> RpcServer<RpcMiddleware>
 where
-    RpcMiddleware: Layer<RpcService> + Clone + Send + 'static,
-    <RpcMiddleware as Layer<RpcService>>::Service: RpcServiceT<
+    RpcMiddleware: Layer<InnerRpcService> + Clone + Send + 'static,
+    <RpcMiddleware as Layer<InnerRpcService>>::Service: RpcServiceT<
             MethodResponse = MethodResponse,
             BatchResponse = MethodResponse,
             NotificationResponse = MethodResponse,
@@ -242,33 +318,98 @@ where
         let http_middleware = ServiceBuilder::new()
             .layer(http_tracer)
             .option_layer(self.cors.clone())
+            .option_layer(self.auth.clone().map(AuthLayer::new))
+            .option_layer(
+                self.rate_limit
+                    .as_ref()
+                    .map(|limiter| ClientIdLayer::new(limiter.config().trusts_proxy_headers())),
+            )
+            .option_layer(self.health_checks.clone().map(HealthLayer::new))
             .option_layer(health_check_proxy)
             .timeout(self.timeout);
 
         #[cfg(feature = "explorer")]
         let http_middleware = http_middleware.option_layer(explorer_layer);
 
+        // Access control and rate limiting are enforced right before the call is executed, so
+        // that the other middlewares still observe the rejected calls. Calls rejected by the
+        // access control don't count towards the rate limit.
+        let rpc_middleware = self
+            .rpc_middleware
+            .clone()
+            .layer(AuthRpcLayer::new(self.auth.is_some()))
+            .layer(RateLimitRpcLayer::new(self.rate_limit.clone()));
+
         let cfg = ServerConfig::builder()
             .max_connections(self.max_connections)
             .max_request_body_size(self.max_request_body_size)
             .max_response_body_size(self.max_response_body_size)
             .build();
 
-        let server = Server::builder()
+        let builder = Server::builder()
             .set_http_middleware(http_middleware)
-            .set_rpc_middleware(self.rpc_middleware.clone())
-            .set_config(cfg)
-            .build(addr)
-            .await?;
-
-        let actual_addr = server.local_addr()?;
-        let handle = server.start(modules);
+            .set_rpc_middleware(rpc_middleware)
+            .set_config(cfg);
+
+        let listener = TcpListener::bind(addr).await?;
+        let actual_addr = listener.local_addr()?;
+
+        let mut listener = if let Some(tls) = &self.tls {
+            let acceptor = TlsAcceptor::new(tls.clone(), HTTP_ALPN_PROTOCOLS)?;
+            acceptor.spawn_reloader(DEFAULT_RELOAD_INTERVAL);
+            // Connections only count towards the connection limit once their handshake
+            // completes, so the pending handshakes are bounded by the same limit.
+            let listener = TlsListener::new(listener, acceptor)
+                .max_pending_handshakes(self.max_connections as usize);
+            Listener::Tls(listener)
+        } else {
+            Listener::Tcp(listener)
+        };
 
-        let handle = RpcServerHandle { handle, addr: actual_addr };
+        // We run the accept loop ourselves and only use jsonrpsee to serve the connections, so
+        // that the address of the peer can be attached to its requests (jsonrpsee doesn't expose
+        // it) and because jsonrpsee can't terminate TLS itself. Hence, the connection limit must
+        // also be enforced here.
+        let svc_builder = builder.to_service_builder();
+        let methods = Methods::from(modules);
+        let connections = Arc::new(Semaphore::new(self.max_connections as usize));
+        let (stop_handle, handle) = stop_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, remote_addr) = tokio::select! {
+                    conn = listener.accept() => conn,
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+
+                let Ok(permit) = connections.clone().try_acquire_owned() else {
+                    warn!(target: "rpc", %remote_addr, "Too many connections.");
+                    continue;
+                };
+
+                let svc = svc_builder.clone().build(methods.clone(), stop_handle.clone());
+                let svc = ServiceBuilder::new()
+                    .map_request(move |mut req: http::Request<_>| {
+                        req.extensions_mut().insert(PeerAddr(remote_addr));
+                        req
+                    })
+                    .service(svc);
+                let stopped = stop_handle.clone().shutdown();
+
+                tokio::spawn(async move {
+                    if let Err(error) = serve_with_graceful_shutdown(stream, svc, stopped).await {
+                        debug!(target: "rpc", %remote_addr, %error, "Connection error.");
+                    }
+                    drop(permit);
+                });
+            }
+        });
+
+        let handle = RpcServerHandle { handle, addr: actual_addr, tls: self.tls.is_some() };
 
         // The socket address that we log out must be from the RPC handle, in the case that the
         // `addr` passed to this method has port number 0. As the 0 port will be resolved to
-        // a free port during the call to `ServerBuilder::build(addr)`.
+        // a free port during the call to `TcpListener::bind(addr)`.
 
         info!(target: "rpc", addr = %handle.addr, "RPC server started.");
 
@@ -281,6 +422,50 @@ where
     }
 }
 
+/// The address of the peer that sent a request, attached to the extensions of the HTTP requests
+/// received by the [`RpcServer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerAddr(pub SocketAddr);
+
+/// The listener of the connections served by the [`RpcServer`].
+#[derive(Debug)]
+enum Listener {
+    Tcp(TcpListener),
+    Tls(TlsListener),
+}
+
+impl Listener {
+    /// Accepts the next connection, along with the address of the peer.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    async fn accept(&mut self) -> (Either<TcpStream, TlsStream<TcpStream>>, SocketAddr) {
+        match self {
+            Self::Tls(listener) => {
+                let (stream, addr) = listener.accept().await;
+                (Either::Right(stream), addr)
+            }
+
+            Self::Tcp(listener) => loop {
+                match listener.accept().await {
+                    Ok((stream, addr)) => {
+                        let _ = stream.set_nodelay(true);
+                        return (Either::Left(stream), addr);
+                    }
+
+                    // Accept errors are transient (eg, the process ran out of file descriptors),
+                    // so back off for a bit instead of spinning.
+                    Err(error) => {
+                        warn!(target: "rpc", %error, "Failed to accept connection.");
+                        tokio::time::sleep(tls::ACCEPT_ERROR_BACKOFF).await;
+                    }
+                }
+            },
+        }
+    }
+}
+
 impl Default for RpcServer<Identity> {
     fn default() -> Self {
         Self::new()
@@ -294,7 +479,7 @@ mod tests {
 
     use jsonrpsee::{rpc_params, RpcModule};
 
-    use crate::RpcServer;
+    use crate::{PeerAddr, RpcServer};
 
     #[tokio::test]
     async fn test_rpc_server_timeout() {
@@ -315,4 +500,24 @@ mod tests {
 
         assert!(result.is_err(), "the request failed due to timeout");
     }
+
+    #[tokio::test]
+    async fn test_rpc_server_attaches_peer_addr() {
+        use jsonrpsee::core::client::ClientT;
+
+        let mut module = RpcModule::new(());
+        module
+            .register_method("test_peerAddr", |_, _, extensions| {
+                extensions.get::<PeerAddr>().map(|PeerAddr(addr)| addr.ip().to_string())
+            })
+            .unwrap();
+
+        let server = RpcServer::new().module(module).unwrap();
+        let server_handle = server.start("127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+        let client = server_handle.http_client().unwrap();
+        let peer: Option<String> = client.request("test_peerAddr", rpc_params![]).await.unwrap();
+
+        assert_eq!(peer.as_deref(), Some("127.0.0.1"));
+    }
 }
@@ -0,0 +1,641 @@
+//! Authentication and per-method access control for the RPC server.
+//!
+//! Callers authenticate with either a static API key or an HS256 JSON Web Token, passed through
+//! the `x-api-key` header or as a bearer token in the `Authorization` header. Each credential
+//! grants access to a set of methods, described by a [`MethodFilter`]. Requests without any
+//! credentials are only allowed to call the configured public methods.
+//!
+//! The credentials are resolved by [`AuthLayer`] at the HTTP level, which rejects requests with
+//! invalid credentials and attaches the caller's [`Access`] to the valid ones. The access is then
+//! enforced for every call (including the ones in a batch) by [`AuthRpcLayer`].
+//!
+//! JWTs must be signed with the configured secret and contain an `exp` claim. The methods a token
+//! may call can be restricted with a `methods` claim, which is a list of method patterns:
+//!
+//! ```json
+//! { "sub": "ops", "exp": 1767225600, "methods": ["dev_*", "katana_*"] }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use http::header::{AUTHORIZATION, CONTENT_TYPE, WWW_AUTHENTICATE};
+use http::{HeaderMap, Request, Response, StatusCode};
+use jsonrpsee::core::http_helpers::Body;
+use jsonrpsee::core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification, RpcServiceT};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::MethodResponse;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use katana_node_config::rpc::RpcAuthConfig;
+use serde::Deserialize;
+use serde_json::json;
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// Error code returned when the caller isn't allowed to call a method.
+pub const METHOD_NOT_ALLOWED_CODE: i32 = -32001;
+
+/// Header that API keys can be passed with.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Methods that can always be called, regardless of the caller's access.
+const ALWAYS_ALLOWED_METHODS: &[&str] = &[
+    // The method behind the `GET /` health check.
+    "health",
+];
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid method pattern '{0}': wildcard is only allowed at the end")]
+pub struct InvalidMethodPattern(String);
+
+/// A single method pattern: either an exact method name (eg, `starknet_call`), a prefix followed
+/// by a wildcard (eg, `starknet_*`), or a lone wildcard (`*`) that matches every method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MethodPattern {
+    Exact(String),
+    Prefix(String),
+}
+
+impl MethodPattern {
+    fn matches(&self, method: &str) -> bool {
+        match self {
+            Self::Exact(name) => name == method,
+            Self::Prefix(prefix) => method.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+impl FromStr for MethodPattern {
+    type Err = InvalidMethodPattern;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = s.trim();
+        match pattern.strip_suffix('*') {
+            Some(prefix) if !prefix.contains('*') => Ok(Self::Prefix(prefix.to_string())),
+            None if !pattern.is_empty() && !pattern.contains('*') => {
+                Ok(Self::Exact(pattern.to_string()))
+            }
+            _ => Err(InvalidMethodPattern(s.to_string())),
+        }
+    }
+}
+
+/// A set of method patterns describing which methods can be called.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MethodFilter(Vec<MethodPattern>);
+
+impl MethodFilter {
+    /// Creates a filter from a list of method patterns (eg, `starknet_*`, `dev_generateBlock`).
+    pub fn new<I, S>(patterns: I) -> Result<Self, InvalidMethodPattern>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns =
+            patterns.into_iter().map(|p| p.as_ref().parse()).collect::<Result<_, _>>()?;
+        Ok(Self(patterns))
+    }
+
+    /// Creates a filter that allows every method.
+    pub fn any() -> Self {
+        Self(vec![MethodPattern::Prefix(String::new())])
+    }
+
+    /// Creates a filter that doesn't allow any method.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if `method` is allowed by the filter.
+    pub fn allows(&self, method: &str) -> bool {
+        self.0.iter().any(|pattern| pattern.matches(method))
+    }
+}
+
+/// The methods an authenticated (or anonymous) caller is allowed to call.
+///
+/// This is attached to the HTTP request extensions by [`AuthLayer`].
+#[derive(Clone)]
+pub struct Access {
+    methods: Arc<MethodFilter>,
+    /// Identifies the caller, if authenticated.
+    client: Option<Arc<str>>,
+}
+
+impl Access {
+    fn new(methods: MethodFilter, client: Option<Arc<str>>) -> Self {
+        Self { methods: Arc::new(methods), client }
+    }
+
+    /// Returns `true` if the caller is allowed to call `method`.
+    pub fn allows(&self, method: &str) -> bool {
+        ALWAYS_ALLOWED_METHODS.contains(&method) || self.methods.allows(method)
+    }
+
+    /// Returns an identifier of the caller, if known.
+    ///
+    /// This is the API key for API key authentication, and the `sub` claim for JWTs. Anonymous
+    /// callers, and JWTs without a subject, don't have an identifier.
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+}
+
+impl std::fmt::Debug for Access {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the API key.
+        f.debug_struct("Access")
+            .field("methods", &self.methods)
+            .field("authenticated", &self.client.is_some())
+            .finish()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("invalid API key")]
+    InvalidApiKey,
+
+    #[error("malformed authorization header")]
+    MalformedAuthorization,
+
+    #[error("invalid token: {0}")]
+    InvalidToken(#[from] jsonwebtoken::errors::Error),
+
+    #[error("invalid token: {0}")]
+    InvalidTokenMethods(#[from] InvalidMethodPattern),
+}
+
+/// The claims of a JWT that are relevant for access control.
+#[derive(Debug, Deserialize)]
+struct Claims {
+    /// The subject of the token, used to identify the caller.
+    #[serde(default)]
+    sub: Option<String>,
+    /// The method patterns the token may call. If absent, the default JWT methods are used.
+    #[serde(default)]
+    methods: Option<Vec<String>>,
+}
+
+#[derive(Clone)]
+struct JwtAuth {
+    key: DecodingKey,
+    validation: Validation,
+    default_methods: MethodFilter,
+}
+
+/// Authentication configuration of the RPC server.
+#[derive(Clone)]
+pub struct AuthConfig {
+    public: Access,
+    api_keys: Vec<(String, Access)>,
+    jwt: Option<JwtAuth>,
+}
+
+impl AuthConfig {
+    /// Creates a new configuration where requests without credentials can only call
+    /// `public_methods`.
+    pub fn new(public_methods: MethodFilter) -> Self {
+        Self { public: Access::new(public_methods, None), api_keys: Vec::new(), jwt: None }
+    }
+
+    /// Adds a static API key that is allowed to call `methods`.
+    pub fn api_key(mut self, key: impl Into<String>, methods: MethodFilter) -> Self {
+        let key = key.into();
+        let access = Access::new(methods, Some(Arc::from(key.as_str())));
+        self.api_keys.push((key, access));
+        self
+    }
+
+    /// Accepts HS256 JWTs signed with `secret`. Tokens without a `methods` claim are allowed to
+    /// call `default_methods`.
+    pub fn jwt(mut self, secret: impl AsRef<[u8]>, default_methods: MethodFilter) -> Self {
+        let key = DecodingKey::from_secret(secret.as_ref());
+        let validation = Validation::new(Algorithm::HS256);
+        self.jwt = Some(JwtAuth { key, validation, default_methods });
+        self
+    }
+
+    /// Resolves the access granted by the credentials in `headers`.
+    pub fn authenticate(&self, headers: &HeaderMap) -> Result<Access, AuthError> {
+        if let Some(value) = headers.get(API_KEY_HEADER) {
+            let key = value.to_str().map_err(|_| AuthError::InvalidApiKey)?;
+            return self.find_api_key(key).ok_or(AuthError::InvalidApiKey);
+        }
+
+        let Some(value) = headers.get(AUTHORIZATION) else {
+            return Ok(self.public.clone());
+        };
+
+        let token = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or(AuthError::MalformedAuthorization)?;
+
+        if let Some(access) = self.find_api_key(token) {
+            return Ok(access);
+        }
+
+        match &self.jwt {
+            Some(jwt) => {
+                let claims =
+                    jsonwebtoken::decode::<Claims>(token, &jwt.key, &jwt.validation)?.claims;
+                let methods = match claims.methods {
+                    Some(methods) => MethodFilter::new(methods)?,
+                    None => jwt.default_methods.clone(),
+                };
+                Ok(Access::new(methods, claims.sub.map(Arc::from)))
+            }
+            None => Err(AuthError::InvalidApiKey),
+        }
+    }
+
+    fn find_api_key(&self, key: &str) -> Option<Access> {
+        // Compare against every key in constant time to not leak which keys exist through timing.
+        let mut found = None;
+        for (candidate, access) in &self.api_keys {
+            if constant_time_eq(candidate.as_bytes(), key.as_bytes()) {
+                found = Some(access.clone());
+            }
+        }
+        found
+    }
+}
+
+impl TryFrom<&RpcAuthConfig> for AuthConfig {
+    type Error = InvalidMethodPattern;
+
+    fn try_from(config: &RpcAuthConfig) -> Result<Self, Self::Error> {
+        let mut auth = AuthConfig::new(MethodFilter::new(&config.public_methods)?);
+
+        for api_key in &config.api_keys {
+            auth = auth.api_key(&api_key.key, MethodFilter::new(&api_key.methods)?);
+        }
+
+        if let Some(secret) = &config.jwt_secret {
+            auth = auth.jwt(secret, MethodFilter::new(&config.jwt_methods)?);
+        }
+
+        Ok(auth)
+    }
+}
+
+impl std::fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the credentials.
+        f.debug_struct("AuthConfig")
+            .field("public", &self.public)
+            .field("api_keys", &self.api_keys.len())
+            .field("jwt", &self.jwt.is_some())
+            .finish()
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// HTTP layer that authenticates requests using an [`AuthConfig`].
+#[derive(Debug, Clone)]
+pub struct AuthLayer {
+    config: Arc<AuthConfig>,
+}
+
+impl AuthLayer {
+    pub fn new(config: AuthConfig) -> Self {
+        Self { config: Arc::new(config) }
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = AuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService { inner, config: self.config.clone() }
+    }
+}
+
+/// Service created by [`AuthLayer`].
+#[derive(Debug, Clone)]
+pub struct AuthService<S> {
+    inner: S,
+    config: Arc<AuthConfig>,
+}
+
+impl<S, B> Service<Request<B>> for AuthService<S>
+where
+    S: Service<Request<B>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        match self.config.authenticate(req.headers()) {
+            Ok(access) => {
+                req.extensions_mut().insert(access);
+                Box::pin(self.inner.call(req))
+            }
+            Err(error) => {
+                debug!(target: "rpc", %error, "Rejected unauthenticated request.");
+                Box::pin(async move { Ok(unauthorized(&error)) })
+            }
+        }
+    }
+}
+
+fn unauthorized(error: &AuthError) -> Response<Body> {
+    let body = json!({ "error": error.to_string() }).to_string();
+    Response::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .header(CONTENT_TYPE, "application/json")
+        .header(WWW_AUTHENTICATE, "Bearer")
+        .body(Body::from(body))
+        .expect("qed; valid response")
+}
+
+/// RPC layer that enforces the [`Access`] resolved by [`AuthLayer`].
+///
+/// When enabled, calls without an attached access are rejected.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthRpcLayer {
+    enabled: bool,
+}
+
+impl AuthRpcLayer {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+impl<S> Layer<S> for AuthRpcLayer {
+    type Service = AuthRpcService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        AuthRpcService { service, enabled: self.enabled }
+    }
+}
+
+/// Service created by [`AuthRpcLayer`].
+#[derive(Debug, Clone)]
+pub struct AuthRpcService<S> {
+    service: S,
+    enabled: bool,
+}
+
+impl<S> AuthRpcService<S> {
+    fn check(&self, method: &str, extensions: &http::Extensions) -> Result<(), ErrorObjectOwned> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        match extensions.get::<Access>() {
+            Some(access) if access.allows(method) => Ok(()),
+            _ => Err(method_not_allowed(method)),
+        }
+    }
+}
+
+fn method_not_allowed(method: &str) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(
+        METHOD_NOT_ALLOWED_CODE,
+        format!("Not allowed to call method: {method}"),
+        None::<()>,
+    )
+}
+
+impl<S> RpcServiceT for AuthRpcService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type BatchResponse = S::BatchResponse;
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+
+    fn call<'a>(
+        &self,
+        req: jsonrpsee::types::Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let result = self.check(req.method_name(), req.extensions());
+        let service = self.service.clone();
+
+        async move {
+            match result {
+                Ok(()) => service.call(req).await,
+                Err(error) => MethodResponse::error(req.id().clone(), error),
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut batch: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // Replace the calls that aren't allowed with errors, so that the rest of the batch is
+        // still executed.
+        for entry in batch.iter_mut() {
+            if let Ok(BatchEntry::Call(req)) = entry {
+                if let Err(error) = self.check(req.method_name(), req.extensions()) {
+                    *entry = Err(BatchEntryErr::new(req.id().clone(), error));
+                }
+            }
+        }
+
+        self.service.batch(batch)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.service.notification(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{HeaderMap, HeaderValue};
+    use jsonrpsee::RpcModule;
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::{json, Value};
+
+    use super::{AuthConfig, AuthError, MethodFilter, METHOD_NOT_ALLOWED_CODE};
+    use crate::{RpcServer, RpcServerHandle};
+
+    const SECRET: &[u8] = b"secret";
+
+    fn config() -> AuthConfig {
+        AuthConfig::new(MethodFilter::new(["starknet_*"]).unwrap())
+            .api_key("admin", MethodFilter::any())
+            .jwt(SECRET, MethodFilter::new(["dev_*"]).unwrap())
+    }
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        HeaderMap::from_iter([(name.parse().unwrap(), HeaderValue::from_str(value).unwrap())])
+    }
+
+    fn token(claims: Value) -> String {
+        jsonwebtoken::encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET))
+            .unwrap()
+    }
+
+    fn exp() -> u64 {
+        jsonwebtoken::get_current_timestamp() + 60
+    }
+
+    #[test]
+    fn method_filter() {
+        let filter = MethodFilter::new(["starknet_*", "dev_generateBlock"]).unwrap();
+        assert!(filter.allows("starknet_call"));
+        assert!(filter.allows("dev_generateBlock"));
+        assert!(!filter.allows("dev_setStorageAt"));
+        assert!(!filter.allows("katana_getTransactions"));
+
+        assert!(MethodFilter::any().allows("anything"));
+        assert!(!MethodFilter::none().allows("starknet_call"));
+
+        assert!(MethodFilter::new(["dev_*_foo"]).is_err());
+        assert!(MethodFilter::new([""]).is_err());
+    }
+
+    #[test]
+    fn anonymous_gets_public_access() {
+        let access = config().authenticate(&HeaderMap::new()).unwrap();
+        assert!(access.allows("starknet_chainId"));
+        assert!(!access.allows("dev_generateBlock"));
+        assert_eq!(access.client(), None);
+        // The health check method is always allowed.
+        assert!(access.allows("health"));
+    }
+
+    #[test]
+    fn api_key() {
+        let config = config();
+
+        let access = config.authenticate(&headers("x-api-key", "admin")).unwrap();
+        assert!(access.allows("dev_generateBlock"));
+        assert_eq!(access.client(), Some("admin"));
+
+        let access = config.authenticate(&headers("authorization", "Bearer admin")).unwrap();
+        assert!(access.allows("katana_getTransactions"));
+
+        let result = config.authenticate(&headers("x-api-key", "wrong"));
+        assert!(matches!(result, Err(AuthError::InvalidApiKey)));
+    }
+
+    #[test]
+    fn jwt() {
+        let config = config();
+
+        // Tokens without the `methods` claim get the default JWT access.
+        let bearer = format!("Bearer {}", token(json!({ "exp": exp() })));
+        let access = config.authenticate(&headers("authorization", &bearer)).unwrap();
+        assert!(access.allows("dev_generateBlock"));
+        assert!(!access.allows("katana_getTransactions"));
+
+        let claims = json!({ "exp": exp(), "sub": "ops", "methods": ["katana_*"] });
+        let bearer = format!("Bearer {}", token(claims));
+        let access = config.authenticate(&headers("authorization", &bearer)).unwrap();
+        assert!(access.allows("katana_getTransactions"));
+        assert!(!access.allows("dev_generateBlock"));
+        assert_eq!(access.client(), Some("ops"));
+
+        // Expired token.
+        let claims = json!({ "exp": jsonwebtoken::get_current_timestamp() - 3600 });
+        let bearer = format!("Bearer {}", token(claims));
+        let result = config.authenticate(&headers("authorization", &bearer));
+        assert!(matches!(result, Err(AuthError::InvalidToken(..))));
+
+        let result = config.authenticate(&headers("authorization", "Basic foo"));
+        assert!(matches!(result, Err(AuthError::MalformedAuthorization)));
+    }
+
+    async fn start_server() -> (String, RpcServerHandle) {
+        let mut module = RpcModule::new(());
+        module.register_method("starknet_chainId", |_, _, _| "0x1").unwrap();
+        module.register_method("dev_generateBlock", |_, _, _| "ok").unwrap();
+
+        let server = RpcServer::new().auth(config()).module(module).unwrap();
+        let handle = server.start("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        (format!("http://{}", handle.addr()), handle)
+    }
+
+    fn call(method: &str, id: u64) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": [] })
+    }
+
+    #[tokio::test]
+    async fn enforces_access_per_call() {
+        let (url, _handle) = start_server().await;
+        let client = reqwest::Client::new();
+
+        // Anonymous callers can only call the public methods.
+        let response: Value = client
+            .post(&url)
+            .json(&call("starknet_chainId", 1))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["result"], "0x1");
+
+        let response: Value = client
+            .post(&url)
+            .json(&call("dev_generateBlock", 1))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_ALLOWED_CODE);
+
+        // Disallowed calls in a batch are rejected individually.
+        let batch = json!([call("starknet_chainId", 1), call("dev_generateBlock", 2)]);
+        let response: Vec<Value> =
+            client.post(&url).json(&batch).send().await.unwrap().json().await.unwrap();
+        let result = response.iter().find(|r| r["id"] == 1).unwrap();
+        let error = response.iter().find(|r| r["id"] == 2).unwrap();
+        assert_eq!(result["result"], "0x1");
+        assert_eq!(error["error"]["code"], METHOD_NOT_ALLOWED_CODE);
+
+        // Authenticated callers get their granted access.
+        let response: Value = client
+            .post(&url)
+            .header("x-api-key", "admin")
+            .json(&call("dev_generateBlock", 1))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["result"], "ok");
+
+        // Invalid credentials are rejected before reaching the RPC handlers.
+        let response = client
+            .post(&url)
+            .header("x-api-key", "wrong")
+            .json(&call("starknet_chainId", 1))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    }
+}
@@ -18,6 +18,7 @@
 //! - Number of calls started for each method
 //! - Number of successful calls for each method
 //! - Number of failed calls for each method
+//! - Number of calls rejected by the rate limiter for each method
 //! - Response time for each method call
 
 use std::collections::HashMap;
@@ -32,6 +33,8 @@ use katana_metrics::metrics::{Counter, Histogram};
 use katana_metrics::Metrics;
 use tower::Layer;
 
+use super::rate_limit::RATE_LIMITED_CODE;
+
 /// Metrics for the RPC server.
 #[allow(missing_debug_implementations)]
 #[derive(Default, Clone)]
@@ -91,6 +94,8 @@ struct RpcServerCallMetrics {
     successful: Counter,
     /// The number of failed calls
     failed: Counter,
+    /// The number of calls rejected by the rate limiter
+    rate_limited: Counter,
     /// Response for a single call
     time_seconds: Histogram,
 }
@@ -176,6 +181,10 @@ where
                 } else {
                     call_metrics.failed.increment(1)
                 }
+
+                if result.as_error_code() == Some(RATE_LIMITED_CODE) {
+                    call_metrics.rate_limited.increment(1)
+                }
             }
 
             result
@@ -1,7 +1,9 @@
+pub mod auth;
 pub mod cartridge;
 pub mod cors;
 pub mod logger;
 pub mod metrics;
+pub mod rate_limit;
 
 #[cfg(test)]
 mod tests;
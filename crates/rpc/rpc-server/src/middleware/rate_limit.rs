@@ -0,0 +1,581 @@
+//! Per-client rate limiting for the RPC server.
+//!
+//! Every client gets its own token bucket, which is refilled at a constant rate up to its burst
+//! capacity. Each call consumes a number of tokens equal to the weight of the called method, so
+//! that expensive methods (eg, `starknet_getEvents`, `starknet_traceBlockTransactions`) are
+//! throttled sooner than cheap ones. Calls made when the bucket doesn't hold enough tokens are
+//! rejected with the [`RATE_LIMITED_CODE`] error code.
+//!
+//! Clients are identified by [`ClientIdLayer`] at the HTTP level:
+//!
+//! 1. Authenticated callers (see [`super::auth`]) are identified by their credentials.
+//! 2. Otherwise, if the server is configured to trust proxy headers, by the IP address in the
+//!    `X-Forwarded-For` or `X-Real-IP` header.
+//! 3. Otherwise, by the IP address of the peer that sent the request (see [`PeerAddr`]).
+//! 4. Otherwise, all the remaining callers share a single bucket.
+//!
+//! IPv6 callers are identified by their /64 prefix, as a single host is usually assigned a whole
+//! /64 and could otherwise get a fresh bucket for every address.
+//!
+//! Proxy headers can be set by the callers themselves, so they should only be trusted when the
+//! server is running behind a reverse proxy that overrides them.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::{IpAddr, Ipv6Addr};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use http::{HeaderMap, Request};
+use jsonrpsee::core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification, RpcServiceT};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::MethodResponse;
+use katana_node_config::rpc::RpcRateLimitConfig;
+use parking_lot::Mutex;
+use quick_cache::sync::Cache;
+use serde_json::json;
+use tower::{Layer, Service};
+
+use super::auth::Access;
+use crate::PeerAddr;
+
+/// Error code returned when a call is rejected by the rate limiter.
+pub const RATE_LIMITED_CODE: i32 = -32005;
+
+/// The weight of methods without an explicitly configured weight.
+pub const DEFAULT_METHOD_WEIGHT: u32 = 1;
+
+/// The default weights of the methods that are more expensive to serve.
+pub const DEFAULT_METHOD_WEIGHTS: &[(&str, u32)] = &[
+    ("starknet_getEvents", 10),
+    ("starknet_getStorageProof", 10),
+    ("starknet_traceBlockTransactions", 20),
+    ("starknet_traceTransaction", 5),
+    ("starknet_simulateTransactions", 10),
+    ("starknet_estimateFee", 5),
+    ("starknet_estimateMessageFee", 5),
+];
+
+/// The maximum number of clients tracked by the rate limiter.
+///
+/// Once reached, the least recently seen clients are evicted, which resets their bucket.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// The mask of the /64 prefix IPv6 clients are identified by.
+const IPV6_PREFIX_MASK: u128 = u128::MAX << 64;
+
+/// Identifies the client a call is accounted to.
+///
+/// This is attached to the HTTP request extensions by [`ClientIdLayer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientId {
+    /// A caller authenticated with credentials.
+    Authenticated(Arc<str>),
+    /// A caller identified by its IP address, or the /64 prefix of its address for IPv6.
+    Ip(IpAddr),
+    /// A caller that couldn't be identified, ie the address of the peer isn't known.
+    Unknown,
+}
+
+impl ClientId {
+    /// Identifies the caller of a request, see the [module](self) documentation.
+    pub fn from_request(
+        extensions: &http::Extensions,
+        headers: &HeaderMap,
+        trust_proxy: bool,
+    ) -> Self {
+        if let Some(client) = extensions.get::<Access>().and_then(Access::client) {
+            return Self::Authenticated(Arc::from(client));
+        }
+
+        if trust_proxy {
+            if let Some(ip) = forwarded_ip(headers) {
+                return Self::ip(ip);
+            }
+        }
+
+        match extensions.get::<PeerAddr>() {
+            Some(PeerAddr(addr)) => Self::ip(addr.ip()),
+            None => Self::Unknown,
+        }
+    }
+
+    /// Identifies a caller by its IP address, keeping only the /64 prefix of IPv6 addresses.
+    fn ip(ip: IpAddr) -> Self {
+        match ip.to_canonical() {
+            IpAddr::V6(ip) => Self::Ip(IpAddr::V6(Ipv6Addr::from(ip.to_bits() & IPV6_PREFIX_MASK))),
+            ip => Self::Ip(ip),
+        }
+    }
+}
+
+/// Returns the client IP address from the `X-Forwarded-For` or `X-Real-IP` headers.
+fn forwarded_ip(headers: &HeaderMap) -> Option<IpAddr> {
+    // The left-most address of `X-Forwarded-For` is the original client.
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .and_then(|ip| ip.trim().parse().ok());
+
+    forwarded_for.or_else(|| {
+        headers.get("x-real-ip").and_then(|value| value.to_str().ok()?.trim().parse().ok())
+    })
+}
+
+/// Rate limiting configuration of the RPC server.
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// The number of tokens added to each client's bucket every second.
+    requests_per_second: NonZeroU32,
+    /// The maximum number of tokens a client's bucket can hold.
+    burst: NonZeroU32,
+    /// The number of tokens consumed by each method.
+    weights: HashMap<String, u32>,
+    /// Whether to identify clients using the `X-Forwarded-For` and `X-Real-IP` headers.
+    trust_proxy_headers: bool,
+}
+
+impl RateLimitConfig {
+    /// Creates a new configuration where every client can make `requests_per_second` calls of
+    /// weight 1 per second, with a burst capacity of the same amount.
+    pub fn new(requests_per_second: NonZeroU32) -> Self {
+        let weights = DEFAULT_METHOD_WEIGHTS.iter().map(|(m, w)| (m.to_string(), *w)).collect();
+        Self {
+            requests_per_second,
+            burst: requests_per_second,
+            weights,
+            trust_proxy_headers: false,
+        }
+    }
+
+    /// Sets the maximum number of tokens a client's bucket can hold.
+    pub fn burst(mut self, burst: NonZeroU32) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Sets the number of tokens consumed by each call to `method`.
+    pub fn method_weight(mut self, method: impl Into<String>, weight: u32) -> Self {
+        self.weights.insert(method.into(), weight);
+        self
+    }
+
+    /// Identifies unauthenticated clients by the IP address in the `X-Forwarded-For` and
+    /// `X-Real-IP` headers. Default is `false`.
+    pub fn trust_proxy_headers(mut self, trust: bool) -> Self {
+        self.trust_proxy_headers = trust;
+        self
+    }
+
+    /// Returns `true` if clients are identified using proxy headers.
+    pub fn trusts_proxy_headers(&self) -> bool {
+        self.trust_proxy_headers
+    }
+
+    fn weight(&self, method: &str) -> u32 {
+        self.weights.get(method).copied().unwrap_or(DEFAULT_METHOD_WEIGHT)
+    }
+}
+
+impl From<&RpcRateLimitConfig> for RateLimitConfig {
+    fn from(config: &RpcRateLimitConfig) -> Self {
+        let mut limit = RateLimitConfig::new(config.requests_per_second)
+            .trust_proxy_headers(config.trust_proxy_headers);
+
+        if let Some(burst) = config.burst {
+            limit = limit.burst(burst);
+        }
+
+        for weight in &config.method_weights {
+            limit = limit.method_weight(&weight.method, weight.weight);
+        }
+
+        limit
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// The token buckets of all the clients.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Cache<ClientId, Arc<Mutex<TokenBucket>>>,
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config, buckets: Cache::new(MAX_TRACKED_CLIENTS) }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Consumes the tokens for a call to `method` by `client`.
+    ///
+    /// Returns how long the client has to wait before retrying if there aren't enough tokens.
+    pub fn try_acquire(&self, client: &ClientId, method: &str) -> Result<(), Duration> {
+        self.try_acquire_at(client, method, Instant::now())
+    }
+
+    fn try_acquire_at(
+        &self,
+        client: &ClientId,
+        method: &str,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let rate = self.config.requests_per_second.get() as f64;
+        let capacity = self.config.burst.get() as f64;
+        // Clamp the cost so that methods heavier than the burst capacity can still be called.
+        let cost = (self.config.weight(method) as f64).min(capacity);
+
+        let Ok(bucket) = self.buckets.get_or_insert_with(client, || {
+            let bucket = TokenBucket { tokens: capacity, updated_at: now };
+            Ok::<_, Infallible>(Arc::new(Mutex::new(bucket)))
+        });
+
+        let mut bucket = bucket.lock();
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= cost {
+            bucket.tokens -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((cost - bucket.tokens) / rate))
+        }
+    }
+}
+
+/// HTTP layer that attaches the [`ClientId`] of the caller to the request extensions.
+///
+/// Must be placed after [`super::auth::AuthLayer`] for authenticated callers to be identified by
+/// their credentials.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIdLayer {
+    trust_proxy_headers: bool,
+}
+
+impl ClientIdLayer {
+    pub fn new(trust_proxy_headers: bool) -> Self {
+        Self { trust_proxy_headers }
+    }
+}
+
+impl<S> Layer<S> for ClientIdLayer {
+    type Service = ClientIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientIdService { inner, trust_proxy_headers: self.trust_proxy_headers }
+    }
+}
+
+/// Service created by [`ClientIdLayer`].
+#[derive(Debug, Clone)]
+pub struct ClientIdService<S> {
+    inner: S,
+    trust_proxy_headers: bool,
+}
+
+impl<S, B> Service<Request<B>> for ClientIdService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let client =
+            ClientId::from_request(req.extensions(), req.headers(), self.trust_proxy_headers);
+        req.extensions_mut().insert(client);
+        self.inner.call(req)
+    }
+}
+
+/// RPC layer that rate limits calls using the [`ClientId`] resolved by [`ClientIdLayer`].
+///
+/// Calls are let through if rate limiting is disabled.
+#[derive(Debug, Clone)]
+pub struct RateLimitRpcLayer {
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RateLimitRpcLayer {
+    pub fn new(limiter: Option<Arc<RateLimiter>>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitRpcLayer {
+    type Service = RateLimitRpcService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RateLimitRpcService { service, limiter: self.limiter.clone() }
+    }
+}
+
+/// Service created by [`RateLimitRpcLayer`].
+#[derive(Debug, Clone)]
+pub struct RateLimitRpcService<S> {
+    service: S,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl<S> RateLimitRpcService<S> {
+    fn check(&self, method: &str, extensions: &http::Extensions) -> Result<(), ErrorObjectOwned> {
+        let Some(limiter) = &self.limiter else { return Ok(()) };
+        let client = extensions.get::<ClientId>().unwrap_or(&ClientId::Unknown);
+        limiter.try_acquire(client, method).map_err(rate_limited)
+    }
+}
+
+fn rate_limited(retry_after: Duration) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(
+        RATE_LIMITED_CODE,
+        "Rate limit exceeded",
+        Some(json!({ "retry_after_ms": retry_after.as_millis() as u64 })),
+    )
+}
+
+impl<S> RpcServiceT for RateLimitRpcService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type BatchResponse = S::BatchResponse;
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+
+    fn call<'a>(
+        &self,
+        req: jsonrpsee::types::Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let result = self.check(req.method_name(), req.extensions());
+        let service = self.service.clone();
+
+        async move {
+            match result {
+                Ok(()) => service.call(req).await,
+                Err(error) => MethodResponse::error(req.id().clone(), error),
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut batch: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // Every call in the batch is accounted for individually.
+        for entry in batch.iter_mut() {
+            if let Ok(BatchEntry::Call(req)) = entry {
+                if let Err(error) = self.check(req.method_name(), req.extensions()) {
+                    *entry = Err(BatchEntryErr::new(req.id().clone(), error));
+                }
+            }
+        }
+
+        self.service.batch(batch)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.service.notification(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::net::{IpAddr, SocketAddr};
+    use std::num::NonZeroU32;
+    use std::time::{Duration, Instant};
+
+    use http::{HeaderMap, HeaderValue};
+    use jsonrpsee::RpcModule;
+    use serde_json::{json, Value};
+    use tower::{service_fn, Layer, ServiceExt};
+
+    use super::{
+        forwarded_ip, ClientId, ClientIdLayer, RateLimitConfig, RateLimiter, RATE_LIMITED_CODE,
+    };
+    use crate::{PeerAddr, RpcServer};
+
+    fn limiter(rps: u32, burst: u32) -> RateLimiter {
+        let config = RateLimitConfig::new(NonZeroU32::new(rps).unwrap())
+            .burst(NonZeroU32::new(burst).unwrap())
+            .method_weight("heavy", 5);
+        RateLimiter::new(config)
+    }
+
+    #[test]
+    fn token_bucket() {
+        let limiter = limiter(2, 4);
+        let client = ClientId::Unknown;
+        let now = Instant::now();
+
+        // The bucket starts full.
+        for _ in 0..4 {
+            limiter.try_acquire_at(&client, "light", now).unwrap();
+        }
+        let retry_after = limiter.try_acquire_at(&client, "light", now).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+
+        // Refilled at 2 tokens per second.
+        let now = now + Duration::from_secs(1);
+        limiter.try_acquire_at(&client, "light", now).unwrap();
+        limiter.try_acquire_at(&client, "light", now).unwrap();
+        assert!(limiter.try_acquire_at(&client, "light", now).is_err());
+
+        // Methods heavier than the burst capacity consume the whole bucket.
+        let now = now + Duration::from_secs(10);
+        limiter.try_acquire_at(&client, "heavy", now).unwrap();
+        assert!(limiter.try_acquire_at(&client, "light", now).is_err());
+    }
+
+    #[test]
+    fn clients_are_limited_independently() {
+        let limiter = limiter(1, 1);
+        let now = Instant::now();
+
+        let a = ClientId::Authenticated("a".into());
+        let b = ClientId::Ip(IpAddr::from([127, 0, 0, 1]));
+
+        limiter.try_acquire_at(&a, "light", now).unwrap();
+        assert!(limiter.try_acquire_at(&a, "light", now).is_err());
+        limiter.try_acquire_at(&b, "light", now).unwrap();
+    }
+
+    #[test]
+    fn proxy_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(forwarded_ip(&headers), None);
+
+        headers.insert("x-real-ip", HeaderValue::from_static("10.0.0.2"));
+        assert_eq!(forwarded_ip(&headers), Some(IpAddr::from([10, 0, 0, 2])));
+
+        headers.insert("x-forwarded-for", HeaderValue::from_static("10.0.0.1, 192.168.0.1"));
+        assert_eq!(forwarded_ip(&headers), Some(IpAddr::from([10, 0, 0, 1])));
+
+        let mut extensions = http::Extensions::new();
+        assert_eq!(ClientId::from_request(&extensions, &headers, false), ClientId::Unknown);
+        assert_eq!(
+            ClientId::from_request(&extensions, &headers, true),
+            ClientId::Ip(IpAddr::from([10, 0, 0, 1]))
+        );
+
+        // Without trusting the proxy headers, clients are identified by the peer address.
+        extensions.insert(PeerAddr(SocketAddr::from(([192, 168, 0, 2], 8080))));
+        assert_eq!(
+            ClientId::from_request(&extensions, &headers, false),
+            ClientId::Ip(IpAddr::from([192, 168, 0, 2]))
+        );
+        assert_eq!(
+            ClientId::from_request(&extensions, &headers, true),
+            ClientId::Ip(IpAddr::from([10, 0, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn ipv6_clients_are_identified_by_prefix() {
+        let client = |ip: &str| {
+            let mut extensions = http::Extensions::new();
+            let addr = SocketAddr::new(ip.parse().unwrap(), 8080);
+            extensions.insert(PeerAddr(addr));
+            ClientId::from_request(&extensions, &HeaderMap::new(), false)
+        };
+
+        // Addresses in the same /64 share a bucket.
+        let prefix = ClientId::Ip("2001:db8:0:1::".parse().unwrap());
+        assert_eq!(client("2001:db8:0:1::1"), prefix);
+        assert_eq!(client("2001:db8:0:1:ffff:ffff:ffff:ffff"), prefix);
+        assert_ne!(client("2001:db8:0:2::1"), prefix);
+
+        // IPv4-mapped addresses are identified as IPv4 ones.
+        assert_eq!(client("::ffff:10.0.0.1"), ClientId::Ip(IpAddr::from([10, 0, 0, 1])));
+    }
+
+    #[tokio::test]
+    async fn rejects_calls_over_the_limit() {
+        let mut module = RpcModule::new(());
+        module.register_method("starknet_chainId", |_, _, _| "0x1").unwrap();
+
+        let config = RateLimitConfig::new(NonZeroU32::new(1).unwrap()).trust_proxy_headers(true);
+        let server = RpcServer::new().rate_limit(config).module(module).unwrap();
+        let handle = server.start("127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+        let url = format!("http://{}", handle.addr());
+        let client = reqwest::Client::new();
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId" });
+
+        let call = |ip: &'static str| {
+            client.post(&url).header("x-forwarded-for", ip).json(&request).send()
+        };
+
+        let response: Value = call("10.0.0.1").await.unwrap().json().await.unwrap();
+        assert_eq!(response["result"], "0x1");
+
+        let response: Value = call("10.0.0.1").await.unwrap().json().await.unwrap();
+        assert_eq!(response["error"]["code"], RATE_LIMITED_CODE);
+        assert!(response["error"]["data"]["retry_after_ms"].is_u64());
+
+        // Other clients have their own limit.
+        let response: Value = call("10.0.0.2").await.unwrap().json().await.unwrap();
+        assert_eq!(response["result"], "0x1");
+    }
+
+    #[tokio::test]
+    async fn peers_are_limited_independently() {
+        let limiter = limiter(1, 1);
+        let now = Instant::now();
+
+        // Resolves the client of a request sent by `peer` with a spoofed proxy header. The peer
+        // address is injected the same way the server's accept loop does.
+        let client_id = |peer: [u8; 4]| {
+            let mut req = http::Request::new(());
+            req.headers_mut().insert("x-forwarded-for", HeaderValue::from_static("10.0.0.1"));
+            req.extensions_mut().insert(PeerAddr(SocketAddr::from((peer, 8080))));
+
+            let service = ClientIdLayer::new(false).layer(service_fn(|req: http::Request<()>| {
+                let client = req.extensions().get::<ClientId>().cloned();
+                async move { Ok::<_, Infallible>(client) }
+            }));
+
+            service.oneshot(req)
+        };
+
+        let a = client_id([127, 0, 0, 1]).await.unwrap().expect("client id must be attached");
+        let b = client_id([127, 0, 0, 2]).await.unwrap().expect("client id must be attached");
+        assert_eq!(a, ClientId::Ip(IpAddr::from([127, 0, 0, 1])));
+        assert_eq!(b, ClientId::Ip(IpAddr::from([127, 0, 0, 2])));
+
+        limiter.try_acquire_at(&a, "light", now).unwrap();
+        assert!(limiter.try_acquire_at(&a, "light", now).is_err());
+        limiter.try_acquire_at(&b, "light", now).unwrap();
+    }
+}
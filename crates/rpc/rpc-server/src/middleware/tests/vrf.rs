@@ -453,7 +453,7 @@ mod setup {
     use tower::Layer;
     use url::Url;
 
-    use crate::cartridge::{encode_calls, VrfService, VrfServiceConfig};
+    use crate::cartridge::{encode_calls, VrfProvider, VrfService, VrfServiceConfig};
     use crate::middleware::cartridge::{VrfLayer, VrfMiddlewareService};
 
     const ANY_CALLER: Felt = felt!("0x414e595f43414c4c4552");
@@ -495,7 +495,7 @@ mod setup {
 
         let vrf_service = VrfService::new(VrfServiceConfig {
             rpc_url: Url::parse("http://127.0.0.1:0").unwrap(),
-            service_url: vrf_url,
+            provider: VrfProvider::Remote(vrf_url),
             vrf_contract: VRF_CONTRACT,
         });
 
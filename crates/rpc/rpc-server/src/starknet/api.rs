@@ -23,16 +23,18 @@ use katana_rpc_types::broadcasted::{
 };
 use katana_rpc_types::event::{EventFilterWithPage, GetEventsResponse};
 use katana_rpc_types::message::MsgFromL1;
+use katana_rpc_types::overrides::{BlockOverride, StateOverride};
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
 use katana_rpc_types::state_update::StateUpdate;
 use katana_rpc_types::trace::{
-    SimulatedTransactionsResponse, TraceBlockTransactionsResponse, TxTrace,
+    DecodedTxTrace, SimulatedTransactionsResponse, TraceBlockTransactionsResponse, TxTrace,
 };
 use katana_rpc_types::transaction::RpcTxWithHash;
 use katana_rpc_types::trie::{ContractStorageKeys, GetStorageProofResponse};
 use katana_rpc_types::{
     BroadcastedTx, BroadcastedTxWithChainId, CallResponse, CasmClass, Class,
-    EstimateFeeSimulationFlag, FeeEstimate, FunctionCall, SimulationFlag, TxStatus,
+    EstimateFeeSimulationFlag, FeeEstimate, FunctionCall, KatanaSimulationFlag, SimulationFlag,
+    TxStatus,
 };
 
 use super::StarknetApi;
@@ -134,6 +136,60 @@ where
         self.wait_for_tx_receipt(response.transaction_hash).await
     }
 
+    pub async fn estimate_fee_txs(
+        &self,
+        request: Vec<BroadcastedTx>,
+        simulation_flags: Vec<EstimateFeeSimulationFlag>,
+        block_id: BlockIdOrTag,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> Result<Vec<FeeEstimate>, StarknetApiError> {
+        let chain = self.inner.chain_spec.id();
+
+        let transactions = request
+            .into_iter()
+            .map(|tx| {
+                let is_query = tx.is_query();
+                let tx = ExecutableTx::from(BroadcastedTxWithChainId { tx, chain });
+                ExecutableTxWithHash::new_query(tx, is_query)
+            })
+            .collect::<Vec<_>>();
+
+        let skip_validate = simulation_flags.contains(&EstimateFeeSimulationFlag::SkipValidate);
+
+        // If the node is run with transaction validation disabled, then we should not validate
+        // transactions when estimating the fee even if the `SKIP_VALIDATE` flag is not set.
+        let should_validate =
+            !skip_validate && self.inner.config.simulation_flags.account_validation();
+
+        // We don't care about the nonce when estimating the fee as the nonce value
+        // doesn't affect transaction execution.
+        //
+        // This doesn't completely disregard the nonce as nonce < account nonce will
+        // return an error. It only 'relaxes' the check for nonce >= account nonce.
+        let flags = katana_executor::ExecutionFlags::new()
+            .with_account_validation(should_validate)
+            .with_nonce_check(false);
+
+        let permit =
+            self.inner.estimate_fee_permit.acquire().await.map_err(|e| {
+                StarknetApiError::unexpected(format!("Failed to acquire permit: {e}"))
+            })?;
+
+        self.on_cpu_blocking_task(move |this| async move {
+            let _permit = permit;
+            let results = this.estimate_fee_with(
+                transactions,
+                block_id,
+                flags,
+                state_override,
+                block_override,
+            )?;
+            Ok(results)
+        })
+        .await?
+    }
+
     async fn wait_for_tx_receipt(
         &self,
         transaction_hash: TxHash,
@@ -263,7 +319,7 @@ where
     }
 
     async fn call(&self, request: FunctionCall, block_id: BlockIdOrTag) -> RpcResult<CallResponse> {
-        Ok(self.call_contract(request, block_id).await?)
+        Ok(self.call_contract(request, block_id, None, None).await?)
     }
 
     async fn get_storage_at(
@@ -281,44 +337,7 @@ where
         simulation_flags: Vec<EstimateFeeSimulationFlag>,
         block_id: BlockIdOrTag,
     ) -> RpcResult<Vec<FeeEstimate>> {
-        let chain = self.inner.chain_spec.id();
-
-        let transactions = request
-            .into_iter()
-            .map(|tx| {
-                let is_query = tx.is_query();
-                let tx = ExecutableTx::from(BroadcastedTxWithChainId { tx, chain });
-                ExecutableTxWithHash::new_query(tx, is_query)
-            })
-            .collect::<Vec<_>>();
-
-        let skip_validate = simulation_flags.contains(&EstimateFeeSimulationFlag::SkipValidate);
-
-        // If the node is run with transaction validation disabled, then we should not validate
-        // transactions when estimating the fee even if the `SKIP_VALIDATE` flag is not set.
-        let should_validate =
-            !skip_validate && self.inner.config.simulation_flags.account_validation();
-
-        // We don't care about the nonce when estimating the fee as the nonce value
-        // doesn't affect transaction execution.
-        //
-        // This doesn't completely disregard the nonce as nonce < account nonce will
-        // return an error. It only 'relaxes' the check for nonce >= account nonce.
-        let flags = katana_executor::ExecutionFlags::new()
-            .with_account_validation(should_validate)
-            .with_nonce_check(false);
-
-        let permit =
-            self.inner.estimate_fee_permit.acquire().await.map_err(|e| {
-                StarknetApiError::unexpected(format!("Failed to acquire permit: {e}"))
-            })?;
-
-        self.on_cpu_blocking_task(move |this| async move {
-            let _permit = permit;
-            let results = this.estimate_fee_with(transactions, block_id, flags)?;
-            Ok(results)
-        })
-        .await?
+        Ok(self.estimate_fee_txs(request, simulation_flags, block_id, None, None).await?)
     }
 
     async fn estimate_message_fee(
@@ -336,6 +355,8 @@ where
                 vec![ExecutableTxWithHash { hash, transaction: tx.into() }],
                 block_id,
                 Default::default(),
+                None,
+                None,
             );
 
             match result {
@@ -415,7 +436,8 @@ where
         transactions: Vec<BroadcastedTx>,
         simulation_flags: Vec<SimulationFlag>,
     ) -> RpcResult<SimulatedTransactionsResponse> {
-        let transactions = self.simulate_txs(block_id, transactions, simulation_flags).await?;
+        let flags = simulation_flags.into_iter().map(KatanaSimulationFlag::from).collect();
+        let transactions = self.simulate_txs(block_id, transactions, flags, None, None).await?;
         Ok(SimulatedTransactionsResponse { transactions })
     }
 
@@ -461,4 +483,49 @@ where
     ) -> RpcResult<TxReceiptWithBlockInfo> {
         Ok(self.add_deploy_account_tx_sync(deploy_account_transaction).await?)
     }
+
+    async fn trace_transaction_decoded(
+        &self,
+        transaction_hash: TxHash,
+    ) -> RpcResult<DecodedTxTrace> {
+        Ok(self.trace_decoded(transaction_hash).await?)
+    }
+
+    async fn call_with_overrides(
+        &self,
+        request: FunctionCall,
+        block_id: BlockIdOrTag,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> RpcResult<CallResponse> {
+        Ok(self.call_contract(request, block_id, state_override, block_override).await?)
+    }
+
+    async fn estimate_fee_with_overrides(
+        &self,
+        request: Vec<BroadcastedTx>,
+        simulation_flags: Vec<EstimateFeeSimulationFlag>,
+        block_id: BlockIdOrTag,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> RpcResult<Vec<FeeEstimate>> {
+        let estimates = self
+            .estimate_fee_txs(request, simulation_flags, block_id, state_override, block_override)
+            .await?;
+        Ok(estimates)
+    }
+
+    async fn simulate_transactions_with_overrides(
+        &self,
+        block_id: BlockIdOrTag,
+        transactions: Vec<BroadcastedTx>,
+        simulation_flags: Vec<KatanaSimulationFlag>,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> RpcResult<SimulatedTransactionsResponse> {
+        let transactions = self
+            .simulate_txs(block_id, transactions, simulation_flags, state_override, block_override)
+            .await?;
+        Ok(SimulatedTransactionsResponse { transactions })
+    }
 }
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use katana_chain_spec::ChainSpec;
 use katana_executor::blockifier::cache::ClassCache;
 use katana_executor::blockifier::call::execute_call;
-use katana_executor::blockifier::state::CachedState;
+use katana_executor::blockifier::state::{CachedState, StateAccessRecorder};
 use katana_executor::blockifier::utils::{self, block_context_from_envs};
 use katana_executor::error::ExecutionError;
 use katana_executor::{ExecutionFlags, ExecutionResult, ResultAndStates};
@@ -16,6 +16,11 @@ use katana_rpc_types::{FeeEstimate, FunctionCall};
 
 use crate::starknet::StarknetApiResult;
 
+/// Simulates a list of transactions, each executed on top of the state left by the previous ones.
+///
+/// If `record_accesses` is set, the state accessed and updated by each transaction is recorded in
+/// its [`ResultAndStates`].
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", target = "rpc", skip_all, fields(total_txs = transactions.len()))]
 pub fn simulate(
     chain_spec: &ChainSpec,
@@ -24,6 +29,7 @@ pub fn simulate(
     overrides: Option<&VersionedConstantsOverrides>,
     transactions: Vec<ExecutableTxWithHash>,
     flags: ExecutionFlags,
+    record_accesses: bool,
     class_cache: &ClassCache,
 ) -> Vec<ResultAndStates> {
     let block_context = Arc::new(block_context_from_envs(chain_spec, &block_env, overrides));
@@ -34,8 +40,16 @@ pub fn simulate(
         for tx in transactions {
             // Safe to unwrap here because the only way the call to `transact` can return an error
             // is when bouncer is `Some`.
-            let result = utils::transact(state, &block_context, &flags, tx, None).unwrap();
-            let simulated_result = ResultAndStates { result, states: Default::default() };
+            let simulated_result = if record_accesses {
+                let mut recorder = StateAccessRecorder::new(state);
+                let result =
+                    utils::transact(&mut recorder, &block_context, &flags, tx, None).unwrap();
+                let (accesses, states) = recorder.finish();
+                ResultAndStates { result, states, accesses: Some(accesses) }
+            } else {
+                let result = utils::transact(state, &block_context, &flags, tx, None).unwrap();
+                ResultAndStates { result, states: Default::default(), accesses: None }
+            };
 
             results.push(simulated_result);
         }
@@ -67,6 +67,23 @@ impl RpcCache {
         }
     }
 
+    /// Removes all the cached entries.
+    ///
+    /// Must be called when the chain is replaced (eg. when re-forking), as the cached data might
+    /// then no longer be valid.
+    pub fn clear(&self) {
+        let inner = &self.inner;
+        inner.blocks_with_txs.clear();
+        inner.blocks_with_tx_hashes.clear();
+        inner.blocks_with_receipts.clear();
+        inner.transactions.clear();
+        inner.receipts.clear();
+        inner.classes.clear();
+        inner.state_updates.clear();
+        inner.traces.clear();
+        inner.block_traces.clear();
+    }
+
     // --- Blocks with transactions ---
 
     pub fn get_block_with_txs(&self, block_num: BlockNumber) -> Option<BlockWithTxs> {
@@ -1,5 +1,6 @@
 //! Server implementation for the Starknet JSON-RPC API.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::future::Future;
 use std::sync::Arc;
@@ -7,15 +8,18 @@ use std::sync::Arc;
 use katana_chain_spec::ChainSpec;
 use katana_core::utils::get_current_timestamp;
 use katana_executor::blockifier::cache::ClassCache;
-use katana_executor::{ExecutionResult, ResultAndStates};
+use katana_executor::{revert, ExecutionResult, ResultAndStates};
 use katana_gas_price_oracle::GasPriceOracle;
 use katana_pool::api::TransactionPool;
 use katana_primitives::block::{BlockHashOrNumber, BlockIdOrTag, FinalityStatus, GasPrices};
-use katana_primitives::class::{ClassHash, CompiledClass};
+use katana_primitives::class::{
+    ClassHash, CompiledClass, ContractClass, MaybeInvalidSierraContractAbi,
+};
 use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
 use katana_primitives::env::BlockEnv;
 use katana_primitives::event::MaybeForkedContinuationToken;
 use katana_primitives::execution::TypedTransactionExecutionInfo;
+use katana_primitives::revert::RevertStack;
 use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, TxHash, TxNumber};
 use katana_primitives::Felt;
 use katana_provider::api::block::{BlockHashProvider, BlockIdReader, BlockNumberProvider};
@@ -30,7 +34,9 @@ use katana_provider::api::ProviderError;
 use katana_provider::{ProviderFactory, ProviderRO};
 use katana_rpc_api::error::starknet::{
     CompilationErrorData, PageSizeTooBigData, ProofLimitExceededData, StarknetApiError,
+    TransactionExecutionErrorData,
 };
+use katana_rpc_types::abi::AbiDecoder;
 use katana_rpc_types::block::{
     BlockHashAndNumberResponse, BlockNumberResponse, GetBlockWithReceiptsResponse,
     GetBlockWithTxHashesResponse, MaybePreConfirmedBlock,
@@ -41,6 +47,7 @@ use katana_rpc_types::list::{
     ContinuationToken as ListContinuationToken, GetBlocksRequest, GetBlocksResponse,
     GetTransactionsRequest, GetTransactionsResponse, TransactionListItem,
 };
+use katana_rpc_types::overrides::{BlockOverride, StateOverride};
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
 use katana_rpc_types::state_update::StateUpdate;
 use katana_rpc_types::transaction::RpcTxWithHash;
@@ -50,12 +57,14 @@ use katana_rpc_types::trie::{
 };
 use katana_rpc_types::{
     to_rpc_fee_estimate, BroadcastedTx, BroadcastedTxWithChainId, CallResponse,
-    ConfirmedBlockIdOrTag, FeeEstimate, FunctionCall, SimulatedTransactions, SimulationFlag,
-    TxStatus, TxTrace, TxTraceWithHash,
+    ConfirmedBlockIdOrTag, DecodedTxTrace, ExecuteInvocation, FeeEstimate, FunctionCall,
+    KatanaSimulationFlag, RevertedInvocation, SimulatedTransactions, TxStatus, TxTrace,
+    TxTraceWithHash,
 };
 use katana_rpc_types_builder::{BlockBuilder, ReceiptBuilder};
 use katana_tasks::{Result as TaskResult, TaskSpawner};
 
+use self::overrides::OverriddenStateProvider;
 use crate::permit::Permits;
 use crate::utils::events::{Cursor, EventBlockId};
 use crate::{utils, DEFAULT_ESTIMATE_FEE_MAX_CONCURRENT_REQUESTS};
@@ -65,6 +74,7 @@ mod blockifier;
 pub mod cache;
 mod config;
 mod list;
+mod overrides;
 mod pending;
 
 pub use cache::RpcCache;
@@ -169,6 +179,11 @@ where
     pub fn chain_id(&self) -> Felt {
         self.inner.chain_spec.id().id()
     }
+
+    /// Returns the chain spec.
+    pub fn chain_spec(&self) -> &ChainSpec {
+        &self.inner.chain_spec
+    }
 }
 
 impl<Pool, PP, PF> StarknetApi<Pool, PP, PF>
@@ -244,10 +259,11 @@ where
         transactions: Vec<ExecutableTxWithHash>,
         block_id: BlockIdOrTag,
         flags: katana_executor::ExecutionFlags,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
     ) -> StarknetApiResult<Vec<FeeEstimate>> {
         // get the state and block env at the specified block for execution
-        let state = self.state(&block_id)?;
-        let env = self.block_env_at(&block_id)?;
+        let (state, env) = self.execution_env(&block_id, state_override, block_override)?;
         let versioned_constant_overrides = self.inner.config.versioned_constant_overrides.as_ref();
 
         let estimates = blockifier::estimate_fees(
@@ -297,6 +313,40 @@ where
         state.ok_or(StarknetApiError::BlockNotFound)
     }
 
+    /// Returns the state and block env at `block_id` for executing a request, with the request's
+    /// overrides applied on top of them.
+    fn execution_env(
+        &self,
+        block_id: &BlockIdOrTag,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
+    ) -> StarknetApiResult<(Box<dyn StateProvider>, BlockEnv)> {
+        let mut state = self.state(block_id)?;
+        let mut env = self.block_env_at(block_id)?;
+
+        if let Some(overrides) = state_override {
+            let fee_contracts = self.inner.chain_spec.fee_contracts();
+            state = Box::new(OverriddenStateProvider::new(state, overrides, fee_contracts));
+        }
+
+        if let Some(overrides) = block_override {
+            overrides.apply(&mut env);
+        }
+
+        Ok((state, env))
+    }
+
+    /// Resolves the Cairo functions of the revert frames using the classes at `block_id`.
+    fn resolve_revert_functions(
+        &self,
+        block_id: &BlockIdOrTag,
+        stack: &mut RevertStack,
+    ) -> StarknetApiResult<()> {
+        let state = self.state(block_id)?;
+        revert::resolve_revert_functions(stack, |hash| state.class(hash).ok().flatten());
+        Ok(())
+    }
+
     fn block_env_at(&self, block_id: &BlockIdOrTag) -> StarknetApiResult<BlockEnv> {
         let provider = self.storage().provider();
 
@@ -450,11 +500,12 @@ where
         &self,
         request: FunctionCall,
         block_id: BlockIdOrTag,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
     ) -> StarknetApiResult<CallResponse> {
         self.on_io_blocking_task(move |this| {
             // get the state and block env at the specified block for function call execution
-            let state = this.state(&block_id)?;
-            let env = this.block_env_at(&block_id)?;
+            let (state, env) = this.execution_env(&block_id, state_override, block_override)?;
             let cfg_env = this.inner.config.versioned_constant_overrides.as_ref();
             let max_call_gas = this.inner.config.max_call_gas.unwrap_or(1_000_000_000);
 
@@ -681,7 +732,10 @@ where
                     };
 
                     let exec_status = if let Some(reason) = receipt.revert_reason() {
-                        katana_rpc_types::ExecutionResult::Reverted { reason: reason.to_string() }
+                        katana_rpc_types::ExecutionResult::Reverted {
+                            reason: reason.to_string(),
+                            revert_error: None,
+                        }
                     } else {
                         katana_rpc_types::ExecutionResult::Succeeded
                     };
@@ -1245,6 +1299,35 @@ where
         })
         .await?
     }
+
+    /// Returns the trace of a transaction with its calls and events decoded using the ABIs of the
+    /// executed classes.
+    pub async fn trace_decoded(&self, tx_hash: TxHash) -> StarknetApiResult<DecodedTxTrace> {
+        let trace = self.trace(tx_hash).await?;
+
+        self.on_io_blocking_task(move |this| {
+            // Classes are immutable once declared, so the latest state has every class that the
+            // transaction could have executed.
+            let state = this.state(&BlockIdOrTag::PreConfirmed)?;
+            let mut decoders = HashMap::<ClassHash, Option<Arc<AbiDecoder>>>::new();
+
+            Ok(DecodedTxTrace::new(trace, |class_hash| {
+                decoders
+                    .entry(class_hash)
+                    .or_insert_with(|| match state.class(class_hash).ok().flatten()? {
+                        ContractClass::Class(class) => match class.abi? {
+                            MaybeInvalidSierraContractAbi::Valid(abi) => {
+                                Some(Arc::new(AbiDecoder::new(&abi)))
+                            }
+                            MaybeInvalidSierraContractAbi::Invalid(_) => None,
+                        },
+                        ContractClass::Legacy(_) => None,
+                    })
+                    .clone()
+            }))
+        })
+        .await?
+    }
 }
 
 impl<Pool, PoolTx, Pending, PF> StarknetApi<Pool, Pending, PF>
@@ -1259,7 +1342,9 @@ where
         &self,
         block_id: BlockIdOrTag,
         transactions: Vec<BroadcastedTx>,
-        simulation_flags: Vec<SimulationFlag>,
+        simulation_flags: Vec<KatanaSimulationFlag>,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
     ) -> Result<Vec<SimulatedTransactions>, StarknetApiError> {
         self.on_cpu_blocking_task(move |this| async move {
             let chain = this.inner.chain_spec.id();
@@ -1275,12 +1360,13 @@ where
 
             // If the node is run with transaction validation disabled, then we should not validate
             // even if the `SKIP_VALIDATE` flag is not set.
-            let should_validate = !simulation_flags.contains(&SimulationFlag::SkipValidate)
+            let should_validate = !simulation_flags.contains(&KatanaSimulationFlag::SkipValidate)
                 && this.inner.config.simulation_flags.account_validation();
 
             // If the node is run with fee charge disabled, then we should disable charing fees even
             // if the `SKIP_FEE_CHARGE` flag is not set.
-            let should_charge_fee = !simulation_flags.contains(&SimulationFlag::SkipFeeCharge)
+            let should_charge_fee = !simulation_flags
+                .contains(&KatanaSimulationFlag::SkipFeeCharge)
                 && this.inner.config.simulation_flags.fee();
 
             let flags = katana_executor::ExecutionFlags::new()
@@ -1288,9 +1374,11 @@ where
                 .with_fee(should_charge_fee)
                 .with_nonce_check(false);
 
+            let record_accesses =
+                simulation_flags.contains(&KatanaSimulationFlag::RecordStateAccess);
+
             // get the state and block env at the specified block for execution
-            let state = this.state(&block_id)?;
-            let env = this.block_env_at(&block_id)?;
+            let (state, env) = this.execution_env(&block_id, state_override, block_override)?;
 
             // use the blockifier utils function
             let chain_spec = this.inner.chain_spec.as_ref();
@@ -1302,28 +1390,60 @@ where
                 overrides,
                 executables,
                 flags,
+                record_accesses,
                 &this.inner.class_cache,
             );
 
             let mut simulated = Vec::with_capacity(results.len());
-            for (i, ResultAndStates { result, .. }) in results.into_iter().enumerate() {
+            for (i, ResultAndStates { result, states, accesses }) in results.into_iter().enumerate()
+            {
                 match result {
                     ExecutionResult::Success { trace, receipt } => {
                         let trace = TypedTransactionExecutionInfo::new(receipt.r#type(), trace);
 
-                        let transaction_trace = TxTrace::from(trace);
+                        let mut transaction_trace = TxTrace::from(trace);
+                        let reverted = match &mut transaction_trace {
+                            TxTrace::Invoke(trace) => Some(&mut trace.execute_invocation),
+                            TxTrace::L1Handler(trace) => Some(&mut trace.function_invocation),
+                            TxTrace::Declare(_) | TxTrace::DeployAccount(_) => None,
+                        };
+
+                        if let Some(ExecuteInvocation::Reverted(RevertedInvocation {
+                            revert_error: Some(stack),
+                            ..
+                        })) = reverted
+                        {
+                            this.resolve_revert_functions(&block_id, stack)?;
+                        }
+
+                        if record_accesses {
+                            transaction_trace.set_state_diff(states.into());
+                        }
+
                         let fee_estimation =
                             to_rpc_fee_estimate(receipt.resources_used(), receipt.fee());
-                        let value = SimulatedTransactions { transaction_trace, fee_estimation };
+                        let value = SimulatedTransactions {
+                            transaction_trace,
+                            fee_estimation,
+                            state_access: accesses,
+                        };
 
                         simulated.push(value)
                     }
 
                     ExecutionResult::Failed { error } => {
-                        return Err(StarknetApiError::transaction_execution_error(
-                            i as u64,
-                            error.to_string(),
-                        ));
+                        let mut revert_error = error.revert_stack();
+                        if let Some(stack) = revert_error.as_mut() {
+                            this.resolve_revert_functions(&block_id, stack)?;
+                        }
+
+                        let data = TransactionExecutionErrorData {
+                            transaction_index: i as u64,
+                            execution_error: error.to_string(),
+                            revert_error,
+                        };
+
+                        return Err(StarknetApiError::TransactionExecutionError(data));
                     }
                 }
             }
@@ -1345,7 +1465,7 @@ where
     PF: ProviderFactory,
     <PF as ProviderFactory>::Provider: ProviderRO,
 {
-    async fn blocks(&self, request: GetBlocksRequest) -> StarknetApiResult<GetBlocksResponse> {
+    pub async fn blocks(&self, request: GetBlocksRequest) -> StarknetApiResult<GetBlocksResponse> {
         self.on_io_blocking_task(move |this| {
             let provider = this.storage().provider();
 
@@ -1418,7 +1538,7 @@ where
     }
 
     // NOTE: The current implementation of this method doesn't support pending transactions.
-    async fn transactions(
+    pub async fn transactions(
         &self,
         request: GetTransactionsRequest,
     ) -> StarknetApiResult<GetTransactionsResponse> {
@@ -1491,7 +1611,7 @@ where
         .await?
     }
 
-    async fn total_transactions(&self) -> StarknetApiResult<TxNumber> {
+    pub async fn total_transactions(&self) -> StarknetApiResult<TxNumber> {
         self.on_io_blocking_task(move |this| {
             let provider = this.storage().provider();
             let total = provider.total_transactions()? as TxNumber;
@@ -0,0 +1,126 @@
+//! State provider that layers request-scoped overrides on top of an existing state.
+
+use std::collections::HashMap;
+
+use katana_chain_spec::FeeContracts;
+use katana_genesis::constant::get_fee_token_balance_base_storage_address;
+use katana_primitives::class::{ClassHash, CompiledClassHash, ContractClass};
+use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
+use katana_primitives::utils::split_u256;
+use katana_primitives::{Felt, U256};
+use katana_provider::api::contract::ContractClassProvider;
+use katana_provider::api::state::{StateProofProvider, StateProvider, StateRootProvider};
+use katana_provider::ProviderResult;
+use katana_rpc_types::overrides::StateOverride;
+
+/// A [`StateProvider`] that returns the overridden nonces, class hashes and storage values of a
+/// [`StateOverride`], and falls back to the underlying state for everything else.
+///
+/// The overrides are never written anywhere, so they only live as long as this provider does.
+/// State proofs and roots are not supported as they would not reflect the overridden values.
+#[derive(Debug)]
+pub struct OverriddenStateProvider<S> {
+    state: S,
+    nonces: HashMap<ContractAddress, Nonce>,
+    class_hashes: HashMap<ContractAddress, ClassHash>,
+    storage: HashMap<(ContractAddress, StorageKey), StorageValue>,
+}
+
+impl<S: StateProvider> OverriddenStateProvider<S> {
+    /// Creates a new provider over `state`.
+    ///
+    /// Balance overrides are converted into storage overrides of the ERC20 balance slots of the
+    /// corresponding fee token in `fee_contracts`. If the storage of a balance slot is also
+    /// overridden explicitly, the explicit storage override takes precedence, regardless of the
+    /// order of the contracts in `overrides`.
+    pub fn new(state: S, overrides: StateOverride, fee_contracts: &FeeContracts) -> Self {
+        let mut nonces = HashMap::new();
+        let mut class_hashes = HashMap::new();
+        let mut storage = HashMap::new();
+
+        for (address, contract) in overrides {
+            if let Some(nonce) = contract.nonce {
+                nonces.insert(address, nonce);
+            }
+
+            if let Some(class_hash) = contract.class_hash {
+                class_hashes.insert(address, class_hash);
+            }
+
+            // Explicit storage overrides replace the balance slots inserted for previous contracts.
+            for (key, value) in contract.storage {
+                storage.insert((address, key), value);
+            }
+
+            if let Some(balance) = contract.eth_balance {
+                insert_balance(&mut storage, fee_contracts.eth, address, balance);
+            }
+
+            if let Some(balance) = contract.strk_balance {
+                insert_balance(&mut storage, fee_contracts.strk, address, balance);
+            }
+        }
+
+        Self { state, nonces, class_hashes, storage }
+    }
+}
+
+/// Inserts the storage values of the `owner`'s balance in the ERC20 `token`, unless the slots are
+/// already overridden.
+fn insert_balance(
+    storage: &mut HashMap<(ContractAddress, StorageKey), StorageValue>,
+    token: ContractAddress,
+    owner: ContractAddress,
+    balance: U256,
+) {
+    let (low, high) = split_u256(balance);
+    let base = get_fee_token_balance_base_storage_address(owner);
+    storage.entry((token, base)).or_insert(low);
+    storage.entry((token, base + Felt::ONE)).or_insert(high);
+}
+
+impl<S: StateProvider> ContractClassProvider for OverriddenStateProvider<S> {
+    fn class(&self, hash: ClassHash) -> ProviderResult<Option<ContractClass>> {
+        self.state.class(hash)
+    }
+
+    fn compiled_class_hash_of_class_hash(
+        &self,
+        hash: ClassHash,
+    ) -> ProviderResult<Option<CompiledClassHash>> {
+        self.state.compiled_class_hash_of_class_hash(hash)
+    }
+}
+
+impl<S: StateProvider> StateProvider for OverriddenStateProvider<S> {
+    fn nonce(&self, address: ContractAddress) -> ProviderResult<Option<Nonce>> {
+        match self.nonces.get(&address) {
+            Some(nonce) => Ok(Some(*nonce)),
+            None => self.state.nonce(address),
+        }
+    }
+
+    fn storage(
+        &self,
+        address: ContractAddress,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        match self.storage.get(&(address, storage_key)) {
+            Some(value) => Ok(Some(*value)),
+            None => self.state.storage(address, storage_key),
+        }
+    }
+
+    fn class_hash_of_contract(
+        &self,
+        address: ContractAddress,
+    ) -> ProviderResult<Option<ClassHash>> {
+        match self.class_hashes.get(&address) {
+            Some(class_hash) => Ok(Some(*class_hash)),
+            None => self.state.class_hash_of_contract(address),
+        }
+    }
+}
+
+impl<S: StateProvider> StateProofProvider for OverriddenStateProvider<S> {}
+impl<S: StateProvider> StateRootProvider for OverriddenStateProvider<S> {}
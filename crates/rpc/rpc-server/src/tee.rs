@@ -1,5 +1,6 @@
 //! TEE RPC API implementation.
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use jsonrpsee::core::{async_trait, RpcResult};
@@ -34,8 +35,9 @@ where
     /// TEE provider for generating attestation quotes.
     tee_provider: Arc<dyn TeeProvider>,
     /// The block number Katana forked from (if running in fork mode).
-    /// Included in report_data so SP1 can prove fork freshness.
-    fork_block_number: Option<u64>,
+    /// Included in report_data so SP1 can prove fork freshness. Shared with the fork controller,
+    /// which updates it when the fork is reset.
+    fork_block_number: Option<Arc<AtomicU64>>,
     /// Versioned environment config hash precomputed from the chain spec at
     /// construction time. Bound into every attestation's `report_data`.
     katana_tee_config_hash: Felt,
@@ -54,7 +56,7 @@ where
     pub fn new(
         provider_factory: PF,
         tee_provider: Arc<dyn TeeProvider>,
-        fork_block_number: Option<u64>,
+        fork_block_number: Option<Arc<AtomicU64>>,
         chain_spec: &ChainSpec,
     ) -> Self {
         let chain_id: Felt = chain_spec.id().into();
@@ -90,6 +92,7 @@ where
         block: BlockNumber,
     ) -> RpcResult<TeeQuoteResponse> {
         let katana_tee_config_hash = self.katana_tee_config_hash;
+        let fork_block_number = self.fork_block_number.as_ref().map(|n| n.load(Ordering::Acquire));
 
         debug!(
             target: "rpc::tee",
@@ -139,7 +142,7 @@ where
         let state_root = header.state_root;
         let events_commitment = header.events_commitment;
 
-        if let Some(fork_block) = self.fork_block_number {
+        if let Some(fork_block) = fork_block_number {
             let report_data = compute_report_data_sharding(
                 prev_state_root,
                 state_root,
@@ -176,7 +179,7 @@ where
                 block_hash,
                 prev_block_number: prev_block,
                 block_number: block,
-                fork_block_number: self.fork_block_number,
+                fork_block_number,
                 events_commitment,
                 katana_tee_config_hash,
                 l1_to_l2_messages: Vec::new(),
@@ -295,7 +298,7 @@ where
                 block_hash,
                 prev_block_number: prev_block,
                 block_number: block,
-                fork_block_number: self.fork_block_number,
+                fork_block_number,
                 events_commitment,
                 katana_tee_config_hash,
                 l1_to_l2_messages,
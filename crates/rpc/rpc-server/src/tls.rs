@@ -0,0 +1,541 @@
+//! TLS termination for the node's servers.
+//!
+//! [`TlsAcceptor`] performs the TLS handshakes using the certificates of a [`TlsConfig`], and
+//! [`TlsListener`] accepts the TCP connections and completes their handshakes before handing them
+//! over to the server.
+//!
+//! The certificate files are periodically checked for changes, so that certificates can be
+//! rotated without restarting the node. If the updated files can't be loaded (eg, the certificate
+//! was replaced but not its key yet), the previous certificates keep being used until the files
+//! change again. Existing connections are unaffected by a reload.
+//!
+//! If [`TlsConfig::client_ca`] is set, clients must present a certificate signed by one of the
+//! configured CAs (ie, mutual TLS), and connections without one are rejected during the handshake.
+//!
+//! [`load_client_config`] loads the other side of the handshakes, for the clients connecting to
+//! servers over TLS.
+
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+pub use katana_node_config::tls::{ClientIdentity, ClientTlsConfig, TlsConfig};
+use parking_lot::{Mutex, RwLock};
+use rustls::pki_types::pem::{self, PemObject};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{VerifierBuilderError, WebPkiClientVerifier};
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+use tokio::time::error::Elapsed;
+pub use tokio_rustls::server::TlsStream;
+use tracing::{debug, error, info, warn};
+
+/// How often the certificate files are checked for changes.
+pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The ALPN protocols advertised by HTTP servers.
+pub const HTTP_ALPN_PROTOCOLS: &[&[u8]] = &[b"h2", b"http/1.1"];
+
+/// The maximum duration of a TLS handshake before the connection is dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default maximum number of TLS handshakes that a [`TlsListener`] performs concurrently.
+pub const DEFAULT_MAX_PENDING_HANDSHAKES: usize = 256;
+
+/// How long to wait before accepting new connections after failing to accept one.
+pub(crate) const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to load {}: {source}", path.display())]
+    Pem { path: PathBuf, source: pem::Error },
+
+    #[error("no certificates found in {}", .0.display())]
+    NoCertificates(PathBuf),
+
+    #[error("invalid client CA certificates: {0}")]
+    ClientVerifier(#[from] VerifierBuilderError),
+
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+}
+
+/// Performs the server side of TLS handshakes.
+///
+/// Cloning the acceptor is cheap, and all the clones share the same certificates.
+#[derive(Debug, Clone)]
+pub struct TlsAcceptor {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    config: TlsConfig,
+    alpn_protocols: Vec<Vec<u8>>,
+    server_config: RwLock<Arc<ServerConfig>>,
+    /// The modification times of the certificate files when they were last loaded.
+    modified: Mutex<Vec<Option<SystemTime>>>,
+}
+
+impl TlsAcceptor {
+    /// Creates a new acceptor, loading the certificates of `config`.
+    ///
+    /// `alpn_protocols` are the application protocols advertised to the clients, in order of
+    /// preference.
+    pub fn new(config: TlsConfig, alpn_protocols: &[&[u8]]) -> Result<Self, Error> {
+        let alpn_protocols: Vec<Vec<u8>> = alpn_protocols.iter().map(|p| p.to_vec()).collect();
+
+        let modified = modification_times(&config);
+        let server_config = load_server_config(&config, &alpn_protocols)?;
+
+        let inner = Inner {
+            config,
+            alpn_protocols,
+            server_config: RwLock::new(server_config),
+            modified: Mutex::new(modified),
+        };
+
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Performs the TLS handshake over `stream` using the current certificates.
+    pub async fn accept<IO>(&self, stream: IO) -> io::Result<TlsStream<IO>>
+    where
+        IO: AsyncRead + AsyncWrite + Unpin,
+    {
+        let server_config = self.inner.server_config.read().clone();
+        tokio_rustls::TlsAcceptor::from(server_config).accept(stream).await
+    }
+
+    /// Reloads the certificates if any of their files changed since they were last loaded.
+    ///
+    /// Returns whether the certificates have been reloaded. If loading the changed files fails,
+    /// the current certificates are kept and the files aren't reloaded until they change again.
+    pub fn reload_if_changed(&self) -> Result<bool, Error> {
+        let modified = modification_times(&self.inner.config);
+
+        {
+            let mut last_modified = self.inner.modified.lock();
+            if *last_modified == modified {
+                return Ok(false);
+            }
+            *last_modified = modified;
+        }
+
+        let server_config = load_server_config(&self.inner.config, &self.inner.alpn_protocols)?;
+        *self.inner.server_config.write() = server_config;
+
+        Ok(true)
+    }
+
+    /// Spawns a task that checks the certificate files for changes every `interval`, and reloads
+    /// the certificates when they do.
+    ///
+    /// The task stops once all the clones of the acceptor have been dropped.
+    pub fn spawn_reloader(&self, interval: Duration) {
+        let acceptor = Arc::downgrade(&self.inner);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately.
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                let Some(inner) = acceptor.upgrade() else { break };
+                let acceptor = TlsAcceptor { inner };
+                let cert = acceptor.inner.config.cert.display();
+
+                match acceptor.reload_if_changed() {
+                    Ok(true) => info!(target: "tls", %cert, "TLS certificates reloaded."),
+                    Ok(false) => {}
+                    Err(error) => {
+                        warn!(target: "tls", %cert, %error, "Failed to reload TLS certificates.")
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// A TCP listener that completes the TLS handshake of the connections it accepts.
+///
+/// Handshakes are performed concurrently, so that slow clients don't prevent other connections
+/// from being accepted. The number of concurrent handshakes is bounded though, and once the limit
+/// is reached, new connections wait in the listen backlog of the socket until a handshake
+/// completes.
+#[derive(Debug)]
+pub struct TlsListener {
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    handshakes: JoinSet<(SocketAddr, Result<io::Result<TlsStream<TcpStream>>, Elapsed>)>,
+    max_pending_handshakes: usize,
+}
+
+impl TlsListener {
+    pub fn new(listener: TcpListener, acceptor: TlsAcceptor) -> Self {
+        Self {
+            listener,
+            acceptor,
+            handshakes: JoinSet::new(),
+            max_pending_handshakes: DEFAULT_MAX_PENDING_HANDSHAKES,
+        }
+    }
+
+    /// Sets the maximum number of handshakes performed concurrently. Default is
+    /// [`DEFAULT_MAX_PENDING_HANDSHAKES`].
+    pub fn max_pending_handshakes(mut self, max: usize) -> Self {
+        // At least one handshake must be allowed, otherwise no connection is ever accepted.
+        self.max_pending_handshakes = max.max(1);
+        self
+    }
+
+    /// Returns the local address that this listener is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts the next connection that completes its TLS handshake, along with the address of
+    /// the client.
+    ///
+    /// Connections whose handshake fails (eg, because the client didn't present a valid
+    /// certificate) or times out are dropped.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. Connections with an ongoing handshake are returned by the next
+    /// call.
+    pub async fn accept(&mut self) -> (TlsStream<TcpStream>, SocketAddr) {
+        loop {
+            let accepting = self.handshakes.len() < self.max_pending_handshakes;
+
+            tokio::select! {
+                result = self.listener.accept(), if accepting => match result {
+                    Ok((stream, addr)) => {
+                        let _ = stream.set_nodelay(true);
+                        let acceptor = self.acceptor.clone();
+
+                        self.handshakes.spawn(async move {
+                            let handshake = acceptor.accept(stream);
+                            (addr, tokio::time::timeout(HANDSHAKE_TIMEOUT, handshake).await)
+                        });
+                    }
+
+                    // Accept errors are transient (eg, the process ran out of file descriptors),
+                    // so back off for a bit instead of spinning.
+                    Err(error) => {
+                        warn!(target: "tls", %error, "Failed to accept connection.");
+                        tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    }
+                },
+
+                Some(result) = self.handshakes.join_next() => match result {
+                    Ok((addr, Ok(Ok(stream)))) => return (stream, addr),
+                    Ok((addr, Ok(Err(error)))) => {
+                        debug!(target: "tls", %addr, %error, "TLS handshake failed.")
+                    }
+                    Ok((addr, Err(_))) => debug!(target: "tls", %addr, "TLS handshake timed out."),
+                    Err(error) => error!(target: "tls", %error, "TLS handshake task failed."),
+                },
+            }
+        }
+    }
+}
+
+fn load_server_config(
+    config: &TlsConfig,
+    alpn_protocols: &[Vec<u8>],
+) -> Result<Arc<ServerConfig>, Error> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    let certs = load_certs(&config.cert)?;
+    let key = load_key(&config.key)?;
+
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let builder = match &config.client_ca {
+        Some(path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(path)? {
+                roots.add(cert)?;
+            }
+
+            let verifier =
+                WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build()?;
+            builder.with_client_cert_verifier(verifier)
+        }
+
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut server_config = builder.with_single_cert(certs, key)?;
+    server_config.alpn_protocols = alpn_protocols.to_vec();
+
+    Ok(Arc::new(server_config))
+}
+
+/// Loads the client side TLS configuration of `config`, used to connect to servers over TLS.
+///
+/// The certificates of the servers are verified against the CAs of [`ClientTlsConfig::ca`] in
+/// addition to the Mozilla root CAs, and the client certificate is presented to the servers
+/// requiring mutual TLS. Unlike [`TlsAcceptor`], the files aren't reloaded when they change.
+pub fn load_client_config(config: &ClientTlsConfig) -> Result<ClientConfig, Error> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    let mut roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+    if let Some(path) = &config.ca {
+        for cert in load_certs(path)? {
+            roots.add(cert)?;
+        }
+    }
+
+    let builder = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(roots);
+
+    let client_config = match &config.identity {
+        Some(identity) => {
+            let certs = load_certs(&identity.cert)?;
+            let key = load_key(&identity.key)?;
+            builder.with_client_auth_cert(certs, key)?
+        }
+
+        None => builder.with_no_client_auth(),
+    };
+
+    Ok(client_config)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>, Error> {
+    PrivateKeyDer::from_pem_file(path)
+        .map_err(|source| Error::Pem { path: path.to_path_buf(), source })
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, Error> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|source| Error::Pem { path: path.to_path_buf(), source })?;
+
+    if certs.is_empty() {
+        return Err(Error::NoCertificates(path.to_path_buf()));
+    }
+
+    Ok(certs)
+}
+
+/// Returns the modification times of the files of `config`, or `None` for the files that can't be
+/// read.
+fn modification_times(config: &TlsConfig) -> Vec<Option<SystemTime>> {
+    [Some(&config.cert), Some(&config.key), config.client_ca.as_ref()]
+        .into_iter()
+        .flatten()
+        .map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    use jsonrpsee::RpcModule;
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+    use rustls::{ClientConfig, RootCertStore};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_rustls::TlsConnector;
+
+    use super::{TlsAcceptor, TlsConfig, TlsListener, HTTP_ALPN_PROTOCOLS};
+    use crate::RpcServer;
+
+    fn test_data(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/test_data/tls").join(name)
+    }
+
+    /// Returns a connector that trusts the test CA, and presents the client certificate if
+    /// `client_auth` is set.
+    fn connector(client_auth: bool) -> TlsConnector {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let mut roots = RootCertStore::empty();
+        roots.add(CertificateDer::from_pem_file(test_data("ca.pem")).unwrap()).unwrap();
+
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots);
+
+        let config = if client_auth {
+            let cert = CertificateDer::from_pem_file(test_data("client.pem")).unwrap();
+            let key = PrivateKeyDer::from_pem_file(test_data("client.key")).unwrap();
+            builder.with_client_auth_cert(vec![cert], key).unwrap()
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        TlsConnector::from(Arc::new(config))
+    }
+
+    /// Calls the `ping` method over a new TLS connection and returns the raw HTTP response.
+    async fn ping(addr: std::net::SocketAddr, connector: TlsConnector) -> std::io::Result<String> {
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"ping","params":[]}"#;
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: \
+             application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let stream = TcpStream::connect(addr).await?;
+        let server_name = ServerName::try_from("localhost").unwrap();
+        let mut stream = connector.connect(server_name, stream).await?;
+
+        stream.write_all(request.as_bytes()).await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+
+        Ok(response)
+    }
+
+    #[tokio::test]
+    async fn mutual_tls() {
+        let mut module = RpcModule::new(());
+        module.register_method("ping", |_, _, _| "pong").unwrap();
+
+        let config = TlsConfig {
+            cert: test_data("server.pem"),
+            key: test_data("server.key"),
+            client_ca: Some(test_data("ca.pem")),
+        };
+
+        let server = RpcServer::new().tls(config).module(module).unwrap();
+        let handle = server.start("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let addr = *handle.addr();
+
+        let response = ping(addr, connector(true)).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains(r#""result":"pong""#));
+
+        // Clients without a certificate are rejected during the handshake.
+        let result = ping(addr, connector(false)).await;
+        assert!(result.is_err());
+
+        // Plaintext requests never reach the RPC.
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = Vec::new();
+        let _ = stream.read_to_end(&mut response).await;
+        assert!(!response.starts_with(b"HTTP/1.1"));
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn pending_handshakes_are_bounded() {
+        let config = TlsConfig {
+            cert: test_data("server.pem"),
+            key: test_data("server.key"),
+            client_ca: None,
+        };
+
+        let acceptor = TlsAcceptor::new(config, HTTP_ALPN_PROTOCOLS).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut listener = TlsListener::new(listener, acceptor).max_pending_handshakes(1);
+        let addr = listener.local_addr().unwrap();
+
+        let (accepted_tx, mut accepted_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await;
+                if accepted_tx.send(stream).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // A client that never starts its handshake takes up the only handshake slot.
+        let stalled = TcpStream::connect(addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let client = tokio::spawn(async move {
+            let stream = TcpStream::connect(addr).await.unwrap();
+            let server_name = ServerName::try_from("localhost").unwrap();
+            connector(false).connect(server_name, stream).await
+        });
+
+        // The handshake of the other client only starts once the slot is released.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!client.is_finished());
+
+        drop(stalled);
+        let _stream = client.await.unwrap().expect("handshake must complete");
+        assert!(accepted_rx.recv().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn reload_certificates() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TlsConfig {
+            cert: dir.path().join("server.pem"),
+            key: dir.path().join("server.key"),
+            client_ca: None,
+        };
+
+        std::fs::copy(test_data("server.pem"), &config.cert).unwrap();
+        std::fs::copy(test_data("server.key"), &config.key).unwrap();
+
+        let acceptor = TlsAcceptor::new(config.clone(), HTTP_ALPN_PROTOCOLS).unwrap();
+        assert_eq!(served_certificate(&acceptor).await, test_data("server.pem"));
+        assert!(!acceptor.reload_if_changed().unwrap());
+
+        // Invalid files fail to load, and the current certificates are kept until the files
+        // change again.
+        std::fs::write(&config.key, "not a key").unwrap();
+        touch(&config.key, 1);
+        assert!(acceptor.reload_if_changed().is_err());
+        assert!(!acceptor.reload_if_changed().unwrap());
+        assert_eq!(served_certificate(&acceptor).await, test_data("server.pem"));
+
+        std::fs::copy(test_data("server-renewed.pem"), &config.cert).unwrap();
+        std::fs::copy(test_data("server-renewed.key"), &config.key).unwrap();
+        touch(&config.cert, 2);
+        touch(&config.key, 2);
+        assert!(acceptor.reload_if_changed().unwrap());
+        assert_eq!(served_certificate(&acceptor).await, test_data("server-renewed.pem"));
+    }
+
+    /// Sets the modification time of `path` to `secs` seconds from now, so that the change is
+    /// detected regardless of the resolution of the file system timestamps.
+    fn touch(path: &std::path::Path, secs: u64) {
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(secs)).unwrap();
+    }
+
+    /// Performs a handshake with `acceptor` and returns the path of the test certificate that it
+    /// presented.
+    async fn served_certificate(acceptor: &TlsAcceptor) -> PathBuf {
+        let (client, server) = tokio::io::duplex(16 * 1024);
+
+        let server_name = ServerName::try_from("localhost").unwrap();
+        let (client, server) =
+            tokio::join!(connector(false).connect(server_name, client), acceptor.accept(server));
+        let (_, client) = client.unwrap().into_inner();
+        drop(server.unwrap());
+
+        let served = &client.peer_certificates().unwrap()[0];
+        ["server.pem", "server-renewed.pem"]
+            .into_iter()
+            .map(test_data)
+            .find(|path| CertificateDer::from_pem_file(path).unwrap() == *served)
+            .expect("unknown certificate")
+    }
+}
@@ -23,7 +23,14 @@ impl<P> TxPoolApi<P> {
 }
 
 impl<P: TransactionPool> TxPoolApi<P> {
-    fn build_content(&self, filter: Option<ContractAddress>) -> TxPoolContent {
+    /// Returns the number of pending and queued transactions in the pool.
+    pub fn status(&self) -> TxPoolStatus {
+        let pending = self.pool.size() as u64;
+        TxPoolStatus { pending, queued: 0 }
+    }
+
+    /// Returns the content of the pool, optionally filtered to a single sender address.
+    pub fn content(&self, filter: Option<ContractAddress>) -> TxPoolContent {
         let txs = self.pool.take_transactions_snapshot();
         let mut pending: BTreeMap<ContractAddress, BTreeMap<_, _>> = BTreeMap::new();
 
@@ -49,24 +56,9 @@ impl<P: TransactionPool> TxPoolApi<P> {
 
         TxPoolContent { pending, queued: BTreeMap::new() }
     }
-}
-
-#[async_trait]
-impl<P: TransactionPool + 'static> TxPoolApiServer for TxPoolApi<P> {
-    async fn txpool_status(&self) -> RpcResult<TxPoolStatus> {
-        let pending = self.pool.size() as u64;
-        Ok(TxPoolStatus { pending, queued: 0 })
-    }
-
-    async fn txpool_content(&self) -> RpcResult<TxPoolContent> {
-        Ok(self.build_content(None))
-    }
-
-    async fn txpool_content_from(&self, address: ContractAddress) -> RpcResult<TxPoolContent> {
-        Ok(self.build_content(Some(address)))
-    }
 
-    async fn txpool_inspect(&self) -> RpcResult<TxPoolInspect> {
+    /// Returns a textual summary of the transactions in the pool.
+    pub fn inspect(&self) -> TxPoolInspect {
         let txs = self.pool.take_transactions_snapshot();
         let mut pending: BTreeMap<ContractAddress, BTreeMap<_, _>> = BTreeMap::new();
 
@@ -82,6 +74,25 @@ impl<P: TransactionPool + 'static> TxPoolApiServer for TxPoolApi<P> {
             pending.entry(tx.sender()).or_default().insert(tx.nonce(), summary);
         }
 
-        Ok(TxPoolInspect { pending, queued: BTreeMap::new() })
+        TxPoolInspect { pending, queued: BTreeMap::new() }
+    }
+}
+
+#[async_trait]
+impl<P: TransactionPool + 'static> TxPoolApiServer for TxPoolApi<P> {
+    async fn txpool_status(&self) -> RpcResult<TxPoolStatus> {
+        Ok(self.status())
+    }
+
+    async fn txpool_content(&self) -> RpcResult<TxPoolContent> {
+        Ok(self.content(None))
+    }
+
+    async fn txpool_content_from(&self, address: ContractAddress) -> RpcResult<TxPoolContent> {
+        Ok(self.content(Some(address)))
+    }
+
+    async fn txpool_inspect(&self) -> RpcResult<TxPoolInspect> {
+        Ok(self.inspect())
     }
 }
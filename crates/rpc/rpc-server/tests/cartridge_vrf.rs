@@ -403,7 +403,7 @@ fn create_node_config(
     vrf_account_address: katana_primitives::ContractAddress,
 ) -> katana_sequencer_node::config::Config {
     use katana_sequencer_node::config::paymaster::{
-        CartridgeApiConfig, PaymasterConfig, VrfConfig,
+        CartridgeApiConfig, PaymasterConfig, VrfConfig, VrfProviderConfig,
     };
 
     let mut config = test_config();
@@ -420,7 +420,10 @@ fn create_node_config(
             cartridge_api_url,
             controller_deployer_address: *deployer_address,
             controller_deployer_private_key: deployer_private_key,
-            vrf: Some(VrfConfig { url: vrf_url, vrf_account: vrf_account_address }),
+            vrf: Some(VrfConfig {
+                provider: VrfProviderConfig::Remote(vrf_url),
+                vrf_account: vrf_account_address,
+            }),
         }),
     });
 
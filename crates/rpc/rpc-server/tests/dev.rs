@@ -1,5 +1,8 @@
+use assert_matches::assert_matches;
 use common::{Erc20Contract, Uint256};
+use jsonrpsee::core::ClientError;
 use katana_genesis::constant::DEFAULT_ETH_FEE_TOKEN_ADDRESS;
+use katana_primitives::block::{BlockHashOrNumber, ConfirmedBlockIdOrTag};
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::Felt;
 use katana_provider::api::block::{BlockNumberProvider, BlockProvider};
@@ -8,7 +11,10 @@ use katana_provider::api::state::StateFactoryProvider;
 use katana_provider::ProviderFactory;
 use katana_rpc_server::api::dev::DevApiClient;
 use katana_rpc_server::api::txpool::TxPoolApiClient;
+use katana_sequencer_node::config::fork::ForkingConfig;
+use katana_utils::node::ForkTestNode;
 use katana_utils::TestNode;
+use url::Url;
 
 mod common;
 
@@ -224,3 +230,132 @@ async fn test_generate_block_drains_pool_transactions() {
     assert_eq!(status.pending, 0, "pool should be drained after force mining");
     assert_eq!(status.queued, 0, "queued pool is currently unsupported");
 }
+
+#[tokio::test]
+async fn test_reset_fork_when_not_forked() {
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+
+    let err = client.reset_fork(None, None).await.expect_err("node is not forked");
+
+    assert_matches!(err, ClientError::Call(e) => {
+        assert_eq!(e.code(), 3);
+        assert_eq!(&e.message(), &"Node is not running in forking mode.");
+    });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reset_fork_at_new_block() {
+    let recipient = Felt::from(0x111u64);
+    let amount = Uint256 { low: Felt::from(0x1000u64), high: Felt::ZERO };
+
+    // the network to fork, with two blocks that each transfer `amount` to `recipient`
+    let main_node = TestNode::new().await;
+    let main_provider = main_node.starknet_rpc_client();
+    let main_account = main_node.account();
+    let main_contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &main_account);
+
+    for _ in 0..2 {
+        let res = main_contract.transfer(&recipient, &amount).send().await.unwrap();
+        katana_utils::TxWaiter::new(res.transaction_hash, &main_provider).await.unwrap();
+    }
+
+    let mut config = katana_utils::node::test_config();
+    config.sequencing.no_mining = true;
+    config.forking = Some(ForkingConfig {
+        url: Url::parse(&format!("http://{}", main_node.rpc_addr())).unwrap(),
+        block: Some(BlockHashOrNumber::Num(1)),
+        init_dev_genesis: false,
+        pre_confirmed: false,
+        cache_dir: None,
+        backend: Default::default(),
+    });
+
+    let fork_node = ForkTestNode::new_forked_with_config(config).await;
+    let client = fork_node.rpc_http_client();
+    let provider = fork_node.starknet_rpc_client();
+    let account = fork_node.account();
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+
+    // the chain continues from the fork block, with the forked state
+    assert_eq!(provider.block_number().await.unwrap().block_number, 2);
+    let balance = contract.balanceOf(&recipient).call().await.unwrap();
+    assert_eq!(balance.low, Felt::from(0x1000u64));
+
+    // leave a transaction in the pool
+    contract.transfer(&Felt::ONE, &amount).send().await.unwrap();
+    let status = client.txpool_status().await.unwrap();
+    assert_eq!(status.pending, 1);
+
+    client.reset_fork(None, Some(ConfirmedBlockIdOrTag::Number(2))).await.unwrap();
+
+    // the chain now continues from the new fork block, with the state at that block
+    assert_eq!(provider.block_number().await.unwrap().block_number, 3);
+    let balance = contract.balanceOf(&recipient).call().await.unwrap();
+    assert_eq!(balance.low, Felt::from(0x2000u64));
+
+    // the transactions submitted against the previous fork are dropped
+    let status = client.txpool_status().await.unwrap();
+    assert_eq!(status.pending, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reset_fork_back_to_previous_block_with_cache() {
+    let recipient = Felt::from(0x111u64);
+    let amount = Uint256 { low: Felt::from(0x1000u64), high: Felt::ZERO };
+
+    // the network to fork, with two blocks that each transfer `amount` to `recipient`
+    let main_node = TestNode::new().await;
+    let main_provider = main_node.starknet_rpc_client();
+    let main_account = main_node.account();
+    let main_contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &main_account);
+
+    for _ in 0..2 {
+        let res = main_contract.transfer(&recipient, &amount).send().await.unwrap();
+        katana_utils::TxWaiter::new(res.transaction_hash, &main_provider).await.unwrap();
+    }
+
+    let cache_dir = tempfile::tempdir().unwrap();
+
+    let mut config = katana_utils::node::test_config();
+    config.sequencing.no_mining = true;
+    config.forking = Some(ForkingConfig {
+        url: Url::parse(&format!("http://{}", main_node.rpc_addr())).unwrap(),
+        block: Some(BlockHashOrNumber::Num(1)),
+        init_dev_genesis: false,
+        pre_confirmed: false,
+        cache_dir: Some(cache_dir.path().to_path_buf()),
+        backend: Default::default(),
+    });
+
+    let fork_node = ForkTestNode::new_forked_with_config(config).await;
+    let client = fork_node.rpc_http_client();
+    let account = fork_node.account();
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+
+    // fork A
+    let balance = contract.balanceOf(&recipient).call().await.unwrap();
+    assert_eq!(balance.low, Felt::from(0x1000u64));
+
+    // fork B
+    client.reset_fork(None, Some(ConfirmedBlockIdOrTag::Number(2))).await.unwrap();
+    let balance = contract.balanceOf(&recipient).call().await.unwrap();
+    assert_eq!(balance.low, Felt::from(0x2000u64));
+
+    // fork A again, whose cache must be reused rather than B's
+    client.reset_fork(None, Some(ConfirmedBlockIdOrTag::Number(1))).await.unwrap();
+    let balance = contract.balanceOf(&recipient).call().await.unwrap();
+    assert_eq!(balance.low, Felt::from(0x1000u64));
+
+    // each fork block has its own cache, under the directory of the forked network
+    let mut chains = std::fs::read_dir(cache_dir.path()).unwrap();
+    let chain_dir = chains.next().unwrap().unwrap().path();
+    assert!(chains.next().is_none());
+
+    let mut blocks = std::fs::read_dir(chain_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    blocks.sort();
+    assert_eq!(blocks, vec!["1", "2"]);
+}
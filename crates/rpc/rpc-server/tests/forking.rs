@@ -31,6 +31,9 @@ fn forking_cfg() -> ForkingConfig {
         url: Url::parse(SEPOLIA_URL).unwrap(),
         block: Some(FORK_BLOCK_NUMBER.into()),
         init_dev_genesis: true,
+        pre_confirmed: false,
+        cache_dir: None,
+        backend: Default::default(),
     }
 }
 
@@ -663,6 +666,62 @@ async fn get_events_with_invalid_block_hash(#[case] hash: BlockHash) {
     assert_provider_starknet_err!(result, StarknetApiError::BlockNotFound);
 }
 
+/// Forking from the pre-confirmed state carries the pre-confirmed transactions of the forked
+/// network over into the first block on top of the forked chain's genesis.
+#[tokio::test(flavor = "multi_thread")]
+async fn fork_from_pre_confirmed_state() {
+    use common::{Erc20Contract, Uint256};
+    use katana_rpc_types::ReceiptBlockInfo;
+
+    let recipient = felt!("0x111");
+    let amount = Uint256 { low: felt!("0x1000"), high: Felt::ZERO };
+
+    // the forked network, with a transaction in its pre-confirmed block
+    let mut config = katana_utils::node::test_config();
+    config.sequencing.no_mining = true;
+    let main_node = TestNode::new_with_config(config).await;
+
+    let main_account = main_node.account();
+    let main_contract = Erc20Contract::new(DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(), &main_account);
+    let tx_hash =
+        main_contract.transfer(&recipient, &amount).send().await.unwrap().transaction_hash;
+    katana_utils::TxWaiter::new(tx_hash, &main_node.starknet_rpc_client()).await.unwrap();
+
+    let mut config = katana_utils::node::test_config();
+    config.forking = Some(ForkingConfig {
+        url: Url::parse(&format!("http://{}", main_node.rpc_addr())).unwrap(),
+        block: None,
+        init_dev_genesis: false,
+        pre_confirmed: true,
+        cache_dir: None,
+        backend: Default::default(),
+    });
+
+    let fork_node = ForkTestNode::new_forked_with_config(config).await;
+    let provider = fork_node.starknet_rpc_client();
+
+    // the chain is forked at the parent of the pre-confirmed block
+    let genesis = fork_node.backend().chain_spec.genesis().number;
+    assert_eq!(genesis, 1);
+
+    // the carried-over block has the pre-confirmed transactions...
+    let block = provider.get_block_with_txs(BlockIdOrTag::Number(genesis + 1)).await.unwrap();
+    let MaybePreConfirmedBlock::Confirmed(block) = block else { panic!("expected a block") };
+    let hashes = block.transactions.iter().map(|tx| tx.transaction_hash).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![tx_hash]);
+
+    let receipt = provider.get_transaction_receipt(tx_hash).await.unwrap();
+    assert_matches!(receipt.block, ReceiptBlockInfo::Block { block_number, .. } => {
+        assert_eq!(block_number, genesis + 1);
+    });
+
+    // ...and their state changes
+    let fork_account = fork_node.account();
+    let fork_contract = Erc20Contract::new(DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(), &fork_account);
+    let balance = fork_contract.balanceOf(&recipient).call().await.unwrap();
+    assert_eq!(balance.low, felt!("0x1000"));
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, BTreeSet};
@@ -1024,8 +1083,14 @@ mod tests {
 
         fork_config.chain = Arc::new(ChainSpec::Dev(fork_chain_spec));
         let fork_block = katana_primitives::block::BlockHashOrNumber::Num(fork_block_number);
-        fork_config.forking =
-            Some(ForkingConfig { url: fork_url, block: Some(fork_block), init_dev_genesis: false });
+        fork_config.forking = Some(ForkingConfig {
+            url: fork_url,
+            block: Some(fork_block),
+            init_dev_genesis: false,
+            pre_confirmed: false,
+            cache_dir: None,
+            backend: Default::default(),
+        });
 
         let fork_node = ForkTestNode::new_forked_with_config(fork_config).await;
         let fork_backend = fork_node.backend();
@@ -1187,8 +1252,14 @@ mod tests {
         let fork_url: Url = Url::parse(&url).unwrap();
         let mut fork_config = katana_utils::node::test_config();
         let fork_block = katana_primitives::block::BlockHashOrNumber::Num(fork_block_number);
-        fork_config.forking =
-            Some(ForkingConfig { url: fork_url, block: Some(fork_block), init_dev_genesis: false });
+        fork_config.forking = Some(ForkingConfig {
+            url: fork_url,
+            block: Some(fork_block),
+            init_dev_genesis: false,
+            pre_confirmed: false,
+            cache_dir: None,
+            backend: Default::default(),
+        });
 
         let fork_node = ForkTestNode::new_forked_with_config(fork_config).await;
         let fork_backend = fork_node.backend();
@@ -1,19 +1,27 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use assert_matches::assert_matches;
-use katana_genesis::constant::DEFAULT_STRK_FEE_TOKEN_ADDRESS;
-use katana_primitives::block::BlockIdOrTag;
-use katana_primitives::{felt, Felt};
+use katana_genesis::constant::{
+    get_fee_token_balance_base_storage_address, DEFAULT_LEGACY_ERC20_CLASS_HASH,
+    DEFAULT_STRK_FEE_TOKEN_ADDRESS,
+};
+use katana_primitives::block::{BlockIdOrTag, GasPrice, GasPrices};
+use katana_primitives::transaction::TxType;
+use katana_primitives::{felt, Felt, U256};
 use katana_rpc_api::katana::KatanaApiClient;
 use katana_rpc_types::broadcasted::{
     BroadcastedDeclareTx, BroadcastedDeployAccountTx, BroadcastedInvokeTx,
 };
+use katana_rpc_types::overrides::{BlockOverride, ContractOverride, StateOverride};
 use katana_rpc_types::receipt::{ReceiptBlockInfo, RpcDeployAccountTxReceipt, RpcTxReceipt};
+use katana_rpc_types::{BroadcastedTx, FunctionCall, KatanaSimulationFlag};
 use katana_utils::node::test_config;
 use katana_utils::TestNode;
 use starknet::accounts::{
     Account, AccountFactory, ConnectedAccount, OpenZeppelinAccountFactory as OZAccountFactory,
 };
+use starknet::macros::selector;
 use starknet::signers::{LocalWallet, SigningKey};
 
 mod common;
@@ -290,3 +298,295 @@ async fn katana_add_transactions_return_preconfirmed_receipts() {
         provider.get_transaction_receipt(deploy_receipt.transaction_hash).await.unwrap();
     assert_eq!(deploy_receipt, deploy_receipt_from_provider);
 }
+
+#[tokio::test]
+async fn katana_trace_transaction_decoded() {
+    let sequencer = TestNode::new().await;
+    let rpc_client = sequencer.rpc_http_client();
+    let provider = sequencer.starknet_rpc_client();
+    let account = sequencer.account();
+
+    let erc20 = Erc20Contract::new(DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(), &account);
+    let recipient = Felt::ONE;
+    let amount = Uint256 { low: Felt::ONE, high: Felt::ZERO };
+
+    let res = erc20.transfer(&recipient, &amount).send().await.unwrap();
+    katana_utils::TxWaiter::new(res.transaction_hash, &provider).await.unwrap();
+
+    let trace = rpc_client.trace_transaction_decoded(res.transaction_hash).await.unwrap();
+    assert_eq!(trace.r#type, TxType::Invoke);
+    assert_eq!(trace.revert_reason, None);
+
+    // The dev account class has a Sierra ABI, so its entry points are decoded.
+    let validate = trace.validate_invocation.expect("must have validate invocation");
+    assert_eq!(validate.function_name.as_deref(), Some("__validate__"));
+
+    let execute = trace.execute_invocation.expect("must have execute invocation");
+    assert_eq!(execute.function_name.as_deref(), Some("__execute__"));
+
+    let arguments = execute.arguments.expect("must decode __execute__ arguments");
+    assert_eq!(arguments.len(), 1);
+    assert_eq!(arguments[0].name.as_deref(), Some("calls"));
+
+    let calls = arguments[0].value.as_array().expect("calls must be an array");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0]["to"], format!("{:#x}", Felt::from(DEFAULT_STRK_FEE_TOKEN_ADDRESS)));
+    assert_eq!(calls[0]["selector"], format!("{:#x}", selector!("transfer")));
+
+    // The fee token is a legacy class without a Sierra ABI, so its invocation is left undecoded.
+    assert_eq!(execute.calls.len(), 1);
+    let transfer = &execute.calls[0];
+    assert_eq!(transfer.entry_point_selector, selector!("transfer"));
+    assert_eq!(transfer.function_name, None);
+    assert_eq!(transfer.arguments, None);
+    assert!(transfer.events.iter().all(|e| e.name.is_none()));
+}
+
+#[tokio::test]
+async fn katana_call_with_state_override() {
+    let sequencer = TestNode::new().await;
+    let rpc_client = sequencer.rpc_http_client();
+    let provider = sequencer.starknet_rpc_client();
+
+    let owner = felt!("0x1234");
+    let balance_of = FunctionCall {
+        contract_address: DEFAULT_STRK_FEE_TOKEN_ADDRESS,
+        entry_point_selector: selector!("balanceOf"),
+        calldata: vec![owner],
+    };
+
+    // without overrides, this is equivalent to `starknet_call`
+    let result = rpc_client
+        .call_with_overrides(balance_of.clone(), BlockIdOrTag::PreConfirmed, None, None)
+        .await
+        .unwrap();
+    assert_eq!(result.result, vec![Felt::ZERO, Felt::ZERO]);
+
+    let mut state_override = StateOverride::new();
+    state_override.insert(
+        owner.into(),
+        ContractOverride { strk_balance: Some(U256::from(1000)), ..Default::default() },
+    );
+
+    let result = rpc_client
+        .call_with_overrides(
+            balance_of.clone(),
+            BlockIdOrTag::PreConfirmed,
+            Some(state_override),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.result, vec![felt!("0x3e8"), Felt::ZERO]);
+
+    // storage overrides take effect as-is
+    let base = get_fee_token_balance_base_storage_address(owner.into());
+    let mut state_override = StateOverride::new();
+    state_override.insert(
+        DEFAULT_STRK_FEE_TOKEN_ADDRESS,
+        ContractOverride {
+            storage: BTreeMap::from([(base + Felt::ONE, Felt::TWO)]),
+            ..Default::default()
+        },
+    );
+
+    let result = rpc_client
+        .call_with_overrides(
+            balance_of.clone(),
+            BlockIdOrTag::PreConfirmed,
+            Some(state_override.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.result, vec![Felt::ZERO, Felt::TWO]);
+
+    // storage overrides take precedence over balance overrides of the same slots
+    state_override.insert(
+        owner.into(),
+        ContractOverride { strk_balance: Some(U256::from(1000)), ..Default::default() },
+    );
+
+    let result = rpc_client
+        .call_with_overrides(
+            balance_of.clone(),
+            BlockIdOrTag::PreConfirmed,
+            Some(state_override),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.result, vec![felt!("0x3e8"), Felt::TWO]);
+
+    // the node state is left untouched
+    let result = provider.call(balance_of, BlockIdOrTag::PreConfirmed).await.unwrap();
+    assert_eq!(result.result, vec![Felt::ZERO, Felt::ZERO]);
+}
+
+#[tokio::test]
+async fn katana_estimate_fee_with_block_override() {
+    let sequencer = TestNode::new().await;
+    let rpc_client = sequencer.rpc_http_client();
+    let account = sequencer.account();
+
+    let erc20 = Erc20Contract::new(DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(), &account);
+    let recipient = Felt::ONE;
+    let amount = Uint256 { low: Felt::ONE, high: Felt::ZERO };
+
+    let fee = erc20.transfer(&recipient, &amount).estimate_fee().await.unwrap();
+    let nonce = account.get_nonce().await.unwrap();
+
+    let l2_gas_price = fee.l2_gas_price * 2;
+    let prepared_invoke = erc20
+        .transfer(&recipient, &amount)
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed)
+        .l1_gas_price(fee.l1_gas_price)
+        .l2_gas(fee.l2_gas_consumed)
+        .l2_gas_price(l2_gas_price)
+        .l1_data_gas(fee.l1_data_gas_consumed)
+        .l1_data_gas_price(fee.l1_data_gas_price)
+        .tip(0)
+        .prepared()
+        .unwrap();
+
+    let invoke_tx: BroadcastedInvokeTx =
+        convert_broadcasted_tx(prepared_invoke.get_invoke_request(true, false).await.unwrap());
+
+    let price = GasPrice::try_from(l2_gas_price).unwrap();
+    let block_override =
+        BlockOverride { l2_gas_prices: Some(GasPrices::new(price, price)), ..Default::default() };
+
+    let estimates = rpc_client
+        .estimate_fee_with_overrides(
+            vec![BroadcastedTx::Invoke(invoke_tx)],
+            Vec::new(),
+            BlockIdOrTag::PreConfirmed,
+            None,
+            Some(block_override),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(estimates.len(), 1);
+    assert_eq!(estimates[0].l2_gas_price, l2_gas_price);
+    assert_eq!(estimates[0].l2_gas_consumed, fee.l2_gas_consumed);
+}
+
+#[tokio::test]
+async fn katana_simulate_transactions_records_state_access() {
+    let sequencer = TestNode::new().await;
+    let rpc_client = sequencer.rpc_http_client();
+    let account = sequencer.account();
+
+    let erc20 = Erc20Contract::new(DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(), &account);
+    let recipient = felt!("0x1234");
+    let amount = Uint256 { low: Felt::ONE, high: Felt::ZERO };
+
+    let fee = erc20.transfer(&recipient, &amount).estimate_fee().await.unwrap();
+    let nonce = account.get_nonce().await.unwrap();
+
+    let prepared_invoke = erc20
+        .transfer(&recipient, &amount)
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed)
+        .l1_gas_price(fee.l1_gas_price)
+        .l2_gas(fee.l2_gas_consumed)
+        .l2_gas_price(fee.l2_gas_price)
+        .l1_data_gas(fee.l1_data_gas_consumed)
+        .l1_data_gas_price(fee.l1_data_gas_price)
+        .tip(0)
+        .prepared()
+        .unwrap();
+
+    let invoke_tx: BroadcastedInvokeTx =
+        convert_broadcasted_tx(prepared_invoke.get_invoke_request(true, false).await.unwrap());
+    let transactions = vec![BroadcastedTx::Invoke(invoke_tx)];
+
+    // without the flag, nothing is recorded
+    let result = rpc_client
+        .simulate_transactions_with_overrides(
+            BlockIdOrTag::PreConfirmed,
+            transactions.clone(),
+            Vec::new(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.transactions[0].state_access, None);
+    assert_eq!(result.transactions[0].transaction_trace.state_diff(), None);
+
+    let result = rpc_client
+        .simulate_transactions_with_overrides(
+            BlockIdOrTag::PreConfirmed,
+            transactions,
+            vec![KatanaSimulationFlag::RecordStateAccess],
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let simulated = &result.transactions[0];
+    let access = simulated.state_access.as_ref().expect("must record state access");
+
+    let sender = account.address().into();
+    let sender_balance = get_fee_token_balance_base_storage_address(sender);
+    let recipient_balance = get_fee_token_balance_base_storage_address(recipient.into());
+
+    let token_reads = &access.storage_reads[&DEFAULT_STRK_FEE_TOKEN_ADDRESS];
+    assert!(token_reads.contains(&sender_balance));
+    assert!(token_reads.contains(&recipient_balance));
+
+    let token_writes = &access.storage_writes[&DEFAULT_STRK_FEE_TOKEN_ADDRESS];
+    assert!(token_writes.contains(&recipient_balance));
+
+    assert!(access.nonces.contains(&sender));
+    assert!(access.class_hashes.contains(&DEFAULT_STRK_FEE_TOKEN_ADDRESS));
+    assert!(access.classes.contains(&DEFAULT_LEGACY_ERC20_CLASS_HASH));
+
+    let state_diff = simulated.transaction_trace.state_diff().expect("must have state diff");
+    assert_eq!(state_diff.nonces.get(&sender), Some(&(nonce + Felt::ONE)));
+    assert_eq!(
+        state_diff.storage_diffs[&DEFAULT_STRK_FEE_TOKEN_ADDRESS].get(&recipient_balance),
+        Some(&Felt::ONE)
+    );
+
+    // writes that leave the value unchanged are accesses, but not part of the state diff
+    let zero = Uint256 { low: Felt::ZERO, high: Felt::ZERO };
+    let prepared_invoke = erc20
+        .transfer(&recipient, &zero)
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed)
+        .l1_gas_price(fee.l1_gas_price)
+        .l2_gas(fee.l2_gas_consumed)
+        .l2_gas_price(fee.l2_gas_price)
+        .l1_data_gas(fee.l1_data_gas_consumed)
+        .l1_data_gas_price(fee.l1_data_gas_price)
+        .tip(0)
+        .prepared()
+        .unwrap();
+
+    let invoke_tx: BroadcastedInvokeTx =
+        convert_broadcasted_tx(prepared_invoke.get_invoke_request(true, false).await.unwrap());
+
+    let result = rpc_client
+        .simulate_transactions_with_overrides(
+            BlockIdOrTag::PreConfirmed,
+            vec![BroadcastedTx::Invoke(invoke_tx)],
+            vec![KatanaSimulationFlag::RecordStateAccess],
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let simulated = &result.transactions[0];
+    let access = simulated.state_access.as_ref().expect("must record state access");
+    assert!(access.storage_writes[&DEFAULT_STRK_FEE_TOKEN_ADDRESS].contains(&recipient_balance));
+
+    let state_diff = simulated.transaction_trace.state_diff().expect("must have state diff");
+    let token_diff = state_diff.storage_diffs.get(&DEFAULT_STRK_FEE_TOKEN_ADDRESS);
+    assert_eq!(token_diff.and_then(|diff| diff.get(&recipient_balance)), None);
+}
@@ -10,6 +10,7 @@
 //! API passed to the TEE provider — giving us a cryptographic handle on whether the
 //! response is bound to the requested inputs.
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
@@ -43,6 +44,7 @@ fn mock_api(
     fork_block_number: Option<u64>,
 ) -> TeeApi<DbProviderFactory> {
     let chain_spec = sample_chain_spec();
+    let fork_block_number = fork_block_number.map(|number| Arc::new(AtomicU64::new(number)));
     TeeApi::new(factory, Arc::new(MockProvider::new()), fork_block_number, &chain_spec)
 }
 
@@ -371,6 +373,54 @@ async fn generate_quote_fork_sharding_mode() {
     assert_eq!(extract_report_data(&resp.quote), expected_report_data);
 }
 
+/// The fork block number is shared with the fork controller, so quotes generated after the fork
+/// is reset must report the new fork point.
+#[tokio::test]
+async fn generate_quote_follows_fork_reset() {
+    let factory = DbProviderFactory::new_in_memory();
+    insert(
+        &factory,
+        make_block(0, felt!("0xa0"), felt!("0xa1"), felt!("0xe0"), Vec::new()),
+        vec![],
+    );
+    insert(
+        &factory,
+        make_block(1, felt!("0xb0"), felt!("0xb1"), felt!("0xe1"), Vec::new()),
+        vec![],
+    );
+
+    let fork_block_number = Arc::new(AtomicU64::new(42));
+    let api = TeeApi::new(
+        factory,
+        Arc::new(MockProvider::new()),
+        Some(fork_block_number.clone()),
+        &sample_chain_spec(),
+    );
+
+    let resp = api.generate_quote(Some(0), 1).await.expect("generate_quote");
+    assert_eq!(resp.fork_block_number, Some(42));
+
+    fork_block_number.store(100, Ordering::Release);
+
+    let resp = api.generate_quote(Some(0), 1).await.expect("generate_quote");
+    assert_eq!(resp.fork_block_number, Some(100));
+
+    let expected_report_data = sharding_report_data_v1(
+        [
+            felt!("0xa1"),
+            felt!("0xb1"),
+            felt!("0xa0"),
+            felt!("0xb0"),
+            Felt::ZERO,
+            Felt::ONE,
+            Felt::from(100u64),
+            felt!("0xe1"),
+        ],
+        sample_katana_tee_config_hash(),
+    );
+    assert_eq!(extract_report_data(&resp.quote), expected_report_data);
+}
+
 /// Sharding mode round-trips the precomputed config hash into both halves of
 /// `report_data` while preserving the fork-mode behavior of suppressing message
 /// aggregation.
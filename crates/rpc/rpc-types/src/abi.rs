@@ -0,0 +1,537 @@
+//! Decoding of raw felts using the ABI of a Sierra contract class.
+//!
+//! [`AbiDecoder`] indexes the functions, structs, enums and events of a [`ContractAbi`] and uses
+//! them to turn calldata, return data and event payloads back into named, typed values. Values are
+//! rendered as JSON:
+//!
+//! - felt-like types (`felt252`, `ContractAddress`, `ClassHash`, ...) as hex strings,
+//! - integers (including `u256`) as decimal strings,
+//! - `bool` as a JSON boolean and `ByteArray` as a JSON string,
+//! - arrays, spans and tuples as JSON arrays,
+//! - structs as JSON objects keyed by member name,
+//! - enums as `{ "<variant>": <value> }`, or as the variant name for unit variants.
+
+use std::collections::{HashMap, HashSet};
+
+use cairo_lang_starknet_classes::abi::{Enum, Event, EventFieldKind, EventKind, Item, Struct};
+use katana_primitives::class::ContractAbi;
+use katana_primitives::utils::{get_selector_from_name, starknet_keccak};
+use katana_primitives::{Felt, U256};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Error returned when felts can't be decoded against an ABI.
+#[derive(Debug, thiserror::Error)]
+pub enum AbiDecodeError {
+    #[error("function with selector {0:#x} not found in ABI")]
+    FunctionNotFound(Felt),
+
+    #[error("no event in ABI matches the event keys")]
+    EventNotFound,
+
+    #[error("unsupported type {0}")]
+    UnsupportedType(String),
+
+    #[error("not enough felts to decode type {0}")]
+    NotEnoughFelts(String),
+
+    #[error("invalid value {value:#x} for type {ty}")]
+    InvalidValue { ty: String, value: Felt },
+
+    #[error("{0} felts left after decoding")]
+    TrailingFelts(usize),
+
+    #[error("type {0} is nested too deeply")]
+    TooDeep(String),
+}
+
+/// The maximum nesting depth of the decoded types.
+///
+/// ABIs are provided by the class declarer, so a self-referencing type (eg, a struct with a member
+/// of its own type) must not make the decoder recurse forever.
+const MAX_DEPTH: usize = 64;
+
+/// A named value decoded from its felt representation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedValue {
+    /// The parameter or member name. `None` for function return values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The Cairo type of the value, as it appears in the ABI.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The decoded value.
+    pub value: Value,
+}
+
+/// A decoded event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEventData {
+    /// The fully qualified name of the event struct.
+    pub name: String,
+    /// The event members, key members first.
+    pub fields: Vec<DecodedValue>,
+}
+
+#[derive(Debug, Clone)]
+struct FunctionEntry {
+    name: String,
+    inputs: Vec<(String, String)>,
+    outputs: Vec<String>,
+}
+
+/// Decodes calldata, return data and events of a contract class using its ABI.
+#[derive(Debug, Clone, Default)]
+pub struct AbiDecoder {
+    functions: HashMap<Felt, FunctionEntry>,
+    structs: HashMap<String, Struct>,
+    enums: HashMap<String, Enum>,
+    events: HashMap<String, Event>,
+    /// The contract's top-level event enum, ie the one not nested in any other event.
+    root_event: Option<String>,
+}
+
+impl AbiDecoder {
+    pub fn new(abi: &ContractAbi) -> Self {
+        let mut decoder = Self::default();
+        let mut event_order = Vec::new();
+
+        let mut items = abi.clone().into_iter().collect::<Vec<_>>();
+        while let Some(item) = items.pop() {
+            match item {
+                Item::Function(f) => {
+                    let inputs = f.inputs.into_iter().map(|i| (i.name, i.ty)).collect();
+                    let outputs = f.outputs.into_iter().map(|o| o.ty).collect();
+                    decoder.insert_function(f.name, inputs, outputs);
+                }
+                Item::L1Handler(f) => {
+                    let inputs = f.inputs.into_iter().map(|i| (i.name, i.ty)).collect();
+                    let outputs = f.outputs.into_iter().map(|o| o.ty).collect();
+                    decoder.insert_function(f.name, inputs, outputs);
+                }
+                Item::Constructor(f) => {
+                    let inputs = f.inputs.into_iter().map(|i| (i.name, i.ty)).collect();
+                    decoder.insert_function(f.name, inputs, Vec::new());
+                }
+                Item::Struct(s) => {
+                    decoder.structs.insert(s.name.clone(), s);
+                }
+                Item::Enum(e) => {
+                    decoder.enums.insert(e.name.clone(), e);
+                }
+                Item::Event(e) => {
+                    event_order.push(e.name.clone());
+                    decoder.events.insert(e.name.clone(), e);
+                }
+                Item::Interface(i) => items.extend(i.items),
+                Item::Impl(_) => {}
+            }
+        }
+
+        // Items are visited in reverse, so the first enum event that isn't nested in another
+        // event is the last one declared in the ABI.
+        let nested = decoder
+            .events
+            .values()
+            .flat_map(|e| match &e.kind {
+                EventKind::Struct { .. } => Vec::new(),
+                EventKind::Enum { variants } => variants.iter().map(|v| v.ty.as_str()).collect(),
+            })
+            .collect::<HashSet<_>>();
+
+        decoder.root_event = event_order
+            .iter()
+            .find(|name| {
+                !nested.contains(name.as_str())
+                    && matches!(decoder.events[*name].kind, EventKind::Enum { .. })
+            })
+            .cloned();
+
+        decoder
+    }
+
+    /// Returns the name of the function with the given selector, if it exists in the ABI.
+    pub fn function_name(&self, selector: Felt) -> Option<&str> {
+        self.functions.get(&selector).map(|f| f.name.as_str())
+    }
+
+    /// Decodes the calldata of the function with the given selector.
+    pub fn decode_inputs(
+        &self,
+        selector: Felt,
+        calldata: &[Felt],
+    ) -> Result<Vec<DecodedValue>, AbiDecodeError> {
+        let function =
+            self.functions.get(&selector).ok_or(AbiDecodeError::FunctionNotFound(selector))?;
+
+        let mut cursor = Cursor::new(calldata);
+        let values = function
+            .inputs
+            .iter()
+            .map(|(name, ty)| {
+                let value = self.decode(ty, &mut cursor, 0)?;
+                Ok(DecodedValue { name: Some(name.clone()), ty: ty.clone(), value })
+            })
+            .collect::<Result<Vec<_>, AbiDecodeError>>()?;
+
+        cursor.finish()?;
+        Ok(values)
+    }
+
+    /// Decodes the return data of the function with the given selector.
+    pub fn decode_outputs(
+        &self,
+        selector: Felt,
+        result: &[Felt],
+    ) -> Result<Vec<DecodedValue>, AbiDecodeError> {
+        let function =
+            self.functions.get(&selector).ok_or(AbiDecodeError::FunctionNotFound(selector))?;
+
+        let mut cursor = Cursor::new(result);
+        let values = function
+            .outputs
+            .iter()
+            .map(|ty| {
+                let value = self.decode(ty, &mut cursor, 0)?;
+                Ok(DecodedValue { name: None, ty: ty.clone(), value })
+            })
+            .collect::<Result<Vec<_>, AbiDecodeError>>()?;
+
+        cursor.finish()?;
+        Ok(values)
+    }
+
+    /// Decodes an event emitted by the contract.
+    pub fn decode_event(
+        &self,
+        keys: &[Felt],
+        data: &[Felt],
+    ) -> Result<DecodedEventData, AbiDecodeError> {
+        let root = self.root_event.as_deref().ok_or(AbiDecodeError::EventNotFound)?;
+
+        let mut keys = Cursor::new(keys);
+        let mut data = Cursor::new(data);
+        let event = self.decode_event_enum(root, &mut keys, &mut data, 0)?;
+
+        keys.finish()?;
+        data.finish()?;
+        Ok(event)
+    }
+
+    fn insert_function(
+        &mut self,
+        name: String,
+        inputs: Vec<(String, String)>,
+        outputs: Vec<String>,
+    ) {
+        let selector = get_selector_from_name(&name);
+        self.functions.insert(selector, FunctionEntry { name, inputs, outputs });
+    }
+
+    /// Finds the variant of the event enum `name` matching the event keys.
+    fn decode_event_enum(
+        &self,
+        name: &str,
+        keys: &mut Cursor<'_>,
+        data: &mut Cursor<'_>,
+        depth: usize,
+    ) -> Result<DecodedEventData, AbiDecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(AbiDecodeError::TooDeep(name.to_string()));
+        }
+
+        let Some(EventKind::Enum { variants }) = self.events.get(name).map(|e| &e.kind) else {
+            return Err(AbiDecodeError::EventNotFound);
+        };
+
+        for variant in variants {
+            let (mut k, mut d) = (keys.clone(), data.clone());
+
+            let decoded = match variant.kind {
+                EventFieldKind::Nested => {
+                    if k.peek() != Some(starknet_keccak(variant.name.as_bytes())) {
+                        continue;
+                    }
+
+                    k.pos += 1;
+                    self.decode_event_variant(&variant.ty, &mut k, &mut d, depth + 1)
+                }
+                // Flattened variants don't emit their own key, so the only way to know whether
+                // they match is to try decoding them.
+                EventFieldKind::Flat => {
+                    self.decode_event_enum(&variant.ty, &mut k, &mut d, depth + 1)
+                }
+                EventFieldKind::KeySerde | EventFieldKind::DataSerde => continue,
+            };
+
+            match decoded {
+                Ok(event) => {
+                    *keys = k;
+                    *data = d;
+                    return Ok(event);
+                }
+                Err(error @ AbiDecodeError::TooDeep(_)) => return Err(error),
+                Err(_) => {}
+            }
+        }
+
+        Err(AbiDecodeError::EventNotFound)
+    }
+
+    fn decode_event_variant(
+        &self,
+        name: &str,
+        keys: &mut Cursor<'_>,
+        data: &mut Cursor<'_>,
+        depth: usize,
+    ) -> Result<DecodedEventData, AbiDecodeError> {
+        let event = self.events.get(name).ok_or(AbiDecodeError::EventNotFound)?;
+
+        let members = match &event.kind {
+            EventKind::Enum { .. } => return self.decode_event_enum(name, keys, data, depth),
+            EventKind::Struct { members } => members,
+        };
+
+        let mut fields = Vec::with_capacity(members.len());
+        for member in members.iter().filter(|m| m.kind == EventFieldKind::KeySerde) {
+            let value = self.decode(&member.ty, keys, depth + 1)?;
+            fields.push(DecodedValue {
+                name: Some(member.name.clone()),
+                ty: member.ty.clone(),
+                value,
+            });
+        }
+        for member in members.iter().filter(|m| m.kind == EventFieldKind::DataSerde) {
+            let value = self.decode(&member.ty, data, depth + 1)?;
+            fields.push(DecodedValue {
+                name: Some(member.name.clone()),
+                ty: member.ty.clone(),
+                value,
+            });
+        }
+
+        // Entirely consumed keys is what tells apart flattened variants sharing the same key.
+        keys.finish()?;
+        Ok(DecodedEventData { name: name.to_string(), fields })
+    }
+
+    /// Decodes a value of type `ty`, where `depth` is the number of types it's nested in.
+    fn decode(
+        &self,
+        ty: &str,
+        felts: &mut Cursor<'_>,
+        depth: usize,
+    ) -> Result<Value, AbiDecodeError> {
+        let ty = ty.trim().trim_start_matches('@');
+
+        if depth > MAX_DEPTH {
+            return Err(AbiDecodeError::TooDeep(ty.to_string()));
+        }
+
+        if ty == "()" {
+            return Ok(Value::Null);
+        }
+
+        if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            let values = split_tuple(inner)
+                .into_iter()
+                .map(|ty| self.decode(ty, felts, depth + 1))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(Value::Array(values));
+        }
+
+        if let Some(element) =
+            generic_arg(ty, "core::array::Array").or_else(|| generic_arg(ty, "core::array::Span"))
+        {
+            // The length comes from untrusted felts, so it's bounded by the number of felts left
+            // and every element must consume at least one felt.
+            let len = felts.next_u128(ty)?;
+            if len > felts.remaining() as u128 {
+                return Err(AbiDecodeError::InvalidValue { ty: ty.to_string(), value: len.into() });
+            }
+
+            let mut values = Vec::with_capacity(len as usize);
+            for _ in 0..len {
+                let pos = felts.pos;
+                values.push(self.decode(element, felts, depth + 1)?);
+                if felts.pos == pos {
+                    return Err(AbiDecodeError::UnsupportedType(ty.to_string()));
+                }
+            }
+            return Ok(Value::Array(values));
+        }
+
+        match ty {
+            "core::felt252"
+            | "core::starknet::contract_address::ContractAddress"
+            | "core::starknet::class_hash::ClassHash"
+            | "core::starknet::eth_address::EthAddress"
+            | "core::starknet::storage_access::StorageAddress"
+            | "core::starknet::storage_access::StorageBaseAddress"
+            | "core::bytes_31::bytes31" => Ok(Value::String(format!("{:#x}", felts.next(ty)?))),
+
+            "core::bool" => {
+                let value = felts.next(ty)?;
+                if value == Felt::ZERO || value == Felt::ONE {
+                    Ok(Value::Bool(value == Felt::ONE))
+                } else {
+                    Err(AbiDecodeError::InvalidValue { ty: ty.to_string(), value })
+                }
+            }
+
+            "core::integer::u8"
+            | "core::integer::u16"
+            | "core::integer::u32"
+            | "core::integer::u64"
+            | "core::integer::u128"
+            | "core::integer::usize" => Ok(Value::String(felts.next_u128(ty)?.to_string())),
+
+            "core::integer::i8"
+            | "core::integer::i16"
+            | "core::integer::i32"
+            | "core::integer::i64"
+            | "core::integer::i128" => {
+                let value = felts.next(ty)?;
+                let decoded = match u128::try_from(value) {
+                    Ok(value) => value.to_string(),
+                    Err(_) => match u128::try_from(-value) {
+                        Ok(value) => format!("-{value}"),
+                        Err(_) => {
+                            return Err(AbiDecodeError::InvalidValue { ty: ty.to_string(), value })
+                        }
+                    },
+                };
+                Ok(Value::String(decoded))
+            }
+
+            "core::integer::u256" => {
+                let low = felts.next_u128(ty)?;
+                let high = felts.next_u128(ty)?;
+                let value = (U256::from(high) << 128) | U256::from(low);
+                Ok(Value::String(value.to_string()))
+            }
+
+            "core::byte_array::ByteArray" => Ok(Value::String(decode_byte_array(felts)?)),
+
+            _ => {
+                if let Some(s) = self.structs.get(ty) {
+                    let mut object = Map::with_capacity(s.members.len());
+                    for member in &s.members {
+                        object.insert(
+                            member.name.clone(),
+                            self.decode(&member.ty, felts, depth + 1)?,
+                        );
+                    }
+                    Ok(Value::Object(object))
+                } else if let Some(e) = self.enums.get(ty) {
+                    let index = felts.next_u128(ty)?;
+                    let variant = usize::try_from(index)
+                        .ok()
+                        .and_then(|i| e.variants.get(i))
+                        .ok_or_else(|| AbiDecodeError::InvalidValue {
+                            ty: ty.to_string(),
+                            value: index.into(),
+                        })?;
+
+                    if variant.ty == "()" {
+                        Ok(Value::String(variant.name.clone()))
+                    } else {
+                        let value = self.decode(&variant.ty, felts, depth + 1)?;
+                        Ok(Value::Object(Map::from_iter([(variant.name.clone(), value)])))
+                    }
+                } else {
+                    Err(AbiDecodeError::UnsupportedType(ty.to_string()))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Cursor<'a> {
+    felts: &'a [Felt],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(felts: &'a [Felt]) -> Self {
+        Self { felts, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<Felt> {
+        self.felts.get(self.pos).copied()
+    }
+
+    fn next(&mut self, ty: &str) -> Result<Felt, AbiDecodeError> {
+        let felt = self.peek().ok_or_else(|| AbiDecodeError::NotEnoughFelts(ty.to_string()))?;
+        self.pos += 1;
+        Ok(felt)
+    }
+
+    fn next_u128(&mut self, ty: &str) -> Result<u128, AbiDecodeError> {
+        let value = self.next(ty)?;
+        u128::try_from(value)
+            .map_err(|_| AbiDecodeError::InvalidValue { ty: ty.to_string(), value })
+    }
+
+    fn remaining(&self) -> usize {
+        self.felts.len() - self.pos
+    }
+
+    fn finish(&self) -> Result<(), AbiDecodeError> {
+        match self.remaining() {
+            0 => Ok(()),
+            remaining => Err(AbiDecodeError::TrailingFelts(remaining)),
+        }
+    }
+}
+
+/// Decodes a `ByteArray`: `[num_full_words, ...full_words, pending_word, pending_word_len]`.
+fn decode_byte_array(felts: &mut Cursor<'_>) -> Result<String, AbiDecodeError> {
+    const TY: &str = "core::byte_array::ByteArray";
+
+    let mut bytes = Vec::new();
+    for _ in 0..felts.next_u128(TY)? {
+        bytes.extend_from_slice(&felts.next(TY)?.to_bytes_be()[1..]);
+    }
+
+    let pending_word = felts.next(TY)?;
+    let pending_len = felts.next_u128(TY)?;
+    if pending_len > 31 {
+        return Err(AbiDecodeError::InvalidValue { ty: TY.to_string(), value: pending_len.into() });
+    }
+    bytes.extend_from_slice(&pending_word.to_bytes_be()[32 - pending_len as usize..]);
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Returns the generic argument of `ty` if it is an instance of the generic type `name`, eg
+/// `core::felt252` for `core::array::Array::<core::felt252>`.
+fn generic_arg<'a>(ty: &'a str, name: &str) -> Option<&'a str> {
+    ty.strip_prefix(name)?.strip_prefix("::<")?.strip_suffix('>')
+}
+
+/// Splits the elements of a tuple type, ignoring the commas of nested types.
+fn split_tuple(inner: &str) -> Vec<&str> {
+    let mut elements = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                elements.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let last = inner[start..].trim();
+    if !last.is_empty() {
+        elements.push(last);
+    }
+
+    elements
+}
@@ -7,6 +7,7 @@ use katana_primitives::Felt;
 use serde::{Deserialize, Serialize};
 use serde_utils::{deserialize_u128, deserialize_u64, serialize_as_hex};
 
+pub mod abi;
 pub mod account;
 pub mod block;
 pub mod broadcasted;
@@ -17,6 +18,7 @@ pub mod list;
 pub mod message;
 pub mod node;
 pub mod outside_execution;
+pub mod overrides;
 pub mod receipt;
 pub mod state_update;
 pub mod trace;
@@ -110,6 +112,31 @@ pub enum SimulationFlag {
     SkipFeeCharge,
 }
 
+/// Simulation flags for the `katana_simulateTransactions` RPC method.
+///
+/// This is a superset of [`SimulationFlag`] with Katana extensions, which aren't accepted by
+/// `starknet_simulateTransactions`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum KatanaSimulationFlag {
+    #[serde(rename = "SKIP_VALIDATE")]
+    SkipValidate,
+    #[serde(rename = "SKIP_FEE_CHARGE")]
+    SkipFeeCharge,
+    /// Record the state read and written by each transaction, and include it in the result along
+    /// with the transaction's state diff.
+    #[serde(rename = "RECORD_STATE_ACCESS")]
+    RecordStateAccess,
+}
+
+impl From<SimulationFlag> for KatanaSimulationFlag {
+    fn from(flag: SimulationFlag) -> Self {
+        match flag {
+            SimulationFlag::SkipValidate => Self::SkipValidate,
+            SimulationFlag::SkipFeeCharge => Self::SkipFeeCharge,
+        }
+    }
+}
+
 /// A Starknet client node's synchronization status.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SyncingResponse {
@@ -323,6 +350,17 @@ mod tests {
         assert_eq!(deserialized, flag);
     }
 
+    #[rstest]
+    #[case(KatanaSimulationFlag::SkipValidate, json!("SKIP_VALIDATE"))]
+    #[case(KatanaSimulationFlag::SkipFeeCharge, json!("SKIP_FEE_CHARGE"))]
+    #[case(KatanaSimulationFlag::RecordStateAccess, json!("RECORD_STATE_ACCESS"))]
+    fn katana_simulation_flags_serde(#[case] flag: KatanaSimulationFlag, #[case] json: Value) {
+        let serialized = serde_json::to_value(&flag).unwrap();
+        assert_eq!(serialized, json);
+        let deserialized: KatanaSimulationFlag = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, flag);
+    }
+
     #[rstest]
     #[case(json!("INVALID_FLAG"))]
     #[case(json!("skip_validate"))]
@@ -331,6 +369,8 @@ mod tests {
     #[case(json!(123))]
     #[case(json!(null))]
     #[case(json!(false))]
+    // Katana extensions aren't part of the spec flags.
+    #[case(json!("RECORD_STATE_ACCESS"))]
     fn invalid_simulation_flags(#[case] invalid: Value) {
         let result = serde_json::from_value::<SimulationFlag>(invalid.clone());
         assert!(result.is_err(), "expected error for invalid value: {invalid}");
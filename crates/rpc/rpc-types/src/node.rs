@@ -6,9 +6,9 @@ use serde::{Deserialize, Serialize};
 /// Identity and build information for a running Katana node.
 ///
 /// On full nodes, `chain_id` and `chain_kind` reflect the *configured* chain (via
-/// `config.network`), not the chain observed from the upstream sync source. Operators
-/// who point a sync source at the wrong network will still see their configured
-/// identity reported here.
+/// `config.network` or `config.chain`), not the chain observed from the upstream sync
+/// source. Operators who point a sync source at the wrong network will still see their
+/// configured identity reported here.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NodeInfo {
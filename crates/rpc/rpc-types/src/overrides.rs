@@ -0,0 +1,103 @@
+//! Types for overriding the state and block context of a simulated execution.
+//!
+//! These are accepted by the `katana_call`, `katana_estimateFee` and `katana_simulateTransactions`
+//! methods, and only apply to the execution of a single request - the node state is never
+//! modified.
+
+use std::collections::BTreeMap;
+
+use katana_primitives::block::{BlockNumber, GasPrices};
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
+use katana_primitives::env::BlockEnv;
+use katana_primitives::U256;
+use serde::{Deserialize, Serialize};
+
+/// State overrides, keyed by the address of the contract to override.
+pub type StateOverride = BTreeMap<ContractAddress, ContractOverride>;
+
+/// The values to override for a single contract.
+///
+/// Fields that are not set are read from the state the request is executed on.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractOverride {
+    /// The nonce of the contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Nonce>,
+
+    /// The class hash of the contract. Setting this on an undeployed address effectively deploys a
+    /// contract at that address, as long as the class is declared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_hash: Option<ClassHash>,
+
+    /// Storage slots to set. Slots that are not included keep their current value.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<StorageKey, StorageValue>,
+
+    /// The balance of the contract in the ETH fee token.
+    ///
+    /// This overrides the balance slots in the storage of the fee token, unless they're also set
+    /// through the fee token's [`storage`](Self::storage) overrides, which take precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eth_balance: Option<U256>,
+
+    /// The balance of the contract in the STRK fee token. See [`Self::eth_balance`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strk_balance: Option<U256>,
+}
+
+/// Overrides for the block context a request is executed in.
+///
+/// Fields that are not set are taken from the block the request is executed on.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockOverride {
+    /// The block number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<BlockNumber>,
+
+    /// The block timestamp, in seconds since the UNIX epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+
+    /// The L1 gas prices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_gas_prices: Option<GasPrices>,
+
+    /// The L1 data gas prices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_data_gas_prices: Option<GasPrices>,
+
+    /// The L2 gas prices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_gas_prices: Option<GasPrices>,
+
+    /// The address of the sequencer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequencer_address: Option<ContractAddress>,
+}
+
+impl BlockOverride {
+    /// Applies the overrides to the given block environment.
+    pub fn apply(&self, env: &mut BlockEnv) {
+        if let Some(number) = self.number {
+            env.number = number;
+        }
+        if let Some(timestamp) = self.timestamp {
+            env.timestamp = timestamp;
+        }
+        if let Some(prices) = &self.l1_gas_prices {
+            env.l1_gas_prices = prices.clone();
+        }
+        if let Some(prices) = &self.l1_data_gas_prices {
+            env.l1_data_gas_prices = prices.clone();
+        }
+        if let Some(prices) = &self.l2_gas_prices {
+            env.l2_gas_prices = prices.clone();
+        }
+        if let Some(address) = self.sequencer_address {
+            env.sequencer_address = address;
+        }
+    }
+}
@@ -2,6 +2,7 @@ use katana_primitives::block::{BlockHash, BlockNumber, FinalityStatus};
 use katana_primitives::execution::VmResources;
 use katana_primitives::fee::{FeeInfo, PriceUnit};
 use katana_primitives::receipt::{self, Event, MessageToL1, Receipt};
+use katana_primitives::revert::RevertStack;
 use katana_primitives::transaction::TxHash;
 use katana_primitives::{ContractAddress, Felt, B256};
 use serde::{Deserialize, Deserializer, Serialize};
@@ -195,7 +196,7 @@ impl RpcTxReceipt {
                     contract_address: rct.contract_address,
                     execution_resources: rct.execution_resources.into(),
                     execution_result: if let Some(reason) = rct.revert_error {
-                        ExecutionResult::Reverted { reason }
+                        ExecutionResult::reverted(reason)
                     } else {
                         ExecutionResult::Succeeded
                     },
@@ -213,7 +214,7 @@ impl RpcTxReceipt {
                     actual_fee: rct.fee.into(),
                     execution_resources: rct.execution_resources.into(),
                     execution_result: if let Some(reason) = rct.revert_error {
-                        ExecutionResult::Reverted { reason }
+                        ExecutionResult::reverted(reason)
                     } else {
                         ExecutionResult::Succeeded
                     },
@@ -231,7 +232,7 @@ impl RpcTxReceipt {
                     actual_fee: rct.fee.into(),
                     execution_resources: rct.execution_resources.into(),
                     execution_result: if let Some(reason) = rct.revert_error {
-                        ExecutionResult::Reverted { reason }
+                        ExecutionResult::reverted(reason)
                     } else {
                         ExecutionResult::Succeeded
                     },
@@ -250,7 +251,7 @@ impl RpcTxReceipt {
                     execution_resources: rct.execution_resources.into(),
                     message_hash: rct.message_hash,
                     execution_result: if let Some(reason) = rct.revert_error {
-                        ExecutionResult::Reverted { reason }
+                        ExecutionResult::reverted(reason)
                     } else {
                         ExecutionResult::Succeeded
                     },
@@ -269,7 +270,7 @@ impl RpcTxReceipt {
                     contract_address: rct.contract_address,
                     execution_resources: rct.execution_resources.into(),
                     execution_result: if let Some(reason) = rct.revert_error {
-                        ExecutionResult::Reverted { reason }
+                        ExecutionResult::reverted(reason)
                     } else {
                         ExecutionResult::Succeeded
                     },
@@ -372,9 +373,20 @@ pub enum ExecutionResult {
     Reverted {
         #[serde(rename = "revert_reason")]
         reason: String,
+        /// The structured form of the revert reason. This is a Katana extension.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        revert_error: Option<RevertStack>,
     },
 }
 
+impl ExecutionResult {
+    /// Creates a reverted execution result, parsing the revert reason into its structured form.
+    pub fn reverted(reason: String) -> Self {
+        let revert_error = RevertStack::parse(&reason);
+        ExecutionResult::Reverted { reason, revert_error }
+    }
+}
+
 /// The resources consumed by the transaction.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -457,7 +469,7 @@ impl From<RpcTxReceipt> for Receipt {
                 messages_sent: rct.messages_sent,
                 revert_error: match rct.execution_result {
                     ExecutionResult::Succeeded => None,
-                    ExecutionResult::Reverted { reason } => Some(reason),
+                    ExecutionResult::Reverted { reason, .. } => Some(reason),
                 },
                 execution_resources: rct.execution_resources.into(),
             }),
@@ -468,7 +480,7 @@ impl From<RpcTxReceipt> for Receipt {
                 messages_sent: rct.messages_sent,
                 revert_error: match rct.execution_result {
                     ExecutionResult::Succeeded => None,
-                    ExecutionResult::Reverted { reason } => Some(reason),
+                    ExecutionResult::Reverted { reason, .. } => Some(reason),
                 },
                 execution_resources: rct.execution_resources.into(),
             }),
@@ -480,7 +492,7 @@ impl From<RpcTxReceipt> for Receipt {
                 messages_sent: rct.messages_sent,
                 revert_error: match rct.execution_result {
                     ExecutionResult::Succeeded => None,
-                    ExecutionResult::Reverted { reason } => Some(reason),
+                    ExecutionResult::Reverted { reason, .. } => Some(reason),
                 },
                 execution_resources: rct.execution_resources.into(),
             }),
@@ -492,7 +504,7 @@ impl From<RpcTxReceipt> for Receipt {
                     messages_sent: rct.messages_sent,
                     revert_error: match rct.execution_result {
                         ExecutionResult::Succeeded => None,
-                        ExecutionResult::Reverted { reason } => Some(reason),
+                        ExecutionResult::Reverted { reason, .. } => Some(reason),
                     },
                     execution_resources: rct.execution_resources.into(),
                     contract_address: rct.contract_address,
@@ -505,7 +517,7 @@ impl From<RpcTxReceipt> for Receipt {
                 messages_sent: rct.messages_sent,
                 revert_error: match rct.execution_result {
                     ExecutionResult::Succeeded => None,
-                    ExecutionResult::Reverted { reason } => Some(reason),
+                    ExecutionResult::Reverted { reason, .. } => Some(reason),
                 },
                 execution_resources: rct.execution_resources.into(),
                 contract_address: rct.contract_address,
@@ -5,10 +5,13 @@ use katana_primitives::execution::{
     self, CallInfo, EntryPointSelector, EntryPointType, TrackedResource, TransactionExecutionInfo,
 };
 use katana_primitives::fee::FeeInfo;
+use katana_primitives::revert::RevertStack;
+use katana_primitives::state::StateAccesses;
 use katana_primitives::transaction::TxType;
 use katana_primitives::{receipt, ContractAddress, Felt};
 use serde::{Deserialize, Serialize};
 
+use crate::abi::{AbiDecoder, DecodedValue};
 use crate::state_update::StateDiff;
 use crate::{ExecutionResources, FeeEstimate};
 
@@ -30,6 +33,10 @@ pub struct SimulatedTransactions {
     pub transaction_trace: TxTrace,
     /// The transaction's resources and fee
     pub fee_estimation: FeeEstimate,
+    /// The state accessed by the transaction. Only present if the simulation was requested with
+    /// the [`RecordStateAccess`](crate::KatanaSimulationFlag::RecordStateAccess) flag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_access: Option<StateAccesses>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -56,6 +63,29 @@ pub enum TxTrace {
     DeployAccount(DeployAccountTxTrace),
 }
 
+impl TxTrace {
+    /// Returns the state diff produced by the transaction, if any.
+    pub fn state_diff(&self) -> Option<&StateDiff> {
+        match self {
+            TxTrace::Invoke(trace) => trace.state_diff.as_ref(),
+            TxTrace::Declare(trace) => trace.state_diff.as_ref(),
+            TxTrace::L1Handler(trace) => trace.state_diff.as_ref(),
+            TxTrace::DeployAccount(trace) => trace.state_diff.as_ref(),
+        }
+    }
+
+    /// Sets the state diff produced by the transaction.
+    pub fn set_state_diff(&mut self, state_diff: StateDiff) {
+        let slot = match self {
+            TxTrace::Invoke(trace) => &mut trace.state_diff,
+            TxTrace::Declare(trace) => &mut trace.state_diff,
+            TxTrace::L1Handler(trace) => &mut trace.state_diff,
+            TxTrace::DeployAccount(trace) => &mut trace.state_diff,
+        };
+        *slot = Some(state_diff);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CallType {
     #[serde(rename = "LIBRARY_CALL")]
@@ -138,6 +168,17 @@ pub enum ExecuteInvocation {
 pub struct RevertedInvocation {
     /// The revert reason for the failed invocation
     pub revert_reason: String,
+    /// The structured form of the revert reason. This is a Katana extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_error: Option<RevertStack>,
+}
+
+impl RevertedInvocation {
+    /// Creates a new [`RevertedInvocation`], parsing the revert reason into its structured form.
+    pub fn new(revert_reason: String) -> Self {
+        let revert_error = RevertStack::parse(&revert_reason);
+        Self { revert_reason, revert_error }
+    }
 }
 
 /// The execution trace of an invoke transaction.
@@ -202,7 +243,7 @@ impl From<katana_primitives::execution::TypedTransactionExecutionInfo> for TxTra
         match tx_type {
             TxType::Invoke => {
                 let execute_invocation = if let Some(revert_reason) = revert_reason {
-                    let invocation = RevertedInvocation { revert_reason };
+                    let invocation = RevertedInvocation::new(revert_reason);
                     ExecuteInvocation::Reverted(invocation)
                 } else {
                     let invocation = execute_invocation.expect("should exist if not reverted");
@@ -239,7 +280,7 @@ impl From<katana_primitives::execution::TypedTransactionExecutionInfo> for TxTra
 
             TxType::L1Handler => {
                 let function_invocation = if let Some(revert_reason) = revert_reason {
-                    let invocation = RevertedInvocation { revert_reason };
+                    let invocation = RevertedInvocation::new(revert_reason);
                     ExecuteInvocation::Reverted(invocation)
                 } else {
                     let invocation = execute_invocation.expect("should exist if not reverted");
@@ -325,6 +366,163 @@ impl From<katana_primitives::execution::OrderedEvent> for OrderedEvent {
     }
 }
 
+/// Execution trace of a transaction whose calls and events are decoded using the ABIs of the
+/// classes being executed.
+///
+/// Decoding is best-effort: the decoded fields are omitted for classes without a Sierra ABI (eg
+/// legacy Cairo 0 classes) or when the raw felts don't match the ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedTxTrace {
+    #[serde(rename = "type")]
+    pub r#type: TxType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate_invocation: Option<DecodedFunctionInvocation>,
+    /// The `__execute__` invocation for invoke transactions, the constructor invocation for deploy
+    /// account transactions and the handler invocation for L1 handler transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execute_invocation: Option<DecodedFunctionInvocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_transfer_invocation: Option<DecodedFunctionInvocation>,
+    /// The revert reason if the execution was reverted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedFunctionInvocation {
+    pub contract_address: ContractAddress,
+    pub class_hash: ClassHash,
+    pub entry_point_selector: EntryPointSelector,
+    /// The name of the function being called.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function_name: Option<String>,
+    pub caller_address: ContractAddress,
+    pub entry_point_type: EntryPointType,
+    pub call_type: CallType,
+    pub calldata: Vec<Felt>,
+    /// The decoded function arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Vec<DecodedValue>>,
+    pub result: Vec<Felt>,
+    /// The decoded function return values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_values: Option<Vec<DecodedValue>>,
+    pub events: Vec<DecodedOrderedEvent>,
+    pub calls: Vec<DecodedFunctionInvocation>,
+    pub is_reverted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedOrderedEvent {
+    pub order: u64,
+    pub keys: Vec<Felt>,
+    pub data: Vec<Felt>,
+    /// The fully qualified name of the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The decoded event members.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<DecodedValue>>,
+}
+
+impl DecodedTxTrace {
+    /// Decodes a transaction trace. `abi` resolves the decoder of a class, if it has an ABI.
+    pub fn new<F>(trace: TxTrace, mut abi: F) -> Self
+    where
+        F: FnMut(ClassHash) -> Option<Arc<AbiDecoder>>,
+    {
+        let mut decode = |inv: FunctionInvocation| DecodedFunctionInvocation::new(inv, &mut abi);
+
+        let (r#type, validate, execute, fee_transfer) = match trace {
+            TxTrace::Invoke(trace) => (
+                TxType::Invoke,
+                trace.validate_invocation,
+                Some(trace.execute_invocation),
+                trace.fee_transfer_invocation,
+            ),
+            TxTrace::L1Handler(trace) => {
+                (TxType::L1Handler, None, Some(trace.function_invocation), None)
+            }
+            TxTrace::DeployAccount(trace) => (
+                TxType::DeployAccount,
+                trace.validate_invocation,
+                Some(ExecuteInvocation::Success(Box::new(trace.constructor_invocation))),
+                trace.fee_transfer_invocation,
+            ),
+            TxTrace::Declare(trace) => {
+                (TxType::Declare, trace.validate_invocation, None, trace.fee_transfer_invocation)
+            }
+        };
+
+        let (execute_invocation, revert_reason) = match execute {
+            Some(ExecuteInvocation::Success(inv)) => (Some(decode(*inv)), None),
+            Some(ExecuteInvocation::Reverted(inv)) => (None, Some(inv.revert_reason)),
+            None => (None, None),
+        };
+
+        Self {
+            r#type,
+            revert_reason,
+            execute_invocation,
+            validate_invocation: validate.map(&mut decode),
+            fee_transfer_invocation: fee_transfer.map(&mut decode),
+        }
+    }
+}
+
+impl DecodedFunctionInvocation {
+    fn new<F>(inv: FunctionInvocation, abi: &mut F) -> Self
+    where
+        F: FnMut(ClassHash) -> Option<Arc<AbiDecoder>>,
+    {
+        let decoder = abi(inv.class_hash);
+        let selector = inv.entry_point_selector;
+
+        let function_name =
+            decoder.as_ref().and_then(|d| d.function_name(selector)).map(ToString::to_string);
+        let arguments =
+            decoder.as_ref().and_then(|d| d.decode_inputs(selector, &inv.calldata).ok());
+        let return_values =
+            decoder.as_ref().and_then(|d| d.decode_outputs(selector, &inv.result).ok());
+
+        let events = inv
+            .events
+            .into_iter()
+            .map(|event| {
+                let decoded =
+                    decoder.as_ref().and_then(|d| d.decode_event(&event.keys, &event.data).ok());
+                let (name, fields) = decoded.map(|e| (e.name, e.fields)).unzip();
+                DecodedOrderedEvent {
+                    order: event.order,
+                    keys: event.keys,
+                    data: event.data,
+                    name,
+                    fields,
+                }
+            })
+            .collect();
+
+        let calls = inv.calls.into_iter().map(|call| Self::new(call, abi)).collect();
+
+        Self {
+            calls,
+            events,
+            arguments,
+            function_name,
+            return_values,
+            result: inv.result,
+            calldata: inv.calldata,
+            call_type: inv.call_type,
+            class_hash: inv.class_hash,
+            is_reverted: inv.is_reverted,
+            caller_address: inv.caller_address,
+            contract_address: inv.contract_address,
+            entry_point_type: inv.entry_point_type,
+            entry_point_selector: selector,
+        }
+    }
+}
+
 pub fn to_rpc_fee_estimate(resources: &receipt::ExecutionResources, fee: &FeeInfo) -> FeeEstimate {
     FeeEstimate {
         overall_fee: fee.overall_fee,
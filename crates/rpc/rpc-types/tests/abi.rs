@@ -0,0 +1,309 @@
+use katana_primitives::class::ContractAbi;
+use katana_primitives::utils::starknet_keccak;
+use katana_primitives::{felt, Felt};
+use katana_rpc_types::abi::{AbiDecodeError, AbiDecoder, DecodedValue};
+use serde_json::{json, Value};
+use similar_asserts::assert_eq;
+use starknet::macros::{selector, short_string};
+
+fn decoder() -> AbiDecoder {
+    let abi: ContractAbi = serde_json::from_value(json!([
+        {
+            "type": "impl",
+            "name": "TokenImpl",
+            "interface_name": "token::IToken"
+        },
+        {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+                { "name": "low", "type": "core::integer::u128" },
+                { "name": "high", "type": "core::integer::u128" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "token::Metadata",
+            "members": [
+                { "name": "name", "type": "core::byte_array::ByteArray" },
+                { "name": "decimals", "type": "core::integer::u8" },
+                { "name": "tags", "type": "core::array::Span::<core::felt252>" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "core::option::Option::<core::integer::i32>",
+            "variants": [
+                { "name": "Some", "type": "core::integer::i32" },
+                { "name": "None", "type": "()" }
+            ]
+        },
+        {
+            "type": "interface",
+            "name": "token::IToken",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "transfer",
+                    "inputs": [
+                        { "name": "recipient", "type": "core::starknet::contract_address::ContractAddress" },
+                        { "name": "amount", "type": "core::integer::u256" }
+                    ],
+                    "outputs": [{ "type": "core::bool" }],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "set_metadata",
+                    "inputs": [
+                        { "name": "metadata", "type": "token::Metadata" },
+                        { "name": "offset", "type": "core::option::Option::<core::integer::i32>" },
+                        { "name": "pair", "type": "(core::felt252, core::bool)" }
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "ping_all",
+                    "inputs": [
+                        { "name": "pings", "type": "core::array::Array::<()>" }
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "token::component::Transfer",
+            "kind": "struct",
+            "members": [
+                { "name": "from", "type": "core::starknet::contract_address::ContractAddress", "kind": "key" },
+                { "name": "to", "type": "core::starknet::contract_address::ContractAddress", "kind": "key" },
+                { "name": "value", "type": "core::integer::u256", "kind": "data" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "token::component::Event",
+            "kind": "enum",
+            "variants": [
+                { "name": "Transfer", "type": "token::component::Transfer", "kind": "nested" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "token::Upgraded",
+            "kind": "struct",
+            "members": [
+                { "name": "class_hash", "type": "core::starknet::class_hash::ClassHash", "kind": "data" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "token::Event",
+            "kind": "enum",
+            "variants": [
+                { "name": "ComponentEvent", "type": "token::component::Event", "kind": "flat" },
+                { "name": "Upgraded", "type": "token::Upgraded", "kind": "nested" }
+            ]
+        }
+    ]))
+    .unwrap();
+
+    AbiDecoder::new(&abi)
+}
+
+fn value(name: Option<&str>, ty: &str, value: Value) -> DecodedValue {
+    DecodedValue { name: name.map(ToString::to_string), ty: ty.to_string(), value }
+}
+
+#[test]
+fn decode_function_inputs_and_outputs() {
+    let decoder = decoder();
+
+    assert_eq!(decoder.function_name(selector!("transfer")), Some("transfer"));
+    assert_eq!(decoder.function_name(selector!("approve")), None);
+
+    let inputs = decoder
+        .decode_inputs(selector!("transfer"), &[felt!("0x123"), felt!("0x5"), Felt::ONE])
+        .unwrap();
+    assert_eq!(
+        inputs,
+        vec![
+            value(
+                Some("recipient"),
+                "core::starknet::contract_address::ContractAddress",
+                json!("0x123")
+            ),
+            value(
+                Some("amount"),
+                "core::integer::u256",
+                json!("340282366920938463463374607431768211461")
+            ),
+        ]
+    );
+
+    let outputs = decoder.decode_outputs(selector!("transfer"), &[Felt::ONE]).unwrap();
+    assert_eq!(outputs, vec![value(None, "core::bool", json!(true))]);
+}
+
+#[test]
+fn decode_composite_types() {
+    let decoder = decoder();
+
+    let calldata = [
+        // metadata.name: ByteArray "Token"
+        Felt::ZERO,
+        short_string!("Token"),
+        felt!("0x5"),
+        // metadata.decimals
+        felt!("0x12"),
+        // metadata.tags
+        felt!("0x2"),
+        felt!("0xa"),
+        felt!("0xb"),
+        // offset: Some(-3)
+        Felt::ZERO,
+        -felt!("0x3"),
+        // pair
+        felt!("0x7"),
+        Felt::ZERO,
+    ];
+
+    let inputs = decoder.decode_inputs(selector!("set_metadata"), &calldata).unwrap();
+    let values = inputs.into_iter().map(|v| v.value).collect::<Vec<_>>();
+
+    assert_eq!(
+        values,
+        vec![
+            json!({ "name": "Token", "decimals": "18", "tags": ["0xa", "0xb"] }),
+            json!({ "Some": "-3" }),
+            json!(["0x7", false]),
+        ]
+    );
+}
+
+#[test]
+fn decode_function_rejects_mismatching_calldata() {
+    let decoder = decoder();
+
+    // missing the high part of the u256
+    let result = decoder.decode_inputs(selector!("transfer"), &[felt!("0x123"), felt!("0x5")]);
+    assert!(result.is_err());
+
+    // trailing felt
+    let calldata = [felt!("0x123"), felt!("0x5"), Felt::ZERO, Felt::ONE];
+    let result = decoder.decode_inputs(selector!("transfer"), &calldata);
+    assert!(result.is_err());
+
+    // invalid bool
+    let result = decoder.decode_outputs(selector!("transfer"), &[felt!("0x2")]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn decode_rejects_unbounded_arrays() {
+    let decoder = decoder();
+
+    // An array length larger than the number of felts left.
+    let calldata = [Felt::ZERO, Felt::ZERO, Felt::ZERO, felt!("0x12"), felt!("0x10000000000")];
+    let result = decoder.decode_inputs(selector!("set_metadata"), &calldata);
+    assert!(result.is_err());
+
+    // An array of elements that don't consume any felt.
+    let result = decoder.decode_inputs(selector!("ping_all"), &[felt!("0x1")]);
+    assert!(result.is_err());
+
+    let inputs = decoder.decode_inputs(selector!("ping_all"), &[Felt::ZERO]).unwrap();
+    assert_eq!(inputs, vec![value(Some("pings"), "core::array::Array::<()>", json!([]))]);
+}
+
+#[test]
+fn decode_rejects_recursive_types() {
+    let abi: ContractAbi = serde_json::from_value(json!([
+        {
+            "type": "struct",
+            "name": "test::Node",
+            "members": [{ "name": "next", "type": "(core::felt252, test::Node)" }]
+        },
+        {
+            "type": "enum",
+            "name": "test::List",
+            "variants": [
+                { "name": "Cons", "type": "test::List" },
+                { "name": "Nil", "type": "()" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "visit",
+            "inputs": [{ "name": "node", "type": "test::Node" }],
+            "outputs": [{ "type": "test::List" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "event",
+            "name": "test::Loop",
+            "kind": "enum",
+            "variants": [{ "name": "Again", "type": "test::Loop", "kind": "flat" }]
+        },
+        {
+            "type": "event",
+            "name": "test::Event",
+            "kind": "enum",
+            "variants": [{ "name": "Loop", "type": "test::Loop", "kind": "flat" }]
+        }
+    ]))
+    .unwrap();
+
+    let decoder = AbiDecoder::new(&abi);
+
+    // A struct that contains itself never runs out of members to decode.
+    let result = decoder.decode_inputs(selector!("visit"), &[Felt::ONE; 1000]);
+    assert!(matches!(result, Err(AbiDecodeError::TooDeep(_))), "{result:?}");
+
+    // An enum variant of the enum itself consumes one felt per level.
+    let result = decoder.decode_outputs(selector!("visit"), &[Felt::ZERO; 1000]);
+    assert!(matches!(result, Err(AbiDecodeError::TooDeep(_))), "{result:?}");
+
+    // A flattened event variant of the event enum itself.
+    let result = decoder.decode_event(&[Felt::ONE], &[]);
+    assert!(matches!(result, Err(AbiDecodeError::TooDeep(_))), "{result:?}");
+}
+
+#[test]
+fn decode_events() {
+    let decoder = decoder();
+
+    // event from a flattened component
+    let keys = [starknet_keccak(b"Transfer"), felt!("0x1"), felt!("0x2")];
+    let data = [felt!("0x64"), Felt::ZERO];
+
+    let event = decoder.decode_event(&keys, &data).unwrap();
+    assert_eq!(event.name, "token::component::Transfer");
+    assert_eq!(
+        event.fields,
+        vec![
+            value(Some("from"), "core::starknet::contract_address::ContractAddress", json!("0x1")),
+            value(Some("to"), "core::starknet::contract_address::ContractAddress", json!("0x2")),
+            value(Some("value"), "core::integer::u256", json!("100")),
+        ]
+    );
+
+    // event declared by the contract itself
+    let keys = [starknet_keccak(b"Upgraded")];
+    let data = [felt!("0x1234")];
+
+    let event = decoder.decode_event(&keys, &data).unwrap();
+    assert_eq!(event.name, "token::Upgraded");
+    assert_eq!(
+        event.fields,
+        vec![value(Some("class_hash"), "core::starknet::class_hash::ClassHash", json!("0x1234"))]
+    );
+
+    // unknown event
+    let keys = [starknet_keccak(b"Approval")];
+    assert!(decoder.decode_event(&keys, &[]).is_err());
+}
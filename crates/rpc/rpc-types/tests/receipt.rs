@@ -395,3 +395,25 @@ fn l1_handler_preconfirmed_receipt() {
     let serialized = serde_json::to_value(&full_receipt).unwrap();
     assert_eq!(serialized, json);
 }
+
+#[test]
+fn reverted_execution_result() {
+    // Plain revert reasons have no structured form.
+    let result = ExecutionResult::reverted("Out of gas".to_string());
+    assert_matches!(&result, ExecutionResult::Reverted { revert_error: None, .. });
+
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert_eq!(
+        serialized,
+        serde_json::json!({ "execution_status": "REVERTED", "revert_reason": "Out of gas" })
+    );
+
+    let reason = "Error in contract (contract address: 0x1, class hash: 0x2, selector: 0x3):
+0x4f7574206f6620676173 ('Out of gas').";
+    let result = ExecutionResult::reverted(reason.to_string());
+    let ExecutionResult::Reverted { revert_error: Some(stack), .. } = result else {
+        panic!("expected a structured revert error");
+    };
+    assert_eq!(stack.frames.len(), 1);
+    assert_eq!(stack.panic_reason.as_deref(), Some("Out of gas"));
+}
@@ -344,8 +344,10 @@ fn l1_handler_trace() {
 
 #[test]
 fn reverted_invocation() {
-    let reverted =
-        RevertedInvocation { revert_reason: "Transaction execution has failed".to_string() };
+    let reverted = RevertedInvocation {
+        revert_reason: "Transaction execution has failed".to_string(),
+        revert_error: None,
+    };
 
     let invocation = ExecuteInvocation::Reverted(reverted.clone());
 
@@ -492,7 +494,8 @@ fn function_invocation_with_nested_calls() {
 #[test]
 fn execute_invocation_with_revert() {
     let revert_reason = "Custom revert: insufficient balance";
-    let reverted = RevertedInvocation { revert_reason: revert_reason.to_string() };
+    let reverted =
+        RevertedInvocation { revert_reason: revert_reason.to_string(), revert_error: None };
     let invocation = ExecuteInvocation::Reverted(reverted);
 
     // Test serialization
@@ -538,6 +541,7 @@ fn tx_trace_with_state_diff() {
         validate_invocation: None,
         execute_invocation: ExecuteInvocation::Reverted(RevertedInvocation {
             revert_reason: "Test revert".to_string(),
+            revert_error: None,
         }),
         fee_transfer_invocation: None,
         state_diff: Some(state_diff.clone()),
@@ -19,6 +19,15 @@ pub struct Configuration {
     pub block_time: Option<syn::Expr>,
     pub log_path: Option<syn::Expr>,
     pub chain_id: Option<syn::Expr>,
+    pub fork_url: Option<syn::Expr>,
+    pub fork_block: Option<syn::Expr>,
+    pub genesis: Option<syn::Expr>,
+    pub messaging: Option<syn::Expr>,
+    pub no_mining: Option<syn::Expr>,
+    pub grpc: Option<syn::Expr>,
+    pub paymaster: Option<syn::Expr>,
+    pub seed: Option<syn::Expr>,
+    pub in_process: Option<bool>,
 }
 
 impl Configuration {
@@ -34,6 +43,15 @@ impl Configuration {
             block_time: None,
             crate_name: None,
             chain_id: None,
+            fork_url: None,
+            fork_block: None,
+            genesis: None,
+            messaging: None,
+            no_mining: None,
+            grpc: None,
+            paymaster: None,
+            seed: None,
+            in_process: None,
         }
     }
 
@@ -120,6 +138,115 @@ impl Configuration {
         self.chain_id = Some(chain_id);
         Ok(())
     }
+
+    fn set_fork_url(
+        &mut self,
+        fork_url: syn::Expr,
+        span: proc_macro2::Span,
+    ) -> Result<(), syn::Error> {
+        if self.fork_url.is_some() {
+            return Err(syn::Error::new(span, "`fork_url` set multiple times."));
+        }
+
+        self.fork_url = Some(fork_url);
+        Ok(())
+    }
+
+    fn set_fork_block(
+        &mut self,
+        fork_block: syn::Expr,
+        span: proc_macro2::Span,
+    ) -> Result<(), syn::Error> {
+        if self.fork_block.is_some() {
+            return Err(syn::Error::new(span, "`fork_block` set multiple times."));
+        }
+
+        self.fork_block = Some(fork_block);
+        Ok(())
+    }
+
+    fn set_genesis(
+        &mut self,
+        genesis: syn::Expr,
+        span: proc_macro2::Span,
+    ) -> Result<(), syn::Error> {
+        if self.genesis.is_some() {
+            return Err(syn::Error::new(span, "`genesis` set multiple times."));
+        }
+
+        self.genesis = Some(genesis);
+        Ok(())
+    }
+
+    fn set_messaging(
+        &mut self,
+        messaging: syn::Expr,
+        span: proc_macro2::Span,
+    ) -> Result<(), syn::Error> {
+        if self.messaging.is_some() {
+            return Err(syn::Error::new(span, "`messaging` set multiple times."));
+        }
+
+        self.messaging = Some(messaging);
+        Ok(())
+    }
+
+    fn set_no_mining(
+        &mut self,
+        no_mining: syn::Expr,
+        span: proc_macro2::Span,
+    ) -> Result<(), syn::Error> {
+        if self.no_mining.is_some() {
+            return Err(syn::Error::new(span, "`no_mining` set multiple times."));
+        }
+
+        self.no_mining = Some(no_mining);
+        Ok(())
+    }
+
+    fn set_grpc(&mut self, grpc: syn::Expr, span: proc_macro2::Span) -> Result<(), syn::Error> {
+        if self.grpc.is_some() {
+            return Err(syn::Error::new(span, "`grpc` set multiple times."));
+        }
+
+        self.grpc = Some(grpc);
+        Ok(())
+    }
+
+    fn set_paymaster(
+        &mut self,
+        paymaster: syn::Expr,
+        span: proc_macro2::Span,
+    ) -> Result<(), syn::Error> {
+        if self.paymaster.is_some() {
+            return Err(syn::Error::new(span, "`paymaster` set multiple times."));
+        }
+
+        self.paymaster = Some(paymaster);
+        Ok(())
+    }
+
+    fn set_seed(&mut self, seed: syn::Expr, span: proc_macro2::Span) -> Result<(), syn::Error> {
+        if self.seed.is_some() {
+            return Err(syn::Error::new(span, "`seed` set multiple times."));
+        }
+
+        self.seed = Some(seed);
+        Ok(())
+    }
+
+    fn set_in_process(
+        &mut self,
+        in_process: syn::Lit,
+        span: proc_macro2::Span,
+    ) -> Result<(), syn::Error> {
+        if self.in_process.is_some() {
+            return Err(syn::Error::new(span, "`in_process` set multiple times."));
+        }
+
+        self.in_process = Some(parse_bool(in_process, span, "in_process")?);
+        Ok(())
+    }
 }
 
 enum RunnerArg {
@@ -129,6 +256,15 @@ enum RunnerArg {
     Accounts,
     DbDir,
     ChainId,
+    ForkUrl,
+    ForkBlock,
+    Genesis,
+    Messaging,
+    NoMining,
+    Grpc,
+    Paymaster,
+    Seed,
+    InProcess,
 }
 
 impl std::str::FromStr for RunnerArg {
@@ -142,9 +278,19 @@ impl std::str::FromStr for RunnerArg {
             "accounts" => Ok(RunnerArg::Accounts),
             "db_dir" => Ok(RunnerArg::DbDir),
             "chain_id" => Ok(RunnerArg::ChainId),
+            "fork_url" => Ok(RunnerArg::ForkUrl),
+            "fork_block" => Ok(RunnerArg::ForkBlock),
+            "genesis" => Ok(RunnerArg::Genesis),
+            "messaging" => Ok(RunnerArg::Messaging),
+            "no_mining" => Ok(RunnerArg::NoMining),
+            "grpc" => Ok(RunnerArg::Grpc),
+            "paymaster" => Ok(RunnerArg::Paymaster),
+            "seed" => Ok(RunnerArg::Seed),
+            "in_process" => Ok(RunnerArg::InProcess),
             _ => Err(format!(
                 "Unknown attribute {s} is specified; expected one of: `fee`, `validation`, \
-                 `accounts`, `db_dir`, `block_time`, `chain_id`",
+                 `accounts`, `db_dir`, `block_time`, `chain_id`, `fork_url`, `fork_block`, \
+                 `genesis`, `messaging`, `no_mining`, `grpc`, `paymaster`, `seed`, `in_process`",
             )),
         }
     }
@@ -193,6 +339,38 @@ pub fn build_config(
                         config.set_chain_id(expr.clone(), Spanned::span(&namevalue))?
                     }
                     RunnerArg::Fee => config.set_fee(expr.clone(), Spanned::span(&namevalue))?,
+                    RunnerArg::ForkUrl => {
+                        config.set_fork_url(expr.clone(), Spanned::span(&namevalue))?
+                    }
+                    RunnerArg::ForkBlock => {
+                        config.set_fork_block(expr.clone(), Spanned::span(&namevalue))?
+                    }
+                    RunnerArg::Genesis => {
+                        config.set_genesis(expr.clone(), Spanned::span(&namevalue))?
+                    }
+                    RunnerArg::Messaging => {
+                        config.set_messaging(expr.clone(), Spanned::span(&namevalue))?
+                    }
+                    RunnerArg::NoMining => {
+                        config.set_no_mining(expr.clone(), Spanned::span(&namevalue))?
+                    }
+                    RunnerArg::Grpc => config.set_grpc(expr.clone(), Spanned::span(&namevalue))?,
+                    RunnerArg::Paymaster => {
+                        config.set_paymaster(expr.clone(), Spanned::span(&namevalue))?
+                    }
+                    RunnerArg::Seed => config.set_seed(expr.clone(), Spanned::span(&namevalue))?,
+                    RunnerArg::InProcess => {
+                        let lit = match expr {
+                            syn::Expr::Lit(syn::ExprLit { lit, .. }) => lit.clone(),
+                            expr => {
+                                return Err(syn::Error::new_spanned(
+                                    expr,
+                                    "Failed to parse value of `in_process` as bool.",
+                                ));
+                            }
+                        };
+                        config.set_in_process(lit, Spanned::span(&namevalue))?
+                    }
                 }
             }
 
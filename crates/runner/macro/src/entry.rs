@@ -81,6 +81,38 @@ pub fn parse_knobs(input: ItemFn, is_test: bool, config: Configuration) -> Token
         cfg = quote_spanned! (last_stmt_start_span=> #cfg chain_id: Some(#value), );
     }
 
+    if let Some(value) = config.fork_url {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg fork_url: Some(core::str::FromStr::from_str(#value).expect("valid url")), );
+    }
+
+    if let Some(value) = config.fork_block {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg fork_block: Some(#value), );
+    }
+
+    if let Some(value) = config.genesis {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg genesis: Some(core::str::FromStr::from_str(#value).expect("valid path")), );
+    }
+
+    if let Some(value) = config.messaging {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg messaging: Some(core::convert::Into::into(#value)), );
+    }
+
+    if let Some(value) = config.no_mining {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg no_mining: #value, );
+    }
+
+    if let Some(value) = config.grpc {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg grpc: #value, );
+    }
+
+    if let Some(value) = config.paymaster {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg paymaster: #value, );
+    }
+
+    if let Some(value) = config.seed {
+        cfg = quote_spanned! (last_stmt_start_span=> #cfg seed: Some(#value), );
+    }
+
     if config.dev {
         cfg = quote_spanned! (last_stmt_start_span=> #cfg dev: true, );
     }
@@ -109,12 +141,24 @@ pub fn parse_knobs(input: ItemFn, is_test: bool, config: Configuration) -> Token
     inner.outer_attrs.clear();
     let inner_name = &inner.sig.ident;
 
-    let last_block = quote_spanned! {last_stmt_end_span=>
-        {
-            let runner = #crate_path::KatanaRunner::new_with_config(#cfg).expect("Failed to start runner.");
-            let ctx = #crate_path::RunnerCtx::new(runner);
-            #[allow(clippy::needless_return)]
-            return #inner_name(&ctx);
+    let last_block = if config.in_process.unwrap_or(false) {
+        // The runtime running the node must outlive the runner context, so it's declared first.
+        quote_spanned! {last_stmt_end_span=>
+            {
+                let (_runtime, runner) = #crate_path::KatanaRunner::new_in_process_blocking(#cfg).expect("Failed to start runner.");
+                let ctx = #crate_path::RunnerCtx::new(runner);
+                #[allow(clippy::needless_return)]
+                return #inner_name(&ctx);
+            }
+        }
+    } else {
+        quote_spanned! {last_stmt_end_span=>
+            {
+                let runner = #crate_path::KatanaRunner::new_with_config(#cfg).expect("Failed to start runner.");
+                let ctx = #crate_path::RunnerCtx::new(runner);
+                #[allow(clippy::needless_return)]
+                return #inner_name(&ctx);
+            }
         }
     };
     let inner = quote! { #inner };
@@ -0,0 +1,129 @@
+//! Runs the Katana node in the current process instead of spawning the `katana` binary.
+//!
+//! The node is configured by parsing the same CLI arguments that the process backend passes to
+//! the binary, so both backends produce the same node for a given [`KatanaRunnerConfig`].
+
+use std::net::SocketAddr;
+
+use anyhow::{bail, Result};
+use katana_cli::args::Parser;
+use katana_cli::{BuildInfo, SequencerNodeArgs};
+use katana_node_bindings::Account;
+use katana_provider::DbProviderFactory;
+use katana_sequencer_node::{LaunchedNode, Node};
+use starknet::core::types::Felt;
+use starknet::signers::SigningKey;
+
+use crate::KatanaRunnerConfig;
+
+/// A Katana node running on the current tokio runtime.
+///
+/// The node is stopped when this is dropped.
+#[derive(Debug)]
+pub(crate) struct InProcessInstance {
+    node: LaunchedNode<DbProviderFactory>,
+    accounts: Vec<Account>,
+    chain_id: Felt,
+}
+
+impl InProcessInstance {
+    pub(crate) async fn spawn(config: &KatanaRunnerConfig) -> Result<Self> {
+        if config.fork_url.is_some() {
+            bail!("forking is not supported by in-process runners");
+        }
+
+        let args = SequencerNodeArgs::try_parse_from(node_args(config))?;
+        let node_config = args.config(BuildInfo::default())?;
+        let node = Node::build(node_config)?.launch().await?;
+
+        let chain_spec = &node.node().backend().chain_spec;
+        let chain_id = chain_spec.id().into();
+        let accounts = chain_spec
+            .genesis()
+            .accounts()
+            .map(|(address, account)| Account {
+                address: (*address).into(),
+                private_key: account.private_key().map(SigningKey::from_secret_scalar),
+            })
+            .collect();
+
+        Ok(Self { node, accounts, chain_id })
+    }
+
+    pub(crate) fn rpc_addr(&self) -> SocketAddr {
+        *self.node.rpc().addr()
+    }
+
+    pub(crate) fn grpc_addr(&self) -> Option<SocketAddr> {
+        self.node.grpc().map(|handle| *handle.addr())
+    }
+
+    pub(crate) fn accounts(&self) -> &[Account] {
+        &self.accounts
+    }
+
+    pub(crate) fn chain_id(&self) -> Felt {
+        self.chain_id
+    }
+}
+
+impl Drop for InProcessInstance {
+    fn drop(&mut self) {
+        // The rest of the node's tasks are cancelled when its task manager is dropped.
+        let _ = self.node.rpc().stop();
+    }
+}
+
+/// Returns the `katana` CLI arguments for `config`.
+fn node_args(config: &KatanaRunnerConfig) -> Vec<String> {
+    let mut args = vec!["katana".to_string()];
+
+    args.extend(["--http.port".to_string(), config.port.unwrap_or(0).to_string()]);
+    args.extend(["--rpc.max-connections".to_string(), "10000".to_string()]);
+    args.push("--dev".to_string());
+
+    if config.disable_fee {
+        args.push("--dev.no-fee".to_string());
+    }
+
+    if let Some(id) = config.chain_id {
+        args.extend(["--chain-id".to_string(), format!("{id:#x}")]);
+    }
+
+    if let Some(block_time) = config.block_time {
+        args.extend(["--block-time".to_string(), block_time.to_string()]);
+    }
+
+    if config.no_mining {
+        args.push("--no-mining".to_string());
+    }
+
+    if let Some(messaging) = &config.messaging {
+        args.extend(["--messaging".to_string(), messaging.clone()]);
+    }
+
+    if let Some(path) = &config.db_dir {
+        args.extend(["--data-dir".to_string(), path.display().to_string()]);
+    }
+
+    // The genesis file defines its own accounts.
+    if let Some(path) = &config.genesis {
+        args.extend(["--genesis".to_string(), path.display().to_string()]);
+    } else {
+        args.extend(["--dev.accounts".to_string(), config.n_accounts.to_string()]);
+    }
+
+    if let Some(seed) = config.seed {
+        args.extend(["--dev.seed".to_string(), seed.to_string()]);
+    }
+
+    if config.grpc {
+        args.extend(["--grpc".to_string(), "--grpc.port".to_string(), "0".to_string()]);
+    }
+
+    if config.paymaster {
+        args.push("--paymaster".to_string());
+    }
+
+    args
+}
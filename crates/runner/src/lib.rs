@@ -1,6 +1,8 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 mod dev;
+#[cfg(feature = "in-process")]
+mod in_process;
 mod utils;
 use std::net::SocketAddr;
 use std::path::PathBuf;
@@ -40,18 +42,55 @@ impl core::ops::Deref for RunnerCtx {
     }
 }
 
+/// The Katana node a [`KatanaRunner`] is running.
+#[derive(Debug)]
+enum Instance {
+    /// A `katana` child process.
+    Process(KatanaInstance),
+    /// A node running on the current tokio runtime.
+    #[cfg(feature = "in-process")]
+    InProcess(in_process::InProcessInstance),
+}
+
+impl Instance {
+    fn rpc_addr(&self) -> SocketAddr {
+        match self {
+            Self::Process(instance) => instance.rpc_addr(),
+            #[cfg(feature = "in-process")]
+            Self::InProcess(instance) => instance.rpc_addr(),
+        }
+    }
+
+    fn accounts(&self) -> &[katana_node_bindings::Account] {
+        match self {
+            Self::Process(instance) => instance.accounts(),
+            #[cfg(feature = "in-process")]
+            Self::InProcess(instance) => instance.accounts(),
+        }
+    }
+
+    fn chain_id(&self) -> Felt {
+        match self {
+            Self::Process(instance) => instance.chain_id(),
+            #[cfg(feature = "in-process")]
+            Self::InProcess(instance) => instance.chain_id(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct KatanaRunner {
-    instance: KatanaInstance,
+    instance: Instance,
     provider: JsonRpcClient<HttpTransport>,
-    log_file_path: PathBuf,
+    grpc_addr: Option<SocketAddr>,
+    log_file_path: Option<PathBuf>,
     contract: Mutex<Option<Felt>>,
 }
 
 /// Configuration for the KatanaRunner.
 #[derive(Debug)]
 pub struct KatanaRunnerConfig {
-    /// The name of the katana program to run.
+    /// The name of the katana program to run. Ignored by in-process runners.
     pub program_name: Option<String>,
     /// The name used in the log file suffix, the port number is used otherwise.
     pub run_name: Option<String>,
@@ -63,7 +102,8 @@ pub struct KatanaRunnerConfig {
     pub block_time: Option<u64>,
     /// The port to run the katana runner on, if None, a random free port is chosen.
     pub port: Option<u16>,
-    /// The path where to log info, if None, logs are stored in a temp dir.
+    /// The path where to log info, if None, logs are stored in a temp dir. Ignored by in-process
+    /// runners, whose node logs through the tracing subscriber of the current process.
     pub log_path: Option<PathBuf>,
     /// The messaging config file
     pub messaging: Option<String>,
@@ -75,6 +115,18 @@ pub struct KatanaRunnerConfig {
     pub chain_id: Option<Felt>,
     /// Disable auto and interval mining, and mine on demand instead via an endpoint.
     pub no_mining: bool,
+    /// The RPC URL of the network to fork from.
+    pub fork_url: Option<Url>,
+    /// The block number to fork the network at. If None, the latest block is used.
+    pub fork_block: Option<u64>,
+    /// The path to the genesis configuration file.
+    pub genesis: Option<PathBuf>,
+    /// Whether to enable the gRPC server. The server listens on a random free port.
+    pub grpc: bool,
+    /// Whether to enable the paymaster service.
+    pub paymaster: bool,
+    /// The seed used to generate the predeployed accounts.
+    pub seed: Option<u64>,
 }
 
 impl Default for KatanaRunnerConfig {
@@ -92,6 +144,12 @@ impl Default for KatanaRunnerConfig {
             dev: false,
             chain_id: None,
             no_mining: false,
+            fork_url: None,
+            fork_block: None,
+            genesis: None,
+            grpc: false,
+            paymaster: false,
+            seed: None,
         }
     }
 }
@@ -114,6 +172,38 @@ impl KatanaRunner {
         Self::setup_and_start(config)
     }
 
+    /// Creates a new KatanaRunner that runs the node in the current process, with the given
+    /// configuration.
+    ///
+    /// Unlike [`KatanaRunner::new_with_config`], this doesn't require the `katana` binary. The node
+    /// is built directly on the current tokio runtime and is stopped when the runner is dropped.
+    #[cfg(feature = "in-process")]
+    pub async fn new_in_process(config: KatanaRunnerConfig) -> Result<Self> {
+        let instance = in_process::InProcessInstance::spawn(&config).await?;
+
+        // The node logs through the tracing subscriber of the current process.
+        let log_file_path = None;
+        let grpc_addr = instance.grpc_addr();
+        let instance = Instance::InProcess(instance);
+        let provider = starknet_provider(instance.rpc_addr());
+        let contract = Mutex::new(Option::None);
+
+        Ok(KatanaRunner { instance, provider, grpc_addr, log_file_path, contract })
+    }
+
+    /// Like [`KatanaRunner::new_in_process`], but for callers outside of a tokio runtime.
+    ///
+    /// The node runs on the returned runtime, which must outlive the runner. Used by the
+    /// [`test`] macro when `in_process = true` is set.
+    #[cfg(feature = "in-process")]
+    pub fn new_in_process_blocking(
+        config: KatanaRunnerConfig,
+    ) -> Result<(tokio::runtime::Runtime, Self)> {
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+        let runner = runtime.block_on(Self::new_in_process(config))?;
+        Ok((runtime, runner))
+    }
+
     /// Starts a new KatanaRunner with the given configuration.
     ///
     /// # Arguments
@@ -127,7 +217,6 @@ impl KatanaRunner {
         let mut builder = Katana::new()
             .path(program)
             .port(port)
-            .accounts(n_accounts)
             .json_log(true)
             .rpc_max_connections(10000)
             .dev(config.dev)
@@ -153,6 +242,32 @@ impl KatanaRunner {
             builder = builder.data_dir(path);
         }
 
+        if let Some(url) = config.fork_url {
+            builder = builder.fork_url(url);
+        }
+
+        if let Some(block) = config.fork_block {
+            builder = builder.fork_block(block);
+        }
+
+        // The genesis file defines its own accounts.
+        if let Some(path) = config.genesis {
+            builder = builder.genesis(path);
+        } else {
+            builder = builder.accounts(n_accounts);
+        }
+
+        if let Some(seed) = config.seed {
+            builder = builder.seed(seed);
+        }
+
+        let grpc_port = config.grpc.then(find_free_port);
+        if let Some(port) = grpc_port {
+            builder = builder.grpc_port(port);
+        }
+
+        builder = builder.paymaster(config.paymaster);
+
         builder = builder.no_mining(config.no_mining);
 
         builder = builder.dev(config.dev);
@@ -181,25 +296,47 @@ impl KatanaRunner {
         });
 
         let provider = instance.starknet_provider();
+        let grpc_addr = grpc_port.map(|port| SocketAddr::new(instance.rpc_addr().ip(), port));
+        let instance = Instance::Process(instance);
         let contract = Mutex::new(Option::None);
 
-        Ok(KatanaRunner { instance, provider, log_file_path, contract })
+        let log_file_path = Some(log_file_path);
+        Ok(KatanaRunner { instance, provider, grpc_addr, log_file_path, contract })
     }
 
+    /// Returns the path of the file the node logs are written to.
+    ///
+    /// # Panics
+    ///
+    /// Panics for in-process runners, which have no log file. Use
+    /// [`try_log_file_path`](Self::try_log_file_path) when the runner may be in-process.
     pub fn log_file_path(&self) -> &PathBuf {
-        &self.log_file_path
+        self.try_log_file_path().expect("in-process runners have no log file")
+    }
+
+    /// Returns the path of the file the node logs are written to.
+    ///
+    /// Returns `None` for in-process runners, whose node logs through the tracing subscriber of
+    /// the current process.
+    pub fn try_log_file_path(&self) -> Option<&PathBuf> {
+        self.log_file_path.as_ref()
     }
 
     pub fn url(&self) -> Url {
-        Url::parse(&format!("http://localhost:{}", self.instance.port())).unwrap()
+        Url::parse(&format!("http://localhost:{}", self.instance.rpc_addr().port())).unwrap()
     }
 
     pub fn provider(&self) -> &JsonRpcClient<HttpTransport> {
         &self.provider
     }
 
+    /// Returns the address of the gRPC server, if enabled.
+    pub fn grpc_addr(&self) -> Option<SocketAddr> {
+        self.grpc_addr
+    }
+
     pub fn starknet_provider(&self) -> JsonRpcClient<HttpTransport> {
-        self.instance.starknet_provider()
+        starknet_provider(self.instance.rpc_addr())
     }
 
     pub fn dev_client(&self) -> KatanaDevClient {
@@ -264,6 +401,11 @@ impl KatanaRunner {
     }
 }
 
+fn starknet_provider(addr: SocketAddr) -> JsonRpcClient<HttpTransport> {
+    let url = Url::parse(&format!("http://{addr}")).expect("valid url");
+    JsonRpcClient::new(HttpTransport::new(url))
+}
+
 /// Determines the default program path for the katana runner based on the KATANA_RUNNER_BIN
 /// environment variable. If not set, try to use katana from the PATH.
 fn determine_default_program_path() -> String {
@@ -1,10 +1,11 @@
-use katana_runner::RunnerCtx;
+use katana_runner::{KatanaRunner, KatanaRunnerConfig, RunnerCtx};
 use starknet::macros::short_string;
 use starknet::providers::Provider;
 
 #[katana_runner::test(fee = false, accounts = 7)]
 fn simple(runner: &RunnerCtx) {
     assert_eq!(runner.accounts().len(), 7);
+    assert_eq!(runner.try_log_file_path(), Some(runner.log_file_path()));
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -15,6 +16,16 @@ async fn custom_chain_id(runner: &RunnerCtx) {
     assert_eq!(id, short_string!("SN_SEPOLIA"));
 }
 
+#[katana_runner::test(seed = 42)]
+fn custom_seed(runner: &RunnerCtx) {
+    let config = KatanaRunnerConfig { seed: Some(42), ..Default::default() };
+    let same_seed = KatanaRunner::new_with_config(config).unwrap();
+    assert_eq!(runner.account_data(0).address, same_seed.account_data(0).address);
+
+    let default_seed = KatanaRunner::new().unwrap();
+    assert_ne!(runner.account_data(0).address, default_seed.account_data(0).address);
+}
+
 #[katana_runner::test]
 fn with_return(_: &RunnerCtx) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
@@ -28,3 +39,30 @@ async fn with_async(ctx: &RunnerCtx) -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(id, short_string!("KATANA"));
     Ok(())
 }
+
+#[cfg(feature = "in-process")]
+#[tokio::test(flavor = "multi_thread")]
+async fn in_process() {
+    let config = KatanaRunnerConfig {
+        n_accounts: 3,
+        chain_id: Some(short_string!("SN_SEPOLIA")),
+        ..Default::default()
+    };
+
+    let runner = KatanaRunner::new_in_process(config).await.unwrap();
+    assert_eq!(runner.accounts().len(), 3);
+
+    let id = runner.provider().chain_id().await.unwrap();
+    assert_eq!(id, short_string!("SN_SEPOLIA"));
+}
+
+#[cfg(feature = "in-process")]
+#[tokio::test(flavor = "multi_thread")]
+#[katana_runner::test(in_process = true, accounts = 3)]
+async fn in_process_macro(runner: &RunnerCtx) {
+    assert_eq!(runner.accounts().len(), 3);
+    assert!(runner.try_log_file_path().is_none());
+
+    let id = runner.provider().chain_id().await.unwrap();
+    assert_eq!(id, short_string!("KATANA"));
+}
@@ -1,5 +1,5 @@
 use jsonrpsee::core::client;
-use jsonrpsee::http_client::HttpClient;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use katana_primitives::block::{BlockIdOrTag, ConfirmedBlockIdOrTag};
 use katana_primitives::class::ClassHash;
 use katana_primitives::contract::{Nonce, StorageKey};
@@ -41,12 +41,14 @@ pub struct StarknetRpcClient {
 
 impl StarknetRpcClient {
     pub fn new(url: Url) -> Self {
-        // Some blocks can have very large responses (e.g., getBlockWithReceipts for mainnet blocks
-        // 1256131 (~19MB) and 1256132 (~16MB) exceed the default 10MB limit).
-        const MAX_RESPONSE_SIZE: u32 = 50 * 1024 * 1024;
+        Self::http_client_builder().build(url).map(Self::new_with_client).unwrap()
+    }
 
-        HttpClient::builder()
-            .max_response_size(MAX_RESPONSE_SIZE)
+    /// Creates a new client connecting to `url` using the given TLS configuration, eg to trust a
+    /// private CA or to present a client certificate to servers requiring mutual TLS.
+    pub fn new_with_tls(url: Url, config: rustls::ClientConfig) -> Self {
+        Self::http_client_builder()
+            .with_custom_cert_store(config)
             .build(url)
             .map(Self::new_with_client)
             .unwrap()
@@ -56,6 +58,14 @@ impl StarknetRpcClient {
         StarknetRpcClient { client }
     }
 
+    fn http_client_builder() -> HttpClientBuilder {
+        // Some blocks can have very large responses (e.g., getBlockWithReceipts for mainnet blocks
+        // 1256131 (~19MB) and 1256132 (~16MB) exceed the default 10MB limit).
+        const MAX_RESPONSE_SIZE: u32 = 50 * 1024 * 1024;
+
+        HttpClient::builder().max_response_size(MAX_RESPONSE_SIZE)
+    }
+
     ////////////////////////////////////////////////////////////////////////////
     // Read API methods
     ////////////////////////////////////////////////////////////////////////////
@@ -371,14 +381,21 @@ pub enum StarknetRpcClientError {
     Client(client::Error),
 }
 
+/// JSON-RPC error codes that providers use to reject requests because of rate limiting.
+///
+/// `-32005` is the "limit exceeded" code of EIP-1474, which is also what Katana's own rate limiter
+/// returns. Some providers return the HTTP status code instead.
+const RATE_LIMITED_CODES: [i32; 2] = [-32005, 429];
+
 impl StarknetRpcClientError {
     /// Returns `true` if the error is transient and the request may succeed on retry.
     ///
-    /// Transport errors (network issues) and request timeouts are considered retryable.
-    /// Parse errors, API errors, and other client errors are not.
+    /// Transport errors (network issues), request timeouts and rate limiting errors are considered
+    /// retryable. Parse errors, API errors, and other client errors are not.
     pub fn is_retryable(&self) -> bool {
         match self {
             Self::Starknet(_) => false,
+            Self::Client(client::Error::Call(err)) => RATE_LIMITED_CODES.contains(&err.code()),
             Self::Client(inner) => {
                 matches!(inner, client::Error::Transport(_) | client::Error::RequestTimeout)
             }
@@ -1,7 +1,7 @@
 #[cfg(feature = "postcard")]
 pub mod postcard;
 
-use katana_primitives::block::FinalityStatus;
+use katana_primitives::block::{BlockSignature, FinalityStatus};
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::Felt;
 
@@ -100,3 +100,26 @@ impl Decompress for FinalityStatus {
         }
     }
 }
+
+impl Compress for BlockSignature {
+    type Compressed = [u8; 64];
+    fn compress(self) -> Result<Self::Compressed, CodecError> {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(&self.r.to_bytes_be());
+        buf[32..].copy_from_slice(&self.s.to_bytes_be());
+        Ok(buf)
+    }
+}
+
+impl Decompress for BlockSignature {
+    fn decompress<B: AsRef<[u8]>>(bytes: B) -> Result<Self, CodecError> {
+        let bytes = bytes.as_ref();
+        if bytes.len() != 64 {
+            return Err(CodecError::Decode("Invalid block signature length".into()));
+        }
+
+        let r = Felt::from_bytes_be_slice(&bytes[..32]);
+        let s = Felt::from_bytes_be_slice(&bytes[32..]);
+        Ok(BlockSignature { r, s })
+    }
+}
@@ -1,4 +1,4 @@
-use katana_primitives::block::{BlockHash, BlockNumber, FinalityStatus};
+use katana_primitives::block::{BlockHash, BlockNumber, BlockSignature, FinalityStatus};
 use katana_primitives::class::{ClassHash, CompiledClassHash};
 use katana_primitives::contract::{ContractAddress, GenericContractInfo, StorageKey};
 use katana_primitives::execution::TypedTransactionExecutionInfo;
@@ -58,7 +58,7 @@ pub enum TableType {
     DupSort,
 }
 
-pub const NUM_TABLES: usize = 37;
+pub const NUM_TABLES: usize = 38;
 
 /// Macro to declare `libmdbx` tables.
 #[macro_export]
@@ -195,7 +195,8 @@ define_tables_enum! {[
     (ClassesTrieChangeSet, TableType::Table),
     (ContractsTrieChangeSet, TableType::Table),
     (StoragesTrieChangeSet, TableType::Table),
-    (MigrationCheckpoints, TableType::Table)
+    (MigrationCheckpoints, TableType::Table),
+    (BlockSignatures, TableType::Table)
 ]}
 
 tables! {
@@ -284,7 +285,10 @@ tables! {
     StoragesTrieChangeSet: (TrieDatabaseKey) => BlockChangeList,
 
     /// Migration task checkpoints for crash-recoverable migrations.
-    MigrationCheckpoints: (MigrationStageId) => MigrationCheckpoint
+    MigrationCheckpoints: (MigrationStageId) => MigrationCheckpoint,
+
+    /// Stores the sequencer signature of a block according to its block number
+    BlockSignatures: (BlockNumber) => BlockSignature
 }
 
 impl Trie for ClassesTrie {
@@ -347,6 +351,7 @@ mod tests {
         assert_eq!(Tables::ALL[34].name(), ContractsTrieChangeSet::NAME);
         assert_eq!(Tables::ALL[35].name(), StoragesTrieChangeSet::NAME);
         assert_eq!(Tables::ALL[36].name(), MigrationCheckpoints::NAME);
+        assert_eq!(Tables::ALL[37].name(), BlockSignatures::NAME);
 
         assert_eq!(Tables::Headers.table_type(), TableType::Table);
         assert_eq!(Tables::BlockStateUpdates.table_type(), TableType::Table);
@@ -385,9 +390,10 @@ mod tests {
         assert_eq!(Tables::ContractsTrieChangeSet.table_type(), TableType::Table);
         assert_eq!(Tables::StoragesTrieChangeSet.table_type(), TableType::Table);
         assert_eq!(Tables::MigrationCheckpoints.table_type(), TableType::Table);
+        assert_eq!(Tables::BlockSignatures.table_type(), TableType::Table);
     }
 
-    use katana_primitives::block::{BlockHash, BlockNumber, FinalityStatus};
+    use katana_primitives::block::{BlockHash, BlockNumber, BlockSignature, FinalityStatus};
     use katana_primitives::class::{ClassHash, CompiledClass, CompiledClassHash};
     use katana_primitives::contract::{ContractAddress, GenericContractInfo};
     use katana_primitives::execution::TypedTransactionExecutionInfo;
@@ -461,6 +467,7 @@ mod tests {
             (BlockHash, BlockHash::default()),
             (BlockNumber, BlockNumber::default()),
             (FinalityStatus, FinalityStatus::AcceptedOnL1),
+            (BlockSignature, BlockSignature { r: felt!("0x1"), s: felt!("0x2") }),
             (StoredBlockBodyIndices, StoredBlockBodyIndices::default()),
             (TxNumber, 77),
             (TxHash, felt!("0x123456789")),
@@ -0,0 +1,203 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use backon::{ExponentialBuilder, Retryable};
+use katana_starknet::rpc::{
+    StarknetRpcClient as StarknetClient, StarknetRpcClientError as StarknetClientError,
+};
+use tracing::warn;
+
+use crate::rate_limit::RateLimiter;
+use crate::{BackendConfig, BackendMetrics};
+
+/// The client used by the backend worker to send requests to the forked network.
+///
+/// Requests that fail with a [retryable](StarknetClientError::is_retryable) error are retried with
+/// a jittered exponential backoff. Once the retries are exhausted, the request is sent to the next
+/// endpoint, in the order they are configured.
+///
+/// Failover is sticky: requests are sent to the last endpoint that responded first, so that a
+/// down endpoint doesn't delay every request by a full backoff. The other endpoints, including the
+/// primary one, are only tried again once it fails.
+pub(crate) struct ForkClient {
+    /// The primary endpoint followed by the fallback endpoints.
+    clients: Vec<Arc<StarknetClient>>,
+    /// The index of the last endpoint that responded.
+    active: AtomicUsize,
+    backoff: ExponentialBuilder,
+    rate_limiter: Option<RateLimiter>,
+    metrics: BackendMetrics,
+}
+
+impl std::fmt::Debug for ForkClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForkClient")
+            .field("clients", &self.clients)
+            .field("active", &self.active)
+            .field("backoff", &self.backoff)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ForkClient {
+    pub(crate) fn new(
+        clients: Vec<StarknetClient>,
+        config: &BackendConfig,
+        metrics: BackendMetrics,
+    ) -> Self {
+        debug_assert!(!clients.is_empty(), "at least one client is required");
+
+        let backoff = ExponentialBuilder::default()
+            .with_min_delay(config.min_retry_delay)
+            .with_max_delay(config.max_retry_delay)
+            .with_max_times(config.max_retries)
+            .with_jitter();
+
+        let rate_limiter = config.max_requests_per_second.map(RateLimiter::new);
+        let clients = clients.into_iter().map(Arc::new).collect();
+
+        Self { clients, active: AtomicUsize::new(0), backoff, rate_limiter, metrics }
+    }
+
+    /// Sends a request using `f`, which may be called multiple times with different clients.
+    pub(crate) async fn call<T, F, Fut>(&self, f: F) -> Result<T, StarknetClientError>
+    where
+        F: Fn(Arc<StarknetClient>) -> Fut,
+        Fut: Future<Output = Result<T, StarknetClientError>>,
+    {
+        // Starting from the active endpoint, then the ones after it, wrapping around.
+        let active = self.active.load(Ordering::Relaxed);
+        let count = self.clients.len();
+        let mut endpoints = (0..count).map(|i| (active + i) % count).peekable();
+
+        loop {
+            let index = endpoints.next().expect("at least one client");
+            let client = &self.clients[index];
+
+            let result = (|| async {
+                if let Some(limiter) = &self.rate_limiter {
+                    limiter.acquire().await;
+                }
+
+                f(client.clone()).await
+            })
+            .retry(self.backoff)
+            .when(|error| error.is_retryable())
+            .notify(|error, delay| {
+                self.metrics.retried_requests.increment(1);
+                warn!(target: "forking", %error, ?delay, "Retrying request to forked network.");
+            })
+            .await;
+
+            match result {
+                Err(error) if error.is_retryable() && endpoints.peek().is_some() => {
+                    self.metrics.failed_over_requests.increment(1);
+                    warn!(target: "forking", %error, "Request to forked network failed, trying the next endpoint.");
+                }
+
+                Err(error) if error.is_retryable() => return Err(error),
+
+                result => {
+                    self.active.store(index, Ordering::Relaxed);
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use jsonrpsee::core::client::Error as ClientError;
+    use jsonrpsee::types::ErrorObjectOwned;
+    use katana_primitives::{felt, Felt};
+    use katana_starknet::rpc::{
+        StarknetRpcClient as StarknetClient, StarknetRpcClientError as StarknetClientError,
+    };
+
+    use super::ForkClient;
+    use crate::test_utils::{start_mock_rpc_server_on_free_port, unused_url};
+    use crate::{BackendConfig, BackendMetrics};
+
+    #[tokio::test]
+    async fn failover_is_sticky() {
+        let (sender, fallback_url) = start_mock_rpc_server_on_free_port("0x789".to_string());
+
+        // Nothing is listening on the primary endpoint, so every request to it fails.
+        let primary = StarknetClient::new(unused_url());
+        let fallback = StarknetClient::new(fallback_url);
+        let config = BackendConfig {
+            max_retries: 2,
+            min_retry_delay: Duration::from_millis(1),
+            max_retry_delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+
+        let client = ForkClient::new(vec![primary, fallback], &config, BackendMetrics::default());
+        let primary_requests = AtomicUsize::new(0);
+
+        sender.send(()).unwrap();
+        sender.send(()).unwrap();
+
+        // The first request exhausts its retries on the primary endpoint before failing over.
+        assert_eq!(chain_id(&client, &primary_requests).await, felt!("0x789"));
+        assert_eq!(primary_requests.load(Ordering::Relaxed), 3);
+
+        // The second request goes straight to the fallback endpoint.
+        assert_eq!(chain_id(&client, &primary_requests).await, felt!("0x789"));
+        assert_eq!(primary_requests.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_requests_are_retried() {
+        let config = BackendConfig {
+            max_retries: 2,
+            min_retry_delay: Duration::from_millis(1),
+            max_retry_delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+
+        let client = StarknetClient::new(unused_url());
+        let client = ForkClient::new(vec![client], &config, BackendMetrics::default());
+        let attempts = AtomicUsize::new(0);
+
+        // The first attempt is rate limited, the second one succeeds.
+        let result = client
+            .call(|_| {
+                let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    if attempt == 0 {
+                        let err = ErrorObjectOwned::owned(-32005, "rate limited", None::<()>);
+                        Err(StarknetClientError::from(ClientError::Call(err)))
+                    } else {
+                        Ok(felt!("0x1"))
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), felt!("0x1"));
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+    }
+
+    /// Fetches the chain id, counting the requests sent to the primary endpoint.
+    async fn chain_id(client: &ForkClient, primary_requests: &AtomicUsize) -> Felt {
+        let primary = &client.clients[0];
+        let result = client
+            .call(|client| {
+                if Arc::ptr_eq(&client, primary) {
+                    primary_requests.fetch_add(1, Ordering::Relaxed);
+                }
+                async move { client.chain_id().await }
+            })
+            .await;
+
+        result.unwrap()
+    }
+}
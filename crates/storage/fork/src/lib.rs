@@ -2,6 +2,7 @@ use std::collections::hash_map::Entry;
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
 use std::io;
+use std::num::NonZeroU32;
 use std::pin::Pin;
 #[cfg(test)]
 use std::sync::atomic::Ordering;
@@ -10,13 +11,14 @@ use std::sync::mpsc::{
 };
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use anyhow::anyhow;
 use futures::channel::mpsc::{channel as async_channel, Receiver, SendError, Sender};
 use futures::future::BoxFuture;
 use futures::stream::Stream;
 use futures::{Future, FutureExt};
-use katana_metrics::metrics::Gauge;
+use katana_metrics::metrics::{Counter, Gauge};
 use katana_metrics::{metrics, Metrics};
 use katana_primitives::block::{BlockHashOrNumber, BlockIdOrTag, BlockNumber};
 use katana_primitives::class::{
@@ -37,12 +39,81 @@ use katana_starknet::rpc::{
 };
 use parking_lot::Mutex;
 use tracing::{error, trace};
+use url::Url;
+
+use crate::client::ForkClient;
+
+mod client;
+mod rate_limit;
 
 /// Default maximum number of concurrent requests that can be processed.
 /// Heavy game transactions (e.g. Eternum create_building) can trigger 30+ storage
 /// reads each, so 50 is too low for forked chains under gameplay load.
 const DEFAULT_WORKER_MAX_CONCURRENT_REQUESTS: usize = 200;
 
+/// Default maximum number of times a failed request is retried on the same endpoint.
+const DEFAULT_MAX_RETRIES: usize = 5;
+
+/// Default delay before the first retry of a failed request.
+const DEFAULT_MIN_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Default maximum delay between retries of a failed request.
+const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Configurations for how the [`Backend`] sends requests to the forked network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendConfig {
+    /// Maximum number of requests that are processed concurrently.
+    pub max_concurrent_requests: usize,
+    /// Maximum number of times a request that failed with a transient error (eg network issues,
+    /// rate limited by the provider) is retried on the same endpoint.
+    pub max_retries: usize,
+    /// The delay before the first retry. The delay grows exponentially with each retry, with some
+    /// jitter added.
+    pub min_retry_delay: Duration,
+    /// The maximum delay between retries.
+    pub max_retry_delay: Duration,
+    /// Maximum number of requests sent to the forked network per second. Unlimited if `None`.
+    pub max_requests_per_second: Option<NonZeroU32>,
+    /// RPC URLs of the forked network to fall back to, in order, when requests to the primary
+    /// endpoint keep failing.
+    pub fallback_urls: Vec<Url>,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: DEFAULT_WORKER_MAX_CONCURRENT_REQUESTS,
+            max_retries: DEFAULT_MAX_RETRIES,
+            min_retry_delay: DEFAULT_MIN_RETRY_DELAY,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            max_requests_per_second: None,
+            fallback_urls: Vec::new(),
+        }
+    }
+}
+
+impl BackendConfig {
+    /// Checks that every fallback URL is an endpoint of the network with the given `chain_id`, so
+    /// that a misconfigured fallback can't silently serve the state of another network.
+    pub async fn check_fallback_urls(&self, chain_id: Felt) -> Result<(), BackendError> {
+        for url in &self.fallback_urls {
+            let client = StarknetClient::new(url.clone());
+            let actual = client.chain_id().await.map_err(Arc::new)?;
+
+            if actual != chain_id {
+                return Err(BackendError::FallbackChainIdMismatch {
+                    url: url.clone(),
+                    expected: chain_id,
+                    actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
 type BackendResult<T> = Result<T, BackendError>;
 
 pub struct Backend {
@@ -90,27 +161,32 @@ impl Clone for Backend {
 
 impl Backend {
     pub fn new(provider: StarknetClient) -> Result<Self, BackendError> {
-        Self::new_with_config(provider, DEFAULT_WORKER_MAX_CONCURRENT_REQUESTS)
+        Self::new_with_config(provider, BackendConfig::default())
     }
 
     pub fn new_with_config(
         provider: StarknetClient,
-        max_concurrent_requests: usize,
+        config: BackendConfig,
     ) -> Result<Self, BackendError> {
         let (request_tx, request_rx) = async_channel(100);
         let metrics = BackendMetrics::default();
 
+        let clients = std::iter::once(provider)
+            .chain(config.fallback_urls.iter().cloned().map(StarknetClient::new))
+            .collect();
+        let client = ForkClient::new(clients, &config, metrics.clone());
+
         #[cfg(test)]
         let stats = BackendStats::default();
 
         let worker = BackendWorker {
             incoming: request_rx,
             metrics: metrics.clone(),
-            starknet_client: Arc::new(provider),
+            client: Arc::new(client),
             pending_requests: Vec::new(),
             request_dedup_map: HashMap::new(),
             queued_requests: VecDeque::new(),
-            max_concurrent_requests,
+            max_concurrent_requests: config.max_concurrent_requests,
             #[cfg(test)]
             stats: stats.clone(),
         };
@@ -417,6 +493,8 @@ pub enum BackendError {
     StarknetProvider(#[from] Arc<katana_starknet::rpc::StarknetRpcClientError>),
     #[error("unexpected received result: {0}")]
     UnexpectedReceiveResult(Arc<anyhow::Error>),
+    #[error("fallback url {url} is on chain {actual:#x}, expected chain {expected:#x}")]
+    FallbackChainIdMismatch { url: Url, expected: Felt, actual: Felt },
 }
 
 struct Request<P> {
@@ -578,6 +656,10 @@ pub struct BackendMetrics {
     pub pending_requests: Gauge,
     /// Number of requests queued and waiting to be processed
     pub queued_requests: Gauge,
+    /// Number of times a request was retried after a transient error
+    pub retried_requests: Counter,
+    /// Number of times a request was sent to the next endpoint after exhausting its retries
+    pub failed_over_requests: Counter,
 }
 
 /////////////////////////////////////////////////////////////////
@@ -589,8 +671,8 @@ pub struct BackendMetrics {
 /// It is responsible for processing [requests](BackendRequest) to fetch data from the remote
 /// provider.
 struct BackendWorker {
-    /// The Starknet RPC provider that will be used to fetch data from.
-    starknet_client: Arc<StarknetClient>,
+    /// The client that will be used to fetch data from the forked network.
+    client: Arc<ForkClient>,
     // HashMap that keep track of current requests, for dedup purposes.
     request_dedup_map: HashMap<BackendRequestIdentifier, Vec<OneshotSender<BackendResponse>>>,
     /// Requests that are currently being poll.
@@ -615,7 +697,7 @@ impl BackendWorker {
     /// This method is responsible for transforming the incoming request
     /// sent from a [Backend] into a RPC request to the remote network.
     fn handle_requests(&mut self, request: BackendRequest) {
-        let provider = self.starknet_client.clone();
+        let client = self.client.clone();
 
         // Check if there are similar requests in the queue before sending the request
         match request {
@@ -626,8 +708,8 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_transaction_receipt(tx_hash)
+                        let res = client
+                            .call(move |c| async move { c.get_transaction_receipt(tx_hash).await })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -644,8 +726,8 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_block_with_receipts(block_id)
+                        let res = client
+                            .call(move |c| async move { c.get_block_with_receipts(block_id).await })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -662,8 +744,8 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_state_update(block_id)
+                        let res = client
+                            .call(move |c| async move { c.get_state_update(block_id).await })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -680,8 +762,8 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_nonce(block_id, address)
+                        let res = client
+                            .call(move |c| async move { c.get_nonce(block_id, address).await })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -698,8 +780,10 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_storage_at(addr, key, block_id)
+                        let res = client
+                            .call(
+                                move |c| async move { c.get_storage_at(addr, key, block_id).await },
+                            )
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -716,10 +800,13 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_class_hash_at(block_id, address)
-                            .await
-                            .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
+                        let res =
+                            client
+                                .call(move |c| async move {
+                                    c.get_class_hash_at(block_id, address).await
+                                })
+                                .await
+                                .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
                         BackendResponse::ClassHashAt(res)
                     }),
@@ -734,8 +821,8 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_class(block_id, hash)
+                        let res = client
+                            .call(move |c| async move { c.get_class(block_id, hash).await })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -750,8 +837,13 @@ impl BackendWorker {
 
                 let request: BoxFuture<'static, BackendResponse> = match r#type {
                     ProofsType::Classes(classes) => Box::pin(async move {
-                        let res = provider
-                            .get_storage_proof(block_id, Some(classes), None, None)
+                        let res = client
+                            .call(move |c| {
+                                let classes = classes.clone();
+                                async move {
+                                    c.get_storage_proof(block_id, Some(classes), None, None).await
+                                }
+                            })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -759,8 +851,13 @@ impl BackendWorker {
                     }),
 
                     ProofsType::Contracts(contracts) => Box::pin(async move {
-                        let res = provider
-                            .get_storage_proof(block_id, None, Some(contracts), None)
+                        let res = client
+                            .call(move |c| {
+                                let contracts = contracts.clone();
+                                async move {
+                                    c.get_storage_proof(block_id, None, Some(contracts), None).await
+                                }
+                            })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -768,8 +865,14 @@ impl BackendWorker {
                     }),
 
                     ProofsType::Storages(storage_keys) => Box::pin(async move {
-                        let res = provider
-                            .get_storage_proof(block_id, None, None, Some(storage_keys))
+                        let res = client
+                            .call(move |c| {
+                                let storage_keys = storage_keys.clone();
+                                async move {
+                                    c.get_storage_proof(block_id, None, None, Some(storage_keys))
+                                        .await
+                                }
+                            })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -788,8 +891,10 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_storage_proof(block_id, None, None, None)
+                        let res = client
+                            .call(move |c| async move {
+                                c.get_storage_proof(block_id, None, None, None).await
+                            })
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
 
@@ -806,8 +911,11 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let res = provider
-                            .get_storage_proof(block_id, None, Some(vec![contract]), None)
+                        let res = client
+                            .call(move |c| async move {
+                                c.get_storage_proof(block_id, None, Some(vec![contract]), None)
+                                    .await
+                            })
                             .await
                             .map(|mut proof| {
                                 proof
@@ -835,8 +943,10 @@ impl BackendWorker {
                     req_key,
                     sender,
                     Box::pin(async move {
-                        let result = provider
-                            .trace_block_transactions(block_id)
+                        let result = client
+                            .call(
+                                move |c| async move { c.trace_block_transactions(block_id).await },
+                            )
                             .await
                             .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
                         BackendResponse::TraceBlockTransactions(result)
@@ -964,7 +1074,7 @@ impl Future for BackendWorker {
 impl std::fmt::Debug for BackendWorker {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Backend")
-            .field("provider", &self.starknet_client)
+            .field("client", &self.client)
             .field("request_dedup_map", &self.request_dedup_map)
             .field("pending_requests", &self.pending_requests.len())
             .field("queued_requests", &self.queued_requests.len())
@@ -1042,6 +1152,7 @@ fn handle_not_found_err<T>(
 #[cfg(test)]
 pub(crate) mod test_utils {
 
+    use std::net::SocketAddr;
     use std::sync::mpsc::{sync_channel, SyncSender};
     use std::time::Duration;
 
@@ -1064,7 +1175,8 @@ pub(crate) mod test_utils {
     ) -> Backend {
         let url = Url::parse(rpc_url).expect("valid url");
         let provider = StarknetClient::new(url);
-        Backend::new_with_config(provider, max_concurrent_requests).unwrap()
+        let config = BackendConfig { max_concurrent_requests, ..Default::default() };
+        Backend::new_with_config(provider, config).unwrap()
     }
 
     // Starts a TCP server that never close the connection.
@@ -1093,17 +1205,35 @@ pub(crate) mod test_utils {
     // The `result` parameter should be the value to return in the "result" field of the JSON-RPC
     // response
     pub fn start_mock_rpc_server(addr: String, result: String) -> SyncSender<()> {
+        spawn_mock_rpc_server(addr, result).0
+    }
+
+    // Same as `start_mock_rpc_server`, but on a port chosen by the OS. Returns the URL of the
+    // server along with the sender.
+    pub fn start_mock_rpc_server_on_free_port(result: String) -> (SyncSender<()>, Url) {
+        let (sender, addr) = spawn_mock_rpc_server("127.0.0.1:0".to_string(), result);
+        (sender, Url::parse(&format!("http://{addr}")).unwrap())
+    }
+
+    // Returns the URL of a local port that nothing is listening on.
+    pub fn unused_url() -> Url {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        Url::parse(&format!("http://{addr}")).unwrap()
+    }
+
+    fn spawn_mock_rpc_server(addr: String, result: String) -> (SyncSender<()>, SocketAddr) {
         use tokio::runtime::Builder;
 
         let (resp_signal_tx, resp_signal_rx) = sync_channel::<()>(100);
-        let (server_ready_tx, server_ready_rx) = sync_channel::<()>(1);
+        let (server_ready_tx, server_ready_rx) = sync_channel::<SocketAddr>(1);
 
         std::thread::spawn(move || {
             Builder::new_current_thread().enable_all().build().unwrap().block_on(async move {
                 let listener = TcpListener::bind(addr).await.unwrap();
                 let pending_requests = Arc::new(Mutex::new(VecDeque::new()));
 
-                server_ready_tx.send(()).unwrap();
+                server_ready_tx.send(listener.local_addr().unwrap()).unwrap();
 
                 // Spawn a task to accept incoming connections
                 let pending_requests_accept = pending_requests.clone();
@@ -1174,10 +1304,10 @@ pub(crate) mod test_utils {
         });
 
         // Wait for the server to be ready
-        server_ready_rx.recv().unwrap();
+        let addr = server_ready_rx.recv().unwrap();
 
         // Returning the sender to allow controlling the response timing.
-        resp_signal_tx
+        (resp_signal_tx, addr)
     }
 }
 
@@ -1658,6 +1788,45 @@ mod tests {
         assert_eq!(ongoing_requests_count, addresses.len() - max_concurrent,);
     }
 
+    #[test]
+    fn request_should_fail_over_to_fallback_url() {
+        let result = "0x789";
+        let (sender, fallback_url) = start_mock_rpc_server_on_free_port(result.to_string());
+
+        // Nothing is listening on the primary endpoint, so every request to it fails.
+        let provider = StarknetClient::new(unused_url());
+        let config = BackendConfig {
+            max_retries: 2,
+            min_retry_delay: Duration::from_millis(1),
+            max_retry_delay: Duration::from_millis(1),
+            fallback_urls: vec![fallback_url],
+            ..Default::default()
+        };
+
+        let handle = Backend::new_with_config(provider, config).unwrap();
+        sender.send(()).unwrap();
+
+        let nonce = handle.get_nonce(felt!("0x1").into(), 1).unwrap();
+        assert_eq!(nonce, Some(felt!("0x789")));
+    }
+
+    #[tokio::test]
+    async fn fallback_urls_must_be_on_the_same_chain() {
+        let (sender, fallback_url) = start_mock_rpc_server_on_free_port("0x789".to_string());
+        let config = BackendConfig { fallback_urls: vec![fallback_url], ..Default::default() };
+
+        sender.send(()).unwrap();
+        config.check_fallback_urls(felt!("0x789")).await.unwrap();
+
+        sender.send(()).unwrap();
+        let err = config.check_fallback_urls(felt!("0x123")).await.unwrap_err();
+        assert!(matches!(
+            err,
+            BackendError::FallbackChainIdMismatch { expected, actual, .. }
+                if expected == felt!("0x123") && actual == felt!("0x789")
+        ));
+    }
+
     #[test]
     fn test_requests_are_processed_after_limit_freed() {
         // Start mock server with a predefined result
@@ -0,0 +1,90 @@
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+/// A token bucket rate limiter.
+///
+/// The bucket holds up to `rate` tokens and is refilled at `rate` tokens per second. Every request
+/// takes a token, waiting for one to be refilled if the bucket is empty. Waiting requests are
+/// served in FIFO order.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    bucket: Mutex<TokenBucket>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter that allows up to `rate` requests per second.
+    pub(crate) fn new(rate: NonZeroU32) -> Self {
+        Self { bucket: Mutex::new(TokenBucket::new(rate, Instant::now())) }
+    }
+
+    /// Waits until a request is allowed to be sent.
+    pub(crate) async fn acquire(&self) {
+        // The lock is held while waiting so that no later request can take the refilled token.
+        let mut bucket = self.bucket.lock().await;
+        while let Some(delay) = bucket.take(Instant::now()) {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// The number of tokens refilled per second, which is also the capacity of the bucket.
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: NonZeroU32, now: Instant) -> Self {
+        let rate = rate.get() as f64;
+        Self { rate, tokens: rate, last_refill: now }
+    }
+
+    /// Takes a token from the bucket.
+    ///
+    /// Returns `None` if a token was taken, or how long to wait until a token is available.
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_refills_at_rate() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(NonZeroU32::new(2).unwrap(), start);
+
+        // the bucket starts full
+        assert_eq!(bucket.take(start), None);
+        assert_eq!(bucket.take(start), None);
+
+        // and must wait half a second for the next token
+        let delay = bucket.take(start).expect("bucket must be empty");
+        assert_eq!(delay, Duration::from_millis(500));
+
+        let now = start + delay;
+        assert_eq!(bucket.take(now), None);
+        assert!(bucket.take(now).is_some());
+
+        // never refills above its capacity
+        let now = now + Duration::from_secs(10);
+        assert_eq!(bucket.take(now), None);
+        assert_eq!(bucket.take(now), None);
+        assert!(bucket.take(now).is_some());
+    }
+}
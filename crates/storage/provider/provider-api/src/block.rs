@@ -2,8 +2,8 @@ use std::ops::RangeInclusive;
 
 use katana_db::models::block::StoredBlockBodyIndices;
 use katana_primitives::block::{
-    Block, BlockHash, BlockHashOrNumber, BlockIdOrTag, BlockNumber, BlockWithTxHashes,
-    FinalityStatus, Header, SealedBlockWithStatus,
+    Block, BlockHash, BlockHashOrNumber, BlockIdOrTag, BlockNumber, BlockSignature,
+    BlockWithTxHashes, FinalityStatus, Header, SealedBlockWithStatus,
 };
 use katana_primitives::execution::TypedTransactionExecutionInfo;
 use katana_primitives::receipt::Receipt;
@@ -100,6 +100,24 @@ pub trait BlockStatusProvider: Send + Sync {
     fn block_status(&self, id: BlockHashOrNumber) -> ProviderResult<Option<FinalityStatus>>;
 }
 
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait BlockSignatureProvider: Send + Sync {
+    /// Retrieves the sequencer signature of a block.
+    ///
+    /// Returns `None` if the block doesn't exist or was not signed.
+    fn block_signature(&self, id: BlockHashOrNumber) -> ProviderResult<Option<BlockSignature>>;
+}
+
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait BlockSignatureWriter: Send + Sync {
+    /// Stores the sequencer signature of a block.
+    fn insert_block_signature(
+        &self,
+        block_number: BlockNumber,
+        signature: BlockSignature,
+    ) -> ProviderResult<()>;
+}
+
 #[auto_impl::auto_impl(&, Box, Arc)]
 pub trait BlockProvider:
     BlockHashProvider
@@ -1,17 +1,21 @@
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use katana_db::abstraction::Database;
-use katana_fork::Backend;
+use katana_fork::{Backend, BackendConfig};
 use katana_primitives::block::BlockNumber;
 pub use katana_provider_api::{ProviderError, ProviderResult};
 use katana_starknet::rpc::StarknetRpcClient as StarknetClient;
+use parking_lot::RwLock;
 
 // Re-export the API module
 pub mod api {
     pub use katana_provider_api::*;
 }
 
-use crate::api::block::{BlockIdReader, BlockProvider, BlockWriter};
+use crate::api::block::{
+    BlockIdReader, BlockProvider, BlockSignatureProvider, BlockSignatureWriter, BlockWriter,
+};
 use crate::api::contract::ContractClassWriter;
 use crate::api::env::BlockEnvProvider;
 use crate::api::stage::StageCheckpointProvider;
@@ -26,6 +30,7 @@ use crate::api::trie::TrieWriter;
 pub trait ProviderRO:
     BlockIdReader
     + BlockProvider
+    + BlockSignatureProvider
     + TransactionProvider
     + TransactionStatusProvider
     + TransactionTraceProvider
@@ -45,6 +50,7 @@ pub trait ProviderRW:
     MutableProvider
     + ProviderRO
     + BlockWriter
+    + BlockSignatureWriter
     + StateWriter
     + ContractClassWriter
     + TrieWriter
@@ -56,6 +62,7 @@ pub trait ProviderRW:
 impl<T> ProviderRO for T where
     T: BlockProvider
         + BlockIdReader
+        + BlockSignatureProvider
         + TransactionProvider
         + TransactionStatusProvider
         + TransactionTraceProvider
@@ -75,6 +82,7 @@ impl<T> ProviderRW for T where
     T: ProviderRO
         + MutableProvider
         + BlockWriter
+        + BlockSignatureWriter
         + StateWriter
         + ContractClassWriter
         + TrieWriter
@@ -88,7 +96,7 @@ pub mod providers;
 pub mod test_utils;
 
 use crate::providers::db::DbProvider;
-use crate::providers::fork::{ForkedDb, ForkedProvider};
+use crate::providers::fork::{ForkRoots, ForkStateCache, ForkedDb, ForkedProvider};
 
 #[auto_impl::auto_impl(&, Box, Arc)]
 pub trait ProviderFactory: Send + Sync + Debug + 'static {
@@ -139,36 +147,124 @@ impl ProviderFactory for DbProviderFactory {
     }
 }
 
+/// A [`ProviderFactory`] for a chain forked from another network.
+///
+/// Clones of the factory share the same fork, so that [resetting](ForkProviderFactory::reset) it
+/// affects all of them.
 #[derive(Clone, Debug)]
 pub struct ForkProviderFactory {
+    inner: Arc<RwLock<ForkProviderFactoryInner>>,
+}
+
+#[derive(Debug)]
+struct ForkProviderFactoryInner {
     backend: Backend,
+    backend_config: BackendConfig,
     block_id: BlockNumber,
     fork_factory: DbProviderFactory,
     local_factory: DbProviderFactory,
+    cache: Option<ForkStateCache>,
+    roots: Arc<ForkRoots>,
+}
+
+impl ForkProviderFactoryInner {
+    fn forked_db(&self) -> ForkedDb {
+        let db = ForkedDb::new(self.backend.clone(), self.block_id, self.fork_factory.clone())
+            .with_roots(self.roots.clone());
+        match &self.cache {
+            Some(cache) => db.with_cache(cache.clone()),
+            None => db,
+        }
+    }
 }
 
 impl ForkProviderFactory {
     pub fn new(db: katana_db::Db, block_id: BlockNumber, starknet_client: StarknetClient) -> Self {
-        let backend = Backend::new(starknet_client).expect("failed to create backend");
+        Self::new_with_config(db, block_id, starknet_client, BackendConfig::default())
+    }
+
+    /// Creates a new factory whose requests to the forked network are sent according to
+    /// `backend_config`.
+    pub fn new_with_config(
+        db: katana_db::Db,
+        block_id: BlockNumber,
+        starknet_client: StarknetClient,
+        backend_config: BackendConfig,
+    ) -> Self {
+        let backend = Backend::new_with_config(starknet_client, backend_config.clone())
+            .expect("failed to create backend");
 
         let local_factory = DbProviderFactory::new(db);
         let fork_factory = DbProviderFactory::new_in_memory();
 
-        Self { local_factory, fork_factory, backend, block_id }
+        let inner = ForkProviderFactoryInner {
+            local_factory,
+            fork_factory,
+            backend,
+            backend_config,
+            block_id,
+            cache: None,
+            roots: Default::default(),
+        };
+        Self { inner: Arc::new(RwLock::new(inner)) }
     }
 
     pub fn new_in_memory(block_id: BlockNumber, starknet_client: StarknetClient) -> Self {
         Self::new(katana_db::Db::in_memory().unwrap(), block_id, starknet_client)
     }
 
-    /// Returns a reference to the underlying database where the local-only data is stored.
-    pub fn db(&self) -> &katana_db::Db {
-        self.local_factory.db()
+    /// Sets the persistent cache that the state of the forked network at the fork point is read
+    /// from and written to.
+    ///
+    /// The cache must belong to the same network and fork block as the factory.
+    pub fn with_cache(self, cache: ForkStateCache) -> Self {
+        self.inner.write().cache = Some(cache);
+        self
+    }
+
+    /// Returns the underlying database where the local-only data is stored.
+    pub fn db(&self) -> katana_db::Db {
+        self.inner.read().local_factory.db().clone()
     }
 
     /// Returns the block number the provider is forked at.
     pub fn block(&self) -> BlockNumber {
-        self.block_id
+        self.inner.read().block_id
+    }
+
+    /// Returns the persistent cache of the forked network's state, if any.
+    pub fn cache(&self) -> Option<ForkStateCache> {
+        self.inner.read().cache.clone()
+    }
+
+    /// Re-forks the chain at `block_id` of the network `starknet_client` is connected to.
+    ///
+    /// All the local data is dropped, and so is the data cached from the previously forked
+    /// network. The local data is stored in a new in-memory database. The persistent `cache`, if
+    /// any, must belong to the new fork point. Requests to the forked network keep being sent
+    /// according to the backend configuration the factory was created with.
+    pub fn reset(
+        &self,
+        block_id: BlockNumber,
+        starknet_client: StarknetClient,
+        cache: Option<ForkStateCache>,
+    ) -> anyhow::Result<()> {
+        let backend_config = self.inner.read().backend_config.clone();
+        let backend = Backend::new_with_config(starknet_client, backend_config.clone())?;
+        let local_factory = DbProviderFactory::new(katana_db::Db::in_memory()?);
+        let fork_factory = DbProviderFactory::new_in_memory();
+
+        *self.inner.write() = ForkProviderFactoryInner {
+            local_factory,
+            fork_factory,
+            backend,
+            backend_config,
+            block_id,
+            cache,
+            roots: Default::default(),
+        };
+
+        Ok(())
     }
 }
 
@@ -178,16 +274,12 @@ impl ProviderFactory for ForkProviderFactory {
     type ProviderMut = ForkedProvider<<katana_db::Db as Database>::TxMut>;
 
     fn provider(&self) -> Self::Provider {
-        ForkedProvider::new(
-            self.local_factory.provider(),
-            ForkedDb::new(self.backend.clone(), self.block_id, self.fork_factory.clone()),
-        )
+        let inner = self.inner.read();
+        ForkedProvider::new(inner.local_factory.provider(), inner.forked_db())
     }
 
     fn provider_mut(&self) -> Self::ProviderMut {
-        ForkedProvider::new(
-            self.local_factory.provider_mut(),
-            ForkedDb::new(self.backend.clone(), self.block_id, self.fork_factory.clone()),
-        )
+        let inner = self.inner.read();
+        ForkedProvider::new(inner.local_factory.provider_mut(), inner.forked_db())
     }
 }
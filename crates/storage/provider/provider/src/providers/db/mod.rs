@@ -21,8 +21,8 @@ use katana_db::models::{
 };
 use katana_db::tables;
 use katana_primitives::block::{
-    Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithTxHashes, FinalityStatus, Header,
-    SealedBlockWithStatus,
+    Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockSignature, BlockWithTxHashes,
+    FinalityStatus, Header, SealedBlockWithStatus,
 };
 use katana_primitives::class::{ClassHash, CompiledClassHash};
 use katana_primitives::contract::{ContractAddress, GenericContractInfo};
@@ -32,8 +32,8 @@ use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::transaction::{TxHash, TxNumber, TxWithHash};
 use katana_provider_api::block::{
-    BlockHashProvider, BlockIdReader, BlockNumberProvider, BlockProvider, BlockStatusProvider,
-    BlockWriter, HeaderProvider,
+    BlockHashProvider, BlockIdReader, BlockNumberProvider, BlockProvider, BlockSignatureProvider,
+    BlockSignatureWriter, BlockStatusProvider, BlockWriter, HeaderProvider,
 };
 use katana_provider_api::env::BlockEnvProvider;
 use katana_provider_api::stage::StageCheckpointProvider;
@@ -241,6 +241,16 @@ impl<Tx: DbTx> BlockStatusProvider for DbProvider<Tx> {
     }
 }
 
+impl<Tx: DbTx> BlockSignatureProvider for DbProvider<Tx> {
+    fn block_signature(&self, id: BlockHashOrNumber) -> ProviderResult<Option<BlockSignature>> {
+        if let Some(num) = self.block_number_by_id(id)? {
+            Ok(self.0.get::<tables::BlockSignatures>(num)?)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 impl<Tx: DbTx> StateUpdateProvider for DbProvider<Tx> {
     fn state_update(&self, block_id: BlockHashOrNumber) -> ProviderResult<Option<StateUpdates>> {
         let block_num = self.block_number_by_id(block_id)?;
@@ -896,6 +906,17 @@ impl<Tx: DbTxMut> BlockWriter for DbProvider<Tx> {
     }
 }
 
+impl<Tx: DbTxMut> BlockSignatureWriter for DbProvider<Tx> {
+    fn insert_block_signature(
+        &self,
+        block_number: BlockNumber,
+        signature: BlockSignature,
+    ) -> ProviderResult<()> {
+        self.0.put::<tables::BlockSignatures>(block_number, signature)?;
+        Ok(())
+    }
+}
+
 impl<Tx: DbTxMut> StageCheckpointProvider for DbProvider<Tx> {
     fn execution_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
         let result = self.0.get::<tables::StageExecutionCheckpoints>(id.to_string())?;
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use katana_db::abstraction::DbTxMut;
+use katana_db::models::storage::StorageEntry;
+use katana_db::tables;
+use katana_primitives::block::BlockNumber;
+use katana_primitives::chain::ChainId;
+use katana_primitives::class::{ClassHash, CompiledClassHash, ContractClass};
+use katana_primitives::contract::{GenericContractInfo, StorageKey, StorageValue};
+use katana_primitives::ContractAddress;
+use katana_provider_api::state::{StateFactoryProvider, StateProvider};
+use parking_lot::Mutex;
+use tracing::warn;
+
+use crate::{DbProviderFactory, MutableProvider, ProviderFactory, ProviderResult};
+
+/// The number of values that are buffered in memory before being written to the database.
+const MAX_PENDING_WRITES: usize = 256;
+
+/// A persistent cache of the state of a forked network at the fork point.
+///
+/// The state of a network at a given block never changes, so the values fetched from the forked
+/// network can be reused by every node that forks the same network at the same block. The cache is
+/// stored in its own database under a directory unique to the (chain id, fork block) pair. See
+/// [`ForkStateCache::path`].
+///
+/// Only the values that exist on the forked network are cached. Lookups of non-existent values
+/// are always forwarded to the forked network.
+///
+/// New values are buffered in memory and written to the database in batches, or when the last
+/// handle to the cache is dropped. See [`ForkStateCache::flush`].
+#[derive(Debug, Clone)]
+pub struct ForkStateCache {
+    inner: Arc<ForkStateCacheInner>,
+}
+
+#[derive(Debug)]
+struct ForkStateCacheInner {
+    db: DbProviderFactory,
+    /// The values that are yet to be written to the database.
+    pending: Mutex<PendingWrites>,
+}
+
+#[derive(Debug, Default)]
+struct PendingWrites {
+    contracts: HashMap<ContractAddress, GenericContractInfo>,
+    storage: HashMap<(ContractAddress, StorageKey), StorageValue>,
+    classes: HashMap<ClassHash, ContractClass>,
+    compiled_class_hashes: HashMap<ClassHash, CompiledClassHash>,
+}
+
+impl PendingWrites {
+    fn len(&self) -> usize {
+        self.contracts.len()
+            + self.storage.len()
+            + self.classes.len()
+            + self.compiled_class_hashes.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ForkStateCache {
+    /// Opens the cache of the network `chain_id` forked at `block` that is stored under `dir`,
+    /// creating it if it doesn't exist yet.
+    pub fn open<P: AsRef<Path>>(
+        dir: P,
+        chain_id: ChainId,
+        block: BlockNumber,
+    ) -> anyhow::Result<Self> {
+        let path = Self::path(dir, chain_id, block);
+        std::fs::create_dir_all(&path)?;
+        let db = katana_db::Db::new(path)?;
+        let inner = ForkStateCacheInner {
+            db: DbProviderFactory::new(db),
+            pending: Mutex::new(PendingWrites::default()),
+        };
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Returns the directory under `dir` where the cache of the network `chain_id` forked at
+    /// `block` is stored.
+    pub fn path<P: AsRef<Path>>(dir: P, chain_id: ChainId, block: BlockNumber) -> PathBuf {
+        dir.as_ref().join(format!("{:#x}", chain_id.id())).join(block.to_string())
+    }
+
+    /// Returns the underlying database.
+    pub fn db(&self) -> &katana_db::Db {
+        self.inner.db.db()
+    }
+
+    /// Writes the buffered values to the database in a single transaction.
+    pub fn flush(&self) -> ProviderResult<()> {
+        self.inner.flush(&mut self.inner.pending.lock())
+    }
+
+    fn state(&self) -> ProviderResult<Box<dyn StateProvider>> {
+        self.inner.db.provider().latest()
+    }
+
+    pub(crate) fn contract(
+        &self,
+        address: ContractAddress,
+    ) -> ProviderResult<Option<GenericContractInfo>> {
+        if let Some(info) = self.inner.pending.lock().contracts.get(&address) {
+            return Ok(Some(*info));
+        }
+
+        let state = self.state()?;
+        let Some(class_hash) = state.class_hash_of_contract(address)? else { return Ok(None) };
+        let nonce = state.nonce(address)?.unwrap_or_default();
+        Ok(Some(GenericContractInfo { nonce, class_hash }))
+    }
+
+    pub(crate) fn storage(
+        &self,
+        address: ContractAddress,
+        key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.inner.pending.lock().storage.get(&(address, key)) {
+            return Ok(Some(*value));
+        }
+
+        self.state()?.storage(address, key)
+    }
+
+    pub(crate) fn class(&self, hash: ClassHash) -> ProviderResult<Option<ContractClass>> {
+        if let Some(class) = self.inner.pending.lock().classes.get(&hash) {
+            return Ok(Some(class.clone()));
+        }
+
+        self.state()?.class(hash)
+    }
+
+    pub(crate) fn compiled_class_hash(
+        &self,
+        hash: ClassHash,
+    ) -> ProviderResult<Option<CompiledClassHash>> {
+        if let Some(compiled_hash) = self.inner.pending.lock().compiled_class_hashes.get(&hash) {
+            return Ok(Some(*compiled_hash));
+        }
+
+        self.state()?.compiled_class_hash_of_class_hash(hash)
+    }
+
+    pub(crate) fn insert_contract(
+        &self,
+        address: ContractAddress,
+        info: GenericContractInfo,
+    ) -> ProviderResult<()> {
+        self.insert(|pending| pending.contracts.insert(address, info))
+    }
+
+    pub(crate) fn insert_storage(
+        &self,
+        address: ContractAddress,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> ProviderResult<()> {
+        self.insert(|pending| pending.storage.insert((address, key), value))
+    }
+
+    pub(crate) fn insert_class(&self, hash: ClassHash, class: ContractClass) -> ProviderResult<()> {
+        self.insert(|pending| pending.classes.insert(hash, class))
+    }
+
+    pub(crate) fn insert_compiled_class_hash(
+        &self,
+        hash: ClassHash,
+        compiled_hash: CompiledClassHash,
+    ) -> ProviderResult<()> {
+        self.insert(|pending| pending.compiled_class_hashes.insert(hash, compiled_hash))
+    }
+
+    /// Buffers a new value, and writes all the buffered values to the database once there are
+    /// enough of them.
+    fn insert<T>(&self, f: impl FnOnce(&mut PendingWrites) -> T) -> ProviderResult<()> {
+        let mut pending = self.inner.pending.lock();
+        f(&mut pending);
+
+        if pending.len() >= MAX_PENDING_WRITES {
+            self.inner.flush(&mut pending)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ForkStateCacheInner {
+    fn flush(&self, pending: &mut PendingWrites) -> ProviderResult<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let PendingWrites { contracts, storage, classes, compiled_class_hashes } =
+            std::mem::take(pending);
+
+        let provider_mut = self.db.provider_mut();
+        let tx = provider_mut.tx();
+
+        for (address, info) in contracts {
+            tx.put::<tables::ContractInfo>(address, info)?;
+        }
+
+        for ((address, key), value) in storage {
+            tx.put::<tables::ContractStorage>(address, StorageEntry { key, value })?;
+        }
+
+        for (hash, class) in classes {
+            tx.put::<tables::Classes>(hash, class.into())?;
+        }
+
+        for (hash, compiled_hash) in compiled_class_hashes {
+            tx.put::<tables::CompiledClassHashes>(hash, compiled_hash)?;
+        }
+
+        provider_mut.commit()
+    }
+}
+
+impl Drop for ForkStateCacheInner {
+    fn drop(&mut self) {
+        let mut pending = std::mem::take(self.pending.get_mut());
+        if let Err(error) = self.flush(&mut pending) {
+            warn!(%error, "Failed to write the fork state cache.");
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::{Range, RangeInclusive};
+use std::sync::Arc;
 
 use katana_db::abstraction::{DbTx, DbTxMut};
 use katana_db::models::block::StoredBlockBodyIndices;
@@ -7,19 +8,20 @@ use katana_db::models::StateUpdateEnvelope;
 use katana_db::tables;
 use katana_fork::Backend;
 use katana_primitives::block::{
-    Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithTxHashes, FinalityStatus, Header,
-    SealedBlockWithStatus,
+    Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockSignature, BlockWithTxHashes,
+    FinalityStatus, Header, SealedBlockWithStatus,
 };
-use katana_primitives::class::{ClassHash, CompiledClassHash};
-use katana_primitives::contract::ContractAddress;
+use katana_primitives::class::{ClassHash, CompiledClassHash, ContractClass};
+use katana_primitives::contract::{ContractAddress, GenericContractInfo, StorageKey, StorageValue};
 use katana_primitives::env::BlockEnv;
 use katana_primitives::execution::TypedTransactionExecutionInfo;
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::transaction::{TxHash, TxNumber, TxWithHash};
+use katana_primitives::Felt;
 use katana_provider_api::block::{
-    BlockHashProvider, BlockIdReader, BlockNumberProvider, BlockProvider, BlockStatusProvider,
-    BlockWriter, HeaderProvider,
+    BlockHashProvider, BlockIdReader, BlockNumberProvider, BlockProvider, BlockSignatureProvider,
+    BlockSignatureWriter, BlockStatusProvider, BlockWriter, HeaderProvider,
 };
 use katana_provider_api::env::BlockEnvProvider;
 use katana_provider_api::stage::StageCheckpointProvider;
@@ -30,17 +32,22 @@ use katana_provider_api::transaction::{
     TransactionsProviderExt,
 };
 use katana_provider_api::ProviderError;
+use katana_rpc_types::trie::GlobalRoots;
 use katana_rpc_types::{
     GetBlockWithReceiptsResponse, RpcTxWithReceipt, StateUpdate, TxTraceWithHash,
 };
+use parking_lot::Mutex;
 use tracing::trace;
 
 use super::db::{self, DbProvider};
 use crate::{DbProviderFactory, MutableProvider, ProviderFactory, ProviderResult};
 
+mod cache;
 mod state;
 mod trie;
 
+pub use cache::ForkStateCache;
+
 #[derive(Debug)]
 pub struct ForkedProvider<Tx: DbTx> {
     local_db: DbProvider<Tx>,
@@ -52,11 +59,36 @@ pub struct ForkedDb {
     backend: Backend,
     block_id: BlockNumber,
     db: DbProviderFactory,
+    cache: Option<ForkStateCache>,
+    roots: Arc<ForkRoots>,
+}
+
+/// The state roots of the forked network at the fork point.
+///
+/// The roots at the fork point never change, so they only have to be fetched once per fork.
+#[derive(Debug, Default)]
+pub(crate) struct ForkRoots {
+    global: Mutex<Option<GlobalRoots>>,
+    storage: Mutex<HashMap<ContractAddress, Felt>>,
 }
 
 impl ForkedDb {
     pub fn new(backend: Backend, block_id: BlockNumber, db: DbProviderFactory) -> Self {
-        Self { backend, block_id, db }
+        Self { backend, block_id, db, cache: None, roots: Default::default() }
+    }
+
+    /// Sets the state roots at the fork point that were already fetched by other providers of
+    /// the same fork.
+    pub(crate) fn with_roots(mut self, roots: Arc<ForkRoots>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// Sets the persistent cache that the state at the fork point is read from, before falling
+    /// back to the forked network.
+    pub fn with_cache(mut self, cache: ForkStateCache) -> Self {
+        self.cache = Some(cache);
+        self
     }
 
     pub fn block_id(&self) -> BlockNumber {
@@ -67,6 +99,124 @@ impl ForkedDb {
         &self.db
     }
 
+    /// Returns the persistent cache of the state at the fork point, if any.
+    pub fn cache(&self) -> Option<&ForkStateCache> {
+        self.cache.as_ref()
+    }
+
+    /// Fetches the info of a contract at the fork point.
+    fn fetch_contract(
+        &self,
+        address: ContractAddress,
+    ) -> ProviderResult<Option<GenericContractInfo>> {
+        if let Some(cache) = &self.cache {
+            if let info @ Some(..) = cache.contract(address)? {
+                return Ok(info);
+            }
+        }
+
+        let Some(class_hash) = self.backend.get_class_hash_at(address, self.block_id)? else {
+            return Ok(None);
+        };
+
+        let nonce = self.backend.get_nonce(address, self.block_id)?.unwrap_or_default();
+        let info = GenericContractInfo { nonce, class_hash };
+
+        if let Some(cache) = &self.cache {
+            cache.insert_contract(address, info)?;
+        }
+
+        Ok(Some(info))
+    }
+
+    /// Fetches the value of a storage slot at the fork point.
+    fn fetch_storage(
+        &self,
+        address: ContractAddress,
+        key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(cache) = &self.cache {
+            if let value @ Some(..) = cache.storage(address, key)? {
+                return Ok(value);
+            }
+        }
+
+        let value = self.backend.get_storage(address, key, self.block_id)?;
+
+        if let (Some(cache), Some(value)) = (&self.cache, value) {
+            cache.insert_storage(address, key, value)?;
+        }
+
+        Ok(value)
+    }
+
+    /// Fetches a class declared at the fork point.
+    fn fetch_class(&self, hash: ClassHash) -> ProviderResult<Option<ContractClass>> {
+        if let Some(cache) = &self.cache {
+            if let class @ Some(..) = cache.class(hash)? {
+                return Ok(class);
+            }
+        }
+
+        let class = self.backend.get_class_at(hash, self.block_id)?;
+
+        if let (Some(cache), Some(class)) = (&self.cache, &class) {
+            cache.insert_class(hash, class.clone())?;
+        }
+
+        Ok(class)
+    }
+
+    /// Fetches the compiled class hash of a class declared at the fork point.
+    fn fetch_compiled_class_hash(
+        &self,
+        hash: ClassHash,
+    ) -> ProviderResult<Option<CompiledClassHash>> {
+        if let Some(cache) = &self.cache {
+            if let compiled_hash @ Some(..) = cache.compiled_class_hash(hash)? {
+                return Ok(compiled_hash);
+            }
+        }
+
+        let compiled_hash = self.backend.get_compiled_class_hash(hash, self.block_id)?;
+
+        if let (Some(cache), Some(compiled_hash)) = (&self.cache, compiled_hash) {
+            cache.insert_compiled_class_hash(hash, compiled_hash)?;
+        }
+
+        Ok(compiled_hash)
+    }
+
+    /// Fetches the global state roots at the fork point.
+    fn fetch_global_roots(&self) -> ProviderResult<Option<GlobalRoots>> {
+        if let roots @ Some(..) = self.roots.global.lock().clone() {
+            return Ok(roots);
+        }
+
+        let roots = self.backend.get_global_roots(self.block_id)?.map(|res| res.global_roots);
+
+        if let Some(roots) = &roots {
+            *self.roots.global.lock() = Some(roots.clone());
+        }
+
+        Ok(roots)
+    }
+
+    /// Fetches the storage root of a contract at the fork point.
+    fn fetch_storage_root(&self, address: ContractAddress) -> ProviderResult<Option<Felt>> {
+        if let Some(root) = self.roots.storage.lock().get(&address) {
+            return Ok(Some(*root));
+        }
+
+        let root = self.backend.get_storage_root(address, self.block_id)?;
+
+        if let Some(root) = root {
+            self.roots.storage.lock().insert(address, root);
+        }
+
+        Ok(root)
+    }
+
     /// Checks if a block number is before the fork point (and thus should be fetched externally)
     fn should_fetch_externally(&self, block_num: BlockNumber) -> bool {
         block_num <= self.block_id
@@ -364,6 +514,13 @@ impl<Tx1: DbTx> BlockStatusProvider for ForkedProvider<Tx1> {
     }
 }
 
+impl<Tx1: DbTx> BlockSignatureProvider for ForkedProvider<Tx1> {
+    fn block_signature(&self, id: BlockHashOrNumber) -> ProviderResult<Option<BlockSignature>> {
+        // Only blocks produced locally (ie after the fork point) can be signed by this node.
+        self.local_db.block_signature(id)
+    }
+}
+
 impl<Tx1: DbTx> StateUpdateProvider for ForkedProvider<Tx1> {
     fn state_update(&self, block_id: BlockHashOrNumber) -> ProviderResult<Option<StateUpdates>> {
         if self.local_db.header(block_id)?.is_some() {
@@ -665,6 +822,16 @@ impl<Tx1: DbTxMut> BlockWriter for ForkedProvider<Tx1> {
     }
 }
 
+impl<Tx1: DbTxMut> BlockSignatureWriter for ForkedProvider<Tx1> {
+    fn insert_block_signature(
+        &self,
+        block_number: BlockNumber,
+        signature: BlockSignature,
+    ) -> ProviderResult<()> {
+        self.local_db.insert_block_signature(block_number, signature)
+    }
+}
+
 impl<Tx1: DbTxMut> StageCheckpointProvider for ForkedProvider<Tx1> {
     fn execution_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
         self.local_db.execution_checkpoint(id)
@@ -6,7 +6,7 @@ use katana_db::tables;
 use katana_db::trie::TrieDbFactory;
 use katana_primitives::block::BlockHashOrNumber;
 use katana_primitives::class::{ClassHash, CompiledClassHash, ContractClass};
-use katana_primitives::contract::{GenericContractInfo, Nonce, StorageKey, StorageValue};
+use katana_primitives::contract::{Nonce, StorageKey, StorageValue};
 use katana_primitives::{ContractAddress, Felt};
 use katana_provider_api::block::{BlockNumberProvider, HeaderProvider};
 use katana_provider_api::contract::{ContractClassProvider, ContractClassWriter};
@@ -83,9 +83,7 @@ impl<Tx1: DbTx> ContractClassProvider for LatestStateProvider<Tx1> {
     fn class(&self, hash: ClassHash) -> ProviderResult<Option<ContractClass>> {
         if let Some(class) = self.local_provider.class(hash)? {
             Ok(Some(class))
-        } else if let Some(class) =
-            self.fork_provider.backend.get_class_at(hash, self.fork_provider.block_id)?
-        {
+        } else if let Some(class) = self.fork_provider.fetch_class(hash)? {
             let provider_mut = self.fork_provider.db.provider_mut();
             provider_mut.tx().put::<tables::Classes>(hash, class.clone().into())?;
             provider_mut.commit()?;
@@ -102,9 +100,7 @@ impl<Tx1: DbTx> ContractClassProvider for LatestStateProvider<Tx1> {
     ) -> ProviderResult<Option<CompiledClassHash>> {
         if let res @ Some(..) = self.local_provider.compiled_class_hash_of_class_hash(hash)? {
             Ok(res)
-        } else if let Some(compiled_hash) =
-            self.fork_provider.backend.get_compiled_class_hash(hash, self.fork_provider.block_id)?
-        {
+        } else if let Some(compiled_hash) = self.fork_provider.fetch_compiled_class_hash(hash)? {
             let provider_mut = self.fork_provider.db.provider_mut();
             provider_mut.tx().put::<tables::CompiledClassHashes>(hash, compiled_hash)?;
             provider_mut.commit()?;
@@ -157,22 +153,12 @@ impl<Tx1: DbTx> StateProvider for LatestStateProvider<Tx1> {
             }
 
             Ok(Some(nonce))
-        } else if let Some(nonce) =
-            self.fork_provider.backend.get_nonce(address, self.fork_provider.block_id)?
-        {
-            let class_hash = self
-                .fork_provider
-                .backend
-                .get_class_hash_at(address, self.fork_provider.block_id)?
-                .ok_or(ProviderError::MissingContractClassHash { address })?;
-
-            let entry = GenericContractInfo { nonce, class_hash };
-
+        } else if let Some(entry) = self.fork_provider.fetch_contract(address)? {
             let provider_mut = self.fork_provider.db.provider_mut();
             provider_mut.tx().put::<tables::ContractInfo>(address, entry)?;
             provider_mut.commit()?;
 
-            Ok(Some(nonce))
+            Ok(Some(entry.nonce))
         } else {
             Ok(None)
         }
@@ -208,22 +194,12 @@ impl<Tx1: DbTx> StateProvider for LatestStateProvider<Tx1> {
             }
 
             Ok(Some(hash))
-        } else if let Some(class_hash) =
-            self.fork_provider.backend.get_class_hash_at(address, self.fork_provider.block_id)?
-        {
-            let nonce = self
-                .fork_provider
-                .backend
-                .get_nonce(address, self.fork_provider.block_id)?
-                .unwrap_or_default();
-
-            let entry = GenericContractInfo { class_hash, nonce };
-
+        } else if let Some(entry) = self.fork_provider.fetch_contract(address)? {
             let provider_mut = self.fork_provider.db.provider_mut();
             provider_mut.tx().put::<tables::ContractInfo>(address, entry)?;
             provider_mut.commit()?;
 
-            Ok(Some(class_hash))
+            Ok(Some(entry.class_hash))
         } else {
             Ok(None)
         }
@@ -236,9 +212,7 @@ impl<Tx1: DbTx> StateProvider for LatestStateProvider<Tx1> {
     ) -> ProviderResult<Option<StorageValue>> {
         if let res @ Some(..) = self.local_provider.storage(address, key)? {
             Ok(res)
-        } else if let Some(value) =
-            self.fork_provider.backend.get_storage(address, key, self.fork_provider.block_id)?
-        {
+        } else if let Some(value) = self.fork_provider.fetch_storage(address, key)? {
             let entry = StorageEntry { key, value };
 
             let provider_mut = self.fork_provider.db.provider_mut();
@@ -269,12 +243,12 @@ impl<Tx1: DbTx> StateProofProvider for LatestStateProvider<Tx1> {
 
             let rpc_proof =
                 self.fork_provider.backend.get_classes_proofs(classes.clone(), fork_point)?;
-            let rpc_root = self.fork_provider.backend.get_global_roots(fork_point)?;
+            let rpc_root = self.fork_provider.fetch_global_roots()?;
 
             let proof = rpc_proof
                 .map(|p| p.classes_proof.nodes.into())
                 .unwrap_or_else(|| katana_trie::MultiProof(Default::default()));
-            let root = rpc_root.map(|r| r.global_roots.classes_tree_root).unwrap_or(Felt::ZERO);
+            let root = rpc_root.map(|r| r.classes_tree_root).unwrap_or(Felt::ZERO);
 
             let proofs = trie.multiproof(classes, Some(proof), Some(root));
             Ok(proofs)
@@ -300,12 +274,12 @@ impl<Tx1: DbTx> StateProofProvider for LatestStateProvider<Tx1> {
 
             let rpc_proof =
                 self.fork_provider.backend.get_contracts_proofs(addresses.clone(), fork_point)?;
-            let rpc_root = self.fork_provider.backend.get_global_roots(fork_point)?;
+            let rpc_root = self.fork_provider.fetch_global_roots()?;
 
             let proof = rpc_proof
                 .map(|p| p.contracts_proof.nodes.into())
                 .unwrap_or_else(|| katana_trie::MultiProof(Default::default()));
-            let root = rpc_root.map(|r| r.global_roots.contracts_tree_root).unwrap_or(Felt::ZERO);
+            let root = rpc_root.map(|r| r.contracts_tree_root).unwrap_or(Felt::ZERO);
 
             let proofs = trie.multiproof(addresses, Some(proof), Some(root));
             Ok(proofs)
@@ -337,7 +311,7 @@ impl<Tx1: DbTx> StateProofProvider for LatestStateProvider<Tx1> {
 
             let key = vec![ContractStorageKeys { address, keys: storage_keys.clone() }];
             let rpc_proof = self.fork_provider.backend.get_storages_proofs(key, fork_point)?;
-            let rpc_root = self.fork_provider.backend.get_storage_root(address, fork_point)?;
+            let rpc_root = self.fork_provider.fetch_storage_root(address)?;
 
             let proof = rpc_proof
                 .and_then(|mut p| p.contracts_storage_proofs.nodes.pop())
@@ -358,19 +332,16 @@ impl<Tx1: DbTx> StateRootProvider for LatestStateProvider<Tx1> {
         let latest_block_number = self.latest_block_number()?;
 
         if latest_block_number == fork_point {
-            let result = self.fork_provider.backend.get_global_roots(fork_point)?;
-            return Ok(result
-                .expect("proofs should exist for block")
-                .global_roots
-                .classes_tree_root);
+            let result = self.fork_provider.fetch_global_roots()?;
+            return Ok(result.expect("proofs should exist for block").classes_tree_root);
         }
 
         let trie = TrieDbFactory::new(self.local_provider.0.tx().clone()).latest().classes_trie();
         let root = trie.root();
 
         if root == Felt::ZERO {
-            let result = self.fork_provider.backend.get_global_roots(fork_point)?;
-            Ok(result.expect("proofs should exist for block").global_roots.classes_tree_root)
+            let result = self.fork_provider.fetch_global_roots()?;
+            Ok(result.expect("proofs should exist for block").classes_tree_root)
         } else {
             Ok(root)
         }
@@ -381,19 +352,16 @@ impl<Tx1: DbTx> StateRootProvider for LatestStateProvider<Tx1> {
         let latest_block_number = self.latest_block_number()?;
 
         if latest_block_number == fork_point {
-            let result = self.fork_provider.backend.get_global_roots(fork_point)?;
-            return Ok(result
-                .expect("proofs should exist for block")
-                .global_roots
-                .contracts_tree_root);
+            let result = self.fork_provider.fetch_global_roots()?;
+            return Ok(result.expect("proofs should exist for block").contracts_tree_root);
         }
 
         let trie = TrieDbFactory::new(self.local_provider.0.tx().clone()).latest().contracts_trie();
         let root = trie.root();
 
         if root == Felt::ZERO {
-            let result = self.fork_provider.backend.get_global_roots(fork_point)?;
-            Ok(result.expect("proofs should exist for block").global_roots.contracts_tree_root)
+            let result = self.fork_provider.fetch_global_roots()?;
+            Ok(result.expect("proofs should exist for block").contracts_tree_root)
         } else {
             Ok(root)
         }
@@ -404,7 +372,7 @@ impl<Tx1: DbTx> StateRootProvider for LatestStateProvider<Tx1> {
         let latest_block_number = self.latest_block_number()?;
 
         if latest_block_number == fork_point {
-            let result = self.fork_provider.backend.get_storage_root(contract, fork_point)?;
+            let result = self.fork_provider.fetch_storage_root(contract)?;
             let root = result.expect("proofs should exist for block");
             Ok(Some(root))
         } else {
@@ -414,11 +382,7 @@ impl<Tx1: DbTx> StateRootProvider for LatestStateProvider<Tx1> {
                 .root();
 
             if root == Felt::ZERO {
-                Ok(self
-                    .fork_provider
-                    .backend
-                    .get_storage_root(contract, fork_point)?
-                    .or(Some(Felt::ZERO)))
+                Ok(self.fork_provider.fetch_storage_root(contract)?.or(Some(Felt::ZERO)))
             } else {
                 Ok(Some(root))
             }
@@ -436,6 +400,12 @@ impl<Tx1: DbTx> HistoricalStateProvider<Tx1> {
     fn target_block(&self) -> BlockNumber {
         self.local_provider.block().min(self.fork_provider.block_id)
     }
+
+    /// Returns `true` if the state of the forked network is read at the fork point, in which case
+    /// it is read through the fork state cache.
+    fn at_fork_point(&self) -> bool {
+        self.target_block() == self.fork_provider.block_id
+    }
 }
 
 impl<Tx1: DbTx> ContractClassProvider for HistoricalStateProvider<Tx1> {
@@ -444,6 +414,10 @@ impl<Tx1: DbTx> ContractClassProvider for HistoricalStateProvider<Tx1> {
             return Ok(res);
         }
 
+        if self.at_fork_point() {
+            return self.fork_provider.fetch_class(hash);
+        }
+
         let block_id = self.target_block();
 
         if let class @ Some(..) = self.fork_provider.backend.get_class_at(hash, block_id)? {
@@ -461,6 +435,10 @@ impl<Tx1: DbTx> ContractClassProvider for HistoricalStateProvider<Tx1> {
             return Ok(res);
         }
 
+        if self.at_fork_point() {
+            return self.fork_provider.fetch_compiled_class_hash(hash);
+        }
+
         let block_id = self.target_block();
 
         if let Some(compiled_hash) =
@@ -483,6 +461,10 @@ impl<Tx1: DbTx> StateProvider for HistoricalStateProvider<Tx1> {
             return Ok(res);
         }
 
+        if self.at_fork_point() {
+            return Ok(self.fork_provider.fetch_contract(address)?.map(|info| info.nonce));
+        }
+
         let block_id = self.target_block();
 
         Ok(self.fork_provider.backend.get_nonce(address, block_id)?)
@@ -496,6 +478,10 @@ impl<Tx1: DbTx> StateProvider for HistoricalStateProvider<Tx1> {
             return Ok(res);
         }
 
+        if self.at_fork_point() {
+            return Ok(self.fork_provider.fetch_contract(address)?.map(|info| info.class_hash));
+        }
+
         let block_id = self.target_block();
 
         Ok(self.fork_provider.backend.get_class_hash_at(address, block_id)?)
@@ -510,6 +496,10 @@ impl<Tx1: DbTx> StateProvider for HistoricalStateProvider<Tx1> {
             return Ok(res);
         }
 
+        if self.at_fork_point() {
+            return self.fork_provider.fetch_storage(address, key);
+        }
+
         let block_id = self.target_block();
 
         Ok(self.fork_provider.backend.get_storage(address, key, block_id)?)
@@ -528,13 +518,12 @@ impl<Tx1: DbTx> StateProofProvider for HistoricalStateProvider<Tx1> {
                 .fork_provider
                 .backend
                 .get_classes_proofs(classes.clone(), self.fork_provider.block_id)?;
-            let rpc_root =
-                self.fork_provider.backend.get_global_roots(self.fork_provider.block_id)?;
+            let rpc_root = self.fork_provider.fetch_global_roots()?;
 
             let proof = rpc_proof
                 .map(|p| p.classes_proof.nodes.into())
                 .unwrap_or_else(|| katana_trie::MultiProof(Default::default()));
-            let root = rpc_root.map(|r| r.global_roots.classes_tree_root).unwrap_or(Felt::ZERO);
+            let root = rpc_root.map(|r| r.classes_tree_root).unwrap_or(Felt::ZERO);
 
             let proofs = trie.multiproof(classes, Some(proof), Some(root));
             Ok(proofs)
@@ -563,13 +552,12 @@ impl<Tx1: DbTx> StateProofProvider for HistoricalStateProvider<Tx1> {
                 .fork_provider
                 .backend
                 .get_contracts_proofs(addresses.clone(), self.fork_provider.block_id)?;
-            let rpc_root =
-                self.fork_provider.backend.get_global_roots(self.fork_provider.block_id)?;
+            let rpc_root = self.fork_provider.fetch_global_roots()?;
 
             let proof = rpc_proof
                 .map(|p| p.contracts_proof.nodes.into())
                 .unwrap_or_else(|| katana_trie::MultiProof(Default::default()));
-            let root = rpc_root.map(|r| r.global_roots.contracts_tree_root).unwrap_or(Felt::ZERO);
+            let root = rpc_root.map(|r| r.contracts_tree_root).unwrap_or(Felt::ZERO);
 
             let proofs = trie.multiproof(addresses, Some(proof), Some(root));
             Ok(proofs)
@@ -598,10 +586,7 @@ impl<Tx1: DbTx> StateProofProvider for HistoricalStateProvider<Tx1> {
             let key = vec![ContractStorageKeys { address, keys: storage_keys.clone() }];
             let rpc_proof =
                 self.fork_provider.backend.get_storages_proofs(key, self.fork_provider.block_id)?;
-            let rpc_root = self
-                .fork_provider
-                .backend
-                .get_storage_root(address, self.fork_provider.block_id)?;
+            let rpc_root = self.fork_provider.fetch_storage_root(address)?;
 
             let proof = rpc_proof
                 .and_then(|mut p| p.contracts_storage_proofs.nodes.pop())
@@ -658,9 +643,8 @@ impl<Tx1: DbTx> StateRootProvider for HistoricalStateProvider<Tx1> {
                 .root();
 
             if root == Felt::ZERO {
-                let result =
-                    self.fork_provider.backend.get_global_roots(self.fork_provider.block_id)?;
-                Ok(result.expect("proofs should exist for block").global_roots.classes_tree_root)
+                let result = self.fork_provider.fetch_global_roots()?;
+                Ok(result.expect("proofs should exist for block").classes_tree_root)
             } else {
                 Ok(root)
             }
@@ -681,9 +665,8 @@ impl<Tx1: DbTx> StateRootProvider for HistoricalStateProvider<Tx1> {
                 .root();
 
             if root == Felt::ZERO {
-                let result =
-                    self.fork_provider.backend.get_global_roots(self.fork_provider.block_id)?;
-                Ok(result.expect("proofs should exist for block").global_roots.contracts_tree_root)
+                let result = self.fork_provider.fetch_global_roots()?;
+                Ok(result.expect("proofs should exist for block").contracts_tree_root)
             } else {
                 Ok(root)
             }
@@ -704,11 +687,7 @@ impl<Tx1: DbTx> StateRootProvider for HistoricalStateProvider<Tx1> {
                 .root();
 
             if root == Felt::ZERO {
-                Ok(self
-                    .fork_provider
-                    .backend
-                    .get_storage_root(contract, self.fork_provider.block_id)?
-                    .or(Some(Felt::ZERO)))
+                Ok(self.fork_provider.fetch_storage_root(contract)?.or(Some(Felt::ZERO)))
             } else {
                 Ok(Some(root))
             }
@@ -64,14 +64,10 @@ impl<Tx1: DbTxMut> TrieWriter for ForkedProvider<Tx1> {
         };
 
         // Fetch global roots (always needed as fallback when no changes)
-        let original_root = self
-            .fork_db
-            .backend
-            .get_global_roots(self.fork_db.block_id)?
-            .map(|roots| roots.global_roots.contracts_tree_root)
-            .ok_or_else(|| {
-                ProviderError::ParsingError("missing global roots for fork point".to_string())
-            })?;
+        let original_root =
+            self.fork_db.fetch_global_roots()?.map(|roots| roots.contracts_tree_root).ok_or_else(
+                || ProviderError::ParsingError("missing global roots for fork point".to_string()),
+            )?;
 
         let (contracts_proof, contract_leaves_data) =
             if let Some(proof_response) = contracts_proof_result {
@@ -251,12 +247,11 @@ impl<Tx1: DbTxMut> TrieWriter for ForkedProvider<Tx1> {
             classes_proof_result.map(|response| response.classes_proof.nodes.into());
 
         // Fetch global roots (always needed as fallback when no changes)
-        let global_roots =
-            self.fork_db.backend.get_global_roots(self.fork_db.block_id)?.ok_or_else(|| {
-                ProviderError::ParsingError("missing global roots for fork point".to_string())
-            })?;
+        let global_roots = self.fork_db.fetch_global_roots()?.ok_or_else(|| {
+            ProviderError::ParsingError("missing global roots for fork point".to_string())
+        })?;
 
-        let original_root = global_roots.global_roots.classes_tree_root;
+        let original_root = global_roots.classes_tree_root;
 
         // Use proof-based methods if we have proofs (which means we have changes)
         // If no proofs, use the fork point root
@@ -1,12 +1,13 @@
 use anyhow::Result;
 use katana_primitives::block::{
-    Block, BlockHashOrNumber, BlockNumber, BlockWithTxHashes, FinalityStatus,
+    Block, BlockHashOrNumber, BlockNumber, BlockSignature, BlockWithTxHashes, FinalityStatus,
 };
 use katana_primitives::env::BlockEnv;
 use katana_primitives::state::StateUpdatesWithClasses;
 use katana_primitives::transaction::TxWithHash;
 use katana_provider::api::block::{
-    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
+    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockSignatureProvider,
+    BlockSignatureWriter, BlockStatusProvider, BlockWriter,
 };
 use katana_provider::api::env::BlockEnvProvider;
 use katana_provider::api::stage::StageCheckpointProvider;
@@ -284,6 +285,37 @@ where
     Ok(())
 }
 
+#[test]
+fn insert_and_read_block_signature() -> Result<()> {
+    let provider_factory = DbProviderFactory::new_in_memory();
+    let block = utils::generate_dummy_blocks_empty(0, 0).remove(0);
+    let block_hash = block.block.hash;
+
+    let provider_mut = provider_factory.provider_mut();
+    provider_mut.insert_block_with_states_and_receipts(
+        block,
+        Default::default(),
+        vec![],
+        vec![],
+    )?;
+    provider_mut.commit()?;
+
+    // blocks are not signed unless a signature is explicitly stored
+    assert_eq!(provider_factory.provider().block_signature(0.into())?, None);
+
+    let signature = BlockSignature { r: Felt::ONE, s: Felt::TWO };
+    let provider_mut = provider_factory.provider_mut();
+    provider_mut.insert_block_signature(0, signature)?;
+    provider_mut.commit()?;
+
+    let provider = provider_factory.provider();
+    assert_eq!(provider.block_signature(0.into())?, Some(signature));
+    assert_eq!(provider.block_signature(block_hash.into())?, Some(signature));
+    assert_eq!(provider.block_signature(1.into())?, None);
+
+    Ok(())
+}
+
 #[test]
 fn historical_state_retention_is_provider_owned() -> Result<()> {
     let provider_factory = fixtures::provider_with_states(DbProviderFactory::new_in_memory());
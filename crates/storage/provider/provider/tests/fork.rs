@@ -1,6 +1,7 @@
 use katana_primitives::block::{
     Block, BlockHashOrNumber, FinalityStatus, Header, SealedBlockWithStatus,
 };
+use katana_primitives::chain::ChainId;
 use katana_primitives::state::StateUpdatesWithClasses;
 use katana_primitives::transaction::TxType;
 use katana_primitives::{address, felt};
@@ -10,6 +11,7 @@ use katana_provider::api::block::{
 use katana_provider::api::state::StateFactoryProvider;
 use katana_provider::api::state_update::StateUpdateProvider;
 use katana_provider::api::transaction::{ReceiptProvider, TransactionProvider};
+use katana_provider::providers::fork::ForkStateCache;
 use katana_provider::{ForkProviderFactory, MutableProvider, ProviderFactory};
 use katana_rpc_types::MerkleNode;
 use katana_starknet::rpc::StarknetRpcClient as StarknetClient;
@@ -219,6 +221,49 @@ async fn latest_fork_state() {
     assert_eq!(result2, felt!("0xe824b9f2aa225812cf230d276784b99f182ec95066d84be90cd1682e4ad069"));
 }
 
+#[tokio::test]
+async fn latest_fork_state_from_persistent_cache() {
+    let fork_block_number = 2906771;
+    let cache_dir = tempfile::tempdir().unwrap();
+
+    let class_hash = felt!("0x00e022115a73679d4e215da00f53d8f681f5c52b488bf18c71fea115e92181b1");
+    let address = address!("0x0164b86b8fC5C0c84d3c53Bc95760F290420Ea2a32ed49A44fd046683a1CaAc2");
+
+    let expected = {
+        let cache =
+            ForkStateCache::open(cache_dir.path(), ChainId::SEPOLIA, fork_block_number).unwrap();
+        let starknet_client = StarknetClient::new(SEPOLIA_RPC_URL.try_into().unwrap());
+        let provider_factory =
+            ForkProviderFactory::new_in_memory(fork_block_number, starknet_client)
+                .with_cache(cache);
+
+        let state = provider_factory.provider().latest().unwrap();
+        (
+            state.class(class_hash).unwrap().expect("must exist"),
+            state.compiled_class_hash_of_class_hash(class_hash).unwrap().expect("must exist"),
+            state.nonce(address).unwrap().expect("must exist"),
+            state.class_hash_of_contract(address).unwrap().expect("must exist"),
+        )
+    };
+
+    // The forked network is unreachable, so the state can only be read from the cache.
+    let cache =
+        ForkStateCache::open(cache_dir.path(), ChainId::SEPOLIA, fork_block_number).unwrap();
+    let starknet_client = StarknetClient::new("http://localhost:1".try_into().unwrap());
+    let provider_factory =
+        ForkProviderFactory::new_in_memory(fork_block_number, starknet_client).with_cache(cache);
+
+    let state = provider_factory.provider().latest().unwrap();
+    let actual = (
+        state.class(class_hash).unwrap().expect("must be cached"),
+        state.compiled_class_hash_of_class_hash(class_hash).unwrap().expect("must be cached"),
+        state.nonce(address).unwrap().expect("must be cached"),
+        state.class_hash_of_contract(address).unwrap().expect("must be cached"),
+    );
+
+    assert_eq!(actual, expected);
+}
+
 #[tokio::test]
 async fn historical_fork_state() {
     let fork_block_number = 2906771;
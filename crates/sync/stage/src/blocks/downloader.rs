@@ -12,7 +12,7 @@
 
 use std::future::Future;
 
-use katana_primitives::block::{BlockNumber, SealedBlockWithStatus};
+use katana_primitives::block::{BlockNumber, BlockSignature, SealedBlockWithStatus};
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::StateUpdatesWithClasses;
 
@@ -27,6 +27,8 @@ pub struct BlockData {
     pub block: SealedBlockWithStatus,
     pub receipts: Vec<Receipt>,
     pub state_updates: StateUpdatesWithClasses,
+    /// The sequencer signature of the block, if the downloader fetched it.
+    pub signature: Option<BlockSignature>,
 }
 
 /// Trait for downloading block data.
@@ -95,7 +97,8 @@ pub mod gateway {
         BlockStatus, StateUpdate as GatewayStateUpdate, StateUpdateWithBlock,
     };
     use katana_primitives::block::{
-        BlockNumber, FinalityStatus, GasPrices, Header, SealedBlock, SealedBlockWithStatus,
+        BlockNumber, BlockSignature, FinalityStatus, GasPrices, Header, SealedBlock,
+        SealedBlockWithStatus,
     };
     use katana_primitives::fee::{FeeInfo, PriceUnit};
     use katana_primitives::receipt::{
@@ -121,6 +124,15 @@ pub mod gateway {
         ) -> BatchBlockDownloader<GatewayDownloader> {
             Self::new(GatewayDownloader::new(client), batch_size)
         }
+
+        /// Create a new [`BatchBlockDownloader`] using the Starknet gateway for downloading
+        /// blocks along with their sequencer signatures.
+        pub fn new_gateway_with_signatures(
+            client: GatewayClient,
+            batch_size: usize,
+        ) -> BatchBlockDownloader<GatewayDownloader> {
+            Self::new(GatewayDownloader::new(client).with_signatures(), batch_size)
+        }
     }
 
     /// Internal [`Downloader`] implementation that uses the sequencer gateway for downloading a
@@ -128,17 +140,24 @@ pub mod gateway {
     #[derive(Debug)]
     pub struct GatewayDownloader {
         gateway: GatewayClient,
+        fetch_signatures: bool,
     }
 
     impl GatewayDownloader {
         pub fn new(gateway: GatewayClient) -> Self {
-            Self { gateway }
+            Self { gateway, fetch_signatures: false }
+        }
+
+        /// Also download the sequencer signature of every block.
+        pub fn with_signatures(mut self) -> Self {
+            self.fetch_signatures = true;
+            self
         }
     }
 
     impl Downloader for GatewayDownloader {
         type Key = BlockNumber;
-        type Value = StateUpdateWithBlock;
+        type Value = BlockData;
         type Error = katana_gateway_client::Error;
 
         #[allow(clippy::manual_async_fn)]
@@ -150,10 +169,31 @@ pub mod gateway {
             use tracing::error;
 
             async {
-                match self.gateway.get_state_update_with_block((*key).into()).await.inspect_err(
+                let block_id = (*key).into();
+
+                let result = tokio::try_join!(
+                    self.gateway.get_state_update_with_block(block_id),
+                    async {
+                        if self.fetch_signatures {
+                            self.gateway.get_signature(block_id).await.map(Some)
+                        } else {
+                            Ok(None)
+                        }
+                    }
+                )
+                .inspect_err(
                     |error| error!(block = %*key, ?error, "Error downloading block from gateway."),
-                ) {
-                    Ok(data) => DownloaderResult::Ok(data),
+                );
+
+                match result {
+                    Ok((data, signature)) => {
+                        let mut data = BlockData::from(data);
+                        data.signature = signature.map(|signature| BlockSignature {
+                            r: signature.signature[0],
+                            s: signature.signature[1],
+                        });
+                        DownloaderResult::Ok(data)
+                    }
                     Err(err) => match err {
                         GatewayClientError::RateLimited
                         | GatewayClientError::UnknownFormat { .. } => DownloaderResult::Retry(err),
@@ -165,8 +205,8 @@ pub mod gateway {
     }
 
     /// Converts gateway [`StateUpdateWithBlock`] into [`BlockData`].
-    impl From<katana_gateway_types::StateUpdateWithBlock> for BlockData {
-        fn from(data: katana_gateway_types::StateUpdateWithBlock) -> Self {
+    impl From<StateUpdateWithBlock> for BlockData {
+        fn from(data: StateUpdateWithBlock) -> Self {
             fn to_gas_prices(prices: ResourcePrice) -> GasPrices {
                 let eth = prices.price_in_wei.to_u128().expect("valid u128");
                 let strk = prices.price_in_fri.to_u128().expect("valid u128");
@@ -291,7 +331,12 @@ pub mod gateway {
 
             let state_updates = StateUpdatesWithClasses { state_updates, ..Default::default() };
 
-            BlockData { block: SealedBlockWithStatus { block, status }, receipts, state_updates }
+            BlockData {
+                block: SealedBlockWithStatus { block, status },
+                receipts,
+                state_updates,
+                signature: None,
+            }
         }
     }
 }
@@ -498,6 +543,7 @@ pub mod json_rpc {
                 block: SealedBlockWithStatus { block, status },
                 receipts,
                 state_updates,
+                signature: None,
             })
         }
     }
@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use anyhow::Result;
 use futures::future::BoxFuture;
+use katana_primitives::block::ExecutableBlock;
 use katana_primitives::chain::ChainId;
 use katana_primitives::Felt;
-use katana_provider::api::block::BlockHashProvider;
+use katana_provider::api::block::{BlockHashProvider, BlockSignatureWriter};
 use katana_provider::{DbProviderFactory, MutableProvider, ProviderError, ProviderFactory};
 use katana_tasks::TaskSpawner;
 use rayon::prelude::*;
@@ -28,6 +31,8 @@ pub struct Blocks<B> {
     downloader: B,
     chain_id: ChainId,
     task_spawner: TaskSpawner,
+    sequencer_public_key: Option<Felt>,
+    genesis: Option<Arc<ExecutableBlock>>,
 }
 
 impl<B> Blocks<B> {
@@ -38,7 +43,78 @@ impl<B> Blocks<B> {
         chain_id: ChainId,
         task_spawner: TaskSpawner,
     ) -> Self {
-        Self { provider, downloader, chain_id, task_spawner }
+        Self {
+            provider,
+            downloader,
+            chain_id,
+            task_spawner,
+            sequencer_public_key: None,
+            genesis: None,
+        }
+    }
+
+    /// Verifies the sequencer signature of every downloaded block against `public_key`.
+    ///
+    /// The downloader must provide the block signatures, otherwise every block is rejected.
+    pub fn with_sequencer_public_key(mut self, public_key: Felt) -> Self {
+        self.sequencer_public_key = Some(public_key);
+        self
+    }
+
+    /// Verifies that the downloaded genesis block matches `genesis`.
+    ///
+    /// Used when following a chain whose genesis is known locally (ie from its chain spec), to
+    /// make sure the upstream node is serving the expected chain.
+    pub fn with_genesis(mut self, genesis: ExecutableBlock) -> Self {
+        self.genesis = Some(Arc::new(genesis));
+        self
+    }
+}
+
+/// Verifies that a downloaded block matches the expected genesis block.
+///
+/// Only the header fields that are fixed by the chain spec and the transaction hashes are
+/// compared, as the rest of the header is the result of executing the genesis transactions.
+fn verify_genesis_block(block_data: &BlockData, genesis: &ExecutableBlock) -> Result<(), Error> {
+    let block = &block_data.block.block;
+    let block_num = block.header.number;
+
+    let mismatch = |field| Error::GenesisMismatch { block_num, field };
+
+    if block.header.parent_hash != genesis.header.parent_hash {
+        return Err(mismatch("parent hash"));
+    }
+
+    if block.header.timestamp != genesis.header.timestamp {
+        return Err(mismatch("timestamp"));
+    }
+
+    if block.header.sequencer_address != genesis.header.sequencer_address {
+        return Err(mismatch("sequencer address"));
+    }
+
+    let tx_hashes = block.body.iter().map(|tx| tx.hash);
+    if !tx_hashes.eq(genesis.body.iter().map(|tx| tx.hash)) {
+        return Err(mismatch("transactions"));
+    }
+
+    Ok(())
+}
+
+/// Verifies that a block was signed by the sequencer owning `public_key`.
+///
+/// Must be called after the block hash and state diff commitment have been verified, as the
+/// signature is checked against them.
+fn verify_block_signature(block_data: &BlockData, public_key: Felt) -> Result<(), Error> {
+    let block = &block_data.block.block;
+    let block_num = block.header.number;
+
+    let signature = block_data.signature.ok_or(Error::MissingBlockSignature { block_num })?;
+
+    if signature.verify(public_key, block.hash, block.header.state_diff_commitment) {
+        Ok(())
+    } else {
+        Err(Error::InvalidBlockSignature { block_num, block_hash: block.hash })
     }
 }
 
@@ -114,6 +190,8 @@ where
 
             // Validate chain invariant and compute commitments/hashes in parallel on the CPU pool.
             let chain_id = self.chain_id;
+            let public_key = self.sequencer_public_key;
+            let genesis = self.genesis.clone();
             let provider = self.provider.clone();
             let mut blocks = self
                 .task_spawner
@@ -133,14 +211,24 @@ where
                             &chain_id,
                         );
 
-                        if verified {
-                            Ok(())
-                        } else {
-                            Err(Error::BlockVerificationFailed {
+                        if !verified {
+                            return Err(Error::BlockVerificationFailed {
                                 block_num,
                                 expected_block_hash: block_hash,
-                            })
+                            });
+                        }
+
+                        if let Some(genesis) = &genesis {
+                            if block_num == genesis.header.number {
+                                verify_genesis_block(block_data, genesis)?;
+                            }
                         }
+
+                        if let Some(public_key) = public_key {
+                            verify_block_signature(block_data, public_key)?;
+                        }
+
+                        Ok(())
                     })?;
 
                     Result::<_, Error>::Ok(blocks)
@@ -155,7 +243,7 @@ where
                     let provider_mut = provider.provider_mut();
 
                     for block_data in blocks.drain(..) {
-                        let BlockData { block, receipts, state_updates } = block_data;
+                        let BlockData { block, receipts, state_updates, signature } = block_data;
                         let block_number = block.block.header.number;
 
                         provider_mut
@@ -168,6 +256,10 @@ where
                             .inspect_err(
                                 |e| error!(error = %e, block = %block_number, "Error storing block."),
                             )?;
+
+                        if let Some(signature) = signature {
+                            provider_mut.insert_block_signature(block_number, signature)?;
+                        }
                     }
 
                     provider_mut.commit()?;
@@ -206,6 +298,15 @@ pub enum Error {
     #[error("block hash verification failed: block {block_num} hash {expected_block_hash:#x}")]
     BlockVerificationFailed { block_num: u64, expected_block_hash: Felt },
 
+    #[error("missing sequencer signature for block {block_num}")]
+    MissingBlockSignature { block_num: u64 },
+
+    #[error("invalid sequencer signature for block {block_num} with hash {block_hash:#x}")]
+    InvalidBlockSignature { block_num: u64, block_hash: Felt },
+
+    #[error("block {block_num} does not match the expected genesis block: mismatched {field}")]
+    GenesisMismatch { block_num: u64, field: &'static str },
+
     #[error("task join error: {0}")]
     TaskJoinError(katana_tasks::JoinError),
 }
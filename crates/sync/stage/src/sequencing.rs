@@ -11,7 +11,7 @@ use katana_messaging::{MessagingConfig, MessagingService, MessagingTask};
 use katana_pool::api::TransactionPool;
 use katana_pool::TxPool;
 use katana_provider::{ProviderFactory, ProviderRO, ProviderRW};
-use katana_tasks::{JoinHandle, TaskSpawner};
+use katana_tasks::{JoinHandle, TaskSpawner, TaskStatus};
 use tracing::error;
 
 pub type SequencingFut = BoxFuture<'static, Result<()>>;
@@ -27,6 +27,7 @@ where
     task_spawner: TaskSpawner,
     block_producer: BlockProducer<PF>,
     messaging_config: Option<MessagingConfig>,
+    messaging_status: TaskStatus,
 }
 
 impl<PF> Sequencing<PF>
@@ -42,7 +43,16 @@ where
         block_producer: BlockProducer<PF>,
         messaging_config: Option<MessagingConfig>,
     ) -> Self {
-        Self { pool, backend, task_spawner, block_producer, messaging_config }
+        let messaging_status = TaskStatus::new();
+        Self { pool, backend, task_spawner, block_producer, messaging_config, messaging_status }
+    }
+
+    /// Sets the status that tracks whether the messaging task is running.
+    ///
+    /// The status is left untouched if messaging isn't configured.
+    pub fn messaging_status(mut self, status: TaskStatus) -> Self {
+        self.messaging_status = status;
+        self
     }
 
     async fn run_messaging(&self) -> Result<JoinHandle<()>> {
@@ -52,7 +62,7 @@ where
             let chain_spec = self.backend.chain_spec.clone();
 
             let service = MessagingService::new(config, chain_spec, pool).await?;
-            let task = MessagingTask::new(service);
+            let task = self.messaging_status.track(MessagingTask::new(service));
 
             let handle = self.task_spawner.build_task().name("Messaging").spawn(task);
             Ok(handle)
@@ -13,18 +13,22 @@ use katana_gateway_client::Client as SequencerGateway;
 use katana_gateway_types::{
     Block, BlockStatus, ConfirmedStateUpdate, StateDiff, StateUpdate, StateUpdateWithBlock,
 };
-use katana_primitives::block::{BlockHash, BlockNumber};
+use katana_primitives::block::{
+    BlockHash, BlockNumber, BlockSignature, ExecutableBlock, GasPrices, PartialHeader,
+};
 use katana_primitives::chain::ChainId;
 use katana_primitives::da::L1DataAvailabilityMode;
+use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_primitives::{felt, ContractAddress, Felt};
-use katana_provider::api::block::BlockNumberProvider;
+use katana_provider::api::block::{BlockNumberProvider, BlockSignatureProvider};
 use katana_provider::ProviderFactory;
-use katana_stage::blocks::hash::compute_hash;
+use katana_stage::blocks::hash::{compute_hash, patch_and_verify_block_hash};
 use katana_stage::blocks::{BatchBlockDownloader, BlockData, BlockDownloader, Blocks};
 use katana_stage::{Stage, StageExecutionInput};
 use katana_tasks::TaskManager;
 use rstest::rstest;
 use starknet::core::types::ResourcePrice;
+use starknet::signers::SigningKey;
 
 /// Mock BlockDownloader implementation for testing.
 ///
@@ -34,6 +38,8 @@ use starknet::core::types::ResourcePrice;
 struct MockBlockDownloader {
     /// Map of block number to result (Ok or Err).
     responses: Arc<Mutex<HashMap<BlockNumber, Result<StateUpdateWithBlock, String>>>>,
+    /// Map of block number to the signature returned along with the block.
+    signatures: Arc<Mutex<HashMap<BlockNumber, BlockSignature>>>,
     /// Track download calls for verification.
     ///
     /// This is used to verify the input of [`BlockDownloader::download_blocks`] .
@@ -44,6 +50,7 @@ impl MockBlockDownloader {
     fn new() -> Self {
         Self {
             responses: Arc::new(Mutex::new(HashMap::new())),
+            signatures: Arc::new(Mutex::new(HashMap::new())),
             download_calls: Arc::new(Mutex::new(Vec::new())),
         }
     }
@@ -65,6 +72,12 @@ impl MockBlockDownloader {
         self
     }
 
+    /// Configure the signature returned along with a specific block.
+    fn with_signature(self, block_number: BlockNumber, signature: BlockSignature) -> Self {
+        self.signatures.lock().unwrap().insert(block_number, signature);
+        self
+    }
+
     /// Configure an error response for a specific block number.
     fn with_error(self, block_number: BlockNumber, error: String) -> Self {
         self.responses.lock().unwrap().insert(block_number, Err(error));
@@ -109,10 +122,15 @@ impl BlockDownloader for MockBlockDownloader {
 
             let mut results = Vec::new();
             let responses = self.responses.lock().unwrap();
+            let signatures = self.signatures.lock().unwrap();
 
             for block_num in block_numbers {
                 match responses.get(&block_num) {
-                    Some(Ok(block_data)) => results.push(BlockData::from(block_data.clone())),
+                    Some(Ok(block_data)) => {
+                        let mut block_data = BlockData::from(block_data.clone());
+                        block_data.signature = signatures.get(&block_num).copied();
+                        results.push(block_data);
+                    }
                     Some(Err(error)) => {
                         return Err(MockError(error.clone()));
                     }
@@ -168,6 +186,21 @@ fn create_downloaded_block(block_number: BlockNumber, parent_hash: Felt) -> Stat
     downloaded_block
 }
 
+/// Signs a downloaded block the same way a Katana sequencer would.
+fn sign_downloaded_block(block: &StateUpdateWithBlock, signing_key: &SigningKey) -> BlockSignature {
+    let mut data = BlockData::from(block.clone());
+    let state_updates = &data.state_updates.state_updates;
+    patch_and_verify_block_hash(
+        &mut data.block.block,
+        &data.receipts,
+        state_updates,
+        &ChainId::SEPOLIA,
+    );
+
+    let block = &data.block.block;
+    BlockSignature::sign(signing_key, block.hash, block.header.state_diff_commitment).unwrap()
+}
+
 #[rstest]
 #[case(100, 100, vec![100])]
 #[case(100, 105, vec![100, 101, 102, 103, 104, 105])]
@@ -418,3 +451,161 @@ async fn downloaded_blocks_do_not_form_valid_chain() {
     let stored = get_stored_block_numbers(&provider, 99..=102);
     assert_eq!(stored.len(), 1);
 }
+
+#[tokio::test]
+async fn verify_and_store_block_signatures() {
+    let signing_key = SigningKey::from_secret_scalar(felt!("0x1337"));
+
+    let genesis = create_stored_block(99, BlockHash::ZERO);
+    let block1 = create_downloaded_block(100, genesis.block.hash);
+    let block2 = create_downloaded_block(101, block1.block.block_hash.unwrap());
+
+    let signature1 = sign_downloaded_block(&block1, &signing_key);
+    let signature2 = sign_downloaded_block(&block2, &signing_key);
+
+    let provider = create_provider_with_blocks(vec![genesis]);
+    let downloader = MockBlockDownloader::new()
+        .with_block(100, block1)
+        .with_block(101, block2)
+        .with_signature(100, signature1)
+        .with_signature(101, signature2);
+
+    let mut stage = Blocks::new(
+        provider.clone(),
+        downloader,
+        ChainId::SEPOLIA,
+        TaskManager::current().task_spawner(),
+    )
+    .with_sequencer_public_key(signing_key.verifying_key().scalar());
+
+    let result = stage.execute(&StageExecutionInput::new(100, 101)).await;
+    assert!(result.is_ok());
+
+    // The verified signatures must be stored along with the blocks
+    let provider = provider.provider();
+    assert_eq!(provider.block_signature(100.into()).unwrap(), Some(signature1));
+    assert_eq!(provider.block_signature(101.into()).unwrap(), Some(signature2));
+}
+
+#[tokio::test]
+async fn missing_block_signature() {
+    use katana_stage::blocks;
+
+    let public_key = SigningKey::from_secret_scalar(felt!("0x1337")).verifying_key().scalar();
+
+    let genesis = create_stored_block(99, BlockHash::ZERO);
+    let block = create_downloaded_block(100, genesis.block.hash);
+
+    let provider = create_provider_with_blocks(vec![genesis]);
+    let downloader = MockBlockDownloader::new().with_block(100, block);
+
+    let mut stage = Blocks::new(
+        provider.clone(),
+        downloader,
+        ChainId::SEPOLIA,
+        TaskManager::current().task_spawner(),
+    )
+    .with_sequencer_public_key(public_key);
+
+    let result = stage.execute(&StageExecutionInput::new(100, 100)).await;
+
+    match result {
+        Err(katana_stage::Error::Blocks(blocks::Error::MissingBlockSignature { block_num })) => {
+            assert_eq!(block_num, 100)
+        }
+        _ => panic!("Expected missing block signature error, got: {result:#?}"),
+    }
+
+    let stored = get_stored_block_numbers(&provider, 99..=100);
+    assert_eq!(stored.len(), 1);
+}
+
+#[tokio::test]
+async fn invalid_block_signature() {
+    use katana_stage::blocks;
+
+    let public_key = SigningKey::from_secret_scalar(felt!("0x1337")).verifying_key().scalar();
+    let other_key = SigningKey::from_secret_scalar(felt!("0x42"));
+
+    let genesis = create_stored_block(99, BlockHash::ZERO);
+    let block = create_downloaded_block(100, genesis.block.hash);
+    let signature = sign_downloaded_block(&block, &other_key);
+
+    let provider = create_provider_with_blocks(vec![genesis]);
+    let downloader =
+        MockBlockDownloader::new().with_block(100, block).with_signature(100, signature);
+
+    let mut stage = Blocks::new(
+        provider.clone(),
+        downloader,
+        ChainId::SEPOLIA,
+        TaskManager::current().task_spawner(),
+    )
+    .with_sequencer_public_key(public_key);
+
+    let result = stage.execute(&StageExecutionInput::new(100, 100)).await;
+
+    match result {
+        Err(katana_stage::Error::Blocks(blocks::Error::InvalidBlockSignature {
+            block_num,
+            ..
+        })) => assert_eq!(block_num, 100),
+        _ => panic!("Expected invalid block signature error, got: {result:#?}"),
+    }
+
+    let stored = get_stored_block_numbers(&provider, 99..=100);
+    assert_eq!(stored.len(), 1);
+}
+
+#[rstest]
+#[case::matching_genesis(0, None)]
+#[case::mismatched_timestamp(1, Some("timestamp"))]
+#[tokio::test]
+async fn verify_genesis_block(#[case] timestamp: u64, #[case] mismatch: Option<&str>) {
+    use katana_stage::blocks;
+
+    let block = create_downloaded_block(0, BlockHash::ZERO);
+
+    let genesis = ExecutableBlock {
+        header: PartialHeader {
+            parent_hash: BlockHash::ZERO,
+            number: 0,
+            timestamp,
+            sequencer_address: ContractAddress(Felt::ZERO),
+            l1_gas_prices: GasPrices::MIN,
+            l1_data_gas_prices: GasPrices::MIN,
+            l2_gas_prices: GasPrices::MIN,
+            l1_da_mode: L1DataAvailabilityMode::Calldata,
+            starknet_version: CURRENT_STARKNET_VERSION,
+        },
+        body: Vec::new(),
+    };
+
+    let provider = create_provider_with_blocks(Vec::new());
+    let downloader = MockBlockDownloader::new().with_block(0, block);
+
+    let mut stage = Blocks::new(
+        provider.clone(),
+        downloader,
+        ChainId::SEPOLIA,
+        TaskManager::current().task_spawner(),
+    )
+    .with_genesis(genesis);
+
+    let result = stage.execute(&StageExecutionInput::new(0, 0)).await;
+
+    match (result, mismatch) {
+        (Ok(_), None) => {}
+        (
+            Err(katana_stage::Error::Blocks(blocks::Error::GenesisMismatch { block_num, field })),
+            Some(expected),
+        ) => {
+            assert_eq!(block_num, 0);
+            assert_eq!(field, expected);
+        }
+        (result, _) => panic!("Unexpected result: {result:#?}"),
+    }
+
+    let stored = get_stored_block_numbers(&provider, 0..=0);
+    assert_eq!(stored.len(), usize::from(mismatch.is_none()));
+}
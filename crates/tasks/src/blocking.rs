@@ -92,6 +92,18 @@ impl CpuBlockingTaskPool {
         });
         CpuBlockingJoinHandle { inner: rx }
     }
+
+    /// Executes `op` within this thread pool, blocking the current thread until it completes.
+    ///
+    /// Any nested rayon parallel iterators (e.g., `par_iter`) inside `op` will use this pool
+    /// rather than the global thread pool.
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        self.pool.install(op)
+    }
 }
 
 #[derive(Debug)]
@@ -3,9 +3,11 @@
 mod blocking;
 mod manager;
 mod spawner;
+mod status;
 mod task;
 
 pub use blocking::*;
 pub use manager::*;
 pub use spawner::*;
+pub use status::*;
 pub use task::*;
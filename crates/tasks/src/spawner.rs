@@ -6,7 +6,7 @@ use futures::FutureExt;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error};
 
-use crate::{CpuBlockingJoinHandle, Inner, JoinError, JoinHandle};
+use crate::{CpuBlockingJoinHandle, CpuBlockingTaskPool, Inner, JoinError, JoinHandle};
 
 /// A spawner for spawning tasks on the [`TaskManager`] that it was derived from.
 ///
@@ -76,6 +76,11 @@ impl CPUBoundTaskSpawner {
         // TODO(kariy): use TaskBuilder::build_blocking_task to buil the task
         self.0.inner.blocking_pool.spawn(func)
     }
+
+    /// Returns the manager's blocking pool.
+    pub fn pool(&self) -> CpuBlockingTaskPool {
+        self.0.inner.blocking_pool.clone()
+    }
 }
 
 /// A builder for building tasks to be spawned on the associated task manager.
@@ -0,0 +1,116 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// The run state of a task tracked by a [`TaskStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    /// The task hasn't been polled yet.
+    Pending,
+    /// The task has started and hasn't finished yet.
+    Running,
+    /// The task has either completed, panicked or been cancelled.
+    Finished,
+}
+
+impl TaskState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Pending,
+            1 => Self::Running,
+            _ => Self::Finished,
+        }
+    }
+}
+
+/// A cloneable handle for observing whether a long-running task is still alive.
+///
+/// The status is updated by wrapping the task's future with [`TaskStatus::track`], and can be
+/// queried from anywhere through [`TaskStatus::state`] (eg, by health checks).
+#[derive(Debug, Clone, Default)]
+pub struct TaskStatus(Arc<AtomicU8>);
+
+impl TaskStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current state of the tracked task.
+    pub fn state(&self) -> TaskState {
+        TaskState::from_u8(self.0.load(Ordering::Acquire))
+    }
+
+    /// Returns `true` if the tracked task is running.
+    pub fn is_running(&self) -> bool {
+        self.state() == TaskState::Running
+    }
+
+    /// Wraps `fut` so that its run state is reflected by this status.
+    ///
+    /// The state becomes [`TaskState::Running`] once the future is first polled, and
+    /// [`TaskState::Finished`] once it completes, panics or is dropped.
+    pub fn track<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        let status = self.clone();
+        async move {
+            let _guard = FinishGuard::new(status);
+            fut.await
+        }
+    }
+
+    fn set(&self, state: TaskState) {
+        self.0.store(state as u8, Ordering::Release);
+    }
+}
+
+/// Marks the status as finished when dropped, regardless of how the task ended.
+struct FinishGuard(TaskStatus);
+
+impl FinishGuard {
+    fn new(status: TaskStatus) -> Self {
+        status.set(TaskState::Running);
+        Self(status)
+    }
+}
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        self.0.set(TaskState::Finished);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TaskState, TaskStatus};
+
+    #[tokio::test]
+    async fn tracks_task_lifecycle() {
+        let status = TaskStatus::new();
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+
+        let fut = status.track(async move {
+            let _ = rx.await;
+        });
+        assert_eq!(status.state(), TaskState::Pending);
+
+        let handle = tokio::spawn(fut);
+        tokio::task::yield_now().await;
+        assert!(status.is_running());
+
+        tx.send(()).unwrap();
+        handle.await.unwrap();
+        assert_eq!(status.state(), TaskState::Finished);
+    }
+
+    #[tokio::test]
+    async fn aborted_task_is_finished() {
+        let status = TaskStatus::new();
+        let handle = tokio::spawn(status.track(std::future::pending::<()>()));
+
+        tokio::task::yield_now().await;
+        assert!(status.is_running());
+
+        handle.abort();
+        let _ = handle.await;
+        assert_eq!(status.state(), TaskState::Finished);
+    }
+}
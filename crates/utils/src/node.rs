@@ -373,6 +373,7 @@ pub fn test_config() -> Config {
         addr: DEFAULT_GRPC_ADDR,
         port: 0, // Use port 0 for auto-assignment
         timeout: Some(Duration::from_secs(30)),
+        tls: None,
     });
 
     let db = DbConfig { migrate: true, ..Default::default() };
@@ -412,6 +413,7 @@ pub fn test_config_with_controllers() -> Config {
         addr: DEFAULT_GRPC_ADDR,
         port: 0, // Use port 0 for auto-assignment
         timeout: Some(Duration::from_secs(30)),
+        tls: None,
     });
 
     let db = DbConfig { migrate: true, ..Default::default() };
@@ -174,7 +174,7 @@ impl<'a> TxWaiter<'a> {
 
                 match execution_status_from_receipt(&receipt.receipt) {
                     ExecutionResult::Succeeded => Some(Ok(receipt)),
-                    ExecutionResult::Reverted { reason } => {
+                    ExecutionResult::Reverted { reason, .. } => {
                         Some(Err(TxWaitingError::TransactionReverted(reason.clone())))
                     }
                 }
@@ -196,7 +196,7 @@ impl<'a> TxWaiter<'a> {
 
                             match execution_status_from_receipt(&receipt.receipt) {
                                 ExecutionResult::Succeeded => Some(Ok(receipt)),
-                                ExecutionResult::Reverted { reason } => {
+                                ExecutionResult::Reverted { reason, .. } => {
                                     Some(Err(TxWaitingError::TransactionReverted(reason.clone())))
                                 }
                             }
@@ -367,7 +367,8 @@ mod tests {
         let receipt = mock_receipt(AcceptedOnL1, Succeeded);
         assert!(eval_receipt!(receipt.clone(), AcceptedOnL2, true).unwrap().is_ok());
 
-        let receipt = mock_receipt(AcceptedOnL1, Reverted { reason: Default::default() });
+        let receipt =
+            mock_receipt(AcceptedOnL1, Reverted { reason: Default::default(), revert_error: None });
         let evaluation = eval_receipt!(receipt.clone(), AcceptedOnL1, true).unwrap();
         assert_matches!(evaluation, Err(TxWaitingError::TransactionReverted(_)));
     }
@@ -377,10 +378,12 @@ mod tests {
         let receipt = mock_preconf_receipt(Succeeded);
         assert!(eval_receipt!(receipt.clone(), AcceptedOnL2, true).is_none());
 
-        let receipt = mock_preconf_receipt(Reverted { reason: Default::default() });
+        let receipt =
+            mock_preconf_receipt(Reverted { reason: Default::default(), revert_error: None });
         assert!(eval_receipt!(receipt.clone(), false).unwrap().is_ok());
 
-        let receipt = mock_preconf_receipt(Reverted { reason: Default::default() });
+        let receipt =
+            mock_preconf_receipt(Reverted { reason: Default::default(), revert_error: None });
         let evaluation = eval_receipt!(receipt.clone(), true).unwrap();
         assert_matches!(evaluation, Err(TxWaitingError::TransactionReverted(_)));
     }
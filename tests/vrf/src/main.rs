@@ -8,7 +8,9 @@ use katana_primitives::utils::get_contract_address;
 use katana_primitives::{address, felt, ContractAddress, Felt};
 use katana_rpc_api::cartridge::CartridgeApiClient;
 use katana_rpc_types::{OutsideExecution, OutsideExecutionV2};
-use katana_sequencer_node::config::paymaster::{CartridgeApiConfig, PaymasterConfig, VrfConfig};
+use katana_sequencer_node::config::paymaster::{
+    CartridgeApiConfig, PaymasterConfig, VrfConfig, VrfProviderConfig,
+};
 use katana_utils::find_free_port;
 use katana_utils::node::{test_config, TestNode};
 use starknet::accounts::Account;
@@ -88,7 +90,10 @@ async fn main() {
             cartridge_api_url,
             controller_deployer_address: *deployer_address,
             controller_deployer_private_key: deployer_private_key,
-            vrf: Some(VrfConfig { url: vrf_url.clone(), vrf_account: vrf_account_address }),
+            vrf: Some(VrfConfig {
+                provider: VrfProviderConfig::Remote(vrf_url.clone()),
+                vrf_account: vrf_account_address,
+            }),
         }),
     });
 